@@ -0,0 +1,103 @@
+// Measures the cost of the verification equations on an honest signature, decoded beforehand,
+// then the marginal cost of each check of `ValidationPolicy` over the policy checking nothing
+// but the cofactorless equation, to put figures next to the recommendations of the README.
+use criterion::{criterion_group, criterion_main, Criterion};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use ed25519_speccheck::policy::ValidationPolicy;
+use ed25519_speccheck::{
+    algorithm2, compute_hram, serialize_signature, verify_cofactored, verify_cofactorless,
+    verify_pre_reduced_cofactored,
+};
+
+const MESSAGE: &[u8] = b"ed25519-speccheck";
+
+// A signature of an honest signer, which every policy accepts after running all its checks,
+// as the public key and R, S
+fn honest_signature() -> (EdwardsPoint, (EdwardsPoint, Scalar)) {
+    let a = Scalar::from_bytes_mod_order([0x2a; 32]);
+    let r = Scalar::from_bytes_mod_order([0x3b; 32]);
+    let pub_key = a * ED25519_BASEPOINT_POINT;
+    let r_point = r * ED25519_BASEPOINT_POINT;
+    let s = r + compute_hram(MESSAGE, &pub_key, &r_point) * a;
+    (pub_key, (r_point, s))
+}
+
+fn verification_equations(c: &mut Criterion) {
+    let (pub_key, signature) = honest_signature();
+    let mut group = c.benchmark_group("verification equations");
+
+    group.bench_function("cofactored", |b| {
+        b.iter(|| verify_cofactored(MESSAGE, &pub_key, &signature).is_ok())
+    });
+    group.bench_function("cofactorless", |b| {
+        b.iter(|| verify_cofactorless(MESSAGE, &pub_key, &signature).is_ok())
+    });
+    group.bench_function("pre-reduced cofactored", |b| {
+        b.iter(|| verify_pre_reduced_cofactored(MESSAGE, &pub_key, &signature).is_ok())
+    });
+    group.bench_function("algorithm 2", |b| {
+        b.iter(|| algorithm2::verify_signature(&signature.1, &signature.0, MESSAGE, &pub_key))
+    });
+
+    group.finish();
+}
+
+fn marginal_checks(c: &mut Criterion) {
+    let (pub_key, (r, s)) = honest_signature();
+    let pub_key = pub_key.compress().to_bytes();
+    let signature = serialize_signature(&r, &s);
+    let policies = [
+        ("no checks", ValidationPolicy::default()),
+        (
+            "canonical A and R",
+            ValidationPolicy {
+                reject_noncanonical_pk: true,
+                reject_noncanonical_r: true,
+                ..ValidationPolicy::default()
+            },
+        ),
+        (
+            "canonical S",
+            ValidationPolicy {
+                require_canonical_s: true,
+                ..ValidationPolicy::default()
+            },
+        ),
+        (
+            "small-order A and R rejected",
+            ValidationPolicy {
+                reject_small_order_pk: true,
+                reject_small_order_r: true,
+                ..ValidationPolicy::default()
+            },
+        ),
+        (
+            "full-order A",
+            ValidationPolicy {
+                require_full_order_pk: true,
+                ..ValidationPolicy::default()
+            },
+        ),
+        (
+            "re-serialized for hashing",
+            ValidationPolicy {
+                reserialize_for_hash: true,
+                ..ValidationPolicy::default()
+            },
+        ),
+    ];
+    let mut group = c.benchmark_group("checks over the cofactorless equation");
+
+    for (name, policy) in policies.iter() {
+        group.bench_function(*name, |b| {
+            b.iter(|| policy.verify(MESSAGE, &pub_key, &signature).is_ok())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, verification_equations, marginal_checks);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+// Compares verifying every vector against every policy from its bytes, as
+// `ValidationPolicy::verify` does, with decoding each vector once into a `DecodedVector`, as
+// differential loops do.
+use criterion::{criterion_group, criterion_main, Criterion};
+use ed25519_speccheck::decoded::DecodedVector;
+use ed25519_speccheck::policy::ValidationPolicy;
+use ed25519_speccheck::vectors::generate_test_vectors;
+
+fn verify_all_policies(c: &mut Criterion) {
+    let vectors = generate_test_vectors();
+    let policies = ValidationPolicy::all();
+    let mut group = c.benchmark_group("every policy on the 12 vectors");
+
+    group.bench_function("from bytes", |b| {
+        b.iter(|| {
+            let mut accepted = 0;
+            for tv in vectors.iter() {
+                for policy in policies.iter() {
+                    if policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok()
+                    {
+                        accepted += 1;
+                    }
+                }
+            }
+            accepted
+        })
+    });
+
+    group.bench_function("decoded once", |b| {
+        b.iter(|| {
+            let mut accepted = 0;
+            for tv in vectors.iter() {
+                let decoded = match DecodedVector::new(&tv.message, &tv.pub_key, &tv.signature) {
+                    Ok(decoded) => decoded,
+                    Err(_) => continue,
+                };
+                for policy in policies.iter() {
+                    if policy.verify_decoded(&decoded).is_ok() {
+                        accepted += 1;
+                    }
+                }
+            }
+            accepted
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, verify_all_policies);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+#![no_main]
+use ed25519_speccheck::vectors::{generate_test_vectors, TestVector};
+use ed25519_speccheck_fuzz::{check_invariants, split, INPUT_LEN};
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+static CASES: OnceLock<Vec<TestVector>> = OnceLock::new();
+
+// The first byte picks one of the cases of `cases.json`, whose public key || signature ||
+// message is xored with the rest: mutations of signatures passing some of the equations,
+// which random inputs hardly ever do
+fuzz_target!(|data: &[u8]| {
+    let cases = CASES.get_or_init(generate_test_vectors);
+    let (selector, mask) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let tv = &cases[*selector as usize % cases.len()];
+    let mut input = [&tv.pub_key[..], &tv.signature[..], &tv.message[..]].concat();
+    input.truncate(INPUT_LEN);
+    for (byte, m) in input.iter_mut().zip(mask.iter()) {
+        *byte ^= m;
+    }
+    let (message, pub_key, signature) = split(&input);
+    check_invariants(message, pub_key, signature);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+use ed25519_speccheck_fuzz::{check_invariants, split};
+use libfuzzer_sys::fuzz_target;
+
+// Raw public key || signature || message prefix inputs
+fuzz_target!(|data: &[u8]| {
+    let (message, pub_key, signature) = split(data);
+    check_invariants(message, pub_key, signature);
+});
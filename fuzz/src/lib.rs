@@ -0,0 +1,65 @@
+use curve25519_dalek::scalar::Scalar;
+use ed25519_speccheck::{
+    algorithm2, deserialize_point, fips186_5, rfc8032, verify_cofactored, verify_cofactorless,
+    zip215,
+};
+
+/// The length of the inputs of the targets: the public key, the signature and the message,
+/// cut to its first 32 bytes.
+pub const INPUT_LEN: usize = 128;
+
+/// Splits an input of the targets into its message, public key and signature, all of them
+/// empty if the input is too short.
+pub fn split(input: &[u8]) -> (&[u8], &[u8], &[u8]) {
+    if input.len() < 96 {
+        return (&[], &[], &[]);
+    }
+    let (pub_key, rest) = input.split_at(32);
+    let (signature, message) = rest.split_at(64);
+    (&message[..message.len().min(32)], pub_key, signature)
+}
+
+/// Asserts the implications between the verdicts of the reference verifiers on a vector,
+/// each of which follows from the equations and checks of the verifiers: any panic is a bug
+/// in one of them.
+pub fn check_invariants(message: &[u8], pub_key: &[u8], signature: &[u8]) {
+    if pub_key.len() != 32 || signature.len() != 64 {
+        return;
+    }
+    let zip215 = zip215::verify(message, pub_key, signature);
+
+    // the cofactorless equation holding makes the cofactored one hold, [8] applied to both
+    // sides, on the same decoded points and S reduced the same way
+    if let (Ok(a), Ok(r)) = (
+        deserialize_point(pub_key),
+        deserialize_point(&signature[..32]),
+    ) {
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&signature[32..]);
+        let s = Scalar::from_bits(s_bytes);
+        if verify_cofactorless(message, &a, &(r, s)).is_ok() {
+            assert!(
+                verify_cofactored(message, &a, &(r, s)).is_ok(),
+                "cofactorless accepts, cofactored rejects"
+            );
+        }
+        if algorithm2::verify(message, pub_key, signature) {
+            assert!(
+                verify_cofactored(message, &a, &(r, s)).is_ok(),
+                "Algorithm 2 accepts, cofactored rejects"
+            );
+        }
+    }
+
+    // the verifiers requiring canonical A, R and S accept a subset of what ZIP-215 accepts,
+    // which hashes A and R as encoded
+    if algorithm2::verify(message, pub_key, signature) {
+        assert!(zip215, "Algorithm 2 accepts, ZIP-215 rejects");
+    }
+    if rfc8032::verify(message, pub_key, signature) {
+        assert!(zip215, "RFC 8032 accepts, ZIP-215 rejects");
+    }
+    if fips186_5::verify(message, pub_key, signature) {
+        assert!(zip215, "FIPS 186-5 accepts, ZIP-215 rejects");
+    }
+}
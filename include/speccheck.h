@@ -0,0 +1,75 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the APACHE 2.0 license found in
+ * the LICENSE file in the root directory of this source tree.
+ *
+ * The C interface of ed25519-speccheck, built as a cdylib by `cargo build --release`:
+ * the vectors and the reference cofactored and cofactorless verifiers, for C and C++ test
+ * suites to link rather than parse cases.txt. Every buffer comes with its length, no function unwinds into
+ * the caller, and none keeps the pointers it is given.
+ */
+
+#ifndef SPECCHECK_H
+#define SPECCHECK_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The verdicts of the verifiers. */
+#define SPECCHECK_ACCEPTED 0
+#define SPECCHECK_PARSE_REJECTED 1
+#define SPECCHECK_VERIFY_REJECTED 2
+/* A NULL pointer with a non-zero length, an index out of range, or an internal failure. */
+#define SPECCHECK_ERROR (-1)
+
+typedef struct SpeccheckVectors SpeccheckVectors;
+
+/* The fields of a vector, each buffer with its length. */
+typedef struct SpeccheckVector {
+    const uint8_t *message;
+    size_t message_len;
+    const uint8_t *pub_key;
+    size_t pub_key_len;
+    const uint8_t *signature;
+    size_t signature_len;
+} SpeccheckVector;
+
+/* Generates the 12 vectors of cases.json, to be freed with speccheck_vectors_free, or
+ * returns NULL if generation failed. */
+SpeccheckVectors *speccheck_generate_vectors(void);
+
+/* Frees vectors returned by speccheck_generate_vectors; NULL is ignored. */
+void speccheck_vectors_free(SpeccheckVectors *vectors);
+
+/* The number of vectors, 0 for NULL. */
+size_t speccheck_vectors_len(const SpeccheckVectors *vectors);
+
+/* Points the fields of out at the vector index, returning 0; they remain valid until the
+ * vectors are freed. Returns SPECCHECK_ERROR, leaving out unset, for an index out of range
+ * or a NULL pointer. */
+int32_t speccheck_vector_get(const SpeccheckVectors *vectors, size_t index,
+                             SpeccheckVector *out);
+
+/* The verdict of the cofactored verifier on the signature of message under pub_key:
+ * SPECCHECK_ACCEPTED, SPECCHECK_PARSE_REJECTED (e.g. for a public key of other than 32 bytes
+ * or a signature of other than 64 bytes) or SPECCHECK_VERIFY_REJECTED. Each pointer may be
+ * NULL if its length is 0. */
+int32_t speccheck_verify_cofactored(const uint8_t *message, size_t message_len,
+                                    const uint8_t *pub_key, size_t pub_key_len,
+                                    const uint8_t *signature, size_t signature_len);
+
+/* As speccheck_verify_cofactored, for the cofactorless verifier. */
+int32_t speccheck_verify_cofactorless(const uint8_t *message, size_t message_len,
+                                      const uint8_t *pub_key, size_t pub_key_len,
+                                      const uint8_t *signature, size_t signature_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SPECCHECK_H */
@@ -10,8 +10,8 @@ SOURCE_DIR="$( cd "$( dirname "${BASH_SOURCE[0]}" )" >/dev/null 2>&1 && pwd )"
 main() {
 
 cd "$SOURCE_DIR"
-# Dalek, Zebra, BoringSSL, libra-crypto
-cargo test -- --nocapture --test-threads 1
+# Dalek, Zebra, BoringSSL, libra-crypto, aptos-crypto, Hacl*, libsodium, OpenSSL
+cargo test --features libraries -- --nocapture --test-threads 1
 
 }
 
@@ -43,6 +43,7 @@ NC='\\033[0m' # No color
 
 color_output() {
     sed "s/X/${RED}X${NC}/g" |
+    sed "s/ P / ${RED}P${NC} /g" |
     sed "s/V/${GREEN}V${NC}/g" |
     sed "s/aptos-crypto/${BOLDPURPLE}aptos-crypto${NC}/g" |
     sed "s/\[CGN20e\] Alg.2/${GREEN}\[CGN20e] Alg.2${NC}/g"
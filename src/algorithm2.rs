@@ -1,4 +1,5 @@
-use crate::{check_slice_size, verify_cofactored};
+use crate::verdict::Verdict;
+use crate::{check_slice_size, deserialize_point_with, verify_cofactored, DecodeMode};
 use anyhow::{anyhow, Result};
 /// This file implements the individual signature verification algorithm from [CGN20e], a.k.a.
 /// Algorithm 2.
@@ -61,17 +62,7 @@ fn is_small_order_case_10(bytes: &[u8]) -> bool {
 }
 
 pub fn deserialize_point(pt: &[u8]) -> Result<EdwardsPoint> {
-    let mut bytes = [0u8; 32];
-    bytes.copy_from_slice(check_slice_size(pt, 32, "pt")?);
-
-    // Check canonical encoding
-    if !is_canonical_point_encoding(&bytes[..]) {
-        return Err(anyhow!("Non-canonical point encoding!"));
-    }
-
-    curve25519_dalek::edwards::CompressedEdwardsY(bytes)
-        .decompress()
-        .ok_or_else(|| anyhow!("Point decompression failed!"))
+    deserialize_point_with(pt, DecodeMode::Strict)
 }
 
 #[allow(non_snake_case)]
@@ -114,3 +105,21 @@ pub fn verify_signature(s: &Scalar, R: &EdwardsPoint, msg_bytes: &[u8], pk: &Edw
     // Co-factored verification
     verify_cofactored(msg_bytes, pk, &(*R, *s)).is_ok()
 }
+
+/// Deserializes and verifies a signature as a whole, rejecting it if any step fails.
+pub fn verify(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> bool {
+    verdict(msg_bytes, pk_bytes, sig_bytes).is_accepted()
+}
+
+/// As `verify`, telling apart the step rejecting the signature.
+pub fn verdict(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> Verdict {
+    let parsed = deserialize_pk(pk_bytes)
+        .and_then(|pk| deserialize_signature(sig_bytes).map(|(s, r)| (pk, s, r)));
+    Verdict::from_results(parsed, |(pk, s, r)| {
+        if verify_signature(&s, &r, msg_bytes, &pk) {
+            Ok(())
+        } else {
+            Err(())
+        }
+    })
+}
@@ -0,0 +1,112 @@
+use crate::batch;
+use crate::policy::ALGORITHM_2;
+use curve25519_dalek::scalar::Scalar;
+/// This file implements the batch signature verification algorithm from [CGN20e], a.k.a.
+/// Algorithm 3, along with a divide-and-conquer identification of the invalid signatures
+/// of a batch.
+///
+/// References:
+/// [CGN20e] Taming the many EdDSAs; by Konstantinos Chalkias and François Garillot and Valeria Nikolaenko; in Cryptology ePrint Archive, Report 2020/1244; 2020; https://ia.cr/2020/1244
+use rand::RngCore;
+
+/// Draws the 128-bit random coefficients z_i of the batch equation.
+pub fn random_coefficients<R: RngCore>(n: usize, rng: &mut R) -> Vec<Scalar> {
+    (0..n)
+        .map(|_| {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes[..16]);
+            Scalar::from_bytes_mod_order(bytes)
+        })
+        .collect()
+}
+
+/// Verifies the batch of the i-th signatures of the i-th messages under the i-th public keys,
+/// with fresh random coefficients.
+///
+/// # Panics
+///
+/// Panics if the batch is given as slices of different lengths.
+pub fn verify_batch<R: RngCore>(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+    rng: &mut R,
+) -> bool {
+    let coefficients = random_coefficients(messages.len(), rng);
+    verify_batch_with_coefficients(messages, pub_keys, signatures, &coefficients)
+}
+
+/// Verifies a batch as `verify_batch`, with caller-supplied coefficients. Anything but
+/// coefficients drawn at random lets invalid signatures cancel out in the batch equation.
+/// This is `batch::verify_batch_with_coefficients` under `ALGORITHM_2`, which rejects the
+/// non-canonical encodings of A, R and s, along with A of small order, before the
+/// cofactored batch equation.
+pub fn verify_batch_with_coefficients(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+    coefficients: &[Scalar],
+) -> bool {
+    assert_eq!(messages.len(), pub_keys.len());
+    assert_eq!(messages.len(), signatures.len());
+    assert_eq!(messages.len(), coefficients.len());
+
+    batch::verify_batch_with_coefficients(
+        messages,
+        pub_keys,
+        signatures,
+        &ALGORITHM_2,
+        coefficients,
+    )
+    .is_ok()
+}
+
+/// Returns the indices of the invalid signatures in a batch, by verifying the batch and,
+/// when it fails, each of its halves in turn, down to single signatures. This never blames a
+/// valid signature, unlike re-verifying each signature with another equation than that of
+/// the batch, or bisecting without random coefficients.
+pub fn identify_invalid<R: RngCore>(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut invalid = Vec::new();
+    bisect(messages, pub_keys, signatures, 0, rng, &mut invalid);
+    invalid
+}
+
+fn bisect<R: RngCore>(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+    offset: usize,
+    rng: &mut R,
+    invalid: &mut Vec<usize>,
+) {
+    if messages.is_empty() || verify_batch(messages, pub_keys, signatures, rng) {
+        return;
+    }
+    if messages.len() == 1 {
+        invalid.push(offset);
+        return;
+    }
+
+    let mid = messages.len() / 2;
+    bisect(
+        &messages[..mid],
+        &pub_keys[..mid],
+        &signatures[..mid],
+        offset,
+        rng,
+        invalid,
+    );
+    bisect(
+        &messages[mid..],
+        &pub_keys[mid..],
+        &signatures[mid..],
+        offset + mid,
+        rng,
+        invalid,
+    );
+}
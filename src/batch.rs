@@ -0,0 +1,144 @@
+use crate::algorithm3::random_coefficients;
+use crate::eight;
+use crate::policy::ValidationPolicy;
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha512};
+
+/// How `verify_batch` draws the coefficients z_i of the batch equation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coefficients {
+    /// 128-bit coefficients drawn from the operating system, as batch verifiers draw them.
+    Random,
+    /// 128-bit coefficients derived from the SHA-512 digest of the batch, so that a batch
+    /// always gets the same verdict, e.g. to record the outcome of a batch vector. A signer
+    /// choosing the batch can grind it for coefficients letting torsion components cancel
+    /// out: only random coefficients make the batch equation sound.
+    Deterministic,
+}
+
+// The coefficients of `Coefficients::Deterministic`: the first 16 bytes of
+// SHA-512(digest || i) for the i-th entry, digest hashing every entry along with its length
+fn deterministic_coefficients(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+) -> Vec<Scalar> {
+    let mut h = Sha512::new();
+    h.update(b"ed25519-speccheck batch coefficients");
+    for i in 0..messages.len() {
+        for field in [messages[i], pub_keys[i], signatures[i]].iter() {
+            h.update((field.len() as u64).to_le_bytes());
+            h.update(field);
+        }
+    }
+    let digest = h.finalize();
+
+    (0..messages.len() as u64)
+        .map(|i| {
+            let mut h = Sha512::new();
+            h.update(digest);
+            h.update(i.to_le_bytes());
+            let mut bytes = [0u8; 32];
+            bytes[..16].copy_from_slice(&h.finalize()[..16]);
+            Scalar::from_bytes_mod_order(bytes)
+        })
+        .collect()
+}
+
+/// Verifies the batch of the i-th signatures of the i-th messages under the i-th public keys
+/// with the random linear combination of their verification equations, as batch verifiers
+/// do: each signature is decoded and checked as `policy` does, before the batch equation,
+/// `[8] (sum z_i R_i - [sum z_i s_i]B + sum [z_i k_i]A_i) = 0` if `policy` is cofactored,
+/// its pre-reduced counterpart `[8] sum z_i R_i - [8 sum z_i s_i mod L]B + sum [8 z_i k_i
+/// mod L]A_i = 0` if it also pre-reduces, and the same without the factor 8 otherwise.
+///
+/// An invalid signature passes the cofactored equation only with probability 2^-128 over
+/// the random coefficients. The cofactorless one is not deterministic, as opposed to
+/// individual verification: it passes batches whose signatures only fail by a torsion
+/// component for the coefficients multiple of its order, and as the scalars `z_i k_i` are
+/// reduced modulo L, it fails at random on signatures under A of small or mixed order, even
+/// when each passes individually.
+pub fn verify_batch(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+    policy: &ValidationPolicy,
+    coefficients: Coefficients,
+) -> Result<()> {
+    if pub_keys.len() != messages.len() || signatures.len() != messages.len() {
+        return Err(anyhow!(
+            "got {} messages, {} public keys and {} signatures",
+            messages.len(),
+            pub_keys.len(),
+            signatures.len()
+        ));
+    }
+    let coefficients = match coefficients {
+        Coefficients::Random => random_coefficients(messages.len(), &mut OsRng),
+        Coefficients::Deterministic => deterministic_coefficients(messages, pub_keys, signatures),
+    };
+    verify_batch_with_coefficients(messages, pub_keys, signatures, policy, &coefficients)
+}
+
+/// Verifies a batch as `verify_batch`, with caller-supplied coefficients.
+#[allow(non_snake_case)]
+pub fn verify_batch_with_coefficients(
+    messages: &[&[u8]],
+    pub_keys: &[&[u8]],
+    signatures: &[&[u8]],
+    policy: &ValidationPolicy,
+    coefficients: &[Scalar],
+) -> Result<()> {
+    if pub_keys.len() != messages.len()
+        || signatures.len() != messages.len()
+        || coefficients.len() != messages.len()
+    {
+        return Err(anyhow!(
+            "got {} coefficients for {} messages, {} public keys and {} signatures",
+            coefficients.len(),
+            messages.len(),
+            pub_keys.len(),
+            signatures.len()
+        ));
+    }
+    // the factor of the scalars of B and the A_i, 8 if pre-reduced
+    let factor = if policy.cofactored && policy.prereduce {
+        eight()
+    } else {
+        Scalar::one()
+    };
+
+    let mut Rs = Vec::with_capacity(messages.len());
+    let mut scalars = Vec::with_capacity(messages.len() + 1);
+    let mut points = Vec::with_capacity(messages.len() + 1);
+    let mut z_s = Scalar::zero();
+    for i in 0..messages.len() {
+        let (pk, R, s, k) = policy
+            .decode_for_batch(messages[i], pub_keys[i], signatures[i])
+            .map_err(|e| anyhow!("signature {}: {}", i, e))?;
+        Rs.push(R);
+        z_s += coefficients[i] * s;
+        scalars.push(factor * coefficients[i] * k);
+        points.push(pk);
+    }
+    scalars.push(-(factor * z_s));
+    points.push(ED25519_BASEPOINT_POINT);
+
+    let sum_R = EdwardsPoint::vartime_multiscalar_mul(coefficients.iter(), Rs.iter());
+    let rest = EdwardsPoint::vartime_multiscalar_mul(scalars.iter(), points.iter());
+    let holds = match (policy.cofactored, policy.prereduce) {
+        (true, true) => (sum_R.mul_by_cofactor() + rest).is_identity(),
+        (true, false) => (sum_R + rest).mul_by_cofactor().is_identity(),
+        (false, _) => (sum_R + rest).is_identity(),
+    };
+    if holds {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid batch"))
+    }
+}
@@ -0,0 +1,128 @@
+use crate::batch_vectors::{batch_around, BatchOutcome, BatchVector};
+use crate::test_vectors::TestVector;
+use crate::torsion::{DECODE_MODE, TORSION_ORDERS};
+use crate::{
+    compute_hram, deserialize_point_with, serialize_signature, verify_cofactorless, EIGHT_TORSION,
+};
+use anyhow::Result;
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A batch of signatures which each pass individual cofactorless verification, while the
+/// cofactorless batch equation of `batch::verify_batch` fails for most of its random
+/// coefficients: A carries a small-order component, which the scalar `z_i k_i`, reduced
+/// modulo L, no longer cancels. A verifier falling back to individual verification when the
+/// batch fails must accept every signature of the batch, whatever the coefficients drawn,
+/// for its verdicts to be consistent with single verification.
+#[derive(Serialize)]
+pub struct BatchInconsistencyVector {
+    #[serde(flatten)]
+    pub batch: BatchVector,
+    /// The largest order of the small-order components of the batch: 2, 4 or 8.
+    pub torsion_order: u8,
+    /// The outcome of the cofactored batch equation, which always passes.
+    pub batch_cofactored: BatchOutcome,
+    /// The outcome of the cofactorless batch equation, passing for one in `torsion_order`
+    /// draws of the coefficients.
+    pub batch_cofactorless: BatchOutcome,
+}
+
+// A signature under A mixed with `torsion`, valid under the cofactorless equation: R has
+// order L and k is a multiple of the order of `torsion` if `cancel_in_r` is false, else R
+// carries `-torsion` and k is 1 modulo its order, so that the components of R and [k]A
+// cancel out
+fn mixed_order_signature(
+    rng: &mut impl RngCore,
+    torsion: EdwardsPoint,
+    cancel_in_r: bool,
+) -> TestVector {
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT + torsion;
+
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r_torsion = if cancel_in_r {
+        -torsion
+    } else {
+        EdwardsPoint::identity()
+    };
+    let r = r_scalar * ED25519_BASEPOINT_POINT + r_torsion;
+
+    let mut message = [0u8; 32];
+    let s = loop {
+        rng.fill_bytes(&mut message);
+        let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+        if verify_cofactorless(&message, &pub_key, &(r, s)).is_ok() {
+            break s;
+        }
+    };
+
+    TestVector {
+        message: message.to_vec(),
+        pub_key: pub_key.compress().to_bytes(),
+        signature: serialize_signature(&r, &s),
+        metadata: None,
+        trace: None,
+    }
+}
+
+/// For each order of the small-order component of A, a signature under such an A among
+/// honest signatures, then one whose R carries the opposite component, and last a batch
+/// with the three signatures of the first kind.
+pub fn batch_inconsistency_vectors(
+    rng: &mut impl RngCore,
+) -> Result<Vec<BatchInconsistencyVector>> {
+    let vector = |comment: String, torsion_order: usize, entries: Vec<TestVector>| {
+        BatchInconsistencyVector {
+            batch: BatchVector::new(comment, entries),
+            torsion_order: torsion_order as u8,
+            batch_cofactored: BatchOutcome::Passes,
+            batch_cofactorless: BatchOutcome::PassesOneIn(torsion_order as u8),
+        }
+    };
+
+    let mut vectors = Vec::new();
+    let mut mixed = Vec::new();
+    for &(torsion_order, torsion_idx) in TORSION_ORDERS.iter() {
+        let torsion = deserialize_point_with(&EIGHT_TORSION[torsion_idx], DECODE_MODE)?;
+        let position = torsion_order - 1;
+        for &cancel_in_r in [false, true].iter() {
+            let tv = mixed_order_signature(rng, torsion, cancel_in_r);
+            if !cancel_in_r {
+                mixed.push((position, tv.clone()));
+            }
+            let comment = if cancel_in_r {
+                format!(
+                    "A and R with opposite components of order {}, at position {} among \
+                     honest signatures",
+                    torsion_order, position
+                )
+            } else {
+                format!(
+                    "A with a component of order {}, at position {} among honest signatures",
+                    torsion_order, position
+                )
+            };
+            vectors.push(vector(
+                comment,
+                torsion_order,
+                batch_around(rng, vec![(position, tv)]),
+            ));
+        }
+    }
+
+    // the component of order 8 dominates the sum of the others, uniform in the subgroup of
+    // order 8
+    vectors.push(vector(
+        "A with components of orders 2, 4 and 8 among honest signatures".to_string(),
+        8,
+        batch_around(rng, mixed),
+    ));
+    Ok(vectors)
+}
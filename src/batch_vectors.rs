@@ -0,0 +1,376 @@
+use crate::algorithm2;
+use crate::batch;
+use crate::policy::{ValidationPolicy, COFACTORED, COFACTORLESS};
+use crate::test_vectors::{generate_test_vectors, TestVector};
+use crate::{
+    compute_hram, deserialize_point, deserialize_signature, pick_small_nonzero_point,
+    serialize_signature,
+};
+use anyhow::Result;
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, IsIdentity};
+use rand::RngCore;
+use serde::Serialize;
+use std::ops::Neg;
+
+// The number of signatures in each of the interleaved batches
+const BATCH_SIZE: usize = 8;
+
+/// An ordered batch of signatures, along with the indices a correct batch verifier must
+/// identify as invalid when falling back to bisection after the batch equation fails.
+#[derive(Serialize)]
+pub struct BatchVector {
+    pub comment: String,
+    pub entries: Vec<TestVector>,
+    pub invalid_algorithm2: Vec<usize>,
+    pub invalid_cofactored: Vec<usize>,
+    pub invalid_cofactorless: Vec<usize>,
+}
+
+impl BatchVector {
+    pub(crate) fn new(comment: String, entries: Vec<TestVector>) -> BatchVector {
+        let invalid = |policy: ValidationPolicy| {
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(_, tv)| {
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_err()
+                })
+                .map(|(i, _)| i)
+                .collect()
+        };
+        BatchVector {
+            invalid_algorithm2: entries
+                .iter()
+                .enumerate()
+                .filter(|(_, tv)| !algorithm2::verify(&tv.message, &tv.pub_key, &tv.signature))
+                .map(|(i, _)| i)
+                .collect(),
+            invalid_cofactored: invalid(ValidationPolicy {
+                cofactored: true,
+                ..Default::default()
+            }),
+            invalid_cofactorless: invalid(ValidationPolicy::default()),
+            comment,
+            entries,
+        }
+    }
+}
+
+// A signature produced by an honest signer, valid under any policy, except for R which
+// gets the `torsion` component added when given one
+fn honest_signature_with_torsion(
+    rng: &mut impl RngCore,
+    torsion: Option<EdwardsPoint>,
+) -> TestVector {
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT;
+
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT + torsion.unwrap_or_else(EdwardsPoint::identity);
+
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+
+    TestVector {
+        message: message.to_vec(),
+        pub_key: pub_key.compress().to_bytes(),
+        signature: serialize_signature(&r, &s),
+        metadata: None,
+        trace: None,
+    }
+}
+
+fn honest_signature(rng: &mut impl RngCore) -> TestVector {
+    honest_signature_with_torsion(rng, None)
+}
+
+// An honest signature with `delta` added to S, invalid under any policy for a non-zero `delta`
+fn offset_signature(rng: &mut impl RngCore, delta: Scalar) -> TestVector {
+    let mut tv = honest_signature(rng);
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&tv.signature[32..]);
+    let s = Scalar::from_canonical_bytes(s_bytes).unwrap() + delta;
+    tv.signature[32..].copy_from_slice(s.as_bytes());
+    tv
+}
+
+fn forged_signature(rng: &mut impl RngCore) -> TestVector {
+    offset_signature(rng, Scalar::one())
+}
+
+/// Batches interleaving honest signatures with each adversarial class: each of the test
+/// vectors, and a forgery invalid under any policy, sits alone among honest signatures at a
+/// position varying from batch to batch, so that bisection has to descend into every half.
+/// A last batch alternates honest signatures with all the adversarial ones.
+pub fn interleaved_batches(rng: &mut impl RngCore) -> Vec<BatchVector> {
+    let mut adversarial: Vec<(String, TestVector)> = generate_test_vectors()
+        .into_iter()
+        .enumerate()
+        .map(|(i, tv)| (format!("case {}", i), tv))
+        .collect();
+    adversarial.push(("forgery".to_string(), forged_signature(rng)));
+
+    let mut batches = Vec::new();
+    for (i, (class, tv)) in adversarial.iter().enumerate() {
+        let position = i % BATCH_SIZE;
+        let mut entries: Vec<TestVector> = (1..BATCH_SIZE).map(|_| honest_signature(rng)).collect();
+        entries.insert(position, tv.clone());
+        batches.push(BatchVector::new(
+            format!("{} at position {} among honest signatures", class, position),
+            entries,
+        ));
+    }
+
+    let mut entries = Vec::new();
+    for (_, tv) in adversarial.into_iter() {
+        entries.push(honest_signature(rng));
+        entries.push(tv);
+    }
+    batches.push(BatchVector::new(
+        "honest signatures alternating with every adversarial class".to_string(),
+        entries,
+    ));
+
+    batches
+}
+
+// Fills a batch of BATCH_SIZE honest signatures around the `special` ones at their positions
+pub(crate) fn batch_around(
+    rng: &mut impl RngCore,
+    mut special: Vec<(usize, TestVector)>,
+) -> Vec<TestVector> {
+    special.reverse();
+    (0..BATCH_SIZE)
+        .map(|i| match special.last() {
+            Some((position, _)) if *position == i => special.pop().unwrap().1,
+            _ => honest_signature(rng),
+        })
+        .collect()
+}
+
+/// Batches on which naive identifiable-abort strategies misattribute the invalid signatures,
+/// while the divide-and-conquer identification of `algorithm3::identify_invalid` does not:
+/// - two forgeries whose errors cancel out when all the coefficients of the batch equation
+///   are 1, so that an unrandomized batch passes and bisecting it blames nobody,
+/// - a forgery next to an honest signature with a torsion component in R, valid under the
+///   cofactored batch equation, which re-verifying each signature with the cofactorless
+///   equation blames along with the forgery,
+/// - two honest signatures whose torsion components in R cancel out in an unrandomized
+///   cofactorless batch, so that it passes while each fails cofactorless verification.
+pub fn misattribution_batches(rng: &mut impl RngCore) -> Vec<BatchVector> {
+    let mut batches = Vec::new();
+
+    let mut delta_bytes = [0u8; 32];
+    rng.fill_bytes(&mut delta_bytes);
+    let delta = Scalar::from_bytes_mod_order(delta_bytes);
+    let special = vec![
+        (2, offset_signature(rng, delta)),
+        (5, offset_signature(rng, delta.neg())),
+    ];
+    batches.push(BatchVector::new(
+        "forgeries at positions 2 and 5 cancelling out under unit coefficients".to_string(),
+        batch_around(rng, special),
+    ));
+
+    let torsion = pick_small_nonzero_point(rng.next_u64() as usize);
+    let special = vec![
+        (1, honest_signature_with_torsion(rng, Some(torsion))),
+        (3, forged_signature(rng)),
+    ];
+    batches.push(BatchVector::new(
+        "forgery at position 3 next to a torsion component in R at position 1".to_string(),
+        batch_around(rng, special),
+    ));
+
+    let special = vec![
+        (4, honest_signature_with_torsion(rng, Some(torsion))),
+        (6, honest_signature_with_torsion(rng, Some(torsion.neg()))),
+    ];
+    batches.push(BatchVector::new(
+        "torsion components in R at positions 4 and 6 cancelling out".to_string(),
+        batch_around(rng, special),
+    ));
+
+    batches
+}
+
+/// The outcome of a batch equation over the draws of its random coefficients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum BatchOutcome {
+    Passes,
+    /// Fails but for a negligible fraction of the coefficients.
+    Fails,
+    /// Passes for one in that many draws of the coefficients: the batch only holds torsion
+    /// components, which cancel out when the coefficients are multiples of their order.
+    PassesOneIn(u8),
+}
+
+/// A batch along with the outcomes of the reference batch equations on it, as opposed to
+/// the individual verification of each of its entries.
+#[derive(Serialize)]
+pub struct BatchEquationVector {
+    #[serde(flatten)]
+    pub batch: BatchVector,
+    pub batch_cofactored: BatchOutcome,
+    pub batch_cofactorless: BatchOutcome,
+}
+
+impl BatchEquationVector {
+    fn new(comment: String, entries: Vec<TestVector>) -> BatchEquationVector {
+        BatchEquationVector {
+            batch_cofactored: batch_outcome(&entries, true).unwrap(),
+            batch_cofactorless: batch_outcome(&entries, false).unwrap(),
+            batch: BatchVector::new(comment, entries),
+        }
+    }
+}
+
+// The error `R - ([s]B - [k]A)` of the verification equation of each entry, decoding and
+// hashing as `verify_cofactored` does
+fn error_terms(entries: &[TestVector]) -> Result<Vec<EdwardsPoint>> {
+    entries
+        .iter()
+        .map(|tv| {
+            let pub_key = deserialize_point(&tv.pub_key)?;
+            let (r, s) = deserialize_signature(&tv.signature)?;
+            let k = compute_hram(&tv.message, &pub_key, &r);
+            Ok(r - EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &pub_key.neg(), &s))
+        })
+        .collect()
+}
+
+// The batch equation of `batch` under `policy` on `entries`
+fn verify_batch(
+    entries: &[TestVector],
+    coefficients: &[Scalar],
+    policy: &ValidationPolicy,
+) -> Result<()> {
+    let messages: Vec<&[u8]> = entries.iter().map(|tv| &tv.message[..]).collect();
+    let pub_keys: Vec<&[u8]> = entries.iter().map(|tv| &tv.pub_key[..]).collect();
+    let signatures: Vec<&[u8]> = entries.iter().map(|tv| &tv.signature[..]).collect();
+    batch::verify_batch_with_coefficients(&messages, &pub_keys, &signatures, policy, coefficients)
+}
+
+/// The reference cofactored batch equation, `[8] (sum z_i R_i - [sum z_i s_i]B + sum [z_i
+/// k_i]A_i) = 0` for the coefficients z_i: `batch::verify_batch_with_coefficients` under
+/// `COFACTORED`, decoding and hashing as `verify_cofactored`. As it hashes re-serialized
+/// points, it rejects the non-canonical encodings of A and R signed for their hash as
+/// provided, unlike individual verification hashing them as provided.
+pub fn verify_batch_cofactored(entries: &[TestVector], coefficients: &[Scalar]) -> Result<()> {
+    verify_batch(entries, coefficients, &COFACTORED)
+}
+
+/// The reference cofactorless batch equation, `sum z_i R_i - [sum z_i s_i]B + sum [z_i k_i
+/// mod L]A_i = 0`: `batch::verify_batch_with_coefficients` under `COFACTORLESS`, see
+/// `verify_batch_cofactored`.
+pub fn verify_batch_cofactorless(entries: &[TestVector], coefficients: &[Scalar]) -> Result<()> {
+    verify_batch(entries, coefficients, &COFACTORLESS)
+}
+
+// The order of a point of the torsion subgroup, or None for a point with a component of
+// large order
+fn torsion_order(pt: &EdwardsPoint) -> Option<u8> {
+    [1u8, 2, 4, 8]
+        .iter()
+        .copied()
+        .find(|&n| (Scalar::from(n) * pt).is_identity())
+}
+
+/// The outcome of `verify_batch_cofactored` (resp. `verify_batch_cofactorless`) on
+/// `entries` for 128-bit random coefficients, whose residues modulo 8 are uniform, as
+/// follows from the error of each entry. The cofactorless outcome only holds when no A has
+/// a small-order component, which reducing `z_i k_i` modulo L changes the multiple of, as
+/// the vectors of `batch_inconsistency` show.
+pub fn batch_outcome(entries: &[TestVector], cofactored: bool) -> Result<BatchOutcome> {
+    let orders: Option<Vec<u8>> = error_terms(entries)?.iter().map(torsion_order).collect();
+    Ok(match orders {
+        None => BatchOutcome::Fails,
+        Some(_) if cofactored => BatchOutcome::Passes,
+        // the torsion subgroup is cyclic, hence the combinations of the errors span the
+        // subgroup of the largest order, evenly for uniform coefficients
+        Some(orders) => match orders.into_iter().max().unwrap_or(1) {
+            1 => BatchOutcome::Passes,
+            order => BatchOutcome::PassesOneIn(order),
+        },
+    })
+}
+
+/// Batches telling apart the reference batch equations from individual verification:
+/// - the vectors with non-canonical R (case 9) or A (case 11) signed for their hash as
+///   provided, which pass individual verification while the batch equations, hashing them
+///   re-serialized, fail on the former, and on the latter fail cofactorless at random,
+/// - mixed A and R failing cofactorless verification (case 4), and a torsion component in
+///   R, which fail individual cofactorless verification but pass the cofactored batch, and
+///   the cofactorless batch for some coefficients only,
+/// - torsion components in R of orders 4 and 8 summing to the identity, which cancel out in
+///   an unrandomized batch only,
+/// - a torsion component of order 2 in R, on which the cofactorless batch fails at random,
+///   one time in two.
+pub fn batch_equation_vectors(rng: &mut impl RngCore) -> Vec<BatchEquationVector> {
+    let vec = generate_test_vectors();
+    let mut batches = Vec::new();
+    for &(case, position) in [(9, 3), (11, 5), (4, 2)].iter() {
+        let entries = batch_around(rng, vec![(position, vec[case].clone())]);
+        batches.push(BatchEquationVector::new(
+            format!(
+                "case {} at position {} among honest signatures",
+                case, position
+            ),
+            entries,
+        ));
+    }
+
+    let torsion = pick_small_nonzero_point(rng.next_u64() as usize);
+    let special = vec![(1, honest_signature_with_torsion(rng, Some(torsion)))];
+    batches.push(BatchEquationVector::new(
+        format!(
+            "torsion component of order {} in R at position 1",
+            torsion_order(&torsion).unwrap()
+        ),
+        batch_around(rng, special),
+    ));
+
+    // [2]T + [3]T - [5]T = 0, for T of order 8, with components of orders 4, 8 and 8
+    let torsion = (1..8)
+        .map(pick_small_nonzero_point)
+        .find(|pt| torsion_order(pt) == Some(8))
+        .unwrap();
+    let special = vec![
+        (0, Scalar::from(2u8) * torsion),
+        (3, Scalar::from(3u8) * torsion),
+        (7, (Scalar::from(5u8) * torsion).neg()),
+    ]
+    .into_iter()
+    .map(|(position, component)| {
+        (
+            position,
+            honest_signature_with_torsion(rng, Some(component)),
+        )
+    })
+    .collect();
+    batches.push(BatchEquationVector::new(
+        "torsion components in R at positions 0, 3 and 7 summing to the identity".to_string(),
+        batch_around(rng, special),
+    ));
+
+    let torsion = (1..8)
+        .map(pick_small_nonzero_point)
+        .find(|pt| torsion_order(pt) == Some(2))
+        .unwrap();
+    let special = vec![(6, honest_signature_with_torsion(rng, Some(torsion)))];
+    batches.push(BatchEquationVector::new(
+        "torsion component of order 2 in R at position 6".to_string(),
+        batch_around(rng, special),
+    ));
+
+    batches
+}
@@ -0,0 +1,88 @@
+// The classes of scalars and points the cases are built around, for library authors triaging
+// a failing vector: whether its S is reduced, whether its A and R are of small, mixed or
+// prime order, whether its k clears a torsion component.
+
+use curve25519_dalek::constants::BASEPOINT_ORDER;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use serde::Serialize;
+
+/// The class of a 32-byte little-endian scalar, as the checks on S see it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScalarClass {
+    /// Below L, which `Scalar::from_canonical_bytes` accepts.
+    Canonical,
+    /// At least L, with the 3 high bits unset: only a full check of S < L rejects it.
+    GeL,
+    /// One of the 3 high bits set, at least 2^253, which the high bits check rejects.
+    HighBitsSet,
+}
+
+/// The class of the scalar `bytes` encode.
+pub fn scalar_class(bytes: &[u8; 32]) -> ScalarClass {
+    if bytes[31] & 0xe0 != 0 {
+        ScalarClass::HighBitsSet
+    } else if Scalar::from_canonical_bytes(*bytes).is_none() {
+        ScalarClass::GeL
+    } else {
+        ScalarClass::Canonical
+    }
+}
+
+/// Whether the little-endian integer `bytes` encode is a multiple of 8: for k, whether [k]
+/// clears every torsion component, so that [k]A has none whatever A.
+pub fn multiple_of_eight_le(bytes: &[u8; 32]) -> bool {
+    bytes[0] & 7 == 0
+}
+
+/// The order of a point, that of a point of small order, or its class otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PointOrder {
+    /// The identity.
+    One,
+    Two,
+    Four,
+    Eight,
+    /// 2L, 4L or 8L: a point of order L plus a torsion component, see `torsion_order`.
+    Mixed,
+    /// L, in the prime-order subgroup.
+    Prime,
+}
+
+/// The order of `pt`.
+pub fn point_order(pt: &EdwardsPoint) -> PointOrder {
+    if !pt.is_small_order() {
+        return if has_torsion_component(pt) {
+            PointOrder::Mixed
+        } else {
+            PointOrder::Prime
+        };
+    }
+    match torsion_order(pt) {
+        1 => PointOrder::One,
+        2 => PointOrder::Two,
+        4 => PointOrder::Four,
+        _ => PointOrder::Eight,
+    }
+}
+
+/// Whether `pt` has a component of small order other than the identity: true of the points
+/// of small order but the identity, and of those of mixed order.
+pub fn has_torsion_component(pt: &EdwardsPoint) -> bool {
+    !pt.is_torsion_free()
+}
+
+/// The order of the small-order component of `pt`, that of `[L]pt` as L is odd.
+pub fn torsion_order(pt: &EdwardsPoint) -> usize {
+    let mut small =
+        EdwardsPoint::vartime_double_scalar_mul_basepoint(&BASEPOINT_ORDER, pt, &Scalar::zero());
+    let mut order = 1;
+    while !small.is_identity() {
+        small = small + small;
+        order *= 2;
+    }
+    order
+}
@@ -0,0 +1,1112 @@
+use crate::contribution::{self, Contribution};
+use crate::corpus_version::{self, CorpusVersion};
+use crate::differential::{self, Minimized, Minimizer};
+use crate::explain;
+use crate::external::{External, Input};
+use crate::generator::VectorGenerator;
+use crate::io::{self, VectorFormat};
+use crate::matrix::KNOWN_POLICIES;
+use crate::metrics::{self, Metrics};
+use crate::policy::{self, infer_from_partial, ValidationPolicy, CHECK_NAMES};
+use crate::registry::Registry;
+use crate::report::ResultsTable;
+use crate::sarif::{self, CASE_FAMILIES};
+use crate::search::{self, RareProperty, RARE_PROPERTIES};
+use crate::test_vectors::{generate_test_vectors, TestVector, CASE_IDS};
+use crate::verdict::Verdict;
+use crate::verifier;
+use crate::{malleability, new_rng, repro, upstream};
+use anyhow::{anyhow, Result};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{Parser, Subcommand};
+use serde_json::json;
+use std::io::Write;
+use std::iter;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+// The arguments of the utility, as clap parses them before `parse` checks them into a
+// `Command`. Their doc comments are the help of the utility.
+/// Generates the test vectors of [CGN20e] and checks Ed25519 verifiers against them.
+///
+/// Without a command, writes every file of the release to the current directory.
+#[derive(Parser)]
+#[command(name = "ed25519-speccheck")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Args>,
+}
+
+#[derive(Subcommand)]
+enum Args {
+    /// Writes the test vectors
+    Generate {
+        /// Directory to write to
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+        /// json (cases.json), txt (cases.txt) or all (every file; only cases.json and
+        /// cases.txt with --select, --seed or --explain)
+        #[arg(
+            long,
+            default_value = "all",
+            value_parser = formats(&[Format::All, Format::Json, Format::Txt])
+        )]
+        format: Format,
+        /// Comma-separated indices, ranges (4-7), case families (s-out-of-bounds, ...) or
+        /// cases (mixed-order/fails-cofactorless, ...) of the vectors to write
+        #[arg(long)]
+        select: Option<String>,
+        /// Draws each case from an RNG seeded with n, rather than the digits of pi of the
+        /// release
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+        /// Records next to each vector of cases.json how it was built: the small-order
+        /// point, the relation its message was ground for and the scalars of the signature
+        #[arg(long)]
+        explain: bool,
+        /// The frozen version whose files to write, along with its manifest corpus.json
+        #[arg(
+            long,
+            default_value = corpus_version::LATEST.name(),
+            value_parser = corpus_versions()
+        )]
+        corpus: CorpusVersion,
+    },
+    /// Prints the verdict of a validation policy on each vector
+    Verify {
+        /// Comma-separated checks the policy performs, out of reject-small-order-pk,
+        /// reject-noncanonical-pk, reject-small-order-r, reject-noncanonical-r,
+        /// require-canonical-s, cofactored, prereduce, reserialize-for-hash and
+        /// require-full-order-pk, or one of cofactored, cofactorless, algorithm2,
+        /// fips186_5, libsodium, rfc8032 and zip215 for the policy of the verifier of this
+        /// crate
+        #[arg(long, default_value = "", value_parser = parse_policy)]
+        policy: ValidationPolicy,
+        /// Vectors in the layout of cases.json, or of cases.txt for files ending in .txt
+        /// (default: generated)
+        #[arg(long, visible_alias = "input")]
+        vectors: Option<PathBuf>,
+        /// As for generate
+        #[arg(long)]
+        select: Option<String>,
+        /// txt, with a count of each verdict after them, or json
+        #[arg(long, default_value = "txt", value_parser = formats(&[Format::Txt, Format::Json]))]
+        format: Format,
+    },
+    /// Prints the validation policies consistent with the verdicts of a verifier
+    Classify {
+        /// A V (accepted), P (rejected while parsing), X (rejected while verifying), E
+        /// (errored) or ? (not tried) per vector
+        #[arg(
+            long,
+            required_unless_present = "interactive",
+            conflicts_with = "interactive"
+        )]
+        verdicts: Option<String>,
+        /// Asks for the verdicts one vector at a time instead
+        #[arg(long)]
+        interactive: bool,
+        /// As for verify
+        #[arg(long)]
+        vectors: Option<PathBuf>,
+    },
+    /// Reports the verdicts of libraries on the generated vectors
+    Report {
+        /// The verdicts of each library, as for classify but without ?
+        #[arg(value_name = "NAME=VERDICTS", value_parser = parse_library)]
+        libraries: Vec<(String, Vec<Verdict>)>,
+        /// The expected policy, as for verify
+        #[arg(long, default_value = "", value_parser = parse_policy)]
+        policy: ValidationPolicy,
+        /// Runs a command on each vector, given its message, public key and signature in
+        /// hex as its last three arguments, for another row: exit status 0 accepts the
+        /// vector, 2 rejects it while parsing and any other rejects it while verifying
+        #[arg(
+            long,
+            value_name = "NAME=COMMAND",
+            value_parser = |s: &str| parse_external(s, Input::Args)
+        )]
+        external: Vec<(String, External)>,
+        /// As --external, with the vector on standard input in the layout of cases.txt
+        /// (msg=, pbk= and sig= lines)
+        #[arg(
+            long,
+            value_name = "NAME=COMMAND",
+            value_parser = |s: &str| parse_external(s, Input::Stdin)
+        )]
+        external_stdin: Vec<(String, External)>,
+        #[arg(
+            long,
+            default_value = "table",
+            value_parser = formats(&[
+                Format::Table,
+                Format::Markdown,
+                Format::Html,
+                Format::Csv,
+                Format::Sarif,
+            ])
+        )]
+        format: Format,
+    },
+    /// Explains a verifier from its verdicts: the policies it is inferred to implement, its
+    /// differences from RFC 8032 and ZIP-215, the security properties it achieves (SUF-CMA,
+    /// SBS, consensus safety) with the vectors showing each gap, and how to close them
+    Explain {
+        /// The verdicts of the verifier, as for report
+        #[arg(value_name = "NAME=VERDICTS", value_parser = parse_library)]
+        library: (String, Vec<Verdict>),
+        /// As for verify
+        #[arg(long)]
+        vectors: Option<PathBuf>,
+    },
+    /// Runs a library on each vector through a command, as for report, and prints its
+    /// verdicts, the policies it is inferred to implement and the security properties it
+    /// breaks
+    Check {
+        /// The command, e.g. './myverifier {pk} {msg} {sig}', given the message, public key
+        /// and signature in hex in place of {msg}, {pk} and {sig}, or as its last three
+        /// arguments without them; exit statuses as for report --external
+        #[arg(long, value_parser = |s: &str| External::parse(s, Input::Args))]
+        cmd: External,
+        /// As for verify
+        #[arg(long)]
+        vectors: Option<PathBuf>,
+    },
+    /// Runs libraries on random signatures, honest, with a flipped bit or built around the
+    /// divergences of the cases with random keys and messages, printing those on which some
+    /// library accepts and another rejects
+    Differential {
+        /// As for report; at least two libraries, through either option
+        #[arg(
+            long,
+            value_name = "NAME=COMMAND",
+            value_parser = |s: &str| parse_external(s, Input::Args)
+        )]
+        external: Vec<(String, External)>,
+        /// As for report
+        #[arg(
+            long,
+            value_name = "NAME=COMMAND",
+            value_parser = |s: &str| parse_external(s, Input::Stdin)
+        )]
+        external_stdin: Vec<(String, External)>,
+        /// Number of signatures to try
+        #[arg(long, value_name = "N", default_value_t = 1000)]
+        iterations: usize,
+        /// Draws the signatures from an RNG seeded with n (default: the digits of pi of the
+        /// release)
+        #[arg(long, value_name = "N")]
+        seed: Option<u64>,
+        /// Reduces each disagreement, S modulo L, A and R encoded canonically and the
+        /// message shortened as long as the libraries still part the same way, and prints
+        /// the case it is equivalent to under every policy, or that it is novel
+        #[arg(long)]
+        minimize: bool,
+        #[arg(long, default_value = "txt", value_parser = formats(&[Format::Txt, Format::Json]))]
+        format: Format,
+    },
+    /// Prints the operations counted by the most lenient and the strictest policies on the
+    /// vectors, in the Prometheus text format
+    Metrics,
+    /// Plays a transaction whose ID changes with S + L
+    Malleability,
+    /// Checks the generated files against the digests of the release
+    ReproCheck {
+        /// The corpus version whose files to check
+        #[arg(
+            long,
+            default_value = corpus_version::LATEST.name(),
+            value_parser = corpus_versions()
+        )]
+        corpus: CorpusVersion,
+    },
+    /// Reviews a vector proposed for the corpus, in the layout of an entry of cases.json
+    /// along with the claimed metadata under `claim`: prints the claims departing from the
+    /// derived metadata and an existing case the vector is equivalent to, if any, else the
+    /// normalized entry
+    ValidateContribution {
+        contribution: PathBuf,
+        /// The corpus, as for verify
+        #[arg(long)]
+        vectors: Option<PathBuf>,
+    },
+    /// Merges the vectors with those of Wycheproof (eddsa_test.json) and ZIP-215 into
+    /// merged.json
+    Merge {
+        /// The Wycheproof EdDSA file, eddsa_test.json
+        wycheproof: PathBuf,
+    },
+    /// Runs the vectors of a Wycheproof EdDSA file (eddsa_test.json) through the verifiers
+    /// of this crate, printing where they depart from the expected verdicts
+    CrossCheck {
+        /// The Wycheproof EdDSA file, eddsa_test.json
+        wycheproof: PathBuf,
+    },
+    /// Sweeps the generators derived from the release one for instances of the cases with
+    /// rarer properties, printing their seeds
+    Search {
+        /// The property to look for (default: all of them)
+        #[arg(long, value_parser = rare_properties())]
+        property: Option<String>,
+        /// Index of the first derived generator
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        from: u64,
+        /// Number of generators to sweep
+        #[arg(long, value_name = "N", default_value_t = 64)]
+        count: u64,
+        #[arg(long, default_value = "txt", value_parser = formats(&[Format::Txt, Format::Json]))]
+        format: Format,
+    },
+}
+
+/// The formats of the outputs of the commands, each accepting a subset of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    All,
+    Json,
+    Txt,
+    Table,
+    Markdown,
+    Html,
+    Csv,
+    Sarif,
+}
+
+impl Format {
+    const ALL: [Format; 8] = [
+        Format::All,
+        Format::Json,
+        Format::Txt,
+        Format::Table,
+        Format::Markdown,
+        Format::Html,
+        Format::Csv,
+        Format::Sarif,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Format::All => "all",
+            Format::Json => "json",
+            Format::Txt => "txt",
+            Format::Table => "table",
+            Format::Markdown => "markdown",
+            Format::Html => "html",
+            Format::Csv => "csv",
+            Format::Sarif => "sarif",
+        }
+    }
+}
+
+// The parser of the `--format` of a command, accepting the formats it writes only
+fn formats(accepted: &'static [Format]) -> impl TypedValueParser<Value = Format> {
+    PossibleValuesParser::new(accepted.iter().map(Format::name)).try_map(|name| {
+        Format::ALL
+            .iter()
+            .copied()
+            .find(|format| format.name() == name)
+            .ok_or_else(|| anyhow!("unknown format {}", name))
+    })
+}
+
+// The parser of `--corpus`, listing every corpus version in the help
+fn corpus_versions() -> impl TypedValueParser<Value = CorpusVersion> {
+    PossibleValuesParser::new(
+        CorpusVersion::ALL
+            .iter()
+            .map(|version| PossibleValue::new(version.name()).help(version.summary())),
+    )
+    .try_map(|name| name.parse::<CorpusVersion>())
+}
+
+// The parser of `--property`, listing `search::RARE_PROPERTIES` in the help
+fn rare_properties() -> PossibleValuesParser {
+    PossibleValuesParser::new(
+        RARE_PROPERTIES
+            .iter()
+            .map(|property| PossibleValue::new(property.name).help(property.description)),
+    )
+}
+
+/// A command of the command line utility, as parsed by `parse`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Generate {
+        out_dir: PathBuf,
+        format: Format,
+        selection: Option<Vec<usize>>,
+        seed: Option<u64>,
+        corpus: CorpusVersion,
+        explain: bool,
+    },
+    Verify {
+        policy: ValidationPolicy,
+        vectors: Option<PathBuf>,
+        selection: Option<Vec<usize>>,
+        format: Format,
+    },
+    Classify {
+        vectors: Option<PathBuf>,
+        // None when asking for the verdicts interactively
+        verdicts: Option<Vec<Option<Verdict>>>,
+    },
+    Report {
+        policy: ValidationPolicy,
+        libraries: Vec<(String, Vec<Verdict>)>,
+        // the rows run on the vectors, after those of `libraries`
+        externals: Vec<(String, External)>,
+        format: Format,
+    },
+    Explain {
+        library: (String, Vec<Verdict>),
+        vectors: Option<PathBuf>,
+    },
+    Check {
+        external: External,
+        vectors: Option<PathBuf>,
+    },
+    Differential {
+        externals: Vec<(String, External)>,
+        iterations: usize,
+        seed: Option<u64>,
+        minimize: bool,
+        format: Format,
+    },
+    Metrics,
+    Malleability,
+    ReproCheck {
+        corpus: CorpusVersion,
+    },
+    ValidateContribution {
+        contribution: PathBuf,
+        vectors: Option<PathBuf>,
+    },
+    Merge(PathBuf),
+    CrossCheck(PathBuf),
+    Search {
+        property: Option<String>,
+        indices: Range<u64>,
+        format: Format,
+    },
+    /// The help of the utility, or of one of its commands.
+    Help(String),
+}
+
+/// Parses the arguments of the utility, without the name of the binary. No arguments at all
+/// generate every file in the current directory, as releases prior to the subcommands did.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command> {
+    let cli = match Cli::try_parse_from(iter::once("ed25519-speccheck".to_string()).chain(args)) {
+        Ok(cli) => cli,
+        Err(e) if e.kind() == ErrorKind::DisplayHelp => return Ok(Command::Help(e.to_string())),
+        Err(e) => return Err(e.into()),
+    };
+    let args = match cli.command {
+        Some(args) => args,
+        None => {
+            return Ok(Command::Generate {
+                out_dir: PathBuf::from("."),
+                format: Format::All,
+                selection: None,
+                seed: None,
+                corpus: corpus_version::LATEST,
+                explain: false,
+            })
+        }
+    };
+
+    Ok(match args {
+        Args::Generate {
+            out_dir,
+            format,
+            select,
+            seed,
+            explain,
+            corpus,
+        } => Command::Generate {
+            out_dir,
+            format,
+            selection: select.map(|s| parse_selection(&s)).transpose()?,
+            seed,
+            corpus,
+            explain,
+        },
+        Args::Verify {
+            policy,
+            vectors,
+            select,
+            format,
+        } => Command::Verify {
+            policy,
+            vectors,
+            selection: select.map(|s| parse_selection(&s)).transpose()?,
+            format,
+        },
+        Args::Classify {
+            verdicts, vectors, ..
+        } => Command::Classify {
+            vectors,
+            verdicts: verdicts.map(|v| parse_verdicts(&v)).transpose()?,
+        },
+        Args::Report {
+            libraries,
+            policy,
+            mut external,
+            mut external_stdin,
+            format,
+        } => Command::Report {
+            policy,
+            libraries,
+            externals: {
+                external.append(&mut external_stdin);
+                external
+            },
+            format,
+        },
+        Args::Explain { library, vectors } => Command::Explain { library, vectors },
+        Args::Check { cmd, vectors } => Command::Check {
+            external: cmd,
+            vectors,
+        },
+        Args::Differential {
+            mut external,
+            mut external_stdin,
+            iterations,
+            seed,
+            minimize,
+            format,
+        } => {
+            external.append(&mut external_stdin);
+            if external.len() < 2 {
+                return Err(anyhow!("differential compares at least two libraries"));
+            }
+            Command::Differential {
+                externals: external,
+                iterations,
+                seed,
+                minimize,
+                format,
+            }
+        }
+        Args::Metrics => Command::Metrics,
+        Args::Malleability => Command::Malleability,
+        Args::ReproCheck { corpus } => Command::ReproCheck { corpus },
+        Args::ValidateContribution {
+            contribution,
+            vectors,
+        } => Command::ValidateContribution {
+            contribution,
+            vectors,
+        },
+        Args::Merge { wycheproof } => Command::Merge(wycheproof),
+        Args::CrossCheck { wycheproof } => Command::CrossCheck(wycheproof),
+        Args::Search {
+            property,
+            from,
+            count,
+            format,
+        } => Command::Search {
+            property,
+            indices: from..from
+                .checked_add(count)
+                .ok_or_else(|| anyhow!("too many seeds"))?,
+            format,
+        },
+    })
+}
+
+/// Parses a selection of vectors: comma-separated indices, inclusive ranges of indices such
+/// as `4-7`, ids of case families (see `sarif::CASE_FAMILIES`) or of cases (see
+/// `test_vectors::CASE_IDS`), in the order given.
+pub fn parse_selection(spec: &str) -> Result<Vec<usize>> {
+    let mut selection = Vec::new();
+    for item in spec.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+        if let Some(family) = CASE_FAMILIES.iter().find(|family| family.id == item) {
+            selection.extend(family.cases.clone());
+            continue;
+        }
+        if let Some(case) = CASE_IDS.iter().position(|id| *id == item) {
+            selection.push(case);
+            continue;
+        }
+        let index = |s: &str| {
+            s.parse::<usize>().map_err(|_| {
+                anyhow!(
+                    "{} is neither an index, a range, a case family nor a case",
+                    item
+                )
+            })
+        };
+        match item.find('-') {
+            Some(dash) => {
+                let (first, last) = (index(&item[..dash])?, index(&item[dash + 1..])?);
+                if first > last {
+                    return Err(anyhow!("empty range {}", item));
+                }
+                selection.extend(first..=last);
+            }
+            None => selection.push(index(item)?),
+        }
+    }
+    if selection.is_empty() {
+        return Err(anyhow!("no vector selected"));
+    }
+    Ok(selection)
+}
+
+// Parses `<name>=<command>`, the command split on whitespace as `External::parse` does
+fn parse_external(external: &str, input: Input) -> Result<(String, External)> {
+    let eq = external
+        .find('=')
+        .ok_or_else(|| anyhow!("expected <name>=<command>, got {}", external))?;
+    Ok((
+        external[..eq].to_string(),
+        External::parse(&external[eq + 1..], input)?,
+    ))
+}
+
+// Parses the policy of a verifier of this crate by its name in `matrix::KNOWN_POLICIES`, e.g.
+// cofactored rather than the single check of that name, else as `ValidationPolicy::from_str`
+fn parse_policy(spec: &str) -> Result<ValidationPolicy> {
+    match KNOWN_POLICIES.iter().find(|(name, _)| *name == spec.trim()) {
+        Some((_, policy)) => Ok(*policy),
+        None => spec.parse(),
+    }
+}
+
+// Parses a verdict per vector, as the symbols of the results tables, with `?` for the vectors
+// not tried. Whitespace and the `|` separating the columns of the tables are skipped.
+fn parse_verdicts(verdicts: &str) -> Result<Vec<Option<Verdict>>> {
+    verdicts
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '|')
+        .map(|c| match c.to_ascii_uppercase() {
+            'V' => Ok(Some(Verdict::Accepted)),
+            'P' => Ok(Some(Verdict::ParseRejected)),
+            'X' => Ok(Some(Verdict::VerifyRejected)),
+            'E' => Ok(Some(Verdict::Error)),
+            '?' => Ok(None),
+            _ => Err(anyhow!("unknown verdict {}, expected V, P, X, E or ?", c)),
+        })
+        .collect()
+}
+
+// Parses `<name>=<verdicts>`, where every vector must have been tried.
+fn parse_library(library: &str) -> Result<(String, Vec<Verdict>)> {
+    let eq = library
+        .find('=')
+        .ok_or_else(|| anyhow!("expected <name>=<verdicts>, got {}", library))?;
+    let verdicts = parse_verdicts(&library[eq + 1..])?
+        .into_iter()
+        .collect::<Option<Vec<Verdict>>>()
+        .ok_or_else(|| anyhow!("the verdicts of {} must not contain ?", &library[..eq]))?;
+    Ok((library[..eq].to_string(), verdicts))
+}
+
+/// Runs `command`, writing what it prints to `output`; the files it generates are written
+/// to disk.
+pub fn run<W: Write>(command: &Command, output: &mut W) -> Result<()> {
+    match command {
+        Command::Generate {
+            out_dir,
+            format,
+            selection,
+            seed,
+            corpus,
+            explain,
+        } => generate(
+            out_dir,
+            *format,
+            selection.as_deref(),
+            *seed,
+            *corpus,
+            *explain,
+        ),
+        Command::Verify {
+            policy,
+            vectors,
+            selection,
+            format,
+        } => {
+            let vectors = load_vectors(vectors.as_deref())?;
+            let indices = select(vectors.len(), selection.as_deref())?;
+            let selected: Vec<TestVector> = indices.iter().map(|&i| vectors[i].clone()).collect();
+            let verdicts = policy.predict(&selected);
+            if *format == Format::Json {
+                let verdicts: Vec<_> = indices
+                    .iter()
+                    .zip(verdicts.iter())
+                    .map(|(i, verdict)| json!({ "index": i, "verdict": verdict }))
+                    .collect();
+                writeln!(output, "{}", serde_json::to_string(&verdicts)?)?;
+            } else {
+                writeln!(output, "policy: {}", policy)?;
+                for (i, verdict) in indices.iter().zip(verdicts.iter()) {
+                    writeln!(output, "{:>2} {}", i, verdict)?;
+                }
+                let count = |of: Verdict| verdicts.iter().filter(|v| **v == of).count();
+                writeln!(
+                    output,
+                    "{} vectors: {} accepted, {} rejected while parsing, {} rejected while \
+                     verifying, {} errored",
+                    verdicts.len(),
+                    count(Verdict::Accepted),
+                    count(Verdict::ParseRejected),
+                    count(Verdict::VerifyRejected),
+                    count(Verdict::Error)
+                )?;
+            }
+            Ok(())
+        }
+        Command::Classify { vectors, verdicts } => {
+            let vectors = load_vectors(vectors.as_deref())?;
+            match verdicts {
+                None => {
+                    let stdin = std::io::stdin();
+                    policy::fingerprint_interactive(&vectors, stdin.lock(), output)?;
+                }
+                Some(observed) => {
+                    let inference = infer_from_partial(&vectors, observed)?;
+                    writeln!(
+                        output,
+                        "{} consistent policies:",
+                        inference.consistent.len()
+                    )?;
+                    for policy in inference.consistent.iter() {
+                        writeln!(output, "  {}", policy)?;
+                    }
+                    if !inference.is_conclusive() {
+                        let indices: Vec<String> = inference
+                            .disambiguating
+                            .iter()
+                            .map(usize::to_string)
+                            .collect();
+                        writeln!(output, "vectors telling them apart: {}", indices.join(", "))?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        Command::Report {
+            policy,
+            libraries,
+            externals,
+            format,
+        } => {
+            let vectors = generate_test_vectors();
+            let mut libraries = libraries.clone();
+            for (name, external) in externals.iter() {
+                libraries.push((name.clone(), verifier::verdicts(external, &vectors)));
+            }
+            for (name, verdicts) in libraries.iter() {
+                if verdicts.len() != vectors.len() {
+                    return Err(anyhow!(
+                        "{}: got {} observed verdicts for {} test vectors",
+                        name,
+                        verdicts.len(),
+                        vectors.len()
+                    ));
+                }
+            }
+            if *format == Format::Sarif {
+                let observations: Vec<(&str, &[Verdict])> = libraries
+                    .iter()
+                    .map(|(name, verdicts)| (name.as_str(), &verdicts[..]))
+                    .collect();
+                let log = sarif::export(policy, &observations)?;
+                writeln!(output, "{}", serde_json::to_string_pretty(&log)?)?;
+                return Ok(());
+            }
+            let mut table = ResultsTable::new();
+            table.push("expected", policy.predict(&vectors));
+            for (name, verdicts) in libraries.iter() {
+                table.push(name, verdicts.clone());
+            }
+            match format {
+                Format::Markdown => write!(output, "{}", table.to_markdown())?,
+                Format::Html => write!(output, "{}", table.to_html())?,
+                Format::Csv => write!(output, "{}", table.to_csv())?,
+                _ => write!(output, "{}", table)?,
+            }
+            Ok(())
+        }
+        Command::Explain {
+            library: (name, verdicts),
+            vectors,
+        } => {
+            let vectors = load_vectors(vectors.as_deref())?;
+            write!(output, "{}", explain::explain(name, &vectors, verdicts)?)?;
+            Ok(())
+        }
+        Command::Check { external, vectors } => {
+            write_check(external, &load_vectors(vectors.as_deref())?, output)
+        }
+        Command::Differential {
+            externals,
+            iterations,
+            seed,
+            minimize,
+            format,
+        } => write_differential(externals, *iterations, *seed, *minimize, *format, output),
+        Command::Metrics => write_metrics(output),
+        Command::Malleability => malleability::report(&mut new_rng(), output),
+        Command::ReproCheck { corpus } => repro::report(*corpus, output),
+        Command::ValidateContribution {
+            contribution,
+            vectors,
+        } => validate_contribution(contribution, vectors.as_deref(), output),
+        Command::Merge(path) => merge_upstream(path, output),
+        Command::CrossCheck(path) => upstream::cross_check_report(
+            &upstream::import_wycheproof(&std::fs::read_to_string(path)?)?,
+            &upstream::crate_verifiers(),
+            output,
+        ),
+        Command::Search {
+            property,
+            indices,
+            format,
+        } => {
+            let properties: Vec<RareProperty> = RARE_PROPERTIES
+                .iter()
+                .filter(|p| property.is_none() || property.as_deref() == Some(p.name))
+                .cloned()
+                .collect();
+            let findings = search::sweep(&VectorGenerator::default(), indices.clone(), &properties);
+            if *format == Format::Json {
+                writeln!(output, "{}", serde_json::to_string_pretty(&findings)?)?;
+            } else {
+                for finding in findings.iter() {
+                    writeln!(
+                        output,
+                        "{}: case {} of seed {} (derived #{})",
+                        finding.property,
+                        finding.case,
+                        hex::encode(finding.seed),
+                        finding.index
+                    )?;
+                }
+                writeln!(
+                    output,
+                    "{} instances in {} seeds",
+                    findings.len(),
+                    indices.end - indices.start
+                )?;
+            }
+            Ok(())
+        }
+        Command::Help(help) => Ok(write!(output, "{}", help)?),
+    }
+}
+
+fn generate(
+    out_dir: &Path,
+    format: Format,
+    selection: Option<&[usize]>,
+    seed: Option<u64>,
+    corpus: CorpusVersion,
+    explain: bool,
+) -> Result<()> {
+    let files = if selection.is_none() && seed.is_none() && !explain {
+        corpus
+            .artifacts()?
+            .into_iter()
+            .filter(|(name, _)| match format {
+                Format::Json => *name == "cases.json",
+                Format::Txt => *name == "cases.txt",
+                _ => true,
+            })
+            .collect()
+    } else {
+        // the other files are built from the vectors of the release, and are left out
+        let vectors = match seed {
+            Some(seed) => VectorGenerator::from_u64(seed).test_vectors(),
+            None => generate_test_vectors(),
+        };
+        let selected: Vec<TestVector> = select(vectors.len(), selection)?
+            .into_iter()
+            .map(|i| vectors[i].clone())
+            .collect();
+        let mut files = Vec::new();
+        if format != Format::Txt {
+            let json = if explain {
+                let explained = selected
+                    .iter()
+                    .map(TestVector::explained)
+                    .collect::<Result<Vec<_>>>()?;
+                serde_json::to_string(&explained)?.into_bytes()
+            } else {
+                io::encode_vectors(&selected, VectorFormat::Json)?
+            };
+            files.push(("cases.json", json));
+        }
+        if format != Format::Json {
+            files.push((
+                "cases.txt",
+                io::encode_vectors(&selected, VectorFormat::Txt)?,
+            ));
+        }
+        files
+    };
+
+    std::fs::create_dir_all(out_dir)?;
+    for (name, contents) in files {
+        std::fs::write(out_dir.join(name), contents)?;
+    }
+    Ok(())
+}
+
+// The vectors in the layout of `cases.txt` at `path` if it has the extension `txt`, else in
+// that of `cases.json`, or the generated ones.
+fn load_vectors(path: Option<&Path>) -> Result<Vec<TestVector>> {
+    match path {
+        Some(path) if path.extension() == Some("txt".as_ref()) => TestVector::from_txt_file(path),
+        Some(path) => TestVector::from_json_file(path),
+        None => Ok(generate_test_vectors()),
+    }
+}
+
+// The indices of the selected vectors out of `len`, all of them by default.
+fn select(len: usize, selection: Option<&[usize]>) -> Result<Vec<usize>> {
+    match selection {
+        Some(indices) => match indices.iter().find(|&&i| i >= len) {
+            Some(i) => Err(anyhow!("no vector #{}, out of {}", i, len)),
+            None => Ok(indices.to_vec()),
+        },
+        None => Ok((0..len).collect()),
+    }
+}
+
+// Prints the normalized entry of the contribution at `path`, or fails after printing why it
+// is rejected
+fn validate_contribution<W: Write>(
+    path: &Path,
+    vectors: Option<&Path>,
+    output: &mut W,
+) -> Result<()> {
+    let contribution = Contribution::from_json(&std::fs::read_to_string(path)?)?;
+    let review = contribution::review(&contribution, &load_vectors(vectors)?)?;
+    if review.is_accepted() {
+        writeln!(output, "{}", serde_json::to_string(&review.entry)?)?;
+        return Ok(());
+    }
+    for discrepancy in review.discrepancies.iter() {
+        writeln!(output, "{}", discrepancy)?;
+    }
+    if let Some(case) = review.equivalent_to {
+        writeln!(
+            output,
+            "every policy reaches the same verdicts as on case {}",
+            case
+        )?;
+    }
+    Err(anyhow!("{} is rejected", path.display()))
+}
+
+// Writes the vectors of speccheck, Wycheproof and ZIP-215 merged in `merged.json`, and
+// prints how the suites compare
+fn merge_upstream<W: Write>(wycheproof_path: &Path, output: &mut W) -> Result<()> {
+    let mut vectors = upstream::speccheck_vectors();
+    vectors.append(&mut upstream::import_wycheproof(&std::fs::read_to_string(
+        wycheproof_path,
+    )?)?);
+    vectors.append(&mut upstream::zip215_vectors());
+
+    upstream::Coverage::new(&vectors).report(output)?;
+    let merged = upstream::merge(vectors);
+    writeln!(
+        output,
+        "{} distinct vectors written to merged.json",
+        merged.len()
+    )?;
+    std::fs::write("merged.json", serde_json::to_string(&merged)?)?;
+    Ok(())
+}
+
+// Prints the verdicts of `external` on `vectors`, the policies consistent with them, or the
+// closest one, and a warning per security property it breaks, with the cases showing it
+fn write_check<W: Write>(
+    external: &External,
+    vectors: &[TestVector],
+    output: &mut W,
+) -> Result<()> {
+    let observed = verifier::verdicts(external, vectors);
+    if observed.iter().all(|v| v.is_error()) {
+        return Err(anyhow!(
+            "the command errored on every vector: it could not be run, or was killed"
+        ));
+    }
+    let explanation = explain::explain("command", vectors, &observed)?;
+    let row: Vec<String> = observed.iter().map(Verdict::to_string).collect();
+    writeln!(output, "verdicts: {}", row.concat())?;
+    let cases = |indices: &[usize]| -> String {
+        let indices: Vec<String> = indices.iter().map(usize::to_string).collect();
+        indices.join(", ")
+    };
+    match (&explanation.closest, explanation.consistent.as_slice()) {
+        (None, [policy]) => writeln!(output, "inferred policy: {}", policy)?,
+        (None, consistent) => {
+            // the checks the verdicts settle, rather than every combination of the others
+            let undetermined: Vec<&str> = CHECK_NAMES
+                .iter()
+                .filter(|name| {
+                    consistent
+                        .iter()
+                        .any(|p| consistent[0].differing_checks(p).contains(name))
+                })
+                .cloned()
+                .collect();
+            let performed = consistent[0].differing_checks(&ValidationPolicy::new());
+            let (performing, skipping): (Vec<&str>, Vec<&str>) = CHECK_NAMES
+                .iter()
+                .filter(|name| !undetermined.contains(name))
+                .partition(|name| performed.contains(name));
+            writeln!(output, "{} consistent policies", consistent.len())?;
+            for (label, checks) in [
+                ("performing", performing),
+                ("skipping", skipping),
+                ("undetermined", undetermined),
+            ]
+            .iter()
+            {
+                if !checks.is_empty() {
+                    writeln!(output, "  {}: {}", label, checks.join(", "))?;
+                }
+            }
+        }
+        (Some((policy, departing)), _) => {
+            writeln!(output, "no consistent policy, the closest being:")?;
+            writeln!(output, "  {}", policy)?;
+            writeln!(output, "  departing on cases {}", cases(departing))?;
+        }
+    }
+    let errored: Vec<usize> = (0..observed.len())
+        .filter(|&i| observed[i].is_error())
+        .collect();
+    if !errored.is_empty() {
+        writeln!(
+            output,
+            "warning: errored on cases {}, left out of the inference",
+            cases(&errored)
+        )?;
+    }
+    for finding in explanation.findings.iter() {
+        if !finding.is_achieved() {
+            writeln!(
+                output,
+                "warning: {} (cases {}); {}",
+                finding.property.warning(),
+                cases(&finding.evidence),
+                finding.property.recommendation()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// Prints the vectors on which the `externals` disagree, out of `iterations` random ones,
+// each followed by its minimized form if `minimize`
+fn write_differential<W: Write>(
+    externals: &[(String, External)],
+    iterations: usize,
+    seed: Option<u64>,
+    minimize: bool,
+    format: Format,
+    output: &mut W,
+) -> Result<()> {
+    let mut registry = Registry::new();
+    for (name, external) in externals.iter() {
+        registry.register_verifier(name, external.clone());
+    }
+    let mut rng = seed
+        .map(VectorGenerator::from_u64)
+        .unwrap_or_default()
+        .rng();
+    let disagreements = differential::differential_fuzz(&registry, &mut rng, iterations);
+    let cases = generate_test_vectors();
+    let minimizer = if minimize {
+        Some(Minimizer::new(&registry, &cases))
+    } else {
+        None
+    };
+    let minimized: Vec<Option<Minimized>> = disagreements
+        .iter()
+        .map(|d| minimizer.as_ref().map(|m| m.minimize(&d.vector)))
+        .collect();
+
+    if format == Format::Json {
+        let mut entries = Vec::new();
+        for (disagreement, minimized) in disagreements.iter().zip(minimized.iter()) {
+            let mut entry = serde_json::to_value(disagreement)?;
+            if let Some(minimized) = minimized {
+                entry["minimized"] = serde_json::to_value(minimized)?;
+            }
+            entries.push(entry);
+        }
+        writeln!(output, "{}", serde_json::to_string_pretty(&entries)?)?;
+        return Ok(());
+    }
+    let write_vector = |output: &mut W, verdicts: &[(String, Verdict)], tv: &TestVector| {
+        let verdicts: Vec<String> = verdicts
+            .iter()
+            .map(|(name, verdict)| format!("{} {}", name, verdict))
+            .collect();
+        writeln!(
+            output,
+            "{}\n  msg={}\n  pbk={}\n  sig={}",
+            verdicts.join(", "),
+            hex::encode(&tv.message),
+            hex::encode(tv.pub_key),
+            hex::encode(&tv.signature)
+        )
+    };
+    for (disagreement, minimized) in disagreements.iter().zip(minimized.iter()) {
+        write!(output, "{}: ", disagreement.kind)?;
+        write_vector(output, &disagreement.verdicts, &disagreement.vector)?;
+        if let Some(minimized) = minimized {
+            write!(output, "  minimized: ")?;
+            write_vector(output, &minimized.verdicts, &minimized.vector)?;
+            match minimized.equivalent_to {
+                Some(case) => writeln!(output, "  equivalent to case {}", case)?,
+                None => writeln!(output, "  novel")?,
+            }
+        }
+    }
+    writeln!(
+        output,
+        "{} disagreements in {} signatures",
+        disagreements.len(),
+        iterations
+    )?;
+    Ok(())
+}
+
+// Writes the operations counted while the most lenient and the strictest policies verify
+// the test vectors, in the Prometheus text format
+fn write_metrics<W: Write>(output: &mut W) -> Result<()> {
+    let vec = generate_test_vectors();
+    let lenient = ValidationPolicy::default();
+    let strict = ValidationPolicy {
+        reject_small_order_pk: true,
+        reject_noncanonical_pk: true,
+        reject_small_order_r: true,
+        reject_noncanonical_r: true,
+        require_canonical_s: true,
+        cofactored: true,
+        prereduce: false,
+        reserialize_for_hash: false,
+        require_full_order_pk: true,
+    };
+    let policies = [lenient, strict];
+    let checks: Vec<String> = policies.iter().map(ValidationPolicy::to_string).collect();
+    let labels: Vec<[(&str, &str); 1]> = checks.iter().map(|c| [("policy", &c[..])]).collect();
+    let series: Vec<(&[(&str, &str)], Metrics)> = labels
+        .iter()
+        .zip(policies.iter())
+        .map(|(labels, policy)| {
+            let mut counted = Metrics::default();
+            policy.predict_with_metrics(&vec, &mut counted);
+            (&labels[..], counted)
+        })
+        .collect();
+    metrics::write_prometheus(output, &series)
+}
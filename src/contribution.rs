@@ -0,0 +1,198 @@
+use crate::policy::{ValidationPolicy, NAMED_POLICIES};
+use crate::test_vectors::{case_metadata, CaseMetadata, SClass, TestVector};
+use crate::verdict::Verdict;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// The name of the family of the entries `review` normalizes, in their `CaseMetadata`.
+pub const CONTRIBUTED: &str = "Contributed";
+
+/// A vector proposed for the corpus, in the layout of an entry of `cases.json` along with
+/// a `claim` of what it exercises.
+#[derive(Clone, Deserialize)]
+pub struct Contribution {
+    #[serde(flatten)]
+    pub vector: TestVector,
+    pub claim: Claim,
+}
+
+/// The metadata a contributor claims for a vector, as the fields of `CaseMetadata`. Only
+/// the comment is required: the fields left out are derived without being checked.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Claim {
+    pub comment: String,
+    pub s_class: Option<SClass>,
+    pub a_order: Option<String>,
+    pub r_order: Option<String>,
+    pub canonical_a: Option<bool>,
+    pub canonical_r: Option<bool>,
+    pub expected_cofactored: Option<bool>,
+    pub expected_cofactorless: Option<bool>,
+    /// Verdicts of some of `policy::NAMED_POLICIES`, by name.
+    #[serde(default)]
+    pub expected_verdicts: BTreeMap<String, Verdict>,
+}
+
+impl Contribution {
+    pub fn from_json(json: &str) -> Result<Contribution> {
+        serde_json::from_str(json).map_err(|e| anyhow!("contribution: {}", e))
+    }
+}
+
+/// The outcome of the mechanical review of a contribution.
+#[derive(Clone)]
+pub struct Review {
+    /// The vector as it would enter the corpus, with the metadata derived for it in place
+    /// of the claimed one: the family `CONTRIBUTED`, the next index of the corpus, the
+    /// claimed comment, and the verdicts of the named policies.
+    pub entry: TestVector,
+    /// The claims departing from the derived metadata, e.g. `a_order: claimed 8, derived 4`.
+    pub discrepancies: Vec<String>,
+    /// An existing case on which every policy reaches the same verdict as on the vector,
+    /// which then tells no policies apart that the corpus does not already.
+    pub equivalent_to: Option<usize>,
+}
+
+impl Review {
+    pub fn is_accepted(&self) -> bool {
+        self.discrepancies.is_empty() && self.equivalent_to.is_none()
+    }
+}
+
+/// Reviews `contribution` for inclusion in `corpus`: re-derives its metadata, checks the
+/// claim against it, and looks for a case of `corpus` equivalent to it under every
+/// `ValidationPolicy`. The equations of `expected_cofactored` and `expected_cofactorless`
+/// hash A and R as provided. Fails on a vector no policy gets to verify, whose signature
+/// is not 64 bytes or whose A or R does not decompress.
+pub fn review(contribution: &Contribution, corpus: &[TestVector]) -> Result<Review> {
+    let tv = &contribution.vector;
+    let claim = &contribution.claim;
+    let accepts = |policy: ValidationPolicy| {
+        policy
+            .verify(&tv.message, &tv.pub_key, &tv.signature)
+            .is_ok()
+    };
+    let cofactorless = ValidationPolicy::default();
+    let cofactored = ValidationPolicy {
+        cofactored: true,
+        ..cofactorless
+    };
+    let mut metadata = case_metadata(
+        tv,
+        CONTRIBUTED,
+        corpus.len(),
+        claim.comment.trim(),
+        (accepts(cofactored), accepts(cofactorless)),
+    )?;
+    for (name, policy) in NAMED_POLICIES.iter() {
+        let verdict = policy.verdict(&tv.message, &tv.pub_key, &tv.signature);
+        metadata.expected_verdicts.insert(name.to_string(), verdict);
+    }
+
+    let mut discrepancies = claim_discrepancies(claim, &metadata);
+    if claim.comment.trim().is_empty() {
+        discrepancies.push("comment: missing".to_string());
+    }
+
+    let profile = policy_profile(tv);
+    let equivalent_to = corpus
+        .iter()
+        .position(|case| policy_profile(case) == profile);
+
+    Ok(Review {
+        entry: TestVector {
+            metadata: Some(metadata),
+            ..tv.clone()
+        },
+        discrepancies,
+        equivalent_to,
+    })
+}
+
+/// The verdict of every `ValidationPolicy` on `tv`, in the order of `ValidationPolicy::all`:
+/// two vectors with the same profile tell the same policies apart.
+pub fn policy_profile(tv: &TestVector) -> Vec<Verdict> {
+    ValidationPolicy::all()
+        .iter()
+        .map(|policy| policy.verdict(&tv.message, &tv.pub_key, &tv.signature))
+        .collect()
+}
+
+// The claimed fields departing from those of `derived`
+fn claim_discrepancies(claim: &Claim, derived: &CaseMetadata) -> Vec<String> {
+    fn check<T: PartialEq + Display>(
+        discrepancies: &mut Vec<String>,
+        field: &str,
+        claimed: Option<&T>,
+        derived: &T,
+    ) {
+        if let Some(claimed) = claimed {
+            if claimed != derived {
+                discrepancies.push(format!(
+                    "{}: claimed {}, derived {}",
+                    field, claimed, derived
+                ));
+            }
+        }
+    }
+
+    let mut discrepancies = Vec::new();
+    let s_class = |class: &SClass| format!("{:?}", class).to_lowercase();
+    check(
+        &mut discrepancies,
+        "s_class",
+        claim.s_class.as_ref().map(s_class).as_ref(),
+        &s_class(&derived.s_class),
+    );
+    check(
+        &mut discrepancies,
+        "a_order",
+        claim.a_order.as_ref(),
+        &derived.a_order,
+    );
+    check(
+        &mut discrepancies,
+        "r_order",
+        claim.r_order.as_ref(),
+        &derived.r_order,
+    );
+    check(
+        &mut discrepancies,
+        "canonical_a",
+        claim.canonical_a.as_ref(),
+        &derived.canonical_a,
+    );
+    check(
+        &mut discrepancies,
+        "canonical_r",
+        claim.canonical_r.as_ref(),
+        &derived.canonical_r,
+    );
+    check(
+        &mut discrepancies,
+        "expected_cofactored",
+        claim.expected_cofactored.as_ref(),
+        &derived.expected_cofactored,
+    );
+    check(
+        &mut discrepancies,
+        "expected_cofactorless",
+        claim.expected_cofactorless.as_ref(),
+        &derived.expected_cofactorless,
+    );
+    for (name, claimed) in claim.expected_verdicts.iter() {
+        match derived.expected_verdicts.get(name) {
+            Some(verdict) => check(
+                &mut discrepancies,
+                &format!("expected_verdicts.{}", name),
+                Some(claimed),
+                verdict,
+            ),
+            None => discrepancies.push(format!("expected_verdicts.{}: unknown policy", name)),
+        }
+    }
+    discrepancies
+}
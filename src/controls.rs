@@ -0,0 +1,140 @@
+use crate::signing::SigningKey;
+use crate::test_vectors::TestVector;
+use rand::RngCore;
+use serde::Serialize;
+use sha2::{Digest, Sha512};
+
+/// The expectation of every control vector: each verifier, whatever the checks it performs,
+/// accepts it.
+pub const VALID_EVERYWHERE: &str = "valid-everywhere";
+
+/// The expectation of every negative control vector: each verifier rejects it.
+pub const INVALID_EVERYWHERE: &str = "invalid-everywhere";
+
+/// A signature made by an honest signer, as RFC 8032 does, which every verifier accepts, or
+/// one damaged beyond what any verifier accepts: a harness rejecting the former, or
+/// accepting the latter, is broken, e.g. in how it decodes the files or calls the library,
+/// and its verdicts on the other vectors tell nothing.
+#[derive(Serialize)]
+pub struct ControlVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// `VALID_EVERYWHERE` or `INVALID_EVERYWHERE`.
+    pub expected: &'static str,
+}
+
+// The seeds and messages of the tests of RFC 8032, Section 7.1, but TEST 1024, the message
+// of TEST SHA(abc) being the SHA-512 digest of "abc"
+const RFC_8032_TESTS: [(&str, &str, &[u8]); 3] = [
+    (
+        "TEST 1",
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        b"",
+    ),
+    (
+        "TEST 2",
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        b"\x72",
+    ),
+    (
+        "TEST 3",
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        b"\xaf\x82",
+    ),
+];
+const RFC_8032_TEST_SHA_ABC: &str =
+    "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42";
+
+// The lengths of the messages of the random controls
+const MESSAGE_LENGTHS: [usize; 5] = [0, 1, 32, 64, 1000];
+
+fn control(comment: String, key: &SigningKey, message: &[u8]) -> ControlVector {
+    ControlVector {
+        comment,
+        vector: key.sign_vector(message),
+        expected: VALID_EVERYWHERE,
+    }
+}
+
+fn seed(hex_seed: &str) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    // the seeds above are 32 bytes of hex
+    seed.copy_from_slice(&hex::decode(hex_seed).unwrap());
+    seed
+}
+
+/// The tests of RFC 8032, Section 7.1, but TEST 1024, then a signature under a fresh key of
+/// a random message of each length of 0, 1, 32, 64 and 1000 bytes.
+pub fn control_vectors(rng: &mut impl RngCore) -> Vec<ControlVector> {
+    let mut vectors: Vec<ControlVector> = RFC_8032_TESTS
+        .iter()
+        .map(|(name, hex_seed, message)| {
+            let key = SigningKey::from_seed(&seed(hex_seed));
+            control(format!("RFC 8032, Section 7.1, {}", name), &key, message)
+        })
+        .collect();
+    let key = SigningKey::from_seed(&seed(RFC_8032_TEST_SHA_ABC));
+    vectors.push(control(
+        "RFC 8032, Section 7.1, TEST SHA(abc)".to_string(),
+        &key,
+        &Sha512::digest(b"abc"),
+    ));
+
+    for &len in MESSAGE_LENGTHS.iter() {
+        let key = SigningKey::generate(rng);
+        let mut message = vec![0u8; len];
+        rng.fill_bytes(&mut message);
+        vectors.push(control(
+            format!("a random message of {} bytes under a fresh key", len),
+            &key,
+            &message,
+        ));
+    }
+    vectors
+}
+
+// A signature under a fresh key of a random message
+fn fresh_signature(rng: &mut impl RngCore) -> TestVector {
+    let key = SigningKey::generate(rng);
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    key.sign_vector(&message)
+}
+
+/// Negative controls, each made from a fresh signature of a random message: a random
+/// signature, the signature with the lowest bit of S then of R flipped, and the signature
+/// truncated to 0, 32 and 63 bytes or extended to 65 bytes.
+pub fn negative_control_vectors(rng: &mut impl RngCore) -> Vec<ControlVector> {
+    let mut damaged = Vec::new();
+
+    let mut tv = fresh_signature(rng);
+    rng.fill_bytes(&mut tv.signature[..]);
+    damaged.push(("a random signature".to_string(), tv));
+
+    let mut tv = fresh_signature(rng);
+    tv.signature[32] ^= 1;
+    damaged.push(("the lowest bit of S flipped".to_string(), tv));
+
+    let mut tv = fresh_signature(rng);
+    tv.signature[0] ^= 1;
+    damaged.push(("the lowest bit of R flipped".to_string(), tv));
+
+    for &len in [0, 32, 63].iter() {
+        let mut tv = fresh_signature(rng);
+        tv.signature.truncate(len);
+        damaged.push((format!("the signature truncated to {} bytes", len), tv));
+    }
+
+    let mut tv = fresh_signature(rng);
+    tv.signature.push(0);
+    damaged.push(("the signature extended to 65 bytes".to_string(), tv));
+
+    damaged
+        .into_iter()
+        .map(|(comment, vector)| ControlVector {
+            comment,
+            vector,
+            expected: INVALID_EVERYWHERE,
+        })
+        .collect()
+}
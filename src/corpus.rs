@@ -0,0 +1,267 @@
+use crate::test_vectors::TestVector;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::iter;
+use std::mem;
+use std::path::Path;
+
+/// The layout of a corpus file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// A JSON array of vectors, as `cases.json` and the other artifacts of the generator.
+    Json,
+    /// A vector per line, as written by `write_jsonl`.
+    Jsonl,
+}
+
+/// Reads the vectors of a corpus one at a time, holding a single vector in memory whatever
+/// the size of the corpus, for consumers which cannot load it whole. Vectors are looked up by
+/// the stable id of their case, see `CaseMetadata::id`, or by their family, so that lookups
+/// survive cases being inserted; those skipped have their metadata decoded only.
+pub struct CorpusReader<R> {
+    reader: R,
+    layout: Layout,
+    position: usize,
+    started: bool,
+    done: bool,
+    // Whether `record` holds the next vector, read ahead by a lookup
+    pending: bool,
+    record: Vec<u8>,
+}
+
+// The part of a vector a lookup decodes
+#[derive(Deserialize)]
+struct Probe {
+    #[serde(default)]
+    metadata: Option<ProbeMetadata>,
+}
+
+#[derive(Deserialize)]
+struct ProbeMetadata {
+    #[serde(default)]
+    id: String,
+    name: String,
+}
+
+impl CorpusReader<BufReader<File>> {
+    /// Opens a corpus file, read as JSON lines if its extension is `jsonl`, and as a JSON
+    /// array otherwise.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let layout = match path.extension() {
+            Some(extension) if extension == "jsonl" => Layout::Jsonl,
+            _ => Layout::Json,
+        };
+        let file = File::open(path).map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+        Ok(CorpusReader::new(BufReader::new(file), layout))
+    }
+}
+
+impl<R: BufRead> CorpusReader<R> {
+    pub fn new(reader: R, layout: Layout) -> Self {
+        CorpusReader {
+            reader,
+            layout,
+            position: 0,
+            started: false,
+            done: false,
+            pending: false,
+            record: Vec::new(),
+        }
+    }
+
+    /// The position of the next vector to be read in the corpus, starting from 0.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Skips to the vector with the id `id`, without decoding more than the metadata of those
+    /// before it, and fails if the corpus ends before it.
+    pub fn skip_to(&mut self, id: &str) -> Result<()> {
+        if self.next_matching(|metadata| metadata.id == id)? {
+            Ok(())
+        } else {
+            Err(anyhow!("no vector {}", id))
+        }
+    }
+
+    /// The vectors from the one with the id `first` to the one with the id `last`, both
+    /// included, reading no further than the latter, or to the end of the corpus if it is
+    /// missing.
+    pub fn range<'a>(
+        mut self,
+        first: &str,
+        last: &'a str,
+    ) -> impl Iterator<Item = Result<TestVector>> + 'a
+    where
+        R: 'a,
+    {
+        let skipped = self.skip_to(first);
+        let mut past = false;
+        skipped
+            .err()
+            .map(Err)
+            .into_iter()
+            .chain(self.take_while(move |read| {
+                !mem::replace(&mut past, matches!(read, Ok(tv) if tv.id() == Some(last)))
+            }))
+    }
+
+    /// The vectors of the family `name`, see `CaseMetadata::name`, without decoding more than
+    /// the metadata of the others. Vectors without metadata belong to no family.
+    pub fn family<'a>(mut self, name: &'a str) -> impl Iterator<Item = Result<TestVector>> + 'a
+    where
+        R: 'a,
+    {
+        iter::from_fn(
+            move || match self.next_matching(|metadata| metadata.name == name) {
+                Ok(true) => self.next(),
+                Ok(false) => None,
+                Err(e) => Some(Err(e)),
+            },
+        )
+    }
+
+    // Reads ahead to the next vector whose metadata satisfies `wanted`, returning false at
+    // the end of the corpus
+    fn next_matching(&mut self, wanted: impl Fn(&ProbeMetadata) -> bool) -> Result<bool> {
+        loop {
+            if !self.next_record()? {
+                return Ok(false);
+            }
+            let probe: Probe = serde_json::from_slice(&self.record).map_err(|e| {
+                self.done = true;
+                anyhow!("vector {}: {}", self.position, e)
+            })?;
+            if matches!(&probe.metadata, Some(metadata) if wanted(metadata)) {
+                self.pending = true;
+                return Ok(true);
+            }
+            self.position += 1;
+        }
+    }
+
+    // Reads the encoding of the next vector into `record`, returning false at the end of the
+    // corpus
+    fn next_record(&mut self) -> Result<bool> {
+        if mem::replace(&mut self.pending, false) {
+            return Ok(true);
+        }
+        self.record.clear();
+        if self.done {
+            return Ok(false);
+        }
+        let read = match self.layout {
+            Layout::Jsonl => self.next_line(),
+            Layout::Json => self.next_element(),
+        };
+        if !matches!(read, Ok(true)) {
+            self.done = true;
+        }
+        read
+    }
+
+    fn next_line(&mut self) -> Result<bool> {
+        loop {
+            self.record.clear();
+            if self.reader.read_until(b'\n', &mut self.record)? == 0 {
+                return Ok(false);
+            }
+            if !self.record.iter().all(u8::is_ascii_whitespace) {
+                return Ok(true);
+            }
+        }
+    }
+
+    // Reads the next element of the array, tracking the nesting of brackets outside of the
+    // strings to find its end
+    fn next_element(&mut self) -> Result<bool> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for byte in (&mut self.reader).bytes() {
+            let byte = byte?;
+            if depth == 0 {
+                match byte {
+                    b'[' if !self.started => self.started = true,
+                    b',' if self.started => {}
+                    b']' if self.started => return Ok(false),
+                    b'{' if self.started => {
+                        depth = 1;
+                        self.record.push(byte);
+                    }
+                    _ if byte.is_ascii_whitespace() => {}
+                    _ => {
+                        return Err(anyhow!(
+                            "vector {}: unexpected {:?}",
+                            self.position,
+                            byte as char
+                        ))
+                    }
+                }
+                continue;
+            }
+            self.record.push(byte);
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(true);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if depth > 0 || self.started {
+            Err(anyhow!(
+                "vector {}: unexpected end of corpus",
+                self.position
+            ))
+        } else {
+            Err(anyhow!("not a JSON array of vectors"))
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for CorpusReader<R> {
+    type Item = Result<TestVector>;
+
+    fn next(&mut self) -> Option<Result<TestVector>> {
+        match self.next_record() {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(e) => return Some(Err(e)),
+        }
+        let id = self.position;
+        self.position += 1;
+        Some(serde_json::from_slice(&self.record).map_err(|e| {
+            self.done = true;
+            anyhow!("vector {}: {}", id, e)
+        }))
+    }
+}
+
+/// Writes `vectors` a line each, for `CorpusReader` to read back in the `Jsonl` layout.
+pub fn write_jsonl<'a>(
+    mut writer: impl Write,
+    vectors: impl IntoIterator<Item = &'a TestVector>,
+) -> Result<()> {
+    for tv in vectors {
+        serde_json::to_writer(&mut writer, tv)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,367 @@
+use crate::batch_inconsistency::batch_inconsistency_vectors;
+use crate::batch_vectors::{batch_equation_vectors, interleaved_batches, misattribution_batches};
+use crate::controls::{control_vectors, negative_control_vectors};
+use crate::export;
+use crate::full_order::full_order_vectors;
+use crate::hram_pairs::hram_linked_pairs;
+use crate::identity_pubkey::identity_pubkey;
+use crate::identity_r::identity_r;
+use crate::io::{self, VectorFormat};
+use crate::key_substitution::key_substitution_pairs;
+use crate::large_messages::{message_length_vectors, store_message_vectors};
+use crate::large_s::large_s_family;
+use crate::malleability::malleability_pairs;
+use crate::new_rng;
+use crate::non_canonical::non_canonical_vectors;
+use crate::non_canonical_encodings::non_canonical_encoding_vectors;
+use crate::one_sided_non_canonical::one_sided_non_canonical_vectors;
+use crate::pre_reduction::pre_reduction_vectors;
+use crate::reduced_s::reduced_s_pairs;
+use crate::repro::digest;
+use crate::repudiation::repudiation;
+use crate::s_boundary::s_boundary_vectors;
+use crate::s_high_bits::s_high_bits_vectors;
+use crate::test_vectors::{ed25519ctx, ed25519ph, generate_test_vectors, TestVector};
+use crate::tiny_scalars::tiny_scalar_vectors;
+use crate::torsion_clearing::torsion_clearing_vectors;
+use crate::torsion_safe::normalization_vectors;
+use crate::torsion_sweep::sweep_torsion;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A frozen release of the generated files. Once released, a version always generates the
+/// same files, byte for byte, whatever the version of the crate: new families of vectors
+/// land in the version of the next release, so that downstream test suites pinning one are
+/// not affected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CorpusVersion {
+    /// The 12 vectors of the paper, in `cases.json` and `cases.txt`.
+    #[serde(rename = "corpus-v1")]
+    V1,
+    /// Those, with the stable id of each case (see `test_vectors::CASE_IDS`), along with the
+    /// Ed25519ph and Ed25519ctx cases, the batches and the families of vectors added since
+    /// (`full_order.json`, `s_high_bits.json`, ...).
+    #[serde(rename = "corpus-v2")]
+    V2,
+}
+
+/// The version generated by default.
+pub const LATEST: CorpusVersion = CorpusVersion::V2;
+
+/// The name of the manifest written along with the files of a version.
+pub const MANIFEST: &str = "corpus.json";
+
+impl CorpusVersion {
+    pub const ALL: [CorpusVersion; 2] = [CorpusVersion::V1, CorpusVersion::V2];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CorpusVersion::V1 => "corpus-v1",
+            CorpusVersion::V2 => "corpus-v2",
+        }
+    }
+
+    /// What the files of this version hold, in a line, e.g. for the help of the command line.
+    pub fn summary(&self) -> &'static str {
+        match self {
+            CorpusVersion::V1 => "the 12 vectors of the paper, in cases.json and cases.txt",
+            CorpusVersion::V2 => "those with the ids of the cases, and every family of vectors",
+        }
+    }
+
+    /// The version this one adds files to, if any.
+    pub fn previous(&self) -> Option<CorpusVersion> {
+        match self {
+            CorpusVersion::V1 => None,
+            CorpusVersion::V2 => Some(CorpusVersion::V1),
+        }
+    }
+
+    // The names of the files this version adds to those of the previous one
+    fn additions(&self) -> &'static [&'static str] {
+        match self {
+            CorpusVersion::V1 => &["cases.json", "cases.txt"],
+            CorpusVersion::V2 => &[
+                "cases_ph.json",
+                "cases_ctx.json",
+                "batches.json",
+                "batch_equations.json",
+                "hram_pairs.json",
+                "pre_reduction.json",
+                "tiny_scalars.json",
+                "full_order.json",
+                "torsion_clearing.json",
+                "cases_wycheproof.json",
+                "non_canonical.json",
+                "s_high_bits.json",
+                "large_s.json",
+                "identity_r.json",
+                "identity_pubkey.json",
+                "one_sided_non_canonical.json",
+                "non_canonical_encodings.json",
+                "torsion_sweep.json",
+                "key_substitution.json",
+                "repudiation.json",
+                "batch_inconsistency.json",
+                "controls.json",
+                "negative_controls.json",
+                "message_lengths.json",
+                "message_lengths.bin",
+                "normalization.json",
+                "s_boundary.json",
+                "reduced_s.json",
+                "malleability.json",
+            ],
+        }
+    }
+
+    /// The names of the files of this version, without the manifest, in the order
+    /// `artifacts` generates them: those of the previous version, followed by those it adds.
+    pub fn files(&self) -> Vec<&'static str> {
+        let mut files = self
+            .previous()
+            .map_or_else(Vec::new, |previous| previous.files());
+        files.extend_from_slice(self.additions());
+        files
+    }
+
+    /// Generates the files of this version, followed by its manifest.
+    pub fn artifacts(&self) -> Result<Vec<(&'static str, Vec<u8>)>> {
+        let mut files = match self {
+            CorpusVersion::V1 => {
+                let mut vec = generate_test_vectors();
+                clear_case_ids(&mut vec);
+                case_artifacts(&vec)?
+            }
+            // The files of corpus-v1 are generated again, now with the ids of the cases
+            CorpusVersion::V2 => {
+                let mut files = case_artifacts(&generate_test_vectors())?;
+                files.append(&mut family_artifacts()?);
+                files
+            }
+        };
+        debug_assert!(files.iter().map(|(name, _)| *name).eq(self.files()));
+        let manifest = Manifest {
+            corpus: *self,
+            files: files
+                .iter()
+                .map(|(name, contents)| ManifestEntry {
+                    name: name.to_string(),
+                    sha256: digest(contents),
+                })
+                .collect(),
+        };
+        files.push((MANIFEST, serde_json::to_string(&manifest)?.into_bytes()));
+        Ok(files)
+    }
+}
+
+// Clears the ids of the cases, which the files of corpus-v1 lack
+fn clear_case_ids(vectors: &mut [TestVector]) {
+    for metadata in vectors.iter_mut().filter_map(|tv| tv.metadata.as_mut()) {
+        metadata.id.clear();
+    }
+}
+
+// The files of the cases of the paper
+fn case_artifacts(vec: &[TestVector]) -> Result<Vec<(&'static str, Vec<u8>)>> {
+    Ok(vec![
+        // Test vectors in json
+        ("cases.json", io::encode_vectors(vec, VectorFormat::Json)?),
+        // Test vectors in txt (to ease testing C implementations)
+        ("cases.txt", io::encode_vectors(vec, VectorFormat::Txt)?),
+    ])
+}
+
+// The files corpus-v2 adds to those of the cases
+fn family_artifacts() -> Result<Vec<(&'static str, Vec<u8>)>> {
+    // The same cases for Ed25519ph in json
+    let cases_ph_json = serde_json::to_string(&ed25519ph::generate_test_vectors())?;
+
+    // The same cases for Ed25519ctx, under contexts of each length, in json
+    let cases_ctx_json = serde_json::to_string(&ed25519ctx::generate_test_vectors())?;
+
+    // Batches of honest and adversarial signatures in json
+    let mut batches = interleaved_batches(&mut new_rng());
+    batches.append(&mut misattribution_batches(&mut new_rng()));
+    let batches_json = serde_json::to_string(&batches)?;
+
+    // Batches telling apart the batch equations from individual verification in json
+    let batch_equations_json = serde_json::to_string(&batch_equation_vectors(&mut new_rng()))?;
+
+    // Pairs of vectors sharing their hram under re-serialization in json
+    let pairs_json = serde_json::to_string(&hram_linked_pairs(&mut new_rng()))?;
+
+    // Vectors telling apart the orders of operations of cofactored verification in json
+    let pre_reduction_json = serde_json::to_string(&pre_reduction_vectors(&mut new_rng()))?;
+
+    // Vectors with tiny S and R crafted to satisfy the equation in json
+    let tiny_scalars_json = serde_json::to_string(&tiny_scalar_vectors(&mut new_rng()))?;
+
+    // Vectors with a mixed-order A which only the full-order check rejects in json
+    let full_order_json = serde_json::to_string(&full_order_vectors(&mut new_rng()))?;
+
+    // Pairs of vectors under a mixed-order A and under A with its torsion cleared in json
+    let torsion_clearing_json = serde_json::to_string(&torsion_clearing_vectors(&mut new_rng()))?;
+
+    // The test vectors in the schema of Wycheproof
+    let wycheproof_json = export::wycheproof_file()?;
+
+    // Vectors with A or R encoded non-canonically as a point of order 4 in json
+    let non_canonical_json = serde_json::to_string(&non_canonical_vectors(&mut new_rng())?)?;
+
+    // Variants of a signature with each combination of the top three bits of S in json
+    let s_high_bits_json = serde_json::to_string(&s_high_bits_vectors(&mut new_rng()))?;
+
+    // Vectors placing S around L, 2^253 and 2^255 in json
+    let large_s_json = serde_json::to_string(&large_s_family(&mut new_rng()))?;
+
+    // Vectors with R the identity, encoded canonically, in json
+    let identity_r_json = serde_json::to_string(&identity_r(&mut new_rng()))?;
+
+    // Vectors under the identity as public key, encoded in every way, in json
+    let identity_json = serde_json::to_string(&identity_pubkey(&mut new_rng()))?;
+
+    // Vectors encoding exactly one of A and R non-canonically in json
+    let one_sided_json = serde_json::to_string(&one_sided_non_canonical_vectors(&mut new_rng())?)?;
+
+    // Vectors placing every non-canonical encoding in A and in R in json
+    let encodings_json = serde_json::to_string(&non_canonical_encoding_vectors(&mut new_rng())?)?;
+
+    // Cases 1 and 2 around each small-order point in json
+    let sweep_json = serde_json::to_string(&sweep_torsion(&mut new_rng())?)?;
+
+    // Pairs of vectors sharing a signature under two public keys in json
+    let substitution_json = serde_json::to_string(&key_substitution_pairs(&mut new_rng())?)?;
+
+    // A signature valid for two messages under a small-order key in json
+    let repudiation_json = serde_json::to_string(&repudiation())?;
+
+    // Batches passing individually but not as a cofactorless batch in json
+    let inconsistency_json = serde_json::to_string(&batch_inconsistency_vectors(&mut new_rng())?)?;
+
+    // Control vectors, signed as RFC 8032 does, in json
+    let controls_json = serde_json::to_string(&control_vectors(&mut new_rng()))?;
+
+    // Negative control vectors, damaged signatures, in json
+    let negative_json = serde_json::to_string(&negative_control_vectors(&mut new_rng()))?;
+
+    // Vectors over messages of 0 bytes to 1 MiB in json, the long messages in a binary blob
+    let (stored, blob) = store_message_vectors(&message_length_vectors(&mut new_rng()));
+    let lengths_json = serde_json::to_string(&stored)?;
+
+    // The cases before and after normalizing A and R in json
+    let normalization_json = serde_json::to_string(&normalization_vectors()?)?;
+
+    // Vectors with S at L - 1 and L in json
+    let boundary_json = serde_json::to_string(&s_boundary_vectors(&mut new_rng()))?;
+
+    // Pairs of signatures with S and S + kL in json
+    let reduced_s_json = serde_json::to_string(&reduced_s_pairs(&mut new_rng()))?;
+
+    // Pairs of malleated signatures with their digests in json
+    let malleability_json = serde_json::to_string(&malleability_pairs(&mut new_rng())?)?;
+
+    Ok(vec![
+        ("cases_ph.json", cases_ph_json.into_bytes()),
+        ("cases_ctx.json", cases_ctx_json.into_bytes()),
+        ("batches.json", batches_json.into_bytes()),
+        ("batch_equations.json", batch_equations_json.into_bytes()),
+        ("hram_pairs.json", pairs_json.into_bytes()),
+        ("pre_reduction.json", pre_reduction_json.into_bytes()),
+        ("tiny_scalars.json", tiny_scalars_json.into_bytes()),
+        ("full_order.json", full_order_json.into_bytes()),
+        ("torsion_clearing.json", torsion_clearing_json.into_bytes()),
+        ("cases_wycheproof.json", wycheproof_json.into_bytes()),
+        ("non_canonical.json", non_canonical_json.into_bytes()),
+        ("s_high_bits.json", s_high_bits_json.into_bytes()),
+        ("large_s.json", large_s_json.into_bytes()),
+        ("identity_r.json", identity_r_json.into_bytes()),
+        ("identity_pubkey.json", identity_json.into_bytes()),
+        ("one_sided_non_canonical.json", one_sided_json.into_bytes()),
+        ("non_canonical_encodings.json", encodings_json.into_bytes()),
+        ("torsion_sweep.json", sweep_json.into_bytes()),
+        ("key_substitution.json", substitution_json.into_bytes()),
+        ("repudiation.json", repudiation_json.into_bytes()),
+        ("batch_inconsistency.json", inconsistency_json.into_bytes()),
+        ("controls.json", controls_json.into_bytes()),
+        ("negative_controls.json", negative_json.into_bytes()),
+        ("message_lengths.json", lengths_json.into_bytes()),
+        ("message_lengths.bin", blob),
+        ("normalization.json", normalization_json.into_bytes()),
+        ("s_boundary.json", boundary_json.into_bytes()),
+        ("reduced_s.json", reduced_s_json.into_bytes()),
+        ("malleability.json", malleability_json.into_bytes()),
+    ])
+}
+
+impl fmt::Display for CorpusVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for CorpusVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        CorpusVersion::ALL
+            .iter()
+            .find(|version| version.name() == s.trim())
+            .copied()
+            .ok_or_else(|| anyhow!("unknown corpus version {}", s))
+    }
+}
+
+/// The contents of `corpus.json`: the version the files next to it were generated as, and
+/// their SHA-256 digests, for consumers to check they read the version they pinned.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub corpus: CorpusVersion,
+    pub files: Vec<ManifestEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    /// The hex-encoded SHA-256 digest of the file.
+    pub sha256: String,
+}
+
+impl Manifest {
+    pub fn from_json(contents: &[u8]) -> Result<Manifest> {
+        serde_json::from_slice(contents).map_err(|e| anyhow!("{}: {}", MANIFEST, e))
+    }
+
+    /// Checks that the files listed are those of `version`, with the given `contents`
+    /// (e.g. read from disk) digesting as listed.
+    pub fn check(&self, version: CorpusVersion, contents: &[(&str, &[u8])]) -> Result<()> {
+        if self.corpus != version {
+            return Err(anyhow!("the files are of {}, not {}", self.corpus, version));
+        }
+        if !self
+            .files
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .eq(version.files())
+        {
+            return Err(anyhow!(
+                "the manifest does not list the files of {}",
+                version
+            ));
+        }
+        for (name, contents) in contents.iter() {
+            let entry = self
+                .files
+                .iter()
+                .find(|entry| entry.name == *name)
+                .ok_or_else(|| anyhow!("{} is not a file of {}", name, version))?;
+            if digest(contents) != entry.sha256 {
+                return Err(anyhow!("{} differs from that of {}", name, version));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::policy::{has_full_order, is_canonical_encoding, ValidationPolicy};
+use crate::verdict::Verdict;
+use crate::{
+    check_slice_size, compute_hram, compute_hram_with_arrays, deserialize_point,
+    verify_final_cofactored, verify_final_cofactorless, verify_final_pre_reduced_cofactored,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+
+/// A vector decoded once, along with every property of it the checks of a policy depend on
+/// and both of its challenges, for loops verifying the same vectors against many policies
+/// (or many vectors built from points rather than bytes) to only pay a double-scalar
+/// multiplication per verification, rather than two decompressions, the checks and a hash.
+#[derive(Clone, Debug)]
+pub struct DecodedVector {
+    pub pub_key: EdwardsPoint,
+    pub r: EdwardsPoint,
+    /// S as read by the policies not requiring it canonical, i.e. with bit 255 dropped.
+    pub s: Scalar,
+    canonical_pub_key: bool,
+    canonical_r: bool,
+    canonical_s: bool,
+    small_order_pub_key: bool,
+    full_order_pub_key: bool,
+    small_order_r: bool,
+    // k over A and R as provided, and as re-serialized
+    hram: Scalar,
+    reserialized_hram: Scalar,
+}
+
+impl DecodedVector {
+    /// Decodes a vector, failing where every policy rejects it while decoding: on a
+    /// signature of another size than 64 bytes, or on A or R not decompressing.
+    pub fn new(message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<DecodedVector> {
+        let signature = check_slice_size(signature, 64, "signature")?;
+        let (r_bytes, s_bytes) = signature.split_at(32);
+        let pk = deserialize_point(pub_key)?;
+        let r = deserialize_point(r_bytes)?;
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(s_bytes);
+        Ok(DecodedVector {
+            canonical_pub_key: is_canonical_encoding(pub_key, &pk),
+            canonical_r: is_canonical_encoding(r_bytes, &r),
+            canonical_s: Scalar::from_canonical_bytes(bytes).is_some(),
+            small_order_pub_key: pk.is_small_order(),
+            full_order_pub_key: has_full_order(&pk),
+            small_order_r: r.is_small_order(),
+            hram: compute_hram_with_arrays(message, pub_key, r_bytes),
+            reserialized_hram: compute_hram(message, &pk, &r),
+            pub_key: pk,
+            r,
+            s: Scalar::from_bits(bytes),
+        })
+    }
+
+    /// The vector signing `message` with A and R encoded canonically, as a differential
+    /// fuzzer drawing points rather than bytes builds it. S is kept as given, canonical or
+    /// not.
+    pub fn from_points(
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        r: &EdwardsPoint,
+        s: &Scalar,
+    ) -> DecodedVector {
+        let hram = compute_hram(message, pub_key, r);
+        DecodedVector {
+            pub_key: *pub_key,
+            r: *r,
+            s: *s,
+            canonical_pub_key: true,
+            canonical_r: true,
+            canonical_s: s.is_canonical(),
+            small_order_pub_key: pub_key.is_small_order(),
+            full_order_pub_key: has_full_order(pub_key),
+            small_order_r: r.is_small_order(),
+            hram,
+            reserialized_hram: hram,
+        }
+    }
+}
+
+impl ValidationPolicy {
+    /// As `verify`, on a vector decoded beforehand.
+    pub fn verify_decoded(&self, decoded: &DecodedVector) -> Result<()> {
+        if let Some(rejection) = self.decoding_rejection(decoded) {
+            return Err(anyhow!(rejection));
+        }
+        self.check_decoded(decoded)
+    }
+
+    /// As `verdict`, on a vector decoded beforehand.
+    pub fn verdict_decoded(&self, decoded: &DecodedVector) -> Verdict {
+        if self.decoding_rejection(decoded).is_some() {
+            return Verdict::ParseRejected;
+        }
+        Verdict::from_accepted(self.check_decoded(decoded).is_ok())
+    }
+
+    // The canonicity checks `decode` performs, failing the vector before verifying it
+    fn decoding_rejection(&self, decoded: &DecodedVector) -> Option<&'static str> {
+        if self.reject_noncanonical_pk && !decoded.canonical_pub_key {
+            Some("Non-canonical public key encoding")
+        } else if self.reject_noncanonical_r && !decoded.canonical_r {
+            Some("Non-canonical R encoding")
+        } else if self.require_canonical_s && !decoded.canonical_s {
+            Some("Non-canonical s")
+        } else {
+            None
+        }
+    }
+
+    // The checks of `check_with_hram`, in the same order
+    fn check_decoded(&self, decoded: &DecodedVector) -> Result<()> {
+        if self.reject_small_order_pk && decoded.small_order_pub_key {
+            return Err(anyhow!("Small-order public key"));
+        }
+        if self.require_full_order_pk && !decoded.full_order_pub_key {
+            return Err(anyhow!("Public key not of order L"));
+        }
+        if self.reject_small_order_r && decoded.small_order_r {
+            return Err(anyhow!("Small-order R"));
+        }
+        let k = if self.reserialize_for_hash {
+            &decoded.reserialized_hram
+        } else {
+            &decoded.hram
+        };
+        let unpacked = (decoded.r, decoded.s);
+        match (self.cofactored, self.prereduce) {
+            (true, true) => verify_final_pre_reduced_cofactored(&decoded.pub_key, &unpacked, k),
+            (true, false) => verify_final_cofactored(&decoded.pub_key, &unpacked, k),
+            (false, _) => verify_final_cofactorless(&decoded.pub_key, &unpacked, k),
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use crate::contribution::policy_profile;
+use crate::non_reducing_scalar52::BigScalar;
+use crate::registry::Registry;
+use crate::signing::SigningKey;
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::{
+    compute_hram, deserialize_point, deserialize_point_with, serialize_signature, DecodeMode,
+    EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL,
+};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+use std::fmt;
+
+/// The kinds of signatures `random_vector` draws: well-formed ones, which every verifier
+/// accepts, and malformed ones around the divergences of the cases, with random keys,
+/// nonces, messages and small-order points rather than the ground instances of the cases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VectorKind {
+    /// A signature made as RFC 8032 does.
+    Honest,
+    /// An honest signature with a random bit of its public key or signature flipped.
+    FlippedBit,
+    /// An honest signature by `[a]B + T`, for small-order T: the cofactored equation holds,
+    /// and the cofactorless one iff `[k]T = 0`.
+    MixedOrderKey,
+    /// As `MixedOrderKey`, with `R = [r]B - T`: the cofactorless equation holds iff
+    /// `[k]T = T`.
+    MixedOrderKeyAndR,
+    /// An honest signature with S + L in place of S.
+    LargeS,
+    /// `R = [r]B` and `S = r` under a non-canonical encoding of a small-order A: the
+    /// cofactored equation holds, and the cofactorless one iff `[k]A = 0`, which depends on
+    /// how A is hashed.
+    NonCanonicalKey,
+}
+
+impl VectorKind {
+    pub const ALL: [VectorKind; 6] = [
+        VectorKind::Honest,
+        VectorKind::FlippedBit,
+        VectorKind::MixedOrderKey,
+        VectorKind::MixedOrderKeyAndR,
+        VectorKind::LargeS,
+        VectorKind::NonCanonicalKey,
+    ];
+}
+
+impl fmt::Display for VectorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VectorKind::Honest => "honest",
+            VectorKind::FlippedBit => "flipped bit",
+            VectorKind::MixedOrderKey => "mixed-order key",
+            VectorKind::MixedOrderKeyAndR => "mixed-order key and R",
+            VectorKind::LargeS => "large S",
+            VectorKind::NonCanonicalKey => "non-canonical key",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+fn random_scalar(rng: &mut impl RngCore) -> Scalar {
+    let mut bytes = [0u8; 32];
+    rng.fill_bytes(&mut bytes);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+// A random message of up to 64 bytes
+fn random_message(rng: &mut impl RngCore) -> Vec<u8> {
+    let mut message = vec![0u8; (rng.next_u32() % 65) as usize];
+    rng.fill_bytes(&mut message);
+    message
+}
+
+// A point of order 2, 4 or 8
+fn small_order_point(rng: &mut impl RngCore) -> EdwardsPoint {
+    let index = 1 + (rng.next_u32() % 7) as usize;
+    deserialize_point_with(&EIGHT_TORSION[index], DecodeMode::Strict).unwrap()
+}
+
+// The signature of a random message by `[a]B + T`, with `R = [r]B + r_torsion`
+fn mixed_order_signature(
+    rng: &mut impl RngCore,
+    torsion: EdwardsPoint,
+    r_torsion: EdwardsPoint,
+) -> TestVector {
+    let (a, r) = (random_scalar(rng), random_scalar(rng));
+    let pub_key = a * ED25519_BASEPOINT_POINT + torsion;
+    let r_pt = r * ED25519_BASEPOINT_POINT + r_torsion;
+    let message = random_message(rng);
+    let s = r + compute_hram(&message, &pub_key, &r_pt) * a;
+    TestVector {
+        message,
+        pub_key: pub_key.compress().to_bytes(),
+        signature: serialize_signature(&r_pt, &s),
+        metadata: None,
+        trace: None,
+    }
+}
+
+/// A signature of `kind`, drawn from `rng`.
+pub fn random_vector(kind: VectorKind, rng: &mut impl RngCore) -> TestVector {
+    match kind {
+        VectorKind::Honest => {
+            let message = random_message(rng);
+            SigningKey::generate(rng).sign_vector(&message)
+        }
+        VectorKind::FlippedBit => {
+            let mut tv = random_vector(VectorKind::Honest, rng);
+            let bit = (rng.next_u32() % (96 * 8)) as usize;
+            if bit < 32 * 8 {
+                tv.pub_key[bit / 8] ^= 1 << (bit % 8);
+            } else {
+                tv.signature[bit / 8 - 32] ^= 1 << (bit % 8);
+            }
+            tv
+        }
+        VectorKind::MixedOrderKey => {
+            let torsion = small_order_point(rng);
+            mixed_order_signature(rng, torsion, EdwardsPoint::default())
+        }
+        VectorKind::MixedOrderKeyAndR => {
+            let torsion = small_order_point(rng);
+            mixed_order_signature(rng, torsion, -torsion)
+        }
+        VectorKind::LargeS => {
+            let mut tv = random_vector(VectorKind::Honest, rng);
+            let mut s_bytes = [0u8; 32];
+            s_bytes.copy_from_slice(&tv.signature[32..]);
+            let large_s = BigScalar::from_bytes(&s_bytes) + BigScalar::L;
+            tv.signature[32..].copy_from_slice(&large_s.to_bytes());
+            tv
+        }
+        VectorKind::NonCanonicalKey => {
+            let index = (rng.next_u32() as usize) % EIGHT_TORSION_NON_CANONICAL.len();
+            let pub_key = EIGHT_TORSION_NON_CANONICAL[index];
+            let r = random_scalar(rng);
+            let r_pt = r * ED25519_BASEPOINT_POINT;
+            TestVector {
+                message: random_message(rng),
+                pub_key,
+                signature: serialize_signature(&r_pt, &r),
+                metadata: None,
+                trace: None,
+            }
+        }
+    }
+}
+
+/// A vector on which the registered libraries disagree, some accepting it and others
+/// rejecting it.
+#[derive(Serialize)]
+pub struct Disagreement {
+    pub kind: VectorKind,
+    pub vector: TestVector,
+    /// The verdict of each library, in their order of registration.
+    pub verdicts: Vec<(String, Verdict)>,
+}
+
+/// Runs every library of `registry` on `iterations` signatures drawn from `rng`, of each of
+/// `VectorKind::ALL` in turn, and returns those on which some library accepts and another
+/// rejects: new classes of divergences, beyond the cases. Rejecting while parsing or while
+/// verifying are not told apart, as libraries draw that line differently; a library
+/// registered as unavailable rejects every signature, and disagrees on the honest ones.
+pub fn differential_fuzz(
+    registry: &Registry,
+    rng: &mut impl RngCore,
+    iterations: usize,
+) -> Vec<Disagreement> {
+    let mut disagreements = Vec::new();
+    for i in 0..iterations {
+        let kind = VectorKind::ALL[i % VectorKind::ALL.len()];
+        let vector = random_vector(kind, rng);
+        let verdicts = registry.verdicts(&vector);
+        let accepted = verdicts
+            .iter()
+            .filter(|(_, verdict)| verdict.is_accepted())
+            .count();
+        if accepted > 0 && accepted < verdicts.len() {
+            disagreements.push(Disagreement {
+                kind,
+                vector,
+                verdicts: verdicts
+                    .into_iter()
+                    .map(|(name, verdict)| (name.to_string(), verdict))
+                    .collect(),
+            });
+        }
+    }
+    disagreements
+}
+
+/// A disagreement as reduced by `Minimizer::minimize`.
+#[derive(Serialize)]
+pub struct Minimized {
+    pub vector: TestVector,
+    /// The verdict of each library on `vector`, each accepting it iff it accepted the
+    /// original one.
+    pub verdicts: Vec<(String, Verdict)>,
+    /// A case on which every `ValidationPolicy` reaches the same verdict as on `vector`, as
+    /// `contribution::review` looks for: the disagreement then stems from a check the case
+    /// already tells apart.
+    pub equivalent_to: Option<usize>,
+}
+
+impl Minimized {
+    /// Whether the disagreement is of a class no case exhibits: either no case is
+    /// equivalent to it, or every policy accepts it or every policy rejects it, and the
+    /// libraries part on something the policies do not model.
+    pub fn is_novel(&self) -> bool {
+        self.equivalent_to.is_none()
+    }
+}
+
+/// Reduces the vectors on which the libraries of a registry disagree, and places them in
+/// the taxonomy of a set of cases, e.g. those of `generate_test_vectors`.
+pub struct Minimizer<'a> {
+    registry: &'a Registry,
+    // the `policy_profile` of each case
+    cases: Vec<Vec<Verdict>>,
+}
+
+impl<'a> Minimizer<'a> {
+    pub fn new(registry: &'a Registry, cases: &[TestVector]) -> Minimizer<'a> {
+        Minimizer {
+            registry,
+            cases: cases.iter().map(policy_profile).collect(),
+        }
+    }
+
+    // The libraries accepting `tv`, which the reductions keep
+    fn accepting(&self, tv: &TestVector) -> Vec<bool> {
+        self.registry
+            .verdicts(tv)
+            .iter()
+            .map(|(_, verdict)| verdict.is_accepted())
+            .collect()
+    }
+
+    /// Reduces `tv` while every library keeps accepting or rejecting it as it did: reduces
+    /// S modulo L and encodes A and R canonically, each if the disagreement survives it,
+    /// then drops bytes of the message, in chunks of halving sizes, until no single byte can
+    /// be dropped. A message shortened
+    /// changes the challenge, and only survives when the disagreement does not hinge on it,
+    /// e.g. when both equations fail.
+    pub fn minimize(&self, tv: &TestVector) -> Minimized {
+        let accepting = self.accepting(tv);
+        let keeps = |candidate: &TestVector| self.accepting(candidate) == accepting;
+        let mut vector = TestVector {
+            metadata: None,
+            trace: None,
+            ..tv.clone()
+        };
+
+        let normalizations: [fn(&TestVector) -> Option<TestVector>; 3] =
+            [reduce_s, canonical_pub_key, canonical_r];
+        for normalize in normalizations.iter() {
+            if let Some(candidate) = normalize(&vector).filter(|c| keeps(c)) {
+                vector = candidate;
+            }
+        }
+
+        // until no single byte can be dropped, as dropping one changes whether dropping the
+        // others keeps the disagreement
+        let mut shrunk = true;
+        while shrunk {
+            shrunk = false;
+            let mut chunk = vector.message.len();
+            while chunk > 0 {
+                let mut start = 0;
+                while start < vector.message.len() {
+                    let mut candidate = vector.clone();
+                    let end = (start + chunk).min(candidate.message.len());
+                    candidate.message.drain(start..end);
+                    if keeps(&candidate) {
+                        vector = candidate;
+                        shrunk = true;
+                    } else {
+                        start += chunk;
+                    }
+                }
+                chunk /= 2;
+            }
+        }
+
+        let profile = policy_profile(&vector);
+        let unanimous =
+            profile.iter().all(|v| v.is_accepted()) || profile.iter().all(|v| !v.is_accepted());
+        let equivalent_to = if unanimous {
+            None
+        } else {
+            self.cases.iter().position(|case| *case == profile)
+        };
+        Minimized {
+            verdicts: self
+                .registry
+                .verdicts(&vector)
+                .into_iter()
+                .map(|(name, verdict)| (name.to_string(), verdict))
+                .collect(),
+            vector,
+            equivalent_to,
+        }
+    }
+}
+
+// The canonical encoding of the point `bytes` decode to, if they decode to one and are not
+// already canonical
+fn canonical_encoding(bytes: &[u8]) -> Option<[u8; 32]> {
+    let canonical = deserialize_point(bytes).ok()?.compress().to_bytes();
+    if canonical[..] == *bytes {
+        None
+    } else {
+        Some(canonical)
+    }
+}
+
+// `tv` with S reduced modulo L, if it is not already
+fn reduce_s(tv: &TestVector) -> Option<TestVector> {
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(tv.signature.get(32..64)?);
+    let reduced = Scalar::from_bytes_mod_order(s_bytes).to_bytes();
+    if reduced == s_bytes {
+        return None;
+    }
+    let mut tv = tv.clone();
+    tv.signature[32..64].copy_from_slice(&reduced);
+    Some(tv)
+}
+
+// `tv` with A canonically encoded, if it is not already
+fn canonical_pub_key(tv: &TestVector) -> Option<TestVector> {
+    Some(TestVector {
+        pub_key: canonical_encoding(&tv.pub_key)?,
+        ..tv.clone()
+    })
+}
+
+// `tv` with R canonically encoded, if it is not already
+fn canonical_r(tv: &TestVector) -> Option<TestVector> {
+    let canonical = canonical_encoding(tv.signature.get(..32)?)?;
+    let mut tv = tv.clone();
+    tv.signature[..32].copy_from_slice(&canonical);
+    Some(tv)
+}
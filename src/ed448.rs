@@ -0,0 +1,925 @@
+use crate::check_slice_size;
+use crate::verdict::Verdict;
+use crate::verifier::Verifier;
+use anyhow::{anyhow, Result};
+use rand::RngCore;
+use serde::{Serialize, Serializer};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::{Add, Neg, Sub};
+
+// p = 2^448 - 2^224 - 1, as 32-bit little-endian limbs
+const P: [u32; 14] = [
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_fffe,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+    0xffff_ffff,
+];
+
+// d = -39081, the curve being x^2 + y^2 = 1 + d x^2 y^2
+const D: u32 = 39081;
+
+/// L = 2^446 - 13818066809895115352007386748515426880336692474882178609894547503885, the
+/// order of the base point, little-endian.
+pub const L: [u8; 57] = [
+    0xf3, 0x44, 0x58, 0xab, 0x92, 0xc2, 0x78, 0x23, 0x55, 0x8f, 0xc5, 0x8d, 0x72, 0xc2, 0x6c, 0x21,
+    0x90, 0x36, 0xd6, 0xae, 0x49, 0xdb, 0x4e, 0xc4, 0xe9, 0x23, 0xca, 0x7c, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3f, 0x00,
+];
+
+/// The encoding of the base point B of RFC 8032, section 5.2.
+pub const BASEPOINT: [u8; 57] = [
+    0x14, 0xfa, 0x30, 0xf2, 0x5b, 0x79, 0x08, 0x98, 0xad, 0xc8, 0xd7, 0x4e, 0x2c, 0x13, 0xbd, 0xfd,
+    0xc4, 0x39, 0x7c, 0xe6, 0x1c, 0xff, 0xd3, 0x3a, 0xd7, 0xc2, 0xa0, 0x05, 0x1e, 0x9c, 0x78, 0x87,
+    0x40, 0x98, 0xa3, 0x6c, 0x73, 0x73, 0xea, 0x4b, 0x62, 0xc7, 0xc9, 0x56, 0x37, 0x20, 0x76, 0x88,
+    0x24, 0xbc, 0xb6, 0x6e, 0x71, 0x46, 0x3f, 0x69, 0x00,
+];
+
+// dom4(0, ""), prefixing the hashes of Ed448 without context
+const DOM4: &[u8] = b"SigEd448\x00\x00";
+
+// An element of GF(p), reduced, as 32-bit little-endian limbs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fe([u32; 14]);
+
+impl Fe {
+    fn small(n: u32) -> Fe {
+        let mut limbs = [0u32; 14];
+        limbs[0] = n;
+        Fe(limbs)
+    }
+
+    // Reduces limbs below 2^35 each, 2^448 being 2^224 + 1 modulo p
+    fn reduce(mut t: [u64; 29]) -> Fe {
+        loop {
+            // from the top, as the limbs from 2^672 up fold back above 2^448
+            for i in (14..29).rev() {
+                let high = t[i];
+                t[i] = 0;
+                t[i - 14] += high;
+                t[i - 7] += high;
+            }
+            let mut carry = 0;
+            for limb in t.iter_mut() {
+                let v = *limb + carry;
+                *limb = v & 0xffff_ffff;
+                carry = v >> 32;
+            }
+            if t[14..].iter().all(|limb| *limb == 0) {
+                break;
+            }
+        }
+        let mut limbs = [0u32; 14];
+        for (limb, t) in limbs.iter_mut().zip(t.iter()) {
+            *limb = *t as u32;
+        }
+        // below 2^448 < 2p
+        if cmp_limbs(&limbs, &P) != Ordering::Less {
+            limbs = sub_limbs(&limbs, &P);
+        }
+        Fe(limbs)
+    }
+
+    fn mul(&self, other: &Fe) -> Fe {
+        let mut wide = [0u128; 28];
+        for i in 0..14 {
+            for j in 0..14 {
+                wide[i + j] += self.0[i] as u128 * other.0[j] as u128;
+            }
+        }
+        let mut t = [0u64; 29];
+        let mut carry = 0u128;
+        for i in 0..28 {
+            let v = wide[i] + carry;
+            t[i] = v as u32 as u64;
+            carry = v >> 32;
+        }
+        t[28] = carry as u64;
+        Fe::reduce(t)
+    }
+
+    fn square(&self) -> Fe {
+        self.mul(self)
+    }
+
+    // self^e, for the little-endian exponent e
+    fn pow(&self, e: &[u8]) -> Fe {
+        let mut result = Fe::small(1);
+        for bit in (0..8 * e.len()).rev() {
+            result = result.square();
+            if (e[bit / 8] >> (bit % 8)) & 1 == 1 {
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+
+    fn invert(&self) -> Fe {
+        let mut p_minus_two = limbs_to_bytes(&P);
+        p_minus_two[0] -= 2;
+        self.pow(&p_minus_two)
+    }
+
+    // The square root of u / v, if any: (u / v)^((p + 1) / 4), as p = 3 mod 4
+    fn sqrt_ratio(u: &Fe, v: &Fe) -> Option<Fe> {
+        let w = u.mul(&v.invert());
+        // (p + 1) / 4 = 2^446 - 2^222
+        let mut e = [0u8; 56];
+        for bit in 222..446 {
+            e[bit / 8] |= 1 << (bit % 8);
+        }
+        let x = w.pow(&e);
+        if x.square() == w {
+            Some(x)
+        } else {
+            None
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|limb| *limb == 0)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    fn to_bytes(self) -> [u8; 56] {
+        let mut bytes = [0u8; 56];
+        bytes.copy_from_slice(&limbs_to_bytes(&self.0));
+        bytes
+    }
+
+    // The element the 56 bytes encode modulo p, and whether they encode it canonically,
+    // i.e. are below p
+    fn from_bytes(bytes: &[u8]) -> (Fe, bool) {
+        let mut limbs = [0u32; 14];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut word = [0u8; 4];
+            word.copy_from_slice(&bytes[4 * i..4 * i + 4]);
+            *limb = u32::from_le_bytes(word);
+        }
+        let canonical = cmp_limbs(&limbs, &P) == Ordering::Less;
+        if canonical {
+            (Fe(limbs), true)
+        } else {
+            (Fe(sub_limbs(&limbs, &P)), false)
+        }
+    }
+}
+
+impl Add for Fe {
+    type Output = Fe;
+
+    fn add(self, other: Fe) -> Fe {
+        let mut t = [0u64; 29];
+        for (t, (a, b)) in t.iter_mut().zip(self.0.iter().zip(other.0.iter())) {
+            *t = *a as u64 + *b as u64;
+        }
+        Fe::reduce(t)
+    }
+}
+
+impl Neg for Fe {
+    type Output = Fe;
+
+    fn neg(self) -> Fe {
+        if self.is_zero() {
+            self
+        } else {
+            Fe(sub_limbs(&P, &self.0))
+        }
+    }
+}
+
+impl Sub for Fe {
+    type Output = Fe;
+
+    fn sub(self, other: Fe) -> Fe {
+        self + -other
+    }
+}
+
+fn cmp_limbs(a: &[u32; 14], b: &[u32; 14]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+// a - b, for a >= b
+fn sub_limbs(a: &[u32; 14], b: &[u32; 14]) -> [u32; 14] {
+    let mut difference = [0u32; 14];
+    let mut borrow = 0i64;
+    for i in 0..14 {
+        let d = a[i] as i64 - b[i] as i64 - borrow;
+        borrow = (d < 0) as i64;
+        difference[i] = (d + (borrow << 32)) as u32;
+    }
+    difference
+}
+
+fn limbs_to_bytes(limbs: &[u32; 14]) -> Vec<u8> {
+    limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect()
+}
+
+/// A point of Ed448-Goldilocks, in projective coordinates (X : Y : Z).
+#[derive(Clone, Copy, Debug)]
+pub struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Point) -> bool {
+        self.x.mul(&other.z) == other.x.mul(&self.z) && self.y.mul(&other.z) == other.y.mul(&self.z)
+    }
+}
+
+impl Eq for Point {}
+
+impl Point {
+    pub fn identity() -> Point {
+        Point {
+            x: Fe::small(0),
+            y: Fe::small(1),
+            z: Fe::small(1),
+        }
+    }
+
+    pub fn basepoint() -> Point {
+        decompress(&BASEPOINT).unwrap()
+    }
+
+    /// The points of order dividing the cofactor 4, by order: the identity, (0, -1) of order
+    /// 2, then (1, 0) and (-1, 0) of order 4.
+    pub fn torsion() -> [Point; 4] {
+        let affine = |x: Fe, y: Fe| Point {
+            x,
+            y,
+            z: Fe::small(1),
+        };
+        [
+            Point::identity(),
+            affine(Fe::small(0), -Fe::small(1)),
+            affine(Fe::small(1), Fe::small(0)),
+            affine(-Fe::small(1), Fe::small(0)),
+        ]
+    }
+
+    /// [n]P, for the little-endian n of any length, unreduced.
+    pub fn mul(&self, n: &[u8]) -> Point {
+        let mut result = Point::identity();
+        for bit in (0..8 * n.len()).rev() {
+            result = result + result;
+            if (n[bit / 8] >> (bit % 8)) & 1 == 1 {
+                result = result + *self;
+            }
+        }
+        result
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Point::identity()
+    }
+
+    /// Whether [4]P is the identity.
+    pub fn is_small_order(&self) -> bool {
+        self.mul(&[4]).is_identity()
+    }
+
+    /// The encoding of RFC 8032, section 5.2.2: y in 56 bytes, then the sign of x in the top
+    /// bit of a 57th.
+    pub fn compress(&self) -> [u8; 57] {
+        let z_inv = self.z.invert();
+        let mut bytes = [0u8; 57];
+        bytes[..56].copy_from_slice(&self.y.mul(&z_inv).to_bytes());
+        bytes[56] = (self.x.mul(&z_inv).is_odd() as u8) << 7;
+        bytes
+    }
+}
+
+// The addition of RFC 8032, section 5.2.4, complete as d is not a square
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        let a = self.z.mul(&other.z);
+        let b = a.square();
+        let c = self.x.mul(&other.x);
+        let d = self.y.mul(&other.y);
+        let e = -Fe::small(D).mul(&c).mul(&d);
+        let f = b - e;
+        let g = b + e;
+        let h = (self.x + self.y).mul(&(other.x + other.y));
+        Point {
+            x: a.mul(&f).mul(&(h - c - d)),
+            y: a.mul(&g).mul(&(d - c)),
+            z: f.mul(&g),
+        }
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point { x: -self.x, ..self }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        self + -other
+    }
+}
+
+/// Decodes a point as RFC 8032, section 5.2.3: rejecting y >= p, and x = 0 with the sign
+/// bit set.
+pub fn decompress(bytes: &[u8]) -> Result<Point> {
+    decompress_with(bytes, true, true)
+}
+
+// Decodes a point, reducing y >= p and reading x = 0 with the sign bit set as 0 unless told
+// to reject them. Bits 448 to 454 are not part of any encoding, and always rejected.
+fn decompress_with(
+    bytes: &[u8],
+    reject_noncanonical_y: bool,
+    reject_negative_zero: bool,
+) -> Result<Point> {
+    let bytes = check_slice_size(bytes, 57, "point")?;
+    if bytes[56] & 0x7f != 0 {
+        return Err(anyhow!("Bits 448 to 454 of the point set"));
+    }
+    let (y, canonical) = Fe::from_bytes(&bytes[..56]);
+    if !canonical && reject_noncanonical_y {
+        return Err(anyhow!("Non-canonical y"));
+    }
+    let y2 = y.square();
+    let mut x = Fe::sqrt_ratio(
+        &(y2 - Fe::small(1)),
+        &(-Fe::small(D).mul(&y2) - Fe::small(1)),
+    )
+    .ok_or_else(|| anyhow!("Point decompression failed!"))?;
+    let sign = bytes[56] >> 7 == 1;
+    if x.is_zero() && sign && reject_negative_zero {
+        return Err(anyhow!("x = 0 with the sign bit set"));
+    }
+    if x.is_odd() != sign {
+        x = -x;
+    }
+    Ok(Point {
+        x,
+        y,
+        z: Fe::small(1),
+    })
+}
+
+// The little-endian a + b
+fn add_bytes(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut sum = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u16;
+    for i in 0..a.len().max(b.len()) {
+        carry += *a.get(i).unwrap_or(&0) as u16 + *b.get(i).unwrap_or(&0) as u16;
+        sum.push(carry as u8);
+        carry >>= 8;
+    }
+    sum.push(carry as u8);
+    sum
+}
+
+// The little-endian a * b
+fn mul_bytes(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut wide = vec![0u64; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            wide[i + j] += *x as u64 * *y as u64;
+        }
+    }
+    let mut carry = 0u64;
+    wide.iter()
+        .map(|v| {
+            carry += v;
+            let byte = carry as u8;
+            carry >>= 8;
+            byte
+        })
+        .collect()
+}
+
+// The little-endian a compared with b, of any lengths
+fn cmp_bytes(a: &[u8], b: &[u8]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .rev()
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// The little-endian n of any length reduced modulo L, by long division.
+pub fn reduce_mod_l(n: &[u8]) -> [u8; 57] {
+    let mut remainder = [0u8; 58];
+    for bit in (0..8 * n.len()).rev() {
+        // remainder = 2 * remainder + bit, below 2L
+        let mut carry = (n[bit / 8] >> (bit % 8)) & 1;
+        for byte in remainder.iter_mut() {
+            let shifted = (*byte as u16) << 1 | carry as u16;
+            *byte = shifted as u8;
+            carry = (shifted >> 8) as u8;
+        }
+        if cmp_bytes(&remainder, &L) != Ordering::Less {
+            let mut borrow = 0i16;
+            for (i, byte) in remainder.iter_mut().enumerate() {
+                let d = *byte as i16 - *L.get(i).unwrap_or(&0) as i16 - borrow;
+                borrow = (d < 0) as i16;
+                *byte = (d + 256 * borrow) as u8;
+            }
+        }
+    }
+    let mut reduced = [0u8; 57];
+    reduced.copy_from_slice(&remainder[..57]);
+    reduced
+}
+
+// SHAKE256(dom4(0, "") || parts), 114 bytes
+fn shake256(parts: &[&[u8]]) -> [u8; 114] {
+    let mut hasher = Shake256::default().chain(DOM4);
+    for part in parts.iter() {
+        hasher.update(part);
+    }
+    let mut digest = [0u8; 114];
+    hasher.finalize_xof().read(&mut digest);
+    digest
+}
+
+/// k = SHAKE256(dom4(0, "") || R || A || M, 114) reduced modulo L, over the encodings of R
+/// and A given.
+pub fn compute_hram(message: &[u8], pub_key: &[u8], r: &[u8]) -> [u8; 57] {
+    reduce_mod_l(&shake256(&[r, pub_key, message]))
+}
+
+/// An Ed448 key of RFC 8032, section 5.2.5, without context.
+pub struct Ed448Key {
+    secret: [u8; 57],
+    prefix: [u8; 57],
+    pub_key: [u8; 57],
+}
+
+impl Ed448Key {
+    /// The key of the 57-byte secret: the pruned first half of its SHAKE256 digest is the
+    /// secret scalar, the second half the prefix the nonces are derived from.
+    pub fn from_secret(secret: &[u8; 57]) -> Ed448Key {
+        let mut digest = [0u8; 114];
+        Shake256::default()
+            .chain(&secret[..])
+            .finalize_xof()
+            .read(&mut digest);
+        let mut scalar = [0u8; 57];
+        scalar.copy_from_slice(&digest[..57]);
+        scalar[0] &= 0xfc;
+        scalar[55] |= 0x80;
+        scalar[56] = 0;
+        let mut prefix = [0u8; 57];
+        prefix.copy_from_slice(&digest[57..]);
+        Ed448Key {
+            secret: scalar,
+            prefix,
+            pub_key: Point::basepoint().mul(&scalar).compress(),
+        }
+    }
+
+    pub fn pub_key(&self) -> [u8; 57] {
+        self.pub_key
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; 114] {
+        let r = reduce_mod_l(&shake256(&[&self.prefix, message]));
+        let r_bytes = Point::basepoint().mul(&r).compress();
+        let k = compute_hram(message, &self.pub_key, &r_bytes);
+        let s = reduce_mod_l(&add_bytes(&r, &mul_bytes(&k, &self.secret)));
+        let mut signature = [0u8; 114];
+        signature[..57].copy_from_slice(&r_bytes);
+        signature[57..].copy_from_slice(&s);
+        signature
+    }
+}
+
+/// The checks an Ed448 verifier may or may not perform, as `ValidationPolicy` for Ed25519.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed448Policy {
+    /// Rejects the encodings of y >= p, rather than reducing y modulo p.
+    pub reject_noncanonical_y: bool,
+    /// Rejects x = 0 with the sign bit set, rather than reading it as 0.
+    pub reject_negative_zero: bool,
+    pub reject_small_order_pk: bool,
+    pub reject_small_order_r: bool,
+    /// Rejects S >= L, rather than reducing S modulo L.
+    pub require_canonical_s: bool,
+    /// Checks `[4][S]B = [4]R + [4][k]A` rather than `[S]B = R + [k]A`.
+    pub cofactored: bool,
+    /// Hashes the re-encoded A and R rather than the bytes as provided.
+    pub reserialize_for_hash: bool,
+}
+
+/// RFC 8032, section 5.2.7: A and R decoded strictly, S < L and the cofactored equation.
+pub const RFC_8032: Ed448Policy = Ed448Policy {
+    reject_noncanonical_y: true,
+    reject_negative_zero: true,
+    reject_small_order_pk: false,
+    reject_small_order_r: false,
+    require_canonical_s: true,
+    cofactored: true,
+    reserialize_for_hash: false,
+};
+
+/// `RFC_8032` with the cofactorless equation, which the RFC allows as sufficient.
+pub const RFC_8032_COFACTORLESS: Ed448Policy = Ed448Policy {
+    cofactored: false,
+    ..RFC_8032
+};
+
+/// `RFC_8032_COFACTORLESS` also rejecting A and R of small order, as libsodium does for
+/// Ed25519.
+pub const STRICT: Ed448Policy = Ed448Policy {
+    reject_small_order_pk: true,
+    reject_small_order_r: true,
+    ..RFC_8032_COFACTORLESS
+};
+
+/// No check besides the cofactorless equation over the points decoded leniently and hashed
+/// as provided.
+pub const LENIENT: Ed448Policy = Ed448Policy {
+    reject_noncanonical_y: false,
+    reject_negative_zero: false,
+    reject_small_order_pk: false,
+    reject_small_order_r: false,
+    require_canonical_s: false,
+    cofactored: false,
+    reserialize_for_hash: false,
+};
+
+/// `LENIENT` hashing the re-encoded A and R, as `COFACTORLESS` does for Ed25519.
+pub const LENIENT_RESERIALIZED: Ed448Policy = Ed448Policy {
+    reserialize_for_hash: true,
+    ..LENIENT
+};
+
+/// The policies of the expected verdicts of `Ed448Vector`, by name.
+pub const ED448_POLICIES: [(&str, Ed448Policy); 5] = [
+    ("rfc8032", RFC_8032),
+    ("rfc8032_cofactorless", RFC_8032_COFACTORLESS),
+    ("strict", STRICT),
+    ("lenient", LENIENT),
+    ("lenient_reserialized", LENIENT_RESERIALIZED),
+];
+
+impl Ed448Policy {
+    fn decode(&self, pub_key: &[u8], signature: &[u8]) -> Result<(Point, Point, [u8; 57])> {
+        let signature = check_slice_size(signature, 114, "signature")?;
+        let decode =
+            |bytes| decompress_with(bytes, self.reject_noncanonical_y, self.reject_negative_zero);
+        let a = decode(pub_key)?;
+        let r = decode(&signature[..57])?;
+        let s_bytes = &signature[57..];
+        let s = if self.require_canonical_s {
+            if cmp_bytes(s_bytes, &L) != Ordering::Less {
+                return Err(anyhow!("Non-canonical S"));
+            }
+            let mut s = [0u8; 57];
+            s.copy_from_slice(s_bytes);
+            s
+        } else {
+            reduce_mod_l(s_bytes)
+        };
+        Ok((a, r, s))
+    }
+
+    fn check(
+        &self,
+        message: &[u8],
+        pub_key: &[u8],
+        signature: &[u8],
+        (a, r, s): (Point, Point, [u8; 57]),
+    ) -> Result<()> {
+        if self.reject_small_order_pk && a.is_small_order() {
+            return Err(anyhow!("Small-order public key"));
+        }
+        if self.reject_small_order_r && r.is_small_order() {
+            return Err(anyhow!("Small-order R"));
+        }
+        let k = if self.reserialize_for_hash {
+            compute_hram(message, &a.compress(), &r.compress())
+        } else {
+            compute_hram(message, pub_key, &signature[..57])
+        };
+        let mut residue = Point::basepoint().mul(&s) - r - a.mul(&k);
+        if self.cofactored {
+            residue = residue.mul(&[4]);
+        }
+        if residue.is_identity() {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid Ed448 signature"))
+        }
+    }
+}
+
+impl Verifier for Ed448Policy {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        let decoded = self.decode(pub_key, signature)?;
+        self.check(message, pub_key, signature, decoded)
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        Verdict::from_results(self.decode(pub_key, signature), |decoded| {
+            self.check(message, pub_key, signature, decoded)
+        })
+    }
+}
+
+/// An Ed448 signature exercising a divergence, with the verdict of each of
+/// `ED448_POLICIES`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Ed448Vector {
+    pub comment: String,
+    #[serde(serialize_with = "hex_bytes")]
+    pub message: Vec<u8>,
+    #[serde(serialize_with = "hex_bytes")]
+    pub pub_key: [u8; 57],
+    #[serde(serialize_with = "hex_bytes")]
+    pub signature: [u8; 114],
+    pub expected_verdicts: BTreeMap<&'static str, Verdict>,
+}
+
+fn hex_bytes<T: AsRef<[u8]>, S: Serializer>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(bytes))
+}
+
+impl Ed448Vector {
+    fn new(comment: &str, message: Vec<u8>, pub_key: [u8; 57], signature: [u8; 114]) -> Self {
+        let expected_verdicts = ED448_POLICIES
+            .iter()
+            .map(|(name, policy)| (*name, policy.verdict(&message, &pub_key, &signature)))
+            .collect();
+        Ed448Vector {
+            comment: comment.to_string(),
+            message,
+            pub_key,
+            signature,
+            expected_verdicts,
+        }
+    }
+}
+
+fn random_scalar(rng: &mut impl RngCore) -> [u8; 57] {
+    let mut bytes = [0u8; 64];
+    rng.fill_bytes(&mut bytes);
+    reduce_mod_l(&bytes)
+}
+
+// k mod 4, as verifiers reducing k modulo L and those taking the digest as is, as RFC 8032
+// does, compute [k]A for A with a torsion component: None when they differ
+fn k_mod_4(message: &[u8], pub_key: &[u8], r: &[u8]) -> Option<u8> {
+    let digest = shake256(&[r, pub_key, message]);
+    let reduced = reduce_mod_l(&digest)[0] % 4;
+    if digest[0] % 4 == reduced {
+        Some(reduced)
+    } else {
+        None
+    }
+}
+
+// A point [scalar]B + torsion as encoded in a vector, and as hashed into k
+#[derive(Clone, Copy)]
+struct Encoded {
+    bytes: [u8; 57],
+    hashed: [u8; 57],
+    scalar: [u8; 57],
+}
+
+impl Encoded {
+    fn new(bytes: [u8; 57], scalar: [u8; 57]) -> Self {
+        Encoded {
+            bytes,
+            hashed: bytes,
+            scalar,
+        }
+    }
+
+    fn hashed_as(self, hashed: [u8; 57]) -> Self {
+        Encoded { hashed, ..self }
+    }
+}
+
+// A signature R || S = r + k * a of a random message under A, the message ground until
+// `accept` holds of k mod 4 over the encodings hashed, and of k mod 4 over those of the
+// vector when they differ
+fn ground_signature(
+    rng: &mut impl RngCore,
+    a: &Encoded,
+    r: &Encoded,
+    accept: impl Fn(u8, Option<u8>) -> bool,
+) -> (Vec<u8>, [u8; 114]) {
+    loop {
+        let mut message = vec![0u8; 32];
+        rng.fill_bytes(&mut message);
+        let k = match k_mod_4(&message, &a.hashed, &r.hashed) {
+            Some(k) => k,
+            None => continue,
+        };
+        let other = if a.hashed != a.bytes || r.hashed != r.bytes {
+            match k_mod_4(&message, &a.bytes, &r.bytes) {
+                Some(k) => Some(k),
+                None => continue,
+            }
+        } else {
+            None
+        };
+        if !accept(k, other) {
+            continue;
+        }
+        let k = compute_hram(&message, &a.hashed, &r.hashed);
+        let s = reduce_mod_l(&add_bytes(&r.scalar, &mul_bytes(&k, &a.scalar)));
+        let mut signature = [0u8; 114];
+        signature[..57].copy_from_slice(&r.bytes);
+        signature[57..].copy_from_slice(&s);
+        return (message, signature);
+    }
+}
+
+// The encoding of y = p + y_small with the sign bit `sign`, for y_small < 2^224 + 1
+fn non_canonical(y_small: u8, sign: bool) -> [u8; 57] {
+    let mut bytes = [0u8; 57];
+    bytes[..56].copy_from_slice(&limbs_to_bytes(&P));
+    let sum = add_bytes(&bytes[..56], &[y_small]);
+    bytes[..56].copy_from_slice(&sum[..56]);
+    bytes[56] = (sign as u8) << 7;
+    bytes
+}
+
+/// The Ed448 analogues of the cases of `generate_test_vectors`, under the cofactor 4: a
+/// signature of RFC 8032 as a control; A and R of small order, and mixed, passing both
+/// equations or the cofactored one only; S >= L; and A and R encoded with y >= p or with
+/// x = 0 and the sign bit set, signed for the hash of the canonical encoding or of the one
+/// provided. The messages are ground so that [k]A agrees whether k is reduced modulo L or
+/// not.
+pub fn ed448_vectors(rng: &mut impl RngCore) -> Vec<Ed448Vector> {
+    let b = Point::basepoint();
+    let [identity, order_2, order_4, _] = Point::torsion();
+    let zero = [0u8; 57];
+    let mut vectors = Vec::new();
+
+    let mut secret = [0u8; 57];
+    rng.fill_bytes(&mut secret);
+    let key = Ed448Key::from_secret(&secret);
+    let mut message = vec![0u8; 32];
+    rng.fill_bytes(&mut message);
+    let control = key.sign(&message);
+    vectors.push(Ed448Vector::new(
+        "a signature of RFC 8032",
+        message.clone(),
+        key.pub_key(),
+        control,
+    ));
+
+    // small and mixed A and R, with k ground so that R + [k]A has the torsion wanted
+    let a = random_scalar(rng);
+    let r = random_scalar(rng);
+    let small = |pt: Point| Encoded::new(pt.compress(), zero);
+    let mixed =
+        |pt: Point, scalar: [u8; 57]| Encoded::new((b.mul(&scalar) + pt).compress(), scalar);
+    for (comment, pub_key, r_pt, k_mod_4) in [
+        (
+            "A and R of order 4, S = 0",
+            small(order_4),
+            small(order_4),
+            &[3][..],
+        ),
+        (
+            "A of order 4, S = r",
+            small(order_4),
+            mixed(identity, r),
+            &[0],
+        ),
+        (
+            "A mixed with the point of order 2, R of order 2",
+            mixed(order_2, a),
+            small(order_2),
+            &[1, 3],
+        ),
+        (
+            "A mixed with a point of order 4, R with the opposite one, passing both equations",
+            mixed(order_4, a),
+            mixed(-order_4, r),
+            &[1],
+        ),
+        (
+            "A mixed with a point of order 4, passing the cofactored equation only",
+            mixed(order_4, a),
+            mixed(identity, r),
+            &[1],
+        ),
+    ]
+    .iter()
+    {
+        let (message, signature) =
+            ground_signature(rng, pub_key, r_pt, |k, _| k_mod_4.contains(&k));
+        vectors.push(Ed448Vector::new(comment, message, pub_key.bytes, signature));
+    }
+
+    // S + L, which fits the 57 bytes with the top byte 0, and S + nL with the top bit set
+    let s = &control[57..];
+    let mut plus_l = control;
+    plus_l[57..].copy_from_slice(&add_bytes(s, &L)[..57]);
+    vectors.push(Ed448Vector::new(
+        "S + L",
+        message.clone(),
+        key.pub_key(),
+        plus_l,
+    ));
+    let mut plus_nl = control;
+    plus_nl[57..].copy_from_slice(&add_bytes(s, &mul_bytes(&L, &[0xff, 0x03]))[..57]);
+    vectors.push(Ed448Vector::new(
+        "S + 1023 L, with the top bit of the 57th byte set",
+        message,
+        key.pub_key(),
+        plus_nl,
+    ));
+
+    // the identity as R encoded with y = p + 1 or as -0, S = k * a for k hashing either
+    // encoding
+    let a_pt = mixed(identity, a);
+    let identity_bytes = identity.compress();
+    let mut negative_zero = identity_bytes;
+    negative_zero[56] = 0x80;
+    for (comment, r_bytes) in [
+        (
+            "R the identity encoded as y = p + 1",
+            non_canonical(1, false),
+        ),
+        ("R the identity encoded with x = -0", negative_zero),
+    ]
+    .iter()
+    {
+        let r_pt = Encoded::new(*r_bytes, zero);
+        for (hash, r_pt) in [
+            ("canonical", r_pt.hashed_as(identity_bytes)),
+            ("as provided", r_pt),
+        ]
+        .iter()
+        {
+            let (message, signature) = ground_signature(rng, &a_pt, r_pt, |_, _| true);
+            vectors.push(Ed448Vector::new(
+                &format!("{}, hashed {}", comment, hash),
+                message,
+                a_pt.bytes,
+                signature,
+            ));
+        }
+    }
+
+    // the point of order 4 as A encoded with y = p, S = r for k a multiple of 4 over either
+    // encoding, and not over the other
+    let r_pt = mixed(identity, r);
+    let order_4_bytes = order_4.compress();
+    let order_4_y_p = Encoded::new(non_canonical(0, order_4_bytes[56] != 0), zero);
+    for (hash, pub_key) in [
+        ("canonical", order_4_y_p.hashed_as(order_4_bytes)),
+        ("as provided", order_4_y_p),
+    ]
+    .iter()
+    {
+        let (message, signature) =
+            ground_signature(rng, pub_key, &r_pt, |k, other| k == 0 && other != Some(0));
+        vectors.push(Ed448Vector::new(
+            &format!("A of order 4 encoded as y = p, hashed {}", hash),
+            message,
+            pub_key.bytes,
+            signature,
+        ));
+    }
+    // the identity as A with x = -0, whose hash does not matter
+    let pub_key = Encoded::new(negative_zero, zero);
+    let (message, signature) = ground_signature(rng, &pub_key, &r_pt, |_, _| true);
+    vectors.push(Ed448Vector::new(
+        "A the identity encoded with x = -0",
+        message,
+        negative_zero,
+        signature,
+    ));
+    vectors
+}
@@ -0,0 +1,286 @@
+use crate::classification::{scalar_class, ScalarClass};
+use crate::deserialize_point;
+use crate::policy::{infer_from_partial, ValidationPolicy, RFC_8032, ZIP_215};
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::verifier::{self, Verifier};
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+/// A security property of [CGN20e] a verifier may achieve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Property {
+    /// Strong unforgeability under chosen message attacks: no second signature of a signed
+    /// message, which takes rejecting S >= L.
+    StrongUnforgeability,
+    /// Strongly binding signatures: a signature binds a single message under a single key,
+    /// which takes rejecting A of small order along with S >= L.
+    StronglyBinding,
+    /// Consensus safety: the verdicts of single verification match those of any batch
+    /// verification, which takes the cofactored equation without pre-reducing (8h).
+    ConsensusSafety,
+}
+
+impl Property {
+    pub const ALL: [Property; 3] = [
+        Property::StrongUnforgeability,
+        Property::StronglyBinding,
+        Property::ConsensusSafety,
+    ];
+
+    /// What a verifier not achieving the property does, and what breaks, as `check` warns.
+    pub fn warning(self) -> &'static str {
+        match self {
+            Property::StrongUnforgeability => {
+                "accepts non-canonical S (S >= L): strong unforgeability broken"
+            }
+            Property::StronglyBinding => {
+                "accepts non-canonical S or A of small order: signatures not strongly binding"
+            }
+            Property::ConsensusSafety => {
+                "departs from the cofactored equation: consensus safety broken"
+            }
+        }
+    }
+
+    /// The way to close the gap of a verifier not achieving the property.
+    pub fn recommendation(self) -> &'static str {
+        match self {
+            Property::StrongUnforgeability => {
+                "reject S >= L (require-canonical-s), as RFC 8032 does"
+            }
+            Property::StronglyBinding => {
+                "reject A of small order (reject-small-order-pk) and S >= L, as [CGN20e] \
+                 Algorithm 2 does"
+            }
+            Property::ConsensusSafety => {
+                "verify the cofactored equation, without pre-reducing (8h), as ZIP-215 does"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Property::StrongUnforgeability => "SUF-CMA",
+            Property::StronglyBinding => "SBS",
+            Property::ConsensusSafety => "consensus safety",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Whether a verifier achieves a `Property` on the vectors, with the vectors demonstrating
+/// the gap if not.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub property: Property,
+    /// The indices of the vectors on which the verifier breaks the property: those it
+    /// accepts with S >= L, or with A of small order for `StronglyBinding`, and those on
+    /// which it departs from the closest cofactored policy for `ConsensusSafety`. The
+    /// property is achieved on the vectors if there are none.
+    pub evidence: Vec<usize>,
+}
+
+impl Finding {
+    pub fn is_achieved(&self) -> bool {
+        self.evidence.is_empty()
+    }
+}
+
+/// The report on a verifier from its verdicts on a set of vectors: the policies it is
+/// inferred to implement, where it departs from RFC 8032 and ZIP-215, and the security
+/// properties it achieves, as the end-to-end summary of what the vectors tell about it.
+pub struct Explanation {
+    pub library: String,
+    /// The vectors, for the comments of the cases cited.
+    pub vectors: Vec<TestVector>,
+    pub observed: Vec<Verdict>,
+    /// The policies consistent with every verdict, as `classify` prints them.
+    pub consistent: Vec<ValidationPolicy>,
+    /// When none is consistent, the policy departing from the verdicts on the fewest
+    /// vectors, along with those vectors.
+    pub closest: Option<(ValidationPolicy, Vec<usize>)>,
+    /// The vectors on which the verifier accepts what RFC 8032 rejects, or the reverse.
+    pub rfc8032_differences: Vec<usize>,
+    /// The vectors on which the verifier departs from ZIP-215, as for RFC 8032.
+    pub zip215_differences: Vec<usize>,
+    /// A finding per property of `Property::ALL`, in the same order.
+    pub findings: Vec<Finding>,
+}
+
+/// Explains the verifier named `library` from its verdicts on `vectors`, `observed[i]`
+/// being its verdict on `vectors[i]`. Only whether a vector was accepted is compared with
+/// the policies, and `Error` verdicts are left out, as in `infer_from_partial`.
+pub fn explain(library: &str, vectors: &[TestVector], observed: &[Verdict]) -> Result<Explanation> {
+    if vectors.len() != observed.len() {
+        return Err(anyhow!(
+            "got {} observed verdicts for {} test vectors",
+            observed.len(),
+            vectors.len()
+        ));
+    }
+    let tried: Vec<Option<Verdict>> = observed.iter().map(|v| Some(*v)).collect();
+    let consistent = infer_from_partial(vectors, &tried)?.consistent;
+    let departures = |policy: &ValidationPolicy| -> Vec<usize> {
+        policy
+            .predict(vectors)
+            .iter()
+            .zip(observed.iter())
+            .enumerate()
+            .filter(|(_, (p, o))| !o.is_error() && p.is_accepted() != o.is_accepted())
+            .map(|(i, _)| i)
+            .collect()
+    };
+    let closest_of = |policies: &[ValidationPolicy]| -> Option<(ValidationPolicy, Vec<usize>)> {
+        policies
+            .iter()
+            .map(|policy| (*policy, departures(policy)))
+            .min_by_key(|(_, departing)| departing.len())
+    };
+
+    let accepted_with = |breaks: &dyn Fn(&TestVector) -> bool| -> Vec<usize> {
+        (0..vectors.len())
+            .filter(|&i| observed[i].is_accepted() && breaks(&vectors[i]))
+            .collect()
+    };
+    let large_s = |tv: &TestVector| {
+        let mut s = [0u8; 32];
+        match tv.signature.get(32..64) {
+            Some(bytes) => s.copy_from_slice(bytes),
+            None => return false,
+        }
+        scalar_class(&s) != ScalarClass::Canonical
+    };
+    let small_order_pub_key = |tv: &TestVector| {
+        deserialize_point(&tv.pub_key)
+            .map(|pk| pk.is_small_order())
+            .unwrap_or(false)
+    };
+    let cofactored: Vec<ValidationPolicy> = ValidationPolicy::all()
+        .into_iter()
+        .filter(|policy| policy.cofactored && !policy.prereduce)
+        .collect();
+    let findings = Property::ALL
+        .iter()
+        .map(|&property| Finding {
+            property,
+            evidence: match property {
+                Property::StrongUnforgeability => accepted_with(&large_s),
+                Property::StronglyBinding => {
+                    accepted_with(&|tv| large_s(tv) || small_order_pub_key(tv))
+                }
+                Property::ConsensusSafety => closest_of(&cofactored)
+                    .map(|(_, departing)| departing)
+                    .unwrap_or_default(),
+            },
+        })
+        .collect();
+
+    Ok(Explanation {
+        library: library.to_string(),
+        vectors: vectors.to_vec(),
+        observed: observed.to_vec(),
+        closest: if consistent.is_empty() {
+            closest_of(&ValidationPolicy::all())
+        } else {
+            None
+        },
+        consistent,
+        rfc8032_differences: departures(&RFC_8032),
+        zip215_differences: departures(&ZIP_215),
+        findings,
+    })
+}
+
+/// Runs `verifier` over `vectors` and explains it, as `explain` on its verdicts.
+pub fn explain_verifier(
+    library: &str,
+    verifier: &dyn Verifier,
+    vectors: &[TestVector],
+) -> Result<Explanation> {
+    explain(library, vectors, &verifier::verdicts(verifier, vectors))
+}
+
+impl Explanation {
+    /// The properties of `Property::ALL` the verifier does not achieve on the vectors.
+    pub fn gaps(&self) -> Vec<Property> {
+        self.findings
+            .iter()
+            .filter(|finding| !finding.is_achieved())
+            .map(|finding| finding.property)
+            .collect()
+    }
+
+    // The vector `index`, with its comment if it has metadata
+    fn case(&self, index: usize) -> String {
+        match &self.vectors[index].metadata {
+            Some(metadata) => format!("case {} ({})", index, metadata.comment),
+            None => format!("case {}", index),
+        }
+    }
+
+    fn write_differences(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        reference: &str,
+        policy: &ValidationPolicy,
+        differences: &[usize],
+    ) -> fmt::Result {
+        writeln!(f, "differences from {}: {}", reference, differences.len())?;
+        for &i in differences.iter() {
+            let tv = &self.vectors[i];
+            writeln!(
+                f,
+                "  {}: expected {}, observed {}",
+                self.case(i),
+                policy.verdict(&tv.message, &tv.pub_key, &tv.signature),
+                self.observed[i]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The report, as printed by the `explain` command
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} on {} vectors", self.library, self.vectors.len())?;
+        match &self.closest {
+            None => {
+                writeln!(f, "inferred policies:")?;
+                for policy in self.consistent.iter() {
+                    writeln!(f, "  {}", policy)?;
+                }
+            }
+            Some((policy, departing)) => {
+                let cases: Vec<String> = departing.iter().map(usize::to_string).collect();
+                writeln!(f, "no consistent policy, the closest being:")?;
+                writeln!(f, "  {}", policy)?;
+                writeln!(f, "  departing on cases {}", cases.join(", "))?;
+            }
+        }
+        self.write_differences(f, "RFC 8032", &RFC_8032, &self.rfc8032_differences)?;
+        self.write_differences(f, "ZIP-215", &ZIP_215, &self.zip215_differences)?;
+        writeln!(f, "security properties:")?;
+        for finding in self.findings.iter() {
+            if finding.is_achieved() {
+                writeln!(f, "  {}: achieved", finding.property)?;
+                continue;
+            }
+            writeln!(f, "  {}: not achieved, as shown by", finding.property)?;
+            for &i in finding.evidence.iter() {
+                writeln!(f, "    {}: {}", self.case(i), self.observed[i])?;
+            }
+        }
+        let gaps = self.gaps();
+        if !gaps.is_empty() {
+            writeln!(f, "recommendations:")?;
+            for property in gaps {
+                writeln!(f, "  for {}, {}", property, property.recommendation())?;
+            }
+        }
+        Ok(())
+    }
+}
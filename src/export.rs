@@ -0,0 +1,151 @@
+use crate::policy::ValidationPolicy;
+use crate::sarif::{family_of, CASE_FAMILIES};
+use crate::test_vectors::{generate_test_vectors, TestVector};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// The DER prefix of an Ed25519 SubjectPublicKeyInfo, followed by the 32 bytes of the key
+const SPKI_PREFIX: &str = "302a300506032b6570032100";
+
+/// The policies the standards leave to the verifier: RFC 8032 and FIPS 186-5 both require
+/// S < L and canonical encodings of A and R, but allow the cofactored and cofactorless
+/// equations, and rejecting A or R of small order, or A not of order L. Pre-reducing (8h)
+/// is a departure from either, and not included.
+pub fn standard_policies() -> Vec<ValidationPolicy> {
+    ValidationPolicy::all()
+        .into_iter()
+        .filter(|policy| {
+            policy.require_canonical_s
+                && policy.reject_noncanonical_pk
+                && policy.reject_noncanonical_r
+                && !policy.prereduce
+        })
+        .collect()
+}
+
+/// The Wycheproof result of `tv` under `policies`: "valid" if they all accept it, "invalid"
+/// if they all reject it, and "acceptable" if they disagree.
+pub fn wycheproof_result(policies: &[ValidationPolicy], tv: &TestVector) -> &'static str {
+    let accepting = policies
+        .iter()
+        .filter(|policy| {
+            policy
+                .verify(&tv.message, &tv.pub_key, &tv.signature)
+                .is_ok()
+        })
+        .count();
+    if accepting == policies.len() {
+        "valid"
+    } else if accepting == 0 {
+        "invalid"
+    } else {
+        "acceptable"
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofFile {
+    algorithm: &'static str,
+    generator_version: &'static str,
+    number_of_tests: usize,
+    header: Vec<String>,
+    // the flags, along with their descriptions
+    notes: BTreeMap<&'static str, &'static str>,
+    schema: &'static str,
+    test_groups: Vec<WycheproofGroup>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofGroup {
+    key: WycheproofKey,
+    key_der: String,
+    #[serde(rename = "type")]
+    group_type: &'static str,
+    tests: Vec<WycheproofTest>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofKey {
+    curve: &'static str,
+    key_size: usize,
+    pk: String,
+    #[serde(rename = "type")]
+    key_type: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WycheproofTest {
+    tc_id: usize,
+    comment: &'static str,
+    msg: String,
+    sig: String,
+    result: &'static str,
+    flags: Vec<&'static str>,
+}
+
+/// Writes the `vectors` of `generate_test_vectors` in the schema of Wycheproof's
+/// `eddsa_test.json`, with a test group per public key. The test of the i-th vector has
+/// tcId i + 1, the family of the case as its comment and flag, and its result under
+/// `policies`, see `wycheproof_result`.
+pub fn wycheproof(vectors: &[TestVector], policies: &[ValidationPolicy]) -> Result<String> {
+    let mut groups: Vec<WycheproofGroup> = Vec::new();
+    for (i, tv) in vectors.iter().enumerate() {
+        let family = family_of(i);
+        let test = WycheproofTest {
+            tc_id: i + 1,
+            comment: family.map(|f| f.description).unwrap_or_default(),
+            msg: hex::encode(&tv.message),
+            sig: hex::encode(&tv.signature),
+            result: wycheproof_result(policies, tv),
+            flags: family.map(|f| vec![f.name]).unwrap_or_default(),
+        };
+        let pk = hex::encode(tv.pub_key);
+        match groups.iter_mut().find(|group| group.key.pk == pk) {
+            Some(group) => group.tests.push(test),
+            None => groups.push(WycheproofGroup {
+                key_der: format!("{}{}", SPKI_PREFIX, pk),
+                key: WycheproofKey {
+                    curve: "edwards25519",
+                    key_size: 255,
+                    pk,
+                    key_type: "EDDSAPublicKey",
+                },
+                group_type: "EddsaVerify",
+                tests: vec![test],
+            }),
+        }
+    }
+
+    Ok(serde_json::to_string(&WycheproofFile {
+        algorithm: "EDDSA",
+        generator_version: env!("CARGO_PKG_VERSION"),
+        number_of_tests: vectors.len(),
+        header: vec![
+            format!(
+                "Edge cases of Ed25519 verification generated by {} {}.",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION")
+            ),
+            "A vector is valid if every policy it was checked against accepts it, invalid if \
+             every one rejects it, and acceptable otherwise."
+                .to_string(),
+        ],
+        notes: CASE_FAMILIES
+            .iter()
+            .map(|family| (family.name, family.description))
+            .collect(),
+        schema: "eddsa_verify_schema.json",
+        test_groups: groups,
+    })?)
+}
+
+/// The vectors of `generate_test_vectors` as `wycheproof` writes them under the
+/// `standard_policies`.
+pub fn wycheproof_file() -> Result<String> {
+    wycheproof(&generate_test_vectors(), &standard_policies())
+}
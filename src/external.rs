@@ -0,0 +1,118 @@
+use crate::verdict::Verdict;
+use crate::verifier::Verifier;
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// How `External` passes a vector to its command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input {
+    /// The message, public key and signature in hex, in place of the placeholders `{msg}`,
+    /// `{pk}` and `{sig}` in the arguments, or as the last three arguments if there are none.
+    Args,
+    /// The message, public key and signature in hex on standard input, in the layout of an
+    /// entry of `cases.txt`: `msg=<hex>`, `pbk=<hex>` and `sig=<hex>`, one per line.
+    Stdin,
+}
+
+/// A verifier running a command of the user's once per vector, for implementations in other
+/// languages to be classified without Rust bindings, e.g. a shim around a C library, or a Go
+/// binary. The exit status of the command is its verdict: 0 accepts the vector, 2 rejects it
+/// while parsing, and any other status rejects it while verifying. A command which cannot
+/// be run, or which is killed by a signal, gets an `Error` verdict.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct External {
+    program: String,
+    args: Vec<String>,
+    input: Input,
+}
+
+/// The exit status of a command rejecting a vector while parsing it.
+pub const PARSE_REJECTED_STATUS: i32 = 2;
+
+// The placeholders of the message, the public key and the signature in the arguments.
+const PLACEHOLDERS: [&str; 3] = ["{msg}", "{pk}", "{sig}"];
+
+impl External {
+    /// Runs `program` with the arguments `args`, followed by the vector if passed as
+    /// arguments without placeholders.
+    pub fn new(program: &str, args: &[&str], input: Input) -> External {
+        External {
+            program: program.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            input,
+        }
+    }
+
+    /// Parses a command line split on whitespace, without the quoting of a shell: scripts
+    /// taking arguments with spaces in them go in a file.
+    pub fn parse(command: &str, input: Input) -> Result<External> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| anyhow!("empty external command"))?;
+        let args: Vec<&str> = words.collect();
+        Ok(External::new(program, &args, input))
+    }
+
+    // The exit status of the command on the vector, or None if killed by a signal
+    fn run(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<Option<i32>> {
+        let hex = [
+            hex::encode(message),
+            hex::encode(pub_key),
+            hex::encode(signature),
+        ];
+        let placeholders = self
+            .args
+            .iter()
+            .any(|arg| PLACEHOLDERS.iter().any(|p| arg.contains(p)));
+        let mut command = Command::new(&self.program);
+        command.stdout(Stdio::null()).stderr(Stdio::null());
+        let status = match self.input {
+            Input::Args if placeholders => command
+                .args(self.args.iter().map(|arg| {
+                    PLACEHOLDERS
+                        .iter()
+                        .zip(hex.iter())
+                        .fold(arg.clone(), |arg, (p, value)| arg.replace(p, value))
+                }))
+                .stdin(Stdio::null())
+                .status()?,
+            Input::Args => command
+                .args(&self.args)
+                .args(&hex)
+                .stdin(Stdio::null())
+                .status()?,
+            Input::Stdin => {
+                let mut child = command.args(&self.args).stdin(Stdio::piped()).spawn()?;
+                let mut stdin = child.stdin.take().unwrap();
+                // a command exiting without reading its input breaks the pipe, and its exit
+                // status is the verdict all the same
+                let _ = writeln!(stdin, "msg={}\npbk={}\nsig={}", hex[0], hex[1], hex[2]);
+                // closes standard input for the command to finish reading
+                drop(stdin);
+                child.wait()?
+            }
+        };
+        Ok(status.code())
+    }
+}
+
+impl Verifier for External {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        match self.run(message, pub_key, signature)? {
+            Some(0) => Ok(()),
+            Some(code) => Err(anyhow!("{} exited with status {}", self.program, code)),
+            None => Err(anyhow!("{} was killed by a signal", self.program)),
+        }
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        match self.run(message, pub_key, signature) {
+            Ok(Some(0)) => Verdict::Accepted,
+            Ok(Some(PARSE_REJECTED_STATUS)) => Verdict::ParseRejected,
+            Ok(Some(_)) => Verdict::VerifyRejected,
+            Ok(None) | Err(_) => Verdict::Error,
+        }
+    }
+}
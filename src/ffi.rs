@@ -0,0 +1,192 @@
+use crate::test_vectors::{generate_test_vectors, TestVector};
+use crate::verdict::Verdict;
+use crate::verifier::{Cofactored, Cofactorless, Verifier};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+// The C interface of `include/speccheck.h`, for C and C++ test suites to link the vectors and
+// the reference verifiers rather than parse `cases.txt`. Every buffer comes with its length,
+// and no panic unwinds into the caller: a panic, as a null pointer, gets `SPECCHECK_ERROR`.
+
+/// The vectors of `generate_test_vectors`, as an opaque pointer for C callers.
+pub struct SpeccheckVectors(Vec<TestVector>);
+
+/// The verdicts of the verifiers, as `Verdict`.
+pub const SPECCHECK_ACCEPTED: i32 = 0;
+pub const SPECCHECK_PARSE_REJECTED: i32 = 1;
+pub const SPECCHECK_VERIFY_REJECTED: i32 = 2;
+/// A null pointer with a non-zero length, an index out of range, or a panic of the library.
+pub const SPECCHECK_ERROR: i32 = -1;
+
+fn code(verdict: Verdict) -> i32 {
+    match verdict {
+        Verdict::Accepted => SPECCHECK_ACCEPTED,
+        Verdict::ParseRejected => SPECCHECK_PARSE_REJECTED,
+        Verdict::VerifyRejected => SPECCHECK_VERIFY_REJECTED,
+        Verdict::Error => SPECCHECK_ERROR,
+    }
+}
+
+// The `len` bytes at `ptr`, which may be null only if `len` is 0
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if ptr.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(ptr, len))
+    }
+}
+
+/// Generates the 12 vectors of `cases.json`, to be freed with `speccheck_vectors_free`, or
+/// returns null if generation failed.
+#[no_mangle]
+pub extern "C" fn speccheck_generate_vectors() -> *mut SpeccheckVectors {
+    panic::catch_unwind(|| Box::into_raw(Box::new(SpeccheckVectors(generate_test_vectors()))))
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Frees vectors returned by `speccheck_generate_vectors`; null is ignored.
+///
+/// # Safety
+///
+/// `vectors` is null or was returned by `speccheck_generate_vectors`, and not freed since.
+#[no_mangle]
+pub unsafe extern "C" fn speccheck_vectors_free(vectors: *mut SpeccheckVectors) {
+    if !vectors.is_null() {
+        drop(Box::from_raw(vectors));
+    }
+}
+
+/// The number of vectors, 0 for null.
+///
+/// # Safety
+///
+/// `vectors` is null or was returned by `speccheck_generate_vectors`, and not freed since.
+#[no_mangle]
+pub unsafe extern "C" fn speccheck_vectors_len(vectors: *const SpeccheckVectors) -> usize {
+    vectors.as_ref().map_or(0, |vectors| vectors.0.len())
+}
+
+/// The fields of a vector, each buffer with its length, as filled by `speccheck_vector_get`.
+#[repr(C)]
+pub struct SpeccheckVector {
+    pub message: *const u8,
+    pub message_len: usize,
+    pub pub_key: *const u8,
+    pub pub_key_len: usize,
+    pub signature: *const u8,
+    pub signature_len: usize,
+}
+
+/// Points the fields of `out` at the vector `index`, returning 0; they remain valid until the
+/// vectors are freed. Returns `SPECCHECK_ERROR`, leaving `out` unset, for an index out of range
+/// or a null pointer.
+///
+/// # Safety
+///
+/// `vectors` is null or was returned by `speccheck_generate_vectors`, and not freed since,
+/// and `out` is null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn speccheck_vector_get(
+    vectors: *const SpeccheckVectors,
+    index: usize,
+    out: *mut SpeccheckVector,
+) -> i32 {
+    let tv = match vectors.as_ref().and_then(|vectors| vectors.0.get(index)) {
+        Some(tv) => tv,
+        None => return SPECCHECK_ERROR,
+    };
+    match out.as_mut() {
+        Some(out) => {
+            *out = SpeccheckVector {
+                message: tv.message.as_ptr(),
+                message_len: tv.message.len(),
+                pub_key: tv.pub_key.as_ptr(),
+                pub_key_len: tv.pub_key.len(),
+                signature: tv.signature.as_ptr(),
+                signature_len: tv.signature.len(),
+            };
+            0
+        }
+        None => SPECCHECK_ERROR,
+    }
+}
+
+// The verdict of `verifier` on the raw inputs, without unwinding
+unsafe fn verify_raw(
+    verifier: &dyn Verifier,
+    message: *const u8,
+    message_len: usize,
+    pub_key: *const u8,
+    pub_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> i32 {
+    let (message, pub_key, signature) = match (
+        bytes(message, message_len),
+        bytes(pub_key, pub_key_len),
+        bytes(signature, signature_len),
+    ) {
+        (Some(message), Some(pub_key), Some(signature)) => (message, pub_key, signature),
+        _ => return SPECCHECK_ERROR,
+    };
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        code(verifier.verdict(message, pub_key, signature))
+    }))
+    .unwrap_or(SPECCHECK_ERROR)
+}
+
+/// The verdict of `verify_cofactored` on the signature of `message` under `pub_key`:
+/// `SPECCHECK_ACCEPTED`, `SPECCHECK_PARSE_REJECTED` (e.g. for a public key of other than 32
+/// bytes or a signature of other than 64 bytes) or `SPECCHECK_VERIFY_REJECTED`.
+///
+/// # Safety
+///
+/// `message` is valid for `message_len` bytes, `pub_key` for `pub_key_len` bytes, and
+/// `signature` for `signature_len` bytes; each may be null if its length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn speccheck_verify_cofactored(
+    message: *const u8,
+    message_len: usize,
+    pub_key: *const u8,
+    pub_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> i32 {
+    verify_raw(
+        &Cofactored,
+        message,
+        message_len,
+        pub_key,
+        pub_key_len,
+        signature,
+        signature_len,
+    )
+}
+
+/// As `speccheck_verify_cofactored`, for `verify_cofactorless`.
+///
+/// # Safety
+///
+/// As for `speccheck_verify_cofactored`.
+#[no_mangle]
+pub unsafe extern "C" fn speccheck_verify_cofactorless(
+    message: *const u8,
+    message_len: usize,
+    pub_key: *const u8,
+    pub_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> i32 {
+    verify_raw(
+        &Cofactorless,
+        message,
+        message_len,
+        pub_key,
+        pub_key_len,
+        signature,
+        signature_len,
+    )
+}
@@ -0,0 +1,44 @@
+/// This file implements the verification of FIPS 186-5, section 7.7.2, as a reference
+/// verifier: A and R are decoded as in RFC 8032 and S must be canonical, as in `rfc8032`,
+/// but the equation is cofactored, `[8][S]B = [8]R + [8][k]A`. A is also validated as in
+/// section 7.4, or SP 800-186 appendix D.1.3, which validation for FIPS requires: it must
+/// have order exactly L, which rejects the A of small or mixed order.
+///
+/// References:
+/// [FIPS186-5] Digital Signature Standard (DSS); NIST; 2023; https://doi.org/10.6028/NIST.FIPS.186-5
+use crate::algorithm2::deserialize_s;
+use crate::policy::has_full_order;
+use crate::rfc8032::deserialize_point;
+use crate::verdict::Verdict;
+use crate::{check_slice_size, compute_hram_with_arrays, verify_final_cofactored};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+
+#[allow(non_snake_case)]
+fn decode(pk_bytes: &[u8], sig_bytes: &[u8]) -> Result<(EdwardsPoint, EdwardsPoint, Scalar)> {
+    let checked_sig_bytes = check_slice_size(sig_bytes, 64, "sig_bytes")?;
+    let A = deserialize_point(pk_bytes)?;
+    let R = deserialize_point(&checked_sig_bytes[..32])?;
+    let s = deserialize_s(&checked_sig_bytes[32..])?;
+    Ok((A, R, s))
+}
+
+/// Deserializes and verifies a signature as in section 7.7.2, after validating the public
+/// key.
+pub fn verify(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> bool {
+    verdict(msg_bytes, pk_bytes, sig_bytes).is_accepted()
+}
+
+/// As `verify`, telling apart the step rejecting the signature. Public keys failing
+/// validation decode, and are rejected while verifying.
+#[allow(non_snake_case)]
+pub fn verdict(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> Verdict {
+    Verdict::from_results(decode(pk_bytes, sig_bytes), |(A, R, s)| {
+        if !has_full_order(&A) {
+            return Err(anyhow!("Public key not of order L"));
+        }
+        let k = compute_hram_with_arrays(msg_bytes, pk_bytes, &sig_bytes[..32]);
+        verify_final_cofactored(&A, &(R, s), &k)
+    })
+}
@@ -0,0 +1,79 @@
+use crate::policy::has_full_order;
+use crate::test_vectors::TestVector;
+use crate::torsion::{DECODE_MODE, TORSION_ORDERS};
+use crate::{
+    compute_hram, deserialize_point_with, serialize_signature, verify_cofactored,
+    verify_cofactorless, verify_pre_reduced_cofactored, EIGHT_TORSION,
+};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A valid signature under a mixed-order A, i.e. a point of order L plus a small-order
+/// component, which only requiring A to have order L rejects: A is canonical and not of
+/// small order, R has order L, S < L, and the message is ground so that `[k]A` loses the
+/// small-order component, hence the cofactored, pre-reduced and cofactorless equations hold.
+#[derive(Serialize)]
+pub struct FullOrderVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// The order of the small-order component of A: 2, 4 or 8.
+    pub torsion_order: usize,
+}
+
+fn full_order_vector(
+    rng: &mut impl RngCore,
+    (torsion_order, torsion_idx): (usize, usize),
+) -> FullOrderVector {
+    let torsion = deserialize_point_with(&EIGHT_TORSION[torsion_idx], DECODE_MODE).unwrap();
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT + torsion;
+
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+
+    // k must be a multiple of the order of the small-order component for [k]A = [ka]B, and
+    // so must (8k mod L) for verifiers pre-reducing it
+    let mut message = [0u8; 32];
+    let s = loop {
+        rng.fill_bytes(&mut message);
+        let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+        if verify_cofactorless(&message, &pub_key, &(r, s)).is_ok()
+            && verify_pre_reduced_cofactored(&message, &pub_key, &(r, s)).is_ok()
+        {
+            break s;
+        }
+    };
+
+    debug_assert!(!pub_key.is_small_order() && !has_full_order(&pub_key));
+    debug_assert!(has_full_order(&r));
+    debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
+    debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
+
+    FullOrderVector {
+        comment: format!(
+            "A mixed with a component of order {}, passes all but the full-order check",
+            torsion_order
+        ),
+        vector: TestVector {
+            message: message.to_vec(),
+            pub_key: pub_key.compress().to_bytes(),
+            signature: serialize_signature(&r, &s),
+            metadata: None,
+            trace: None,
+        },
+        torsion_order,
+    }
+}
+
+/// A vector for each order of the small-order component of A.
+pub fn full_order_vectors(rng: &mut impl RngCore) -> Vec<FullOrderVector> {
+    TORSION_ORDERS
+        .iter()
+        .map(|&torsion| full_order_vector(rng, torsion))
+        .collect()
+}
@@ -0,0 +1,79 @@
+use crate::message_source::{MessageSource, RandomBytes};
+use crate::test_vectors::{generate_test_vectors_from, TestVector};
+use rand::rngs::{OsRng, StdRng};
+use rand::{RngCore, SeedableRng};
+use sha2::{Digest, Sha512};
+
+/// Generates the test vectors from a caller-supplied seed, drawing each case from a fresh
+/// `StdRng` seeded with it, as `generate_test_vectors` does with the digits of pi. Every
+/// generator grinds until its case holds, so that any seed yields the same cases, and
+/// `derive` gives as many independent instantiations of them as needed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VectorGenerator {
+    seed: [u8; 32],
+}
+
+impl Default for VectorGenerator {
+    /// The generator of the release, seeded with the digits of pi as `new_rng`.
+    fn default() -> VectorGenerator {
+        let mut seed = [0u8; 32];
+        for i in 0..4 {
+            seed[8 * i..8 * i + 8].copy_from_slice(&std::f64::consts::PI.to_le_bytes()[..]);
+        }
+        VectorGenerator { seed }
+    }
+}
+
+impl VectorGenerator {
+    pub fn from_seed(seed: [u8; 32]) -> VectorGenerator {
+        VectorGenerator { seed }
+    }
+
+    /// The generator whose RNG is `StdRng::seed_from_u64(seed)`, as for the `--seed` of the
+    /// command line.
+    pub fn from_u64(seed: u64) -> VectorGenerator {
+        let mut bytes = [0u8; 32];
+        StdRng::seed_from_u64(seed).fill_bytes(&mut bytes);
+        VectorGenerator { seed: bytes }
+    }
+
+    /// A generator seeded by the operating system, whose `seed` must be recorded for the
+    /// vectors to be generated again.
+    pub fn from_entropy() -> VectorGenerator {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        VectorGenerator { seed }
+    }
+
+    pub fn seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// The `index`-th generator derived from this one, seeded with the first 32 bytes of
+    /// `SHA-512(seed || index)` with `index` in 8 little-endian bytes.
+    pub fn derive(&self, index: u64) -> VectorGenerator {
+        let digest = Sha512::new()
+            .chain(self.seed)
+            .chain(index.to_le_bytes())
+            .finalize();
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&digest[..32]);
+        VectorGenerator { seed }
+    }
+
+    /// A fresh RNG, for the generators of the other files (e.g. `full_order_vectors`), which
+    /// take one.
+    pub fn rng(&self) -> StdRng {
+        StdRng::from_seed(self.seed)
+    }
+
+    /// The cases of `generate_test_vectors`, drawn from this generator.
+    pub fn test_vectors(&self) -> Vec<TestVector> {
+        self.test_vectors_from(&mut RandomBytes::default())
+    }
+
+    /// As `test_vectors`, grinding over the messages drawn from `messages`.
+    pub fn test_vectors_from(&self, messages: &mut impl MessageSource) -> Vec<TestVector> {
+        generate_test_vectors_from(|| self.rng(), messages)
+    }
+}
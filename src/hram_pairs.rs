@@ -0,0 +1,163 @@
+use crate::test_vectors::TestVector;
+use crate::{
+    compute_hram, compute_hram_with_arrays, deserialize_point_with, serialize_signature,
+    DecodeMode, HramPrefix, EIGHT_TORSION_NON_CANONICAL,
+};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+
+/// Two vectors which differ only in the encoding of one point, canonical in `first` and
+/// non-canonical in `second`. Verifiers which hash the re-serialized points compute the same
+/// hram scalar `k` for both, while those hashing the bytes as provided compute two distinct
+/// ones, showing how the encoding ambiguity feeds through the challenge computation.
+pub struct LinkedPair {
+    pub comment: String,
+    pub first: TestVector,
+    pub second: TestVector,
+    pub hram_reserialized: Scalar,
+    pub hram_first: Scalar,
+    pub hram_second: Scalar,
+}
+
+impl Serialize for LinkedPair {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("LinkedPair", 6)?;
+        state.serialize_field("comment", &self.comment)?;
+        state.serialize_field("first", &self.first)?;
+        state.serialize_field("second", &self.second)?;
+        state.serialize_field(
+            "hram_reserialized",
+            &hex::encode(self.hram_reserialized.as_bytes()),
+        )?;
+        state.serialize_field("hram_first", &hex::encode(self.hram_first.as_bytes()))?;
+        state.serialize_field("hram_second", &hex::encode(self.hram_second.as_bytes()))?;
+        state.end()
+    }
+}
+
+// The decoding of the small-order points the pairs are built around, lenient as one vector
+// of each pair carries their non-canonical encoding
+const DECODE_MODE: DecodeMode = DecodeMode::Lenient;
+
+// The signature of a fixed message has R of small order, in both its canonical and
+// non-canonical encodings, and S computed for the re-serialized R. The public key is
+// honest, so that verifiers hashing R as provided reject the second vector.
+fn r_side_pair(rng: &mut impl RngCore, non_canonical: [u8; 32]) -> LinkedPair {
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT;
+
+    let r = deserialize_point_with(&non_canonical, DECODE_MODE).unwrap();
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+
+    let k = compute_hram(&message, &pub_key, &r);
+    let s = k * a;
+    let first = TestVector {
+        message: message.to_vec(),
+        pub_key: pub_key.compress().to_bytes(),
+        signature: serialize_signature(&r, &s),
+        metadata: None,
+        trace: None,
+    };
+    let mut second = first.clone();
+    second.signature[..32].copy_from_slice(&non_canonical);
+
+    LinkedPair {
+        comment: format!(
+            "R of order {}, encoded as {} then {}",
+            small_order(&non_canonical),
+            hex::encode(r.compress().as_bytes()),
+            hex::encode(non_canonical)
+        ),
+        hram_reserialized: k,
+        hram_first: compute_hram_with_arrays(&message, &first.pub_key, &first.signature[..32]),
+        hram_second: compute_hram_with_arrays(&message, &second.pub_key, &second.signature[..32]),
+        first,
+        second,
+    }
+}
+
+// The public key is of small order, in both its canonical and non-canonical encodings, and
+// the message ground so that the cofactorless equation holds for the re-serialized A but
+// not for A as provided (unless A is the identity, for which it always holds).
+fn a_side_pair(rng: &mut impl RngCore, non_canonical: [u8; 32]) -> LinkedPair {
+    let pub_key = deserialize_point_with(&non_canonical, DECODE_MODE).unwrap();
+    let canonical = pub_key.compress().to_bytes();
+
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let s = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = s * ED25519_BASEPOINT_POINT;
+    let signature = serialize_signature(&r, &s);
+
+    let hram = HramPrefix::new(&r, &pub_key);
+    let hram_provided = HramPrefix::from_arrays(&signature[..32], &non_canonical);
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    while !(hram.finish(&message) * pub_key).is_identity()
+        || (!pub_key.is_identity() && (hram_provided.finish(&message) * pub_key).is_identity())
+    {
+        rng.fill_bytes(&mut message);
+    }
+
+    let first = TestVector {
+        message: message.to_vec(),
+        pub_key: canonical,
+        signature: signature.clone(),
+        metadata: None,
+        trace: None,
+    };
+    let second = TestVector {
+        message: message.to_vec(),
+        pub_key: non_canonical,
+        signature,
+        metadata: None,
+        trace: None,
+    };
+
+    LinkedPair {
+        comment: format!(
+            "A of order {}, encoded as {} then {}",
+            small_order(&non_canonical),
+            hex::encode(canonical),
+            hex::encode(non_canonical)
+        ),
+        hram_reserialized: compute_hram(&message, &pub_key, &r),
+        hram_first: compute_hram_with_arrays(&message, &first.pub_key, &first.signature[..32]),
+        hram_second: compute_hram_with_arrays(&message, &second.pub_key, &second.signature[..32]),
+        first,
+        second,
+    }
+}
+
+fn small_order(bytes: &[u8; 32]) -> usize {
+    let mut pt = deserialize_point_with(bytes, DECODE_MODE).unwrap();
+    let mut order = 1;
+    while !pt.is_identity() {
+        pt = pt + pt;
+        order *= 2;
+    }
+    order
+}
+
+/// For every non-canonical encoding of a small-order point, a pair placing it in R and a
+/// pair placing it in A.
+pub fn hram_linked_pairs(rng: &mut impl RngCore) -> Vec<LinkedPair> {
+    let mut pairs = Vec::new();
+    for non_canonical in EIGHT_TORSION_NON_CANONICAL.iter() {
+        pairs.push(r_side_pair(rng, *non_canonical));
+    }
+    for non_canonical in EIGHT_TORSION_NON_CANONICAL.iter() {
+        pairs.push(a_side_pair(rng, *non_canonical));
+    }
+    pairs
+}
@@ -0,0 +1,66 @@
+use crate::matrix::{classify, Verdicts};
+use crate::test_vectors::TestVector;
+use crate::{serialize_signature, EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A signature under the identity as public key, with `R = [r]B` of order L and `S = r`:
+/// `[k]A` vanishes whatever k, so that both equations hold whichever way A is hashed, and
+/// only the checks of A reject the vector. Those against small-order keys, or requiring A
+/// to have order L, reject every variant, and those against non-canonical encodings the
+/// non-canonical ones.
+#[derive(Serialize)]
+pub struct IdentityPubKeyVector {
+    pub comment: String,
+    pub vector: TestVector,
+    pub canonical: bool,
+    pub expected_verdicts: Verdicts,
+}
+
+// The encodings of the identity: the canonical one, from `EIGHT_TORSION`, then those of
+// `EIGHT_TORSION_NON_CANONICAL`, setting the sign of x = 0 and/or adding p to y = 1
+fn encodings() -> [(&'static str, [u8; 32]); 4] {
+    [
+        ("canonically", EIGHT_TORSION[0]),
+        ("with the sign of x = 0 set", EIGHT_TORSION_NON_CANONICAL[0]),
+        (
+            "as y = 1 + p, with the sign of x set",
+            EIGHT_TORSION_NON_CANONICAL[1],
+        ),
+        ("as y = 1 + p", EIGHT_TORSION_NON_CANONICAL[3]),
+    ]
+}
+
+/// A vector under each encoding of the identity, the canonical one first, signing the same
+/// message with the same R and S.
+pub fn identity_pubkey(rng: &mut impl RngCore) -> Vec<IdentityPubKeyVector> {
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+
+    encodings()
+        .iter()
+        .enumerate()
+        .map(|(i, (encoding, pub_key))| {
+            let vector = TestVector {
+                message: message.to_vec(),
+                pub_key: *pub_key,
+                signature: serialize_signature(&r, &r_scalar),
+                metadata: None,
+                trace: None,
+            };
+            let expected_verdicts = classify(&vector);
+            IdentityPubKeyVector {
+                comment: format!("A the identity encoded {}, S = r", encoding),
+                vector,
+                canonical: i == 0,
+                expected_verdicts,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+use crate::matrix::{classify, Verdicts};
+use crate::test_vectors::TestVector;
+use crate::torsion::EIGHT_TORSION;
+use crate::{compute_hram, deserialize_point_with, serialize_signature, DecodeMode};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A signature with R the identity, encoded canonically, and `S = ka`: under `A = [a]B`,
+/// both equations hold, and under `A = [a]B + T`, T of order 8, only the cofactored one,
+/// `[k]T` being left over. Only the checks of R rejecting the identity, or small-order
+/// points, reject the first; the second is the case of a mixed-order A with R the identity.
+#[derive(Serialize)]
+pub struct IdentityRVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// Whether A has a torsion component, so that only the cofactored equation holds.
+    pub cofactored_only: bool,
+    pub expected_verdicts: Verdicts,
+}
+
+// A signature with R the identity under [a]B, mixed with the point of order 8 of
+// `EIGHT_TORSION[1]` when `cofactored_only`, the message ground for [k]T not to vanish but
+// [8k mod L]T to, so that the cofactored equation holds whether 8k is reduced or not
+fn identity_r_vector(rng: &mut impl RngCore, cofactored_only: bool) -> TestVector {
+    let r = deserialize_point_with(&EIGHT_TORSION[0], DecodeMode::Strict).unwrap();
+    let torsion = deserialize_point_with(&EIGHT_TORSION[1], DecodeMode::Strict).unwrap();
+    let mut a_bytes = [0u8; 32];
+    rng.fill_bytes(&mut a_bytes);
+    let a = Scalar::from_bytes_mod_order(a_bytes);
+    let mut pub_key = a * ED25519_BASEPOINT_POINT;
+    if cofactored_only {
+        pub_key += torsion;
+    }
+    let mut message = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut message);
+        let k = compute_hram(&message, &pub_key, &r);
+        let eight_k = Scalar::from(8u8) * k;
+        if !cofactored_only || (!(k * torsion).is_identity() && (eight_k * torsion).is_identity()) {
+            return TestVector {
+                message: message.to_vec(),
+                pub_key: pub_key.compress().to_bytes(),
+                signature: serialize_signature(&r, &(k * a)),
+                metadata: None,
+                trace: None,
+            };
+        }
+    }
+}
+
+/// The two vectors with R the identity: under A of order L, passing both equations, then
+/// under A of mixed order, passing the cofactored one only.
+pub fn identity_r(rng: &mut impl RngCore) -> Vec<IdentityRVector> {
+    [false, true]
+        .iter()
+        .map(|&cofactored_only| {
+            let vector = identity_r_vector(rng, cofactored_only);
+            let expected_verdicts = classify(&vector);
+            IdentityRVector {
+                comment: format!(
+                    "R the identity encoded canonically, A of {} order, S = ka",
+                    if cofactored_only { "mixed" } else { "full" }
+                ),
+                vector,
+                cofactored_only,
+                expected_verdicts,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+use crate::test_vectors::TestVector;
+use anyhow::Result;
+use std::io::Write;
+
+/// The formats vectors are written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorFormat {
+    /// The layout of `cases.json`: an array of objects with the message, public key and
+    /// signature in hex, and the metadata of the vectors having one.
+    Json,
+    /// The layout of `cases.txt`: the number of vectors, then the message, public key and
+    /// signature of each in hex, on lines prefixed with `msg=`, `pbk=` and `sig=`, after the
+    /// id of its case on a line prefixed with `id=` if it has one.
+    Txt,
+    /// A header line, then a line per vector with its index, the id of its case (empty if
+    /// none), and its message, public key and signature in hex.
+    Csv,
+    /// The objects of the JSON layout, in CBOR.
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+/// Writes the `vectors` in `format` to `writer`, e.g. a file, a socket or a buffer, the
+/// same bytes the utility writes to the files of the release.
+pub fn write_vectors<W: Write>(
+    vectors: &[TestVector],
+    format: VectorFormat,
+    writer: &mut W,
+) -> Result<()> {
+    match format {
+        VectorFormat::Json => serde_json::to_writer(writer, vectors)?,
+        VectorFormat::Txt => {
+            write!(writer, "{}", vectors.len())?;
+            for tv in vectors.iter() {
+                if let Some(id) = tv.id() {
+                    write!(writer, "\nid={}", id)?;
+                }
+                write!(
+                    writer,
+                    "\nmsg={}\npbk={}\nsig={}",
+                    hex::encode(&tv.message),
+                    hex::encode(tv.pub_key),
+                    hex::encode(&tv.signature)
+                )?;
+            }
+        }
+        VectorFormat::Csv => {
+            writeln!(writer, "index,id,message,pub_key,signature")?;
+            for (i, tv) in vectors.iter().enumerate() {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{}",
+                    i,
+                    tv.id().unwrap_or_default(),
+                    hex::encode(&tv.message),
+                    hex::encode(tv.pub_key),
+                    hex::encode(&tv.signature)
+                )?;
+            }
+        }
+        #[cfg(feature = "cbor")]
+        VectorFormat::Cbor => serde_cbor::to_writer(writer, &vectors)?,
+    }
+    Ok(())
+}
+
+/// The bytes `write_vectors` writes.
+pub fn encode_vectors(vectors: &[TestVector], format: VectorFormat) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    write_vectors(vectors, format, &mut bytes)?;
+    Ok(bytes)
+}
@@ -0,0 +1,194 @@
+use crate::matrix::classify;
+use crate::non_canonical_encodings::non_canonical_encodings;
+use crate::policy::ValidationPolicy;
+use crate::test_vectors::{order_name, TestVector};
+use crate::verdict::Verdict;
+use crate::{
+    deserialize_point, deserialize_point_with, serialize_signature, DecodeMode, HramPrefix,
+    EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Two vectors with the same message and signature under two public keys, A and A', both
+/// valid under both equations without checks on the order or encoding of A: the signature
+/// binds the message but not the key, breaking the exclusive ownership [CGN20e] strongly
+/// binding signatures provide. A' is A plus a small-order point, or another encoding of A.
+/// A key of order L has neither: under A + T, k changes, and `[k]A` along with it, and only
+/// points of small order or of unknown discrete logarithm have non-canonical encodings. A
+/// is of small order, hence, with R = [r]B and S = r.
+#[derive(Serialize)]
+pub struct KeySubstitutionPair {
+    pub comment: String,
+    /// The vector under A.
+    pub original: TestVector,
+    /// The vector under A', with the message and signature of `original`.
+    pub substitute: TestVector,
+    /// Whether A' is another encoding of A, rather than A plus a small-order point.
+    pub by_encoding: bool,
+    /// The verdicts on `original` then on `substitute`, by the name of the policy.
+    pub expected_verdicts: BTreeMap<&'static str, [Verdict; 2]>,
+}
+
+impl KeySubstitutionPair {
+    /// `original` then `substitute`, the way libraries are run on the pairs.
+    pub fn vectors(&self) -> [TestVector; 2] {
+        [self.original.clone(), self.substitute.clone()]
+    }
+}
+
+// R = [r]B and S = r, with the message ground for `[k]A` to vanish for each encoding of
+// `keys`, whatever the point each decodes to
+fn sign_for_all(rng: &mut impl RngCore, keys: &[[u8; 32]]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let points = keys
+        .iter()
+        .map(|bytes| deserialize_point(bytes))
+        .collect::<Result<Vec<EdwardsPoint>>>()?;
+    if points.iter().any(|point| !point.is_small_order()) {
+        return Err(anyhow!("expected public keys of small order"));
+    }
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let prefixes: Vec<(HramPrefix, HramPrefix)> = keys
+        .iter()
+        .zip(points.iter())
+        .map(|(bytes, point)| {
+            (
+                HramPrefix::new(&r, point),
+                HramPrefix::from_arrays(r.compress().as_bytes(), bytes),
+            )
+        })
+        .collect();
+    let mut message = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut message);
+        let vanishes = prefixes.iter().zip(points.iter()).all(|(prefix, point)| {
+            (prefix.0.finish(&message) * point).is_identity()
+                && (prefix.1.finish(&message) * point).is_identity()
+        });
+        if vanishes {
+            break;
+        }
+    }
+    Ok((message.to_vec(), serialize_signature(&r, &r_scalar)))
+}
+
+fn pair(
+    rng: &mut impl RngCore,
+    pub_key: [u8; 32],
+    substitute_key: [u8; 32],
+    by_encoding: bool,
+    comment: String,
+) -> Result<KeySubstitutionPair> {
+    let (message, signature) = sign_for_all(rng, &[pub_key, substitute_key])?;
+    let vector = |pub_key| TestVector {
+        message: message.clone(),
+        pub_key,
+        signature: signature.clone(),
+        metadata: None,
+        trace: None,
+    };
+    let (original, substitute) = (vector(pub_key), vector(substitute_key));
+    let on_substitute = classify(&substitute);
+    let expected_verdicts = classify(&original)
+        .into_iter()
+        .map(|(name, verdict)| (name, [verdict, on_substitute[name]]))
+        .collect();
+    Ok(KeySubstitutionPair {
+        comment,
+        original,
+        substitute,
+        by_encoding,
+        expected_verdicts,
+    })
+}
+
+/// The pairs substituting the identity with each of the 7 other points of `EIGHT_TORSION`,
+/// then each point of `EIGHT_TORSION_NON_CANONICAL`, encoded canonically, with its
+/// non-canonical encoding.
+pub fn key_substitution_pairs(rng: &mut impl RngCore) -> Result<Vec<KeySubstitutionPair>> {
+    let mut pairs = Vec::new();
+    for (index, bytes) in EIGHT_TORSION.iter().enumerate().skip(1) {
+        let torsion = deserialize_point_with(bytes, DecodeMode::Strict)?;
+        let comment = format!(
+            "A the identity, A' of order {} (EIGHT_TORSION[{}])",
+            order_name(&torsion),
+            index
+        );
+        pairs.push(pair(rng, EIGHT_TORSION[0], *bytes, false, comment)?);
+    }
+    for (index, bytes) in EIGHT_TORSION_NON_CANONICAL.iter().enumerate() {
+        let point = deserialize_point(bytes)?;
+        let comment = format!(
+            "A of order {}, A' its non-canonical encoding (EIGHT_TORSION_NON_CANONICAL[{}])",
+            order_name(&point),
+            index
+        );
+        pairs.push(pair(
+            rng,
+            point.compress().to_bytes(),
+            *bytes,
+            true,
+            comment,
+        )?);
+    }
+    Ok(pairs)
+}
+
+/// The keys other than `pub_key` under which `policy` accepts the signature of `message`:
+/// the encodings, canonical or not, of A + T for every point T of `EIGHT_TORSION`, A
+/// included. There are none for a signature made under a key of order L, as k changes
+/// along with the key, and `[k]A` along with k.
+pub fn substitutes(
+    message: &[u8],
+    pub_key: &[u8],
+    signature: &[u8],
+    policy: &ValidationPolicy,
+) -> Result<Vec<[u8; 32]>> {
+    let point = deserialize_point(pub_key)?;
+    let shifted = EIGHT_TORSION
+        .iter()
+        .map(|bytes| Ok(point + deserialize_point_with(bytes, DecodeMode::Strict)?))
+        .collect::<Result<Vec<EdwardsPoint>>>()?;
+    let mut candidates: Vec<[u8; 32]> = shifted
+        .iter()
+        .map(|candidate| candidate.compress().to_bytes())
+        .collect();
+    for encoding in non_canonical_encodings() {
+        if shifted.contains(&deserialize_point(&encoding.bytes)?) {
+            candidates.push(encoding.bytes);
+        }
+    }
+    Ok(candidates
+        .into_iter()
+        .filter(|candidate| &candidate[..] != pub_key)
+        .filter(|candidate| policy.verify(message, candidate, signature).is_ok())
+        .collect())
+}
+
+/// The indices of the pairs of `pairs` whose both vectors a verifier accepts, given its
+/// `verdicts` on the vectors of the pairs, `original` then `substitute` for each pair in
+/// order. A verifier providing strongly binding signatures accepts no pair.
+pub fn substituted(pairs: &[KeySubstitutionPair], verdicts: &[Verdict]) -> Result<Vec<usize>> {
+    if 2 * pairs.len() != verdicts.len() {
+        return Err(anyhow!(
+            "got {} verdicts for {} pairs of vectors",
+            verdicts.len(),
+            pairs.len()
+        ));
+    }
+    Ok(verdicts
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, both)| both.iter().all(|v| v.is_accepted()))
+        .map(|(i, _)| i)
+        .collect())
+}
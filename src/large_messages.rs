@@ -0,0 +1,245 @@
+use crate::signing::SigningKey;
+use crate::test_vectors::TestVector;
+use crate::{
+    compute_hram, deserialize_point_with, serialize_signature, verify_cofactorless, DecodeMode,
+    EIGHT_TORSION,
+};
+use anyhow::{anyhow, bail, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+
+/// A signature over a message of megabytes, for verifiers feeding R || A || M to SHA-512
+/// incrementally: buffering it in chunks, or dropping or repeating a partial one, shows on
+/// such messages only. Those of `large_message_vectors` are generated in place rather than
+/// released as files, which they would swell by their size twice over in hex; those of
+/// `message_length_vectors` are released, the long messages in a binary blob.
+#[derive(Serialize)]
+pub struct LargeMessageVector {
+    pub comment: String,
+    pub vector: TestVector,
+    pub expected_cofactored: bool,
+    pub expected_cofactorless: bool,
+}
+
+/// The lengths of the messages, placing R || A || M, 64 bytes longer, against the 128-byte
+/// blocks of SHA-512: mid-block; on a block boundary; 111 bytes into the last block, the
+/// longest tail its padding still fits after; and 112 bytes into it, the padding then
+/// spilling into another block.
+pub const MESSAGE_LENGTHS: [usize; 4] = [1 << 20, (1 << 21) + 64, (1 << 22) + 47, (1 << 22) + 48];
+
+// A message of `len` bytes drawn from `rng`
+fn large_message(rng: &mut impl RngCore, len: usize) -> Vec<u8> {
+    let mut message = vec![0u8; len];
+    rng.fill_bytes(&mut message);
+    message
+}
+
+// A signature over `message` by `[a]B + T`, T of order 8, which the cofactored equation
+// accepts and the cofactorless one rejects
+fn mixed_order_vector(rng: &mut impl RngCore, message: Vec<u8>) -> TestVector {
+    // [k]T != 0 but for k = 0 mod 8, drawn again until it is not
+    let torsion = deserialize_point_with(&EIGHT_TORSION[1], DecodeMode::Strict).unwrap();
+    let mut a_bytes = [0u8; 32];
+    rng.fill_bytes(&mut a_bytes);
+    let a = Scalar::from_bytes_mod_order(a_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT + torsion;
+    loop {
+        let mut r_bytes = [0u8; 32];
+        rng.fill_bytes(&mut r_bytes);
+        let r = Scalar::from_bytes_mod_order(r_bytes);
+        let r_pt = r * ED25519_BASEPOINT_POINT;
+        let s = r + compute_hram(&message, &pub_key, &r_pt) * a;
+        if verify_cofactorless(&message, &pub_key, &(r_pt, s)).is_err() {
+            return TestVector {
+                message,
+                pub_key: pub_key.compress().to_bytes(),
+                signature: serialize_signature(&r_pt, &s),
+                metadata: None,
+                trace: None,
+            };
+        }
+    }
+}
+
+/// Signatures over messages of each of `MESSAGE_LENGTHS`, which every verifier accepts;
+/// the last of them with the last byte of the message flipped, which every verifier
+/// rejects unless it drops the tail of the message; and a signature by `[a]B + T` over a
+/// message of the first length, T of order 8, which the cofactored equation accepts and
+/// the cofactorless one rejects, telling them apart along the streaming path.
+pub fn large_message_vectors(rng: &mut impl RngCore) -> Vec<LargeMessageVector> {
+    let key = SigningKey::generate(rng);
+    let mut vectors: Vec<LargeMessageVector> = MESSAGE_LENGTHS
+        .iter()
+        .map(|&len| LargeMessageVector {
+            comment: format!("honest signature over {} bytes", len),
+            vector: key.sign_vector(&large_message(rng, len)),
+            expected_cofactored: true,
+            expected_cofactorless: true,
+        })
+        .collect();
+
+    let mut vector = vectors.last().unwrap().vector.clone();
+    *vector.message.last_mut().unwrap() ^= 1;
+    vectors.push(LargeMessageVector {
+        comment: format!(
+            "last byte of the message of {} bytes flipped",
+            vector.message.len()
+        ),
+        vector,
+        expected_cofactored: false,
+        expected_cofactorless: false,
+    });
+
+    let message = large_message(rng, MESSAGE_LENGTHS[0]);
+    let vector = mixed_order_vector(rng, message);
+    vectors.push(LargeMessageVector {
+        comment: format!(
+            "mixed-order A over {} bytes, fails cofactorless",
+            vector.message.len()
+        ),
+        vector,
+        expected_cofactored: true,
+        expected_cofactorless: false,
+    });
+    vectors
+}
+
+/// The lengths of the messages of `message_length_vectors`: empty, a single byte, 1 KiB and
+/// 1 MiB, where the 32-byte messages of the other files never reach the bindings passing
+/// the message with its length off by one, or through a length prefix too narrow for it.
+pub const MESSAGE_SIZES: [usize; 4] = [0, 1, 1 << 10, 1 << 20];
+
+/// The longest message `store_message_vectors` writes inline in hex, the longer ones going
+/// to the blob.
+pub const INLINE_MESSAGE_LIMIT: usize = 1 << 10;
+
+/// Signatures over messages of each of `MESSAGE_SIZES`, which every verifier accepts; that
+/// over a single byte presented with the empty message, which every verifier rejects unless
+/// it reads past the message; and a signature by `[a]B + T` over the empty message, T of
+/// order 8, which the cofactored equation accepts and the cofactorless one rejects.
+pub fn message_length_vectors(rng: &mut impl RngCore) -> Vec<LargeMessageVector> {
+    let key = SigningKey::generate(rng);
+    let mut vectors: Vec<LargeMessageVector> = MESSAGE_SIZES
+        .iter()
+        .map(|&len| LargeMessageVector {
+            comment: format!(
+                "honest signature over {} byte{}",
+                len,
+                if len == 1 { "" } else { "s" }
+            ),
+            vector: key.sign_vector(&large_message(rng, len)),
+            expected_cofactored: true,
+            expected_cofactorless: true,
+        })
+        .collect();
+
+    let mut vector = vectors[1].vector.clone();
+    vector.message.clear();
+    vectors.push(LargeMessageVector {
+        comment: "signature over 1 byte with the empty message".to_string(),
+        vector,
+        expected_cofactored: false,
+        expected_cofactorless: false,
+    });
+
+    vectors.push(LargeMessageVector {
+        comment: "mixed-order A over the empty message, fails cofactorless".to_string(),
+        vector: mixed_order_vector(rng, Vec::new()),
+        expected_cofactored: true,
+        expected_cofactorless: false,
+    });
+    vectors
+}
+
+/// A `LargeMessageVector` as `message_lengths.json` stores it: the message inline in hex if
+/// it is at most `INLINE_MESSAGE_LIMIT` bytes long, else at `blob_offset` in
+/// `message_lengths.bin`, and in either case by its length and SHA-256 digest, which
+/// `load` checks.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StoredMessageVector {
+    pub comment: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_offset: Option<usize>,
+    pub message_len: usize,
+    pub message_sha256: String,
+    pub pub_key: String,
+    pub signature: String,
+    pub expected_cofactored: bool,
+    pub expected_cofactorless: bool,
+}
+
+impl StoredMessageVector {
+    /// The vector, its message read inline or from `blob`.
+    pub fn load(&self, blob: &[u8]) -> Result<LargeMessageVector> {
+        let message = match (&self.message, self.blob_offset) {
+            (Some(message), None) => hex::decode(message)?,
+            (None, Some(offset)) => blob
+                .get(offset..)
+                .and_then(|rest| rest.get(..self.message_len))
+                .ok_or_else(|| anyhow!("Message past the end of the blob!"))?
+                .to_vec(),
+            _ => bail!("Message neither inline nor in the blob!"),
+        };
+        if message.len() != self.message_len {
+            bail!(
+                "Message of {} bytes, not {}!",
+                message.len(),
+                self.message_len
+            );
+        }
+        if hex::encode(Sha256::digest(&message)) != self.message_sha256 {
+            bail!("Message digest mismatch!");
+        }
+        let pub_key = <[u8; 32]>::try_from(&hex::decode(&self.pub_key)?[..])?;
+        Ok(LargeMessageVector {
+            comment: self.comment.clone(),
+            vector: TestVector {
+                message,
+                pub_key,
+                signature: hex::decode(&self.signature)?,
+                metadata: None,
+                trace: None,
+            },
+            expected_cofactored: self.expected_cofactored,
+            expected_cofactorless: self.expected_cofactorless,
+        })
+    }
+}
+
+/// The `vectors` as stored, and the blob of the messages longer than
+/// `INLINE_MESSAGE_LIMIT`, one after another.
+pub fn store_message_vectors(
+    vectors: &[LargeMessageVector],
+) -> (Vec<StoredMessageVector>, Vec<u8>) {
+    let mut blob = Vec::new();
+    let stored = vectors
+        .iter()
+        .map(|lv| {
+            let message = &lv.vector.message;
+            let (inline, blob_offset) = if message.len() <= INLINE_MESSAGE_LIMIT {
+                (Some(hex::encode(message)), None)
+            } else {
+                blob.extend_from_slice(message);
+                (None, Some(blob.len() - message.len()))
+            };
+            StoredMessageVector {
+                comment: lv.comment.clone(),
+                message: inline,
+                blob_offset,
+                message_len: message.len(),
+                message_sha256: hex::encode(Sha256::digest(message)),
+                pub_key: hex::encode(lv.vector.pub_key),
+                signature: hex::encode(&lv.vector.signature),
+                expected_cofactored: lv.expected_cofactored,
+                expected_cofactorless: lv.expected_cofactorless,
+            }
+        })
+        .collect();
+    (stored, blob)
+}
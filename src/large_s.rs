@@ -0,0 +1,155 @@
+use crate::matrix::{classify, Verdicts};
+use crate::non_reducing_scalar52::BigScalar;
+use crate::s_high_bits::{SCheck, S_CHECKS};
+use crate::test_vectors::TestVector;
+use crate::{compute_hram, serialize_signature, verify_cofactorless};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Where `S + jL` lies with respect to the boundaries of the checks of S: L for `S < L`,
+/// 2^253 for ref10's mask of the top three bits, and 2^255 for the mask of the top bit.
+#[derive(Clone, Copy)]
+pub struct Placement {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub multiple_of_l: u8,
+    // Whether S, encoded, lies where described
+    holds: fn(&[u8; 32]) -> bool,
+}
+
+/// The placements of S, by increasing S. L being 2^252 plus a 125-bit number, they hold
+/// for all but a negligible fraction of the signatures.
+pub const PLACEMENTS: [Placement; 6] = [
+    Placement {
+        name: "canonical",
+        description: "S < L, the signature as made",
+        multiple_of_l: 0,
+        holds: |s| Scalar::from_canonical_bytes(*s).is_some(),
+    },
+    Placement {
+        name: "above-l",
+        description: "L <= S < 2L, below 2^253: only fails S < L",
+        multiple_of_l: 1,
+        holds: |s| Scalar::from_canonical_bytes(*s).is_none() && s[31] >> 5 == 0,
+    },
+    Placement {
+        name: "above-2l",
+        description: "2L <= S < 3L, above 2^253: just fails the mask of the top three bits",
+        multiple_of_l: 2,
+        holds: |s| s[31] >> 5 == 1,
+    },
+    Placement {
+        name: "below-2^255",
+        description: "7L <= S < 2^255: the largest S passing the mask of the top bit",
+        multiple_of_l: 7,
+        holds: |s| s[31] >> 7 == 0,
+    },
+    Placement {
+        name: "above-2^255",
+        description: "2^255 <= 8L <= S: the smallest S failing the mask of the top bit",
+        multiple_of_l: 8,
+        holds: |s| s[31] >> 7 == 1,
+    },
+    Placement {
+        name: "largest",
+        description: "15L <= S < 2^256: the largest S fitting in 32 bytes",
+        multiple_of_l: 15,
+        holds: |_| true,
+    },
+];
+
+/// A valid signature with S replaced by `S + jL` as given by its placement. The equation
+/// holds modulo L for every vector, so that a verifier accepts one exactly when its check
+/// of S lets it through.
+#[derive(Serialize)]
+pub struct LargeSVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// The name of the placement of S, out of `PLACEMENTS`.
+    pub placement: String,
+    pub multiple_of_l: u8,
+    pub expected_verdicts: Verdicts,
+    /// Whether each of `s_high_bits::S_CHECKS`, by name, lets S through.
+    pub passes_s_checks: BTreeMap<String, bool>,
+}
+
+/// A vector for each of `PLACEMENTS`, under A and R of order L. The signature is drawn
+/// again in the negligible cases where some placement does not hold.
+pub fn large_s_family(rng: &mut impl RngCore) -> Vec<LargeSVector> {
+    let mut scalar_bytes = [0u8; 32];
+    let mut message = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut scalar_bytes);
+        let a = Scalar::from_bytes_mod_order(scalar_bytes);
+        let pub_key = a * ED25519_BASEPOINT_POINT;
+        rng.fill_bytes(&mut scalar_bytes);
+        let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+        let r = r_scalar * ED25519_BASEPOINT_POINT;
+        rng.fill_bytes(&mut message);
+        let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+        debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
+
+        let placed: Option<Vec<[u8; 32]>> = PLACEMENTS
+            .iter()
+            .map(|placement| {
+                BigScalar::from_scalar(&s)
+                    .add_multiple_of_l(placement.multiple_of_l.into())
+                    .map(|sum| sum.to_bytes())
+                    .filter(|s_bytes| (placement.holds)(s_bytes))
+            })
+            .collect();
+        let placed = match placed {
+            Some(placed) => placed,
+            None => continue,
+        };
+        return PLACEMENTS
+            .iter()
+            .zip(placed)
+            .map(|(placement, s_bytes)| {
+                let mut signature = serialize_signature(&r, &s);
+                signature[32..].copy_from_slice(&s_bytes);
+                let vector = TestVector {
+                    message: message.to_vec(),
+                    pub_key: pub_key.compress().to_bytes(),
+                    signature,
+                    metadata: None,
+                    trace: None,
+                };
+                let expected_verdicts = classify(&vector);
+                let passes_s_checks = S_CHECKS
+                    .iter()
+                    .map(|check| (check.name.to_string(), (check.passes)(&s_bytes)))
+                    .collect();
+                LargeSVector {
+                    comment: format!(
+                        "S + {}L, {}",
+                        placement.multiple_of_l, placement.description
+                    ),
+                    vector,
+                    placement: placement.name.to_string(),
+                    multiple_of_l: placement.multiple_of_l,
+                    expected_verdicts,
+                    passes_s_checks,
+                }
+            })
+            .collect();
+    }
+}
+
+/// The check of S, out of `s_high_bits::S_CHECKS`, letting through exactly the vectors of
+/// `large_s_family` a verifier accepted, given in the same order; None if none does, e.g.
+/// for a verifier rejecting some of them for another reason.
+pub fn s_check_of(vectors: &[LargeSVector], accepted: &[bool]) -> Option<SCheck> {
+    if vectors.len() != accepted.len() {
+        return None;
+    }
+    S_CHECKS.iter().copied().find(|check| {
+        vectors
+            .iter()
+            .zip(accepted.iter())
+            .all(|(v, accepted)| v.passes_s_checks[check.name] == *accepted)
+    })
+}
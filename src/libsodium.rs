@@ -0,0 +1,75 @@
+/// This file emulates the verification of libsodium (`crypto_sign_verify_detached`, as of
+/// 1.0.16 and built without `ED25519_COMPAT`), so that its verdicts can be compared against
+/// without linking the C library.
+///
+/// References:
+/// [libsodium] crypto_sign/ed25519/ref10/open.c and crypto_core/ed25519/ref10/ed25519_ref10.c; https://github.com/jedisct1/libsodium
+use crate::torsion::{EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL};
+use crate::verdict::Verdict;
+use crate::{check_slice_size, compute_hram_with_arrays, deserialize_point};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+
+/// The encodings `ge25519_has_small_order` rejects, in its order, compared with the sign bit
+/// cleared: y = 0, 1, those of the points of order 8, p - 1, p and p + 1. Along with the
+/// canonicity check of A, this rejects every point of small order, but only the canonical
+/// encodings of R, and the non-canonical ones of the identity and of the points of order 4.
+pub const SMALL_ORDER_BLOCKLIST: [[u8; 32]; 7] = [
+    EIGHT_TORSION[6],
+    EIGHT_TORSION[0],
+    EIGHT_TORSION[3],
+    EIGHT_TORSION[1],
+    EIGHT_TORSION[4],
+    EIGHT_TORSION_NON_CANONICAL[5],
+    EIGHT_TORSION_NON_CANONICAL[3],
+];
+
+/// Whether `bytes` is on the blocklist of `ge25519_has_small_order`, whatever its sign bit.
+pub fn has_small_order(bytes: &[u8]) -> bool {
+    SMALL_ORDER_BLOCKLIST
+        .iter()
+        .any(|blocked| bytes[..31] == blocked[..31] && bytes[31] & 0x7f == blocked[31])
+}
+
+/// Whether y < p, as `ge25519_is_canonical`: the sign bit is not checked.
+pub fn is_canonical(bytes: &[u8]) -> bool {
+    !(bytes[0] >= 0xed && bytes[1..31].iter().all(|&b| b == 0xff) && bytes[31] & 0x7f == 0x7f)
+}
+
+// The checks of libsodium before hashing: S canonical, R off the blocklist, A canonical and
+// off the blocklist, and A decoding. R is never decoded.
+fn decode(pk_bytes: &[u8], sig_bytes: &[u8]) -> Result<(EdwardsPoint, Scalar)> {
+    let pk_bytes = check_slice_size(pk_bytes, 32, "pk_bytes")?;
+    let sig_bytes = check_slice_size(sig_bytes, 64, "sig_bytes")?;
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&sig_bytes[32..]);
+    let s = Scalar::from_canonical_bytes(s_bytes).ok_or_else(|| anyhow!("non-canonical s"))?;
+    if has_small_order(&sig_bytes[..32]) {
+        return Err(anyhow!("R on the small-order blocklist"));
+    }
+    if !is_canonical(pk_bytes) || has_small_order(pk_bytes) {
+        return Err(anyhow!("Non-canonical or small-order public key"));
+    }
+    Ok((deserialize_point(pk_bytes)?, s))
+}
+
+/// Deserializes and verifies a signature as libsodium does: the cofactorless equation,
+/// checked by comparing the encoding of `[S]B - [k]A` with the bytes of R, with k the hash of
+/// A and R as provided.
+pub fn verify(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> bool {
+    verdict(msg_bytes, pk_bytes, sig_bytes).is_accepted()
+}
+
+/// As `verify`, telling apart the rejections before hashing, reported as parsing ones.
+pub fn verdict(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> Verdict {
+    Verdict::from_results(decode(pk_bytes, sig_bytes), |(pk, s)| {
+        let k = compute_hram_with_arrays(msg_bytes, pk_bytes, &sig_bytes[..32]);
+        let rcheck = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-pk, &s);
+        if rcheck.compress().as_bytes()[..] == sig_bytes[..32] {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid signature under libsodium"))
+        }
+    })
+}
@@ -0,0 +1,137 @@
+use crate::non_reducing_scalar52::BigScalar;
+use crate::signing::SigningKey;
+use crate::test_vectors::TestVector;
+use crate::{
+    compute_hram, compute_hram_with_r_array, deserialize_point_with, serialize_signature,
+    DecodeMode, EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use std::convert::TryFrom;
+
+/// A key pair signing as RFC 8032 does, which also tampers with its own signatures the
+/// ways the test vectors exercise, for protocols to test their downstream systems on
+/// mutations of real signatures under real keys rather than on the fixed messages and keys
+/// of the vectors. A is the key of the seed, of order L and canonical, so that only R and S
+/// get tampered with.
+pub struct MaliciousSigner {
+    key: SigningKey,
+    secret: Scalar,
+    pub_key: EdwardsPoint,
+}
+
+/// A signature of a `MaliciousSigner`, tampered with or not, along with the nonce it was
+/// made with, which further tampering takes.
+#[derive(Clone)]
+pub struct SignedMessage {
+    pub message: Vec<u8>,
+    pub pub_key: [u8; 32],
+    pub signature: Vec<u8>,
+    // R is [nonce]B + torsion, but for the non-canonical R
+    nonce: Scalar,
+    torsion: EdwardsPoint,
+}
+
+impl SignedMessage {
+    /// The signature as a vector, for the verifiers of this crate.
+    pub fn to_test_vector(&self) -> TestVector {
+        TestVector {
+            message: self.message.clone(),
+            pub_key: self.pub_key,
+            signature: self.signature.clone(),
+            metadata: None,
+            trace: None,
+        }
+    }
+}
+
+// The index in `EIGHT_TORSION_NON_CANONICAL` of the identity encoded with y = 1 + p
+const NON_CANONICAL_IDENTITY: usize = 3;
+
+impl MaliciousSigner {
+    /// The signer of the 32-byte secret key `seed`, expanded as in RFC 8032, Section 5.1.5.
+    pub fn from_seed(seed: &[u8; 32]) -> MaliciousSigner {
+        let key = SigningKey::from_seed(seed);
+        MaliciousSigner {
+            secret: key.secret(),
+            pub_key: key.pub_key_point(),
+            key,
+        }
+    }
+
+    pub fn pub_key(&self) -> [u8; 32] {
+        self.pub_key.compress().to_bytes()
+    }
+
+    /// Signs `message` as RFC 8032, Section 5.1.6, with the nonce derived from the prefix.
+    pub fn sign(&self, message: &[u8]) -> SignedMessage {
+        self.sign_with(message, self.key.nonce(message), EdwardsPoint::identity())
+    }
+
+    // The signature of `message` with R = [nonce]B + torsion, for which the cofactored
+    // equation holds, and the cofactorless one only if the torsion is the identity
+    fn sign_with(&self, message: &[u8], nonce: Scalar, torsion: EdwardsPoint) -> SignedMessage {
+        let r = nonce * ED25519_BASEPOINT_POINT + torsion;
+        let s = nonce + compute_hram(message, &self.pub_key, &r) * self.secret;
+        SignedMessage {
+            message: message.to_vec(),
+            pub_key: self.pub_key(),
+            signature: serialize_signature(&r, &s),
+            nonce,
+            torsion,
+        }
+    }
+
+    /// Signs the message of `sig` again with the point `EIGHT_TORSION[idx]` added to R, for
+    /// `idx` from 1 to 7: the signature passes the cofactored equation, and fails the
+    /// cofactorless one, as R is of mixed order. The torsion adds up with that of `sig`.
+    pub fn tamper_add_torsion(&self, sig: &SignedMessage, idx: usize) -> Result<SignedMessage> {
+        if idx == 0 || idx >= EIGHT_TORSION.len() {
+            return Err(anyhow!(
+                "expected the index of a small-order point from 1 to 7, got {}",
+                idx
+            ));
+        }
+        let torsion = deserialize_point_with(&EIGHT_TORSION[idx], DecodeMode::Strict)?;
+        Ok(self.sign_with(&sig.message, sig.nonce, sig.torsion + torsion))
+    }
+
+    /// `sig` with S replaced by `S + kL`, which only verifiers not checking `S < L` accept.
+    /// Fails if `S + kL` overflows the 32 bytes of S, as it does for any k from 16 on.
+    pub fn tamper_lift_s(&self, sig: &SignedMessage, k: u8) -> Result<SignedMessage> {
+        let s = <[u8; 32]>::try_from(&sig.signature[32..]).unwrap();
+        let lifted = BigScalar::from_bytes(&s)
+            .add_multiple_of_l(k.into())
+            .ok_or_else(|| anyhow!("S + {}L overflows 256 bits", k))?;
+        let mut signature = sig.signature.clone();
+        signature[32..].copy_from_slice(&lifted.to_bytes());
+        Ok(SignedMessage {
+            signature,
+            ..sig.clone()
+        })
+    }
+
+    /// Signs the message of `sig` again with R the identity encoded non-canonically, with
+    /// y = 1 + p, and `S = ka` for k over R as provided: both equations hold, and only
+    /// verifiers rejecting non-canonical encodings of R, R of small order, or hashing R
+    /// re-serialized reject it. The points of order L have no non-canonical encoding, hence
+    /// a small-order R. As `k` is public, S reveals the secret scalar of the key: the seed
+    /// must be one for tests only.
+    pub fn tamper_noncanonical_r(&self, sig: &SignedMessage) -> Result<SignedMessage> {
+        let r_bytes = EIGHT_TORSION_NON_CANONICAL[NON_CANONICAL_IDENTITY];
+        let r = deserialize_point_with(&r_bytes, DecodeMode::Lenient)?;
+        let k = compute_hram_with_r_array(&sig.message, &self.pub_key, &r_bytes);
+        let mut signature = serialize_signature(&r, &(k * self.secret));
+        signature[..32].copy_from_slice(&r_bytes);
+        Ok(SignedMessage {
+            message: sig.message.clone(),
+            pub_key: self.pub_key(),
+            signature,
+            nonce: Scalar::zero(),
+            torsion: EdwardsPoint::identity(),
+        })
+    }
+}
@@ -0,0 +1,338 @@
+use crate::matrix::{classify, Verdicts};
+use crate::non_canonical_encodings::non_canonical_encodings;
+use crate::non_reducing_scalar52::BigScalar;
+use crate::policy::{ValidationPolicy, COFACTORED};
+use crate::signing::SigningKey;
+use crate::test_vectors::{generate_test_vectors, TestVector, CASE_IDS};
+use crate::{compute_hram, deserialize_point, serialize_signature, EIGHT_TORSION};
+use anyhow::{anyhow, ensure, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// A toy payment, standing for any protocol message signed with Ed25519.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transaction {
+    pub sender: [u8; 32],
+    pub recipient: [u8; 32],
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+}
+
+impl Transaction {
+    /// The bytes the sender signs.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        [
+            &self.sender[..],
+            &self.recipient[..],
+            &self.amount.to_le_bytes()[..],
+            &self.fee.to_le_bytes()[..],
+            &self.nonce.to_le_bytes()[..],
+        ]
+        .concat()
+    }
+}
+
+/// A transaction along with the signature of its sender, as broadcast.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedTransaction {
+    pub transaction: Transaction,
+    pub signature: Vec<u8>,
+}
+
+impl SignedTransaction {
+    /// The identifier of the transaction, as the SHA-256 digest of the signed transaction,
+    /// signature included: the way ledgers derive IDs which SUF-CMA signatures make unique.
+    pub fn id(&self) -> [u8; 32] {
+        let digest = Sha256::default()
+            .chain(self.transaction.signing_bytes())
+            .chain(&self.signature)
+            .finalize();
+        <[u8; 32]>::try_from(&digest[..]).unwrap()
+    }
+
+    /// Verifies the signature of the sender under `policy`.
+    pub fn verify(&self, policy: &ValidationPolicy) -> Result<()> {
+        policy.verify(
+            &self.transaction.signing_bytes(),
+            &self.transaction.sender,
+            &self.signature,
+        )
+    }
+}
+
+/// Mutates an Ed25519 signature into another with distinct bytes yet accepted by any
+/// verifier which does not check S < L, by replacing S with S + L. Fails on signatures whose
+/// S is not reduced, as S + L may then not fit in 32 bytes.
+pub fn malleate(signature: &[u8]) -> Result<Vec<u8>> {
+    ensure!(
+        signature.len() == 64,
+        "signatures are 64 bytes, got {}",
+        signature.len()
+    );
+    let s_bytes = <[u8; 32]>::try_from(&signature[32..]).unwrap();
+    if Scalar::from_canonical_bytes(s_bytes).is_none() {
+        return Err(anyhow!("S is not reduced"));
+    }
+    let s_plus_l = BigScalar::from_bytes(&s_bytes) + BigScalar::L;
+    Ok([&signature[..32], &s_plus_l.to_bytes()[..]].concat())
+}
+
+/// Every signature `policy` accepts on `message` under `pub_key` which anyone holding
+/// `signature` can derive from it, other than `signature` itself: S replaced by any S + jL
+/// below 2^256, S reduced included, R by R plus any point of `EIGHT_TORSION`, each R encoded
+/// in any of the ways `CompressedEdwardsY::decompress` accepts. This is the malleation
+/// surface `policy` exposes: an empty result means its signatures are unique, as SUF-CMA
+/// requires. An R which does not decode is kept as provided. Fails unless `signature` is 64
+/// bytes.
+pub fn find_equivalent_signatures(
+    message: &[u8],
+    pub_key: &[u8],
+    signature: &[u8],
+    policy: &ValidationPolicy,
+) -> Result<Vec<Vec<u8>>> {
+    ensure!(
+        signature.len() == 64,
+        "signatures are 64 bytes, got {}",
+        signature.len()
+    );
+    let r_encodings = match deserialize_point(&signature[..32]) {
+        Ok(r) => {
+            let non_canonical = non_canonical_encodings();
+            let mut encodings = Vec::new();
+            for torsion in EIGHT_TORSION.iter() {
+                let point = r + deserialize_point(torsion)?;
+                encodings.push(point.compress().to_bytes());
+                encodings.extend(
+                    non_canonical
+                        .iter()
+                        .filter(|encoding| deserialize_point(&encoding.bytes).ok() == Some(point))
+                        .map(|encoding| encoding.bytes),
+                );
+            }
+            encodings
+        }
+        Err(_) => vec![<[u8; 32]>::try_from(&signature[..32]).unwrap()],
+    };
+    let (_, remainder) =
+        BigScalar::from_bytes(&<[u8; 32]>::try_from(&signature[32..]).unwrap()).div_rem_l();
+    let remainder = BigScalar::from_scalar(&remainder);
+    let s_encodings: Vec<[u8; 32]> = (0..)
+        .map(|j| remainder.add_multiple_of_l(j))
+        .take_while(Option::is_some)
+        .map(|s| s.unwrap().to_bytes())
+        .collect();
+
+    let mut equivalent = Vec::new();
+    for r in r_encodings.iter() {
+        for s in s_encodings.iter() {
+            let candidate = [&r[..], &s[..]].concat();
+            if candidate != signature && policy.verify(message, pub_key, &candidate).is_ok() {
+                equivalent.push(candidate);
+            }
+        }
+    }
+    Ok(equivalent)
+}
+
+/// The ways `find_equivalent_signatures` derives a signature from another, by the names
+/// `malleation` gives them.
+pub const MALLEATIONS: [&str; 3] = ["S + jL", "R re-encoded", "R plus torsion"];
+
+/// The indices of the cases `malleability_pairs` malleates: that of A and R of small order,
+/// whose R moves by torsion, and that of R encoded non-canonically, which can be re-encoded.
+pub const MALLEABLE_CASES: [usize; 2] = [0, 8];
+
+/// How `malleated` was derived from `original`, one of `MALLEATIONS`: S lifted with R
+/// unchanged, R encoded otherwise, or R decoding to another point.
+pub fn malleation(original: &[u8], malleated: &[u8]) -> &'static str {
+    if original[..32] == malleated[..32] {
+        MALLEATIONS[0]
+    } else if deserialize_point(&original[..32]).ok() == deserialize_point(&malleated[..32]).ok() {
+        MALLEATIONS[1]
+    } else {
+        MALLEATIONS[2]
+    }
+}
+
+/// A signature and another derived from it through `find_equivalent_signatures`, of the same
+/// message under the same key, with the SHA-256 digests of both: a system deriving the
+/// identifiers of its messages from their signatures, as `SignedTransaction::id` does, gives
+/// the same message two identifiers under the policies accepting both.
+#[derive(Serialize)]
+pub struct MalleabilityPair {
+    /// Where `original` comes from, and the malleation of `MALLEATIONS` deriving `malleated`.
+    pub comment: String,
+    pub original: TestVector,
+    pub malleated: TestVector,
+    /// The SHA-256 digest of the signature of `original`, in hex.
+    pub original_sha256: String,
+    /// The SHA-256 digest of the signature of `malleated`, in hex.
+    pub malleated_sha256: String,
+    /// The verdicts on `original` and on `malleated`, as `matrix::classify` reaches them.
+    pub verdicts_original: Verdicts,
+    pub verdicts_malleated: Verdicts,
+}
+
+impl MalleabilityPair {
+    /// The names of the policies accepting both signatures, out of `matrix::KNOWN_POLICIES`:
+    /// those under which the message has two identifiers.
+    pub fn accepting_both(&self) -> Vec<String> {
+        self.verdicts_original
+            .iter()
+            .filter(|(name, verdict)| {
+                verdict.is_accepted() && self.verdicts_malleated[*name].is_accepted()
+            })
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+}
+
+/// For an honest signature of a random message under a fresh key, then for the cases of
+/// `cases.json` in `MALLEABLE_CASES`, a pair for each of `MALLEATIONS` which
+/// `policy::COFACTORED`, the most lenient of the policies, accepts: the first signature of
+/// `find_equivalent_signatures` derived that way. The honest signature only admits S + jL.
+pub fn malleability_pairs(rng: &mut impl RngCore) -> Result<Vec<MalleabilityPair>> {
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    let honest = SigningKey::generate(rng).sign_vector(&message);
+    let cases = generate_test_vectors();
+    let mut sources = vec![("honest", honest)];
+    for &index in MALLEABLE_CASES.iter() {
+        let case = TestVector {
+            metadata: None,
+            trace: None,
+            ..cases[index].clone()
+        };
+        sources.push((CASE_IDS[index], case));
+    }
+
+    let mut pairs = Vec::new();
+    for (source, original) in sources.iter() {
+        let equivalent = find_equivalent_signatures(
+            &original.message,
+            &original.pub_key,
+            &original.signature,
+            &COFACTORED,
+        )?;
+        for kind in MALLEATIONS.iter() {
+            let signature = match equivalent
+                .iter()
+                .find(|sig| malleation(&original.signature, sig) == *kind)
+            {
+                Some(signature) => signature,
+                None => continue,
+            };
+            let malleated = TestVector {
+                message: original.message.clone(),
+                pub_key: original.pub_key,
+                signature: signature.clone(),
+                metadata: None,
+                trace: None,
+            };
+            pairs.push(MalleabilityPair {
+                comment: format!("{}: {}", source, kind),
+                original_sha256: hex::encode(Sha256::digest(&original.signature)),
+                malleated_sha256: hex::encode(Sha256::digest(&malleated.signature)),
+                verdicts_original: classify(original),
+                verdicts_malleated: classify(&malleated),
+                original: original.clone(),
+                malleated,
+            });
+        }
+    }
+    Ok(pairs)
+}
+
+/// A transaction signed by an honest sender, and the same transaction with its signature
+/// mutated by a relay through `malleate`.
+pub struct Scenario {
+    pub original: SignedTransaction,
+    pub malleated: SignedTransaction,
+}
+
+/// Plays the scenario with a key and a transaction drawn from `rng`, failing unless the
+/// mutated signature is valid for verifiers omitting the S < L check, the transaction ID
+/// differs nonetheless, and the check suffices to reject the mutation.
+pub fn scenario(rng: &mut impl RngCore) -> Result<Scenario> {
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT;
+
+    let mut recipient = [0u8; 32];
+    rng.fill_bytes(&mut recipient);
+    let transaction = Transaction {
+        sender: pub_key.compress().to_bytes(),
+        recipient,
+        amount: 1_000,
+        fee: 10,
+        nonce: rng.next_u64(),
+    };
+
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let s = r_scalar + compute_hram(&transaction.signing_bytes(), &pub_key, &r) * a;
+    let original = SignedTransaction {
+        transaction: transaction.clone(),
+        signature: serialize_signature(&r, &s),
+    };
+    let malleated = SignedTransaction {
+        transaction,
+        signature: malleate(&original.signature)?,
+    };
+
+    let lenient = ValidationPolicy::default();
+    let strict = ValidationPolicy {
+        require_canonical_s: true,
+        ..Default::default()
+    };
+    original.verify(&strict)?;
+    malleated.verify(&lenient)?;
+    ensure!(
+        malleated.signature != original.signature,
+        "the mutated signature equals the original one"
+    );
+    ensure!(
+        malleated.id() != original.id(),
+        "the mutation leaves the transaction ID unchanged"
+    );
+    ensure!(
+        malleated.verify(&strict).is_err(),
+        "requiring S < L does not reject the mutated signature"
+    );
+    Ok(Scenario {
+        original,
+        malleated,
+    })
+}
+
+/// Plays `scenario` and writes it out step by step.
+pub fn report<W: Write>(rng: &mut impl RngCore, output: &mut W) -> Result<()> {
+    let Scenario {
+        original,
+        malleated,
+    } = scenario(rng)?;
+    writeln!(
+        output,
+        "transaction: {}",
+        hex::encode(original.transaction.signing_bytes())
+    )?;
+    writeln!(output, "signature:   {}", hex::encode(&original.signature))?;
+    writeln!(output, "ID:          {}", hex::encode(original.id()))?;
+    writeln!(output, "with S + L:  {}", hex::encode(&malleated.signature))?;
+    writeln!(output, "ID:          {}", hex::encode(malleated.id()))?;
+    writeln!(
+        output,
+        "Both signatures pass verification unless it requires S < L, under two distinct IDs \
+         for the same payment: a client tracking its payment by ID may believe it failed and \
+         pay again."
+    )?;
+    Ok(())
+}
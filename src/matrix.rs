@@ -0,0 +1,130 @@
+use crate::policy::{
+    ValidationPolicy, ALGORITHM_2, COFACTORED, COFACTORLESS, FIPS_186_5, LIBSODIUM, RFC_8032,
+    ZIP_215,
+};
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::verifier::{
+    Algorithm2, Cofactored, Cofactorless, Fips186_5, Libsodium, Rfc8032, Verifier, Zip215,
+};
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+
+/// The verdicts on a vector, by the name of the policy reaching them.
+pub type Verdicts = BTreeMap<&'static str, Verdict>;
+
+/// The policies of `ExpectedMatrix`, by name: those of the verifiers of this crate.
+pub const KNOWN_POLICIES: [(&str, ValidationPolicy); 7] = [
+    ("cofactored", COFACTORED),
+    ("cofactorless", COFACTORLESS),
+    ("algorithm2", ALGORITHM_2),
+    ("fips186_5", FIPS_186_5),
+    ("libsodium", LIBSODIUM),
+    ("rfc8032", RFC_8032),
+    ("zip215", ZIP_215),
+];
+
+// The verifier implementing each of `KNOWN_POLICIES`, in the same order
+const REFERENCES: [&dyn Verifier; 7] = [
+    &Cofactored,
+    &Cofactorless,
+    &Algorithm2,
+    &Fips186_5,
+    &Libsodium,
+    &Rfc8032,
+    &Zip215,
+];
+
+/// The verdicts the reference verifiers of this crate reach on `vector`, by the name of the
+/// policy each implements.
+pub fn classify(vector: &TestVector) -> Verdicts {
+    KNOWN_POLICIES
+        .iter()
+        .zip(REFERENCES.iter())
+        .map(|((name, _), verifier)| {
+            let verdict = verifier.verdict(&vector.message, &vector.pub_key, &vector.signature);
+            (*name, verdict)
+        })
+        .collect()
+}
+
+/// The verdict each of `KNOWN_POLICIES` predicts on each of a set of vectors, against which
+/// the verdicts of a library can be checked in one call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedMatrix {
+    rows: Vec<Verdicts>,
+}
+
+impl ExpectedMatrix {
+    pub fn new(vectors: &[TestVector]) -> ExpectedMatrix {
+        let mut rows = vec![Verdicts::new(); vectors.len()];
+        for (name, policy) in KNOWN_POLICIES.iter() {
+            for (row, verdict) in rows.iter_mut().zip(policy.predict(vectors)) {
+                row.insert(*name, verdict);
+            }
+        }
+        ExpectedMatrix { rows }
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The expected verdicts on the `index`-th vector.
+    pub fn row(&self, index: usize) -> Option<&Verdicts> {
+        self.rows.get(index)
+    }
+
+    /// The verdict `policy` predicts on the `index`-th vector.
+    pub fn expected(&self, index: usize, policy: &str) -> Option<Verdict> {
+        self.row(index)?.get(policy).copied()
+    }
+
+    /// Checks that `observed`, a verdict per vector, accepts exactly the vectors `policy`
+    /// accepts. Rejections are not told apart, as libraries differ in the step rejecting a
+    /// vector, and an `Error` verdict never matches.
+    pub fn check(&self, policy: &str, observed: &[Verdict]) -> Result<()> {
+        if !KNOWN_POLICIES.iter().any(|(name, _)| *name == policy) {
+            return Err(anyhow!("unknown policy {}", policy));
+        }
+        if observed.len() != self.len() {
+            return Err(anyhow!(
+                "expected {} verdicts, got {}",
+                self.len(),
+                observed.len()
+            ));
+        }
+        let departing: Vec<String> = self
+            .rows
+            .iter()
+            .zip(observed.iter())
+            .enumerate()
+            .filter(|(_, (row, verdict))| {
+                verdict.is_error() || verdict.is_accepted() != row[policy].is_accepted()
+            })
+            .map(|(index, _)| index.to_string())
+            .collect();
+        if departing.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "departs from {} on vectors {}",
+                policy,
+                departing.join(", ")
+            ))
+        }
+    }
+
+    /// The names of the policies `observed` matches, as for `check`.
+    pub fn matching(&self, observed: &[Verdict]) -> Vec<&'static str> {
+        KNOWN_POLICIES
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| self.check(name, observed).is_ok())
+            .collect()
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::{anyhow, Result};
+use rand::RngCore;
+use std::ops::Range;
+
+/// Where the generators draw the messages they grind over from. Grinding asks for a fresh
+/// candidate until the verification equations behave as intended for it, hence a source
+/// must be able to produce distinct messages for as long as it is asked to.
+pub trait MessageSource {
+    fn next_message<R: RngCore>(&mut self, rng: &mut R) -> Vec<u8>;
+}
+
+/// Random messages of a fixed length, 32 bytes by default as in `generate_test_vectors`.
+pub struct RandomBytes(pub usize);
+
+impl Default for RandomBytes {
+    fn default() -> RandomBytes {
+        RandomBytes(32)
+    }
+}
+
+impl MessageSource for RandomBytes {
+    fn next_message<R: RngCore>(&mut self, rng: &mut R) -> Vec<u8> {
+        let mut message = vec![0u8; self.0];
+        rng.fill_bytes(&mut message);
+        message
+    }
+}
+
+/// Messages made of a fixed prefix followed by an incrementing 64-bit little-endian
+/// counter, which need no randomness.
+pub struct Counter {
+    prefix: Vec<u8>,
+    next: u64,
+}
+
+impl Counter {
+    pub fn new(prefix: &[u8]) -> Counter {
+        Counter {
+            prefix: prefix.to_vec(),
+            next: 0,
+        }
+    }
+}
+
+impl MessageSource for Counter {
+    fn next_message<R: RngCore>(&mut self, _rng: &mut R) -> Vec<u8> {
+        let mut message = self.prefix.clone();
+        message.extend_from_slice(&self.next.to_le_bytes());
+        self.next += 1;
+        message
+    }
+}
+
+/// Messages following a protocol payload, e.g. a serialized transaction, in which only a
+/// nonce field (such as a memo) is filled with random bytes. The generated vectors then
+/// carry valid payloads, and can be injected in the pipeline processing them.
+pub struct Template {
+    payload: Vec<u8>,
+    nonce: Range<usize>,
+}
+
+impl Template {
+    /// Fails unless `nonce` is a non-empty range of byte offsets within `payload`.
+    pub fn new(payload: &[u8], nonce: Range<usize>) -> Result<Template> {
+        if nonce.start >= nonce.end || nonce.end > payload.len() {
+            return Err(anyhow!(
+                "the nonce field {:?} is not a non-empty range of the {} bytes payload",
+                nonce,
+                payload.len()
+            ));
+        }
+        Ok(Template {
+            payload: payload.to_vec(),
+            nonce,
+        })
+    }
+}
+
+impl MessageSource for Template {
+    fn next_message<R: RngCore>(&mut self, rng: &mut R) -> Vec<u8> {
+        let mut message = self.payload.clone();
+        rng.fill_bytes(&mut message[self.nonce.clone()]);
+        message
+    }
+}
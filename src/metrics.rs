@@ -0,0 +1,124 @@
+use anyhow::Result;
+use std::io::Write;
+use std::ops::AddAssign;
+
+// A counter, as its name, its description and its value
+type Counter = (&'static str, &'static str, u64);
+
+/// Counts of the costly operations performed by verification, see
+/// `ValidationPolicy::verify_with_metrics`, to estimate what enabling a check costs at a
+/// given volume of signatures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    pub verifications: u64,
+    /// Decoding a point, which takes a field inversion and a square root.
+    pub decompressions: u64,
+    /// Encoding a point, to check the canonicity of its encoding or to hash it re-serialized.
+    pub compressions: u64,
+    /// Multiplications of a point by 8 to check whether it has small order.
+    pub small_order_checks: u64,
+    /// Multiplications of a point by L to check whether it has order L.
+    pub full_order_checks: u64,
+    /// Multiplications of the verification equation (or of R) by the cofactor.
+    pub cofactor_muls: u64,
+    /// Computations of `[s]B - [k]A`, the bulk of the cost of a verification.
+    pub double_scalar_muls: u64,
+    /// Bytes fed to SHA-512 to compute the challenge.
+    pub hashed_bytes: u64,
+}
+
+impl AddAssign for Metrics {
+    fn add_assign(&mut self, other: Metrics) {
+        self.verifications += other.verifications;
+        self.decompressions += other.decompressions;
+        self.compressions += other.compressions;
+        self.small_order_checks += other.small_order_checks;
+        self.full_order_checks += other.full_order_checks;
+        self.cofactor_muls += other.cofactor_muls;
+        self.double_scalar_muls += other.double_scalar_muls;
+        self.hashed_bytes += other.hashed_bytes;
+    }
+}
+
+impl Metrics {
+    /// The counters along with their names and descriptions, as exported.
+    pub fn counters(&self) -> [Counter; 8] {
+        [
+            ("verifications", "Signatures verified.", self.verifications),
+            ("decompressions", "Points decoded.", self.decompressions),
+            ("compressions", "Points encoded.", self.compressions),
+            (
+                "small_order_checks",
+                "Points checked for small order.",
+                self.small_order_checks,
+            ),
+            (
+                "full_order_checks",
+                "Points checked for order L.",
+                self.full_order_checks,
+            ),
+            (
+                "cofactor_muls",
+                "Multiplications by the cofactor.",
+                self.cofactor_muls,
+            ),
+            (
+                "double_scalar_muls",
+                "Double-scalar multiplications.",
+                self.double_scalar_muls,
+            ),
+            (
+                "hashed_bytes",
+                "Bytes hashed into challenges.",
+                self.hashed_bytes,
+            ),
+        ]
+    }
+
+    /// Writes the counters in the Prometheus text exposition format, see `write_prometheus`.
+    pub fn write_prometheus<W: Write>(&self, output: &mut W) -> Result<()> {
+        write_prometheus(output, &[(&[], *self)])
+    }
+}
+
+/// Writes the counters of each of the `series`, told apart by their labels, in the
+/// Prometheus text exposition format, as `speccheck_<counter>_total{<labels>} <value>`.
+pub fn write_prometheus<W: Write>(
+    output: &mut W,
+    series: &[(&[(&str, &str)], Metrics)],
+) -> Result<()> {
+    let series: Vec<(String, [Counter; 8])> = series
+        .iter()
+        .map(|(labels, metrics)| (format_labels(labels), metrics.counters()))
+        .collect();
+    for (i, (name, help, _)) in Metrics::default().counters().iter().enumerate() {
+        writeln!(output, "# HELP speccheck_{}_total {}", name, help)?;
+        writeln!(output, "# TYPE speccheck_{}_total counter", name)?;
+        for (labels, counters) in series.iter() {
+            writeln!(
+                output,
+                "speccheck_{}_total{} {}",
+                name, labels, counters[i].2
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn format_labels(labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
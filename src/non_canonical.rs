@@ -0,0 +1,65 @@
+use crate::message_source::RandomBytes;
+use crate::test_vectors::{
+    non_zero_mixed_small_non_canonical, non_zero_small_non_canonical_mixed, TestVector,
+};
+use crate::EIGHT_TORSION_NON_CANONICAL;
+use anyhow::Result;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A vector of cases 8 to 11 of `generate_test_vectors`, built around one of the
+/// non-canonical encodings of the points of order 4 `(±sqrt(-1), 0)`, whose y is encoded as
+/// 2^255 - 19 (#5 and #6 in Table 1) rather than 0. Only verifiers accepting non-canonical
+/// encodings of the point can accept it, and of those, only the ones hashing the point the
+/// way the signature was made for: re-serialized, or as provided.
+#[derive(Serialize)]
+pub struct NonCanonicalVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// The index of the encoding in `EIGHT_TORSION_NON_CANONICAL`.
+    pub encoding: usize,
+    /// Whether the encoding is that of A, rather than of R.
+    pub in_pub_key: bool,
+    /// Whether the signature was made for the hash of the re-serialized point.
+    pub reserialized: bool,
+}
+
+// The encodings of `EIGHT_TORSION_NON_CANONICAL` with y = 2^255 - 19, of -sqrt(-1) and of
+// sqrt(-1)
+const ORDER_FOUR_ENCODINGS: [usize; 2] = [4, 5];
+
+/// For each non-canonical encoding of a point of order 4, the two vectors of cases 8 and 9
+/// placing it in R, then the two of cases 10 and 11 placing it in A, with ground messages.
+pub fn non_canonical_vectors(rng: &mut impl RngCore) -> Result<Vec<NonCanonicalVector>> {
+    let messages = &mut RandomBytes::default();
+    let mut vectors = Vec::new();
+    for &encoding in ORDER_FOUR_ENCODINGS.iter() {
+        let bytes = EIGHT_TORSION_NON_CANONICAL[encoding];
+        let placements = [
+            (
+                false,
+                non_zero_small_non_canonical_mixed(rng, messages, bytes)?,
+            ),
+            (
+                true,
+                non_zero_mixed_small_non_canonical(rng, messages, bytes)?,
+            ),
+        ];
+        for (in_pub_key, pair) in placements.iter() {
+            for (vector, &reserialized) in pair.iter().zip([true, false].iter()) {
+                vectors.push(NonCanonicalVector {
+                    comment: format!(
+                        "non-canonical {} of order 4, {}reduced for hash",
+                        if *in_pub_key { "A" } else { "R" },
+                        if reserialized { "" } else { "not " }
+                    ),
+                    vector: vector.clone(),
+                    encoding,
+                    in_pub_key: *in_pub_key,
+                    reserialized,
+                });
+            }
+        }
+    }
+    Ok(vectors)
+}
@@ -0,0 +1,217 @@
+use crate::matrix::{classify, Verdicts};
+use crate::test_vectors::{order_name, TestVector};
+use crate::verdict::Verdict;
+use crate::{deserialize_point, serialize_signature, HramPrefix};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A non-canonical encoding decompressing to a point: y >= p, i.e. `y = t + p` for t < 19,
+/// with either sign of x, or the sign of x = 0 set for y = ±1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonCanonicalEncoding {
+    pub bytes: [u8; 32],
+    pub description: String,
+}
+
+/// Every non-canonical encoding of a point, as `CompressedEdwardsY::decompress` accepts
+/// them: the sign of x = 0 set for y = 1 and y = -1, then y = t + p with the sign of x
+/// clear then set, by increasing t. Besides the 6 of the points of small order in
+/// `EIGHT_TORSION_NON_CANONICAL` ([CGN20e] Table 1), they include those of the points of
+/// mixed order whose y is below 19, whose discrete logarithm is unknown.
+pub fn non_canonical_encodings() -> Vec<NonCanonicalEncoding> {
+    let mut y_one = [0u8; 32];
+    y_one[0] = 1;
+    y_one[31] = 0x80;
+    let mut y_minus_one = [0xffu8; 32];
+    y_minus_one[0] = 0xec;
+    let mut candidates = vec![
+        NonCanonicalEncoding {
+            bytes: y_one,
+            description: "y = 1, sign set".to_string(),
+        },
+        NonCanonicalEncoding {
+            bytes: y_minus_one,
+            description: "y = -1, sign set".to_string(),
+        },
+    ];
+    for t in 0..19u8 {
+        for &sign in [false, true].iter() {
+            // p = 2^255 - 19 is ed ff .. ff 7f in little-endian
+            let mut bytes = [0xffu8; 32];
+            bytes[0] = 0xed + t;
+            bytes[31] = if sign { 0xff } else { 0x7f };
+            candidates.push(NonCanonicalEncoding {
+                bytes,
+                description: format!("y = {} + p{}", t, if sign { ", sign set" } else { "" }),
+            });
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|encoding| deserialize_point(&encoding.bytes).is_ok())
+        .collect()
+}
+
+/// A vector placing one of `non_canonical_encodings` in A or in R, the other point being
+/// canonical. For the points of small order, it is valid under both equations, without
+/// checks on the encodings or orders of A and R: in A, the message is ground for `[k]A` to
+/// vanish under both ways of hashing A; in R, A is `[a]B - R` and the message is ground for
+/// `[k - 1]R` to vanish, with k over R as provided, the way verifiers hash the signature.
+/// For the points of mixed order, no equation holds, as their discrete logarithm is
+/// unknown: every verifier rejects the vector, and only the stage at which it does, while
+/// decoding or while verifying, tells whether it accepts the encoding.
+#[derive(Serialize)]
+pub struct EncodingVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// The index of the encoding in `non_canonical_encodings`.
+    pub encoding: usize,
+    /// Whether the encoding is that of A, rather than of R.
+    pub in_pub_key: bool,
+    /// The order of the point, as in `CaseMetadata`: 1, 2 or 4, or 2L, 4L or 8L if mixed.
+    pub order: String,
+    pub expected_verdicts: Verdicts,
+}
+
+fn random_scalar(rng: &mut impl RngCore) -> Scalar {
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    Scalar::from_bytes_mod_order(scalar_bytes)
+}
+
+// A the encoded point, R of order L and S = r
+fn in_pub_key(rng: &mut impl RngCore, bytes: [u8; 32], pub_key: &EdwardsPoint) -> TestVector {
+    let r_scalar = random_scalar(rng);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let hram = HramPrefix::new(&r, pub_key);
+    let hram_provided = HramPrefix::from_arrays(r.compress().as_bytes(), &bytes);
+    let mut message = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut message);
+        let vanishes = |k: Scalar| (k * pub_key).is_identity();
+        if !pub_key.is_small_order()
+            || (vanishes(hram.finish(&message)) && vanishes(hram_provided.finish(&message)))
+        {
+            break;
+        }
+    }
+    TestVector {
+        message: message.to_vec(),
+        pub_key: bytes,
+        signature: serialize_signature(&r, &r_scalar),
+        metadata: None,
+        trace: None,
+    }
+}
+
+// R the encoded point, A = [a]B - R if R has small order, else [a]B, and S = ka
+fn in_r(rng: &mut impl RngCore, bytes: [u8; 32], r: &EdwardsPoint) -> TestVector {
+    let a = random_scalar(rng);
+    let pub_key = if r.is_small_order() {
+        a * ED25519_BASEPOINT_POINT - r
+    } else {
+        a * ED25519_BASEPOINT_POINT
+    };
+    let hram = HramPrefix::from_arrays(&bytes, pub_key.compress().as_bytes());
+    let mut message = [0u8; 32];
+    let k = loop {
+        rng.fill_bytes(&mut message);
+        let k = hram.finish(&message);
+        if !r.is_small_order() || ((k - Scalar::one()) * r).is_identity() {
+            break k;
+        }
+    };
+    let mut signature = serialize_signature(r, &(k * a));
+    signature[..32].copy_from_slice(&bytes);
+    TestVector {
+        message: message.to_vec(),
+        pub_key: pub_key.compress().to_bytes(),
+        signature,
+        metadata: None,
+        trace: None,
+    }
+}
+
+/// For each of `non_canonical_encodings`, the vector placing it in A, then the one placing
+/// it in R.
+pub fn non_canonical_encoding_vectors(rng: &mut impl RngCore) -> Result<Vec<EncodingVector>> {
+    let mut vectors = Vec::new();
+    for (encoding, nc) in non_canonical_encodings().iter().enumerate() {
+        let point = deserialize_point(&nc.bytes)?;
+        for &in_pub_key in [true, false].iter() {
+            let vector = if in_pub_key {
+                self::in_pub_key(rng, nc.bytes, &point)
+            } else {
+                in_r(rng, nc.bytes, &point)
+            };
+            let expected_verdicts = classify(&vector);
+            let order = order_name(&point);
+            vectors.push(EncodingVector {
+                comment: format!(
+                    "{} encoded as {}, of order {}",
+                    if in_pub_key { "A" } else { "R" },
+                    nc.description,
+                    order
+                ),
+                vector,
+                encoding,
+                in_pub_key,
+                order,
+                expected_verdicts,
+            });
+        }
+    }
+    Ok(vectors)
+}
+
+/// Lays out the verdicts of libraries on the vectors of `non_canonical_encoding_vectors`,
+/// given in the same order as rows of the results table: a line per encoding, with the
+/// verdicts of each library on it in A and in R.
+pub fn per_encoding_table(
+    vectors: &[EncodingVector],
+    rows: &[(&str, &[Verdict])],
+) -> Result<String> {
+    let encodings = non_canonical_encodings();
+    if let Some((name, verdicts)) = rows.iter().find(|(_, v)| v.len() != vectors.len()) {
+        return Err(anyhow!(
+            "got {} verdicts of {} for {} vectors",
+            verdicts.len(),
+            name,
+            vectors.len()
+        ));
+    }
+    let mut table = format!("{:<22}|{:>6} ", "encoding (A, R)", "order");
+    for (name, _) in rows.iter() {
+        table.push_str(&format!("|{:^16}", name));
+    }
+    table.push('\n');
+    for (encoding, nc) in encodings.iter().enumerate() {
+        let in_a = vectors
+            .iter()
+            .position(|v| v.encoding == encoding && v.in_pub_key);
+        let in_r = vectors
+            .iter()
+            .position(|v| v.encoding == encoding && !v.in_pub_key);
+        let (in_a, in_r) = match (in_a, in_r) {
+            (Some(in_a), Some(in_r)) => (in_a, in_r),
+            _ => return Err(anyhow!("no vector places {} in A and R", nc.description)),
+        };
+        table.push_str(&format!(
+            "{:<22}|{:>6} ",
+            nc.description, vectors[in_a].order
+        ));
+        for (_, verdicts) in rows.iter() {
+            table.push_str(&format!(
+                "|{:^16}",
+                format!("{} {}", verdicts[in_a], verdicts[in_r])
+            ));
+        }
+        table.push('\n');
+    }
+    Ok(table)
+}
@@ -3,29 +3,49 @@
 // This source code is licensed under the APACHE 2.0 license found in
 // the LICENSE file in the root directory of this source tree.
 
-use core::ops::{Index, IndexMut};
-
-/// The `Scalar52` struct represents an element in
-/// ℤ/ℓℤ as 5 52-bit limbs.
-pub struct Scalar52(pub [u64; 5]);
-
-/// `L` is the order of base point, i.e. 2^252 + 27742317777372353535851937790883648493
-pub const L: Scalar52 = Scalar52([
-    0x0002_631a_5cf5_d3ed,
-    0x000d_ea2f_79cd_6581,
-    0x0000_0000_0014_def9,
-    0x0000_0000_0000_0000,
-    0x0000_1000_0000_0000,
-]);
+use core::cmp::Ordering;
+use core::ops::{Add, Mul, Sub};
+use curve25519_dalek::scalar::Scalar;
+
+/// An integer below 2^256, as S is encoded in a signature, as 5 52-bit limbs. Unlike
+/// `Scalar`, its arithmetic never reduces modulo ℓ: the sums, differences and products
+/// which would leave [0, 2^256) are `None` for the `checked_` operations, and panic for the
+/// operators, as for the primitive integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BigScalar([u64; 5]);
+
+const MASK: u64 = (1u64 << 52) - 1;
+// The top limb holds the bits 208 to 255
+const TOP_MASK: u64 = (1u64 << 48) - 1;
+
+// Not all of it is used by the generators of this crate, for those of the `unstable` feature
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+impl BigScalar {
+    pub const ZERO: BigScalar = BigScalar([0, 0, 0, 0, 0]);
+
+    /// `L` is the order of base point, i.e. 2^252 + 27742317777372353535851937790883648493
+    pub const L: BigScalar = BigScalar([
+        0x0002_631a_5cf5_d3ed,
+        0x000d_ea2f_79cd_6581,
+        0x0000_0000_0014_def9,
+        0x0000_0000_0000_0000,
+        0x0000_1000_0000_0000,
+    ]);
+
+    pub fn from_u64(n: u64) -> BigScalar {
+        BigScalar([n & MASK, n >> 52, 0, 0, 0])
+    }
 
-impl Scalar52 {
-    /// Return the zero scalar
-    pub fn zero() -> Scalar52 {
-        Scalar52([0, 0, 0, 0, 0])
+    /// 2^`exponent`, for an exponent below 256.
+    pub fn pow2(exponent: usize) -> BigScalar {
+        assert!(exponent < 256, "2^{} does not fit in 256 bits", exponent);
+        let mut limbs = [0u64; 5];
+        limbs[exponent / 52] = 1 << (exponent % 52);
+        BigScalar(limbs)
     }
 
     /// Unpack a 32 byte / 256 bit scalar into 5 52-bit limbs.
-    pub fn from_bytes(bytes: &[u8; 32]) -> Scalar52 {
+    pub fn from_bytes(bytes: &[u8; 32]) -> BigScalar {
         let mut words = [0u64; 4];
         for i in 0..4 {
             for j in 0..8 {
@@ -33,21 +53,22 @@ impl Scalar52 {
             }
         }
 
-        let mask = (1u64 << 52) - 1;
-        let top_mask = (1u64 << 48) - 1;
-        let mut s = Scalar52::zero();
-
-        s[0] = words[0] & mask;
-        s[1] = ((words[0] >> 52) | (words[1] << 12)) & mask;
-        s[2] = ((words[1] >> 40) | (words[2] << 24)) & mask;
-        s[3] = ((words[2] >> 28) | (words[3] << 36)) & mask;
-        s[4] = (words[3] >> 16) & top_mask;
+        BigScalar([
+            words[0] & MASK,
+            ((words[0] >> 52) | (words[1] << 12)) & MASK,
+            ((words[1] >> 40) | (words[2] << 24)) & MASK,
+            ((words[2] >> 28) | (words[3] << 36)) & MASK,
+            (words[3] >> 16) & TOP_MASK,
+        ])
+    }
 
-        s
+    /// The integer of the bytes of `s`, i.e. `s` in [0, ℓ).
+    pub fn from_scalar(s: &Scalar) -> BigScalar {
+        BigScalar::from_bytes(s.as_bytes())
     }
 
-    /// Pack the limbs of this `Scalar52` into 32 bytes
-    pub fn to_bytes(&self) -> [u8; 32] {
+    /// Pack the limbs of this `BigScalar` into 32 bytes
+    pub fn to_bytes(self) -> [u8; 32] {
         let mut s = [0u8; 32];
 
         s[0] = self.0[0] as u8;
@@ -86,31 +107,138 @@ impl Scalar52 {
         s
     }
 
-    /// Compute `a + b` (without mod ℓ)
-    pub fn add(a: &Scalar52, b: &Scalar52) -> Scalar52 {
-        let mut sum = Scalar52::zero();
-        let mask = (1u64 << 52) - 1;
+    /// This integer modulo ℓ.
+    pub fn reduce(&self) -> Scalar {
+        Scalar::from_bytes_mod_order(self.to_bytes())
+    }
+
+    /// Whether this integer is below ℓ, as RFC 8032 requires of S.
+    pub fn is_canonical(&self) -> bool {
+        *self < BigScalar::L
+    }
+
+    /// The bit `i` of this integer, for `i` below 256.
+    pub fn bit(&self, i: usize) -> bool {
+        assert!(i < 256, "no bit {} in 256 bits", i);
+        (self.0[i / 52] >> (i % 52)) & 1 == 1
+    }
 
-        // a + b
+    /// This integer with the bit `i` set to `value`, for `i` below 256.
+    pub fn with_bit(&self, i: usize, value: bool) -> BigScalar {
+        assert!(i < 256, "no bit {} in 256 bits", i);
+        let mut limbs = self.0;
+        limbs[i / 52] &= !(1 << (i % 52));
+        limbs[i / 52] |= (value as u64) << (i % 52);
+        BigScalar(limbs)
+    }
+
+    /// Compute `a + b` (without mod ℓ), or `None` from 2^256 on.
+    pub fn checked_add(&self, other: &BigScalar) -> Option<BigScalar> {
+        let mut sum = BigScalar::ZERO;
         let mut carry: u64 = 0;
         for i in 0..5 {
-            carry = a[i] + b[i] + (carry >> 52);
-            sum[i] = carry & mask;
+            carry = self.0[i] + other.0[i] + (carry >> 52);
+            sum.0[i] = carry & MASK;
+        }
+        sum.fits()
+    }
+
+    /// Compute `a - b` (without mod ℓ), or `None` below 0.
+    pub fn checked_sub(&self, other: &BigScalar) -> Option<BigScalar> {
+        let mut difference = BigScalar::ZERO;
+        let mut borrow: u64 = 0;
+        for i in 0..5 {
+            borrow = self.0[i].wrapping_sub(other.0[i] + (borrow >> 63));
+            difference.0[i] = borrow & MASK;
+        }
+        if borrow >> 63 == 0 {
+            Some(difference)
+        } else {
+            None
         }
+    }
+
+    /// Compute `a * b` (without mod ℓ), or `None` from 2^256 on.
+    pub fn checked_mul(&self, other: &BigScalar) -> Option<BigScalar> {
+        let mut product = [0u128; 10];
+        for i in 0..5 {
+            for j in 0..5 {
+                product[i + j] += u128::from(self.0[i]) * u128::from(other.0[j]);
+            }
+        }
+        let mut limbs = [0u64; 5];
+        let mut carry: u128 = 0;
+        for (i, limb) in product.iter().enumerate() {
+            carry += limb;
+            if i < 5 {
+                limbs[i] = (carry as u64) & MASK;
+            } else if carry & u128::from(MASK) != 0 {
+                return None;
+            }
+            carry >>= 52;
+        }
+        BigScalar(limbs).fits()
+    }
+
+    /// `self + j * ℓ`, the encodings of S which only the checks of `S < ℓ` tell apart from
+    /// `self`, or `None` if it does not fit in 256 bits, as for any j from 16 on.
+    pub fn add_multiple_of_l(&self, j: u64) -> Option<BigScalar> {
+        self.checked_add(&BigScalar::L.checked_mul(&BigScalar::from_u64(j))?)
+    }
+
+    /// The quotient and remainder of this integer by ℓ: it is `q * ℓ + r`, with q below 16.
+    pub fn div_rem_l(&self) -> (u64, Scalar) {
+        let mut q = 0;
+        let mut r = *self;
+        while let Some(difference) = r.checked_sub(&BigScalar::L) {
+            r = difference;
+            q += 1;
+        }
+        (q, r.reduce())
+    }
+
+    // This integer if below 2^256
+    fn fits(self) -> Option<BigScalar> {
+        if self.0[4] <= TOP_MASK {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl Ord for BigScalar {
+    fn cmp(&self, other: &BigScalar) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for BigScalar {
+    fn partial_cmp(&self, other: &BigScalar) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        sum
+impl Add for BigScalar {
+    type Output = BigScalar;
+    fn add(self, other: BigScalar) -> BigScalar {
+        self.checked_add(&other)
+            .expect("attempt to add beyond 2^256")
     }
 }
 
-impl Index<usize> for Scalar52 {
-    type Output = u64;
-    fn index(&self, _index: usize) -> &u64 {
-        &(self.0[_index])
+impl Sub for BigScalar {
+    type Output = BigScalar;
+    fn sub(self, other: BigScalar) -> BigScalar {
+        self.checked_sub(&other)
+            .expect("attempt to subtract below 0")
     }
 }
 
-impl IndexMut<usize> for Scalar52 {
-    fn index_mut(&mut self, _index: usize) -> &mut u64 {
-        &mut (self.0[_index])
+impl Mul for BigScalar {
+    type Output = BigScalar;
+    fn mul(self, other: BigScalar) -> BigScalar {
+        self.checked_mul(&other)
+            .expect("attempt to multiply beyond 2^256")
     }
 }
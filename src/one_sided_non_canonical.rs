@@ -0,0 +1,180 @@
+use crate::matrix::{classify, Verdicts};
+use crate::test_vectors::{TestVector, NON_CANONICAL_DECODE_MODE};
+use crate::verdict::Verdict;
+use crate::{
+    compute_hram, compute_hram_with_r_array, deserialize_point_with, serialize_signature,
+    HramPrefix, EIGHT_TORSION_NON_CANONICAL,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+use std::fmt;
+
+/// A vector where exactly one of A and R is encoded non-canonically, the other being of
+/// order L and canonical, so that a verifier's check on the encoding of A is told apart
+/// from its check on that of R. Non-canonical encodings only decode to points of small
+/// order or of unknown discrete logarithm, so the non-canonical point has small order: R
+/// is the identity, encoded with y = 1 + p, for both equations to hold, and A is the point
+/// of order 2, encoded with the sign of x = 0 set, with the message ground for `[k]A` to
+/// vanish under the hashing the signature was made for only.
+#[derive(Serialize)]
+pub struct OneSidedVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// Whether the non-canonical encoding is that of A, rather than of R.
+    pub in_pub_key: bool,
+    /// Whether the signature was made for the hash of the re-serialized point.
+    pub reserialized: bool,
+    pub expected_verdicts: Verdicts,
+}
+
+// The encodings of `EIGHT_TORSION_NON_CANONICAL` of the identity with y = 1 + p, placed in
+// R, and of the point of order 2 with the sign of x set, placed in A
+const R_ENCODING: usize = 3;
+const PUB_KEY_ENCODING: usize = 2;
+
+// A and R of order L signing for the non-canonical R: S = ka, for k over R re-serialized or
+// as provided
+fn non_canonical_r(rng: &mut impl RngCore, reserialized: bool) -> Result<TestVector> {
+    let r_arr = EIGHT_TORSION_NON_CANONICAL[R_ENCODING];
+    let r = deserialize_point_with(&r_arr, NON_CANONICAL_DECODE_MODE)?;
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT;
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+
+    let k = if reserialized {
+        compute_hram(&message, &pub_key, &r)
+    } else {
+        compute_hram_with_r_array(&message, &pub_key, &r_arr)
+    };
+    let mut signature = serialize_signature(&r, &(k * a));
+    signature[..32].copy_from_slice(&r_arr);
+    Ok(TestVector {
+        message: message.to_vec(),
+        pub_key: pub_key.compress().to_bytes(),
+        signature,
+        metadata: None,
+        trace: None,
+    })
+}
+
+// R = [r]B of order L and S = r for the non-canonical A, with k even over A re-serialized
+// or as provided, and odd over the other
+fn non_canonical_pub_key(rng: &mut impl RngCore, reserialized: bool) -> Result<TestVector> {
+    let pub_key_arr = EIGHT_TORSION_NON_CANONICAL[PUB_KEY_ENCODING];
+    let pub_key = deserialize_point_with(&pub_key_arr, NON_CANONICAL_DECODE_MODE)?;
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let hram = HramPrefix::new(&r, &pub_key);
+    let hram_provided = HramPrefix::from_arrays(r.compress().as_bytes(), &pub_key_arr);
+
+    let mut message = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut message);
+        let vanishes = |k: Scalar| (k * pub_key).is_identity();
+        let k_reserialized = hram.finish(&message);
+        let k_provided = hram_provided.finish(&message);
+        if vanishes(k_reserialized) == reserialized && vanishes(k_provided) != reserialized {
+            break;
+        }
+    }
+    Ok(TestVector {
+        message: message.to_vec(),
+        pub_key: pub_key_arr,
+        signature: serialize_signature(&r, &r_scalar),
+        metadata: None,
+        trace: None,
+    })
+}
+
+/// The four vectors, placing the non-canonical encoding in A then in R, each for the hash
+/// of the re-serialized point then of the point as provided.
+pub fn one_sided_non_canonical_vectors(rng: &mut impl RngCore) -> Result<Vec<OneSidedVector>> {
+    let mut vectors = Vec::new();
+    for &in_pub_key in [true, false].iter() {
+        for &reserialized in [true, false].iter() {
+            let vector = if in_pub_key {
+                non_canonical_pub_key(rng, reserialized)?
+            } else {
+                non_canonical_r(rng, reserialized)?
+            };
+            let expected_verdicts = classify(&vector);
+            vectors.push(OneSidedVector {
+                comment: format!(
+                    "non-canonical {} of small order, {} of order L, {}reduced for hash",
+                    if in_pub_key { "A" } else { "R" },
+                    if in_pub_key { "R" } else { "A" },
+                    if reserialized { "" } else { "not " }
+                ),
+                vector,
+                in_pub_key,
+                reserialized,
+                expected_verdicts,
+            });
+        }
+    }
+    Ok(vectors)
+}
+
+/// The verdicts of a verifier on the vectors of `one_sided_non_canonical_vectors`, laid out
+/// as a 2×2 grid: a row per point encoded non-canonically, A then R, and a column per hash
+/// the signature was made for, of the re-serialized point then of the point as provided.
+/// A verifier checking the encoding of A but not that of R rejects the first row only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AcceptancePattern(pub [[Verdict; 2]; 2]);
+
+impl AcceptancePattern {
+    /// Lays out the `verdicts` reached on the `vectors`, given in the same order.
+    pub fn new(vectors: &[OneSidedVector], verdicts: &[Verdict]) -> Result<AcceptancePattern> {
+        if vectors.len() != verdicts.len() {
+            return Err(anyhow!(
+                "got {} verdicts for {} vectors",
+                verdicts.len(),
+                vectors.len()
+            ));
+        }
+        let mut grid = [[None; 2]; 2];
+        for (v, verdict) in vectors.iter().zip(verdicts.iter()) {
+            grid[!v.in_pub_key as usize][!v.reserialized as usize] = Some(*verdict);
+        }
+        let cell = |row: [Option<Verdict>; 2]| -> Result<[Verdict; 2]> {
+            match row {
+                [Some(reserialized), Some(provided)] => Ok([reserialized, provided]),
+                _ => Err(anyhow!("the vectors do not cover every cell of the grid")),
+            }
+        };
+        Ok(AcceptancePattern([cell(grid[0])?, cell(grid[1])?]))
+    }
+
+    /// Whether the verifier accepts a non-canonical A, under either hash.
+    pub fn accepts_non_canonical_pub_key(&self) -> bool {
+        self.0[0].iter().any(|v| v.is_accepted())
+    }
+
+    /// Whether the verifier accepts a non-canonical R, under either hash.
+    pub fn accepts_non_canonical_r(&self) -> bool {
+        self.0[1].iter().any(|v| v.is_accepted())
+    }
+}
+
+impl fmt::Display for AcceptancePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "                | reduced | not reduced")?;
+        for (point, row) in ["A", "R"].iter().zip(self.0.iter()) {
+            writeln!(
+                f,
+                "non-canonical {} |    {}    |      {}",
+                point, row[0], row[1]
+            )?;
+        }
+        Ok(())
+    }
+}
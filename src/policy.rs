@@ -0,0 +1,881 @@
+use crate::metrics::Metrics;
+use crate::test_vectors::{ed25519ph, generate_test_vectors, TestVector, CASE_IDS};
+use crate::verdict::Verdict;
+use crate::{
+    check_slice_size, compute_hram_with_arrays, deserialize_point, deserialize_scalar,
+    verify_final_cofactored, verify_final_cofactorless, verify_final_pre_reduced_cofactored,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::BASEPOINT_ORDER;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/// A combination of the individual checks an Ed25519 verifier may or may not perform,
+/// as catalogued in [CGN20e]. Each combination is a model of a verifier whose verdicts
+/// on the test vectors can be predicted, and hence compared against observations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ValidationPolicy {
+    pub reject_small_order_pk: bool,
+    pub reject_noncanonical_pk: bool,
+    pub reject_small_order_r: bool,
+    pub reject_noncanonical_r: bool,
+    pub require_canonical_s: bool,
+    pub cofactored: bool,
+    // Only meaningful along with `cofactored`: computes (8h mod L) and (8s mod L) before
+    // multiplying, rather than multiplying the final point by 8.
+    pub prereduce: bool,
+    // Hash the re-serialized A and R rather than the bytes as provided.
+    pub reserialize_for_hash: bool,
+    // Rejects A unless it has order exactly L, which also rejects the A of small order.
+    pub require_full_order_pk: bool,
+}
+
+const NUM_CHECKS: usize = 9;
+
+/// The policy of libsodium 1.0.16 and later, as parsed from `libsodium`: it rejects A and R
+/// of small order, the non-canonical encodings of A and S >= L, and recomputes R to compare
+/// it with the bytes provided, which rejects its non-canonical encodings. `libsodium::verify`
+/// emulates the library itself, accepting the same vectors, though rejecting some while
+/// verifying rather than parsing.
+pub const LIBSODIUM: ValidationPolicy = ValidationPolicy {
+    reject_small_order_pk: true,
+    reject_noncanonical_pk: true,
+    reject_small_order_r: true,
+    reject_noncanonical_r: true,
+    require_canonical_s: true,
+    cofactored: false,
+    prereduce: false,
+    reserialize_for_hash: false,
+    require_full_order_pk: false,
+};
+
+/// The policy of `verify_cofactored`, checking nothing besides the cofactored equation over
+/// the re-serialized A and R.
+pub const COFACTORED: ValidationPolicy = ValidationPolicy {
+    cofactored: true,
+    ..COFACTORLESS
+};
+
+/// The policy of `verify_cofactorless`, as `COFACTORED` with the cofactorless equation.
+pub const COFACTORLESS: ValidationPolicy = ValidationPolicy {
+    reserialize_for_hash: true,
+    ..LENIENT
+};
+
+/// The policy of [CGN20e] Algorithm 2, as `algorithm2::verify`.
+pub const ALGORITHM_2: ValidationPolicy = ValidationPolicy {
+    reject_small_order_pk: true,
+    reject_noncanonical_pk: true,
+    reject_noncanonical_r: true,
+    require_canonical_s: true,
+    cofactored: true,
+    ..LENIENT
+};
+
+/// The policy of RFC 8032, as `rfc8032::verify`.
+pub const RFC_8032: ValidationPolicy = ValidationPolicy {
+    reject_noncanonical_pk: true,
+    reject_noncanonical_r: true,
+    require_canonical_s: true,
+    ..LENIENT
+};
+
+/// The policy of FIPS 186-5, as `fips186_5::verify`.
+pub const FIPS_186_5: ValidationPolicy = ValidationPolicy {
+    reject_noncanonical_pk: true,
+    reject_noncanonical_r: true,
+    require_canonical_s: true,
+    cofactored: true,
+    require_full_order_pk: true,
+    ..LENIENT
+};
+
+/// The policy of ZIP-215, as `zip215::verify`.
+pub const ZIP_215: ValidationPolicy = ValidationPolicy {
+    require_canonical_s: true,
+    cofactored: true,
+    ..LENIENT
+};
+
+// The most lenient policy, `ValidationPolicy::default()`
+const LENIENT: ValidationPolicy = ValidationPolicy {
+    reject_small_order_pk: false,
+    reject_noncanonical_pk: false,
+    reject_small_order_r: false,
+    reject_noncanonical_r: false,
+    require_canonical_s: false,
+    cofactored: false,
+    prereduce: false,
+    reserialize_for_hash: false,
+    require_full_order_pk: false,
+};
+
+/// The policies of the reference verifiers of this crate, by the name `from_str` parses them
+/// from, whose verdicts the metadata of the generated vectors record.
+pub const NAMED_POLICIES: [(&str, ValidationPolicy); 5] = [
+    ("algorithm2", ALGORITHM_2),
+    ("fips186_5", FIPS_186_5),
+    ("libsodium", LIBSODIUM),
+    ("rfc8032", RFC_8032),
+    ("zip215", ZIP_215),
+];
+
+/// The names of the checks of a `ValidationPolicy`, as parsed by `from_str`: its fields,
+/// spelled with dashes.
+pub const CHECK_NAMES: [&str; NUM_CHECKS] = [
+    "reject-small-order-pk",
+    "reject-noncanonical-pk",
+    "reject-small-order-r",
+    "reject-noncanonical-r",
+    "require-canonical-s",
+    "cofactored",
+    "prereduce",
+    "reserialize-for-hash",
+    "require-full-order-pk",
+];
+
+impl ValidationPolicy {
+    /// All distinct policies, i.e. every combination of checks except those
+    /// asking to pre-reduce in cofactorless mode.
+    pub fn all() -> Vec<ValidationPolicy> {
+        (0..1u32 << NUM_CHECKS)
+            .map(|bits| {
+                let flag = |i: u32| bits & (1 << i) != 0;
+                ValidationPolicy {
+                    reject_small_order_pk: flag(0),
+                    reject_noncanonical_pk: flag(1),
+                    reject_small_order_r: flag(2),
+                    reject_noncanonical_r: flag(3),
+                    require_canonical_s: flag(4),
+                    cofactored: flag(5),
+                    prereduce: flag(6),
+                    reserialize_for_hash: flag(7),
+                    require_full_order_pk: flag(8),
+                }
+            })
+            .filter(|policy| policy.cofactored || !policy.prereduce)
+            .collect()
+    }
+
+    /// The most lenient policy, checking nothing besides the cofactorless equation, to which
+    /// the builder methods below add checks, e.g. `ZIP_215` is
+    /// `ValidationPolicy::new().require_canonical_s().cofactored(true)`. The policy built is
+    /// itself a `Verifier`.
+    pub fn new() -> ValidationPolicy {
+        LENIENT
+    }
+
+    /// This policy, also rejecting A of small order.
+    pub fn reject_small_order_pk(self) -> ValidationPolicy {
+        ValidationPolicy {
+            reject_small_order_pk: true,
+            ..self
+        }
+    }
+
+    /// This policy, also rejecting the non-canonical encodings of A.
+    pub fn reject_noncanonical_pk(self) -> ValidationPolicy {
+        ValidationPolicy {
+            reject_noncanonical_pk: true,
+            ..self
+        }
+    }
+
+    /// This policy, also rejecting R of small order.
+    pub fn reject_small_order_r(self) -> ValidationPolicy {
+        ValidationPolicy {
+            reject_small_order_r: true,
+            ..self
+        }
+    }
+
+    /// This policy, also rejecting the non-canonical encodings of R.
+    pub fn reject_noncanonical_r(self) -> ValidationPolicy {
+        ValidationPolicy {
+            reject_noncanonical_r: true,
+            ..self
+        }
+    }
+
+    /// This policy, also requiring S < L.
+    pub fn require_canonical_s(self) -> ValidationPolicy {
+        ValidationPolicy {
+            require_canonical_s: true,
+            ..self
+        }
+    }
+
+    /// This policy, also requiring A of order L.
+    pub fn require_full_order_pk(self) -> ValidationPolicy {
+        ValidationPolicy {
+            require_full_order_pk: true,
+            ..self
+        }
+    }
+
+    /// This policy, also hashing the re-serialized A and R.
+    pub fn reserialize_for_hash(self) -> ValidationPolicy {
+        ValidationPolicy {
+            reserialize_for_hash: true,
+            ..self
+        }
+    }
+
+    /// This policy, with the cofactored equation or the cofactorless one, which does not
+    /// pre-reduce.
+    pub fn cofactored(self, cofactored: bool) -> ValidationPolicy {
+        ValidationPolicy {
+            cofactored,
+            prereduce: self.prereduce && cofactored,
+            ..self
+        }
+    }
+
+    /// This policy, pre-reducing (8h) and (8s) or not: pre-reducing only applies to the
+    /// cofactored equation, which it selects.
+    pub fn prereduce(self, prereduce: bool) -> ValidationPolicy {
+        ValidationPolicy {
+            prereduce,
+            cofactored: self.cofactored || prereduce,
+            ..self
+        }
+    }
+
+    /// Verifies the serialized `signature` of `message` under the serialized `pub_key`,
+    /// performing exactly the checks enabled in this policy.
+    pub fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        self.verify_with_metrics(message, pub_key, signature, &mut Metrics::default())
+    }
+
+    /// As `verify`, counting the operations performed in `metrics`.
+    pub fn verify_with_metrics(
+        &self,
+        message: &[u8],
+        pub_key: &[u8],
+        signature: &[u8],
+        metrics: &mut Metrics,
+    ) -> Result<()> {
+        metrics.verifications += 1;
+        let decoded = self.decode(pub_key, signature, metrics)?;
+        self.check(message, pub_key, signature, decoded, metrics)
+    }
+
+    /// As `verify`, telling apart vectors refused while decoding the public key and the
+    /// signature (including the canonicity checks) from those failing verification.
+    pub fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        self.verdict_with_metrics(message, pub_key, signature, &mut Metrics::default())
+    }
+
+    /// As `verdict`, counting the operations performed in `metrics`.
+    pub fn verdict_with_metrics(
+        &self,
+        message: &[u8],
+        pub_key: &[u8],
+        signature: &[u8],
+        metrics: &mut Metrics,
+    ) -> Verdict {
+        metrics.verifications += 1;
+        let decoded = self.decode(pub_key, signature, metrics);
+        Verdict::from_results(decoded, |decoded| {
+            self.check(message, pub_key, signature, decoded, metrics)
+        })
+    }
+
+    #[allow(non_snake_case)]
+    fn decode(
+        &self,
+        pub_key: &[u8],
+        signature: &[u8],
+        metrics: &mut Metrics,
+    ) -> Result<(EdwardsPoint, EdwardsPoint, Scalar)> {
+        let signature = check_slice_size(signature, 64, "signature")?;
+        let (R_bytes, s_bytes) = signature.split_at(32);
+
+        metrics.decompressions += 1;
+        let pk = deserialize_point(pub_key)?;
+        if self.reject_noncanonical_pk {
+            metrics.compressions += 1;
+            if !is_canonical_encoding(pub_key, &pk) {
+                return Err(anyhow!("Non-canonical public key encoding"));
+            }
+        }
+
+        metrics.decompressions += 1;
+        let R = deserialize_point(R_bytes)?;
+        if self.reject_noncanonical_r {
+            metrics.compressions += 1;
+            if !is_canonical_encoding(R_bytes, &R) {
+                return Err(anyhow!("Non-canonical R encoding"));
+            }
+        }
+
+        let s = if self.require_canonical_s {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(s_bytes);
+            Scalar::from_canonical_bytes(bytes).ok_or_else(|| anyhow!("Non-canonical s"))?
+        } else {
+            deserialize_scalar(s_bytes)?
+        };
+        Ok((pk, R, s))
+    }
+
+    fn check(
+        &self,
+        message: &[u8],
+        pub_key: &[u8],
+        signature: &[u8],
+        decoded: (EdwardsPoint, EdwardsPoint, Scalar),
+        metrics: &mut Metrics,
+    ) -> Result<()> {
+        metrics.hashed_bytes += 64 + message.len() as u64;
+        self.check_with_hram(pub_key, signature, decoded, metrics, |pub_key, r| {
+            Ok(compute_hram_with_arrays(message, pub_key, r))
+        })
+    }
+
+    // The checks following decoding, with the challenge k computed by `hram` from the
+    // encodings of A and R it hashes
+    #[allow(non_snake_case)]
+    fn check_with_hram(
+        &self,
+        pub_key: &[u8],
+        signature: &[u8],
+        (pk, R, s): (EdwardsPoint, EdwardsPoint, Scalar),
+        metrics: &mut Metrics,
+        hram: impl FnOnce(&[u8], &[u8]) -> Result<Scalar>,
+    ) -> Result<()> {
+        let k = self.challenge(pub_key, signature, &pk, &R, metrics, hram)?;
+
+        metrics.double_scalar_muls += 1;
+        if self.cofactored {
+            metrics.cofactor_muls += 1;
+        }
+        match (self.cofactored, self.prereduce) {
+            (true, true) => verify_final_pre_reduced_cofactored(&pk, &(R, s), &k),
+            (true, false) => verify_final_cofactored(&pk, &(R, s), &k),
+            (false, _) => verify_final_cofactorless(&pk, &(R, s), &k),
+        }
+    }
+
+    // The checks of decoded A and R, then the challenge k computed by `hram`
+    #[allow(non_snake_case)]
+    fn challenge(
+        &self,
+        pub_key: &[u8],
+        signature: &[u8],
+        pk: &EdwardsPoint,
+        R: &EdwardsPoint,
+        metrics: &mut Metrics,
+        hram: impl FnOnce(&[u8], &[u8]) -> Result<Scalar>,
+    ) -> Result<Scalar> {
+        if self.reject_small_order_pk {
+            metrics.small_order_checks += 1;
+            if pk.is_small_order() {
+                return Err(anyhow!("Small-order public key"));
+            }
+        }
+        if self.require_full_order_pk {
+            metrics.full_order_checks += 1;
+            if !has_full_order(pk) {
+                return Err(anyhow!("Public key not of order L"));
+            }
+        }
+        if self.reject_small_order_r {
+            metrics.small_order_checks += 1;
+            if R.is_small_order() {
+                return Err(anyhow!("Small-order R"));
+            }
+        }
+
+        if self.reserialize_for_hash {
+            metrics.compressions += 2;
+            hram(pk.compress().as_bytes(), R.compress().as_bytes())
+        } else {
+            hram(pub_key, &signature[..32])
+        }
+    }
+
+    // Decodes the signature and performs every check of this policy but the verification
+    // equation, returning A, R, s and the challenge k, for the batch equation of `batch`
+    pub(crate) fn decode_for_batch(
+        &self,
+        message: &[u8],
+        pub_key: &[u8],
+        signature: &[u8],
+    ) -> Result<(EdwardsPoint, EdwardsPoint, Scalar, Scalar)> {
+        let metrics = &mut Metrics::default();
+        let (pk, r, s) = self.decode(pub_key, signature, metrics)?;
+        let k = self.challenge(pub_key, signature, &pk, &r, metrics, |pub_key, r| {
+            Ok(compute_hram_with_arrays(message, pub_key, r))
+        })?;
+        Ok((pk, r, s, k))
+    }
+
+    /// Verifies the Ed25519ph `signature` under `context` of the message whose SHA-512 digest
+    /// is `prehash`, performing the checks of this policy, as verifiers of prehashed messages
+    /// do.
+    pub fn verify_prehashed(
+        &self,
+        context: &[u8],
+        prehash: &[u8],
+        pub_key: &[u8],
+        signature: &[u8],
+    ) -> Result<()> {
+        let metrics = &mut Metrics::default();
+        let decoded = self.decode(pub_key, signature, metrics)?;
+        self.check_with_hram(pub_key, signature, decoded, metrics, |pub_key, r| {
+            ed25519ph::compute_hram_with_prehash(context, prehash, pub_key, r)
+        })
+    }
+
+    /// The verdicts this policy predicts for each of the `vectors`.
+    pub fn predict(&self, vectors: &[TestVector]) -> Vec<Verdict> {
+        self.predict_with_metrics(vectors, &mut Metrics::default())
+    }
+
+    /// As `predict`, counting the operations performed over the whole corpus in `metrics`.
+    pub fn predict_with_metrics(
+        &self,
+        vectors: &[TestVector],
+        metrics: &mut Metrics,
+    ) -> Vec<Verdict> {
+        vectors
+            .iter()
+            .map(|tv| self.verdict_with_metrics(&tv.message, &tv.pub_key, &tv.signature, metrics))
+            .collect()
+    }
+
+    /// The verdicts this policy predicts for each of the vectors of `generate_test_vectors`,
+    /// in the order of `test_vectors::CASE_IDS`.
+    pub fn predict_generated(&self) -> Vec<Verdict> {
+        self.predict(&generate_test_vectors())
+    }
+
+    /// The names of the checks, out of `CHECK_NAMES`, which this policy and `other` do not
+    /// both perform or both skip.
+    pub fn differing_checks(&self, other: &ValidationPolicy) -> Vec<&'static str> {
+        CHECK_NAMES
+            .iter()
+            .zip(self.checks().iter().zip(other.checks().iter()))
+            .filter(|(_, (a, b))| a != b)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Out of the policies consistent with `observed`, the verdicts of a verifier on
+    /// `vectors`, the one differing from this policy by the fewest checks, or `None` if no
+    /// policy models that verifier: starting from a model of what a library does, the checks
+    /// of `differing_checks` are the least it has to change to agree with the verifier
+    /// observed. Verdicts are matched as in `infer_from_partial`.
+    pub fn minimal_change(
+        &self,
+        vectors: &[TestVector],
+        observed: &[Verdict],
+    ) -> Result<Option<ValidationPolicy>> {
+        let observed: Vec<Option<Verdict>> = observed.iter().map(|v| Some(*v)).collect();
+        Ok(infer_from_partial(vectors, &observed)?
+            .consistent
+            .into_iter()
+            .min_by_key(|policy| self.differing_checks(policy).len()))
+    }
+
+    // The checks of this policy, in the order of `CHECK_NAMES`.
+    fn checks(&self) -> [bool; NUM_CHECKS] {
+        [
+            self.reject_small_order_pk,
+            self.reject_noncanonical_pk,
+            self.reject_small_order_r,
+            self.reject_noncanonical_r,
+            self.require_canonical_s,
+            self.cofactored,
+            self.prereduce,
+            self.reserialize_for_hash,
+            self.require_full_order_pk,
+        ]
+    }
+
+    // Whether this policy accepts each of the `vectors`: this is all the classification
+    // relies on, as where a rejection happens depends on the API of the verifier under test
+    // as much as on the checks it performs.
+    fn acceptance(&self, vectors: &[TestVector]) -> Vec<bool> {
+        self.predict(vectors)
+            .into_iter()
+            .map(Verdict::is_accepted)
+            .collect()
+    }
+}
+
+impl fmt::Display for ValidationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut checks = vec![match (self.cofactored, self.prereduce) {
+            (true, true) => "cofactored with (8h) pre-reduced",
+            (true, false) => "cofactored",
+            (false, _) => "cofactorless",
+        }];
+        if self.reject_small_order_pk {
+            checks.push("rejects small-order A");
+        }
+        if self.reject_noncanonical_pk {
+            checks.push("rejects non-canonical A");
+        }
+        if self.require_full_order_pk {
+            checks.push("requires A of order L");
+        }
+        if self.reject_small_order_r {
+            checks.push("rejects small-order R");
+        }
+        if self.reject_noncanonical_r {
+            checks.push("rejects non-canonical R");
+        }
+        if self.require_canonical_s {
+            checks.push("requires S < L");
+        }
+        if self.reserialize_for_hash {
+            checks.push("hashes re-serialized A and R");
+        }
+        write!(f, "{}", checks.join(", "))
+    }
+}
+
+// Parses the comma-separated names of the checks to enable, out of `CHECK_NAMES`, e.g.
+// `cofactored,require-canonical-s`: the empty string is the most lenient policy. The names of
+// `NAMED_POLICIES` stand for their policy.
+impl FromStr for ValidationPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some((_, policy)) = NAMED_POLICIES.iter().find(|(name, _)| *name == s.trim()) {
+            return Ok(*policy);
+        }
+        let mut policy = ValidationPolicy::default();
+        for check in s.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+            let flag = match check {
+                "reject-small-order-pk" => &mut policy.reject_small_order_pk,
+                "reject-noncanonical-pk" => &mut policy.reject_noncanonical_pk,
+                "reject-small-order-r" => &mut policy.reject_small_order_r,
+                "reject-noncanonical-r" => &mut policy.reject_noncanonical_r,
+                "require-canonical-s" => &mut policy.require_canonical_s,
+                "cofactored" => &mut policy.cofactored,
+                "prereduce" => &mut policy.prereduce,
+                "reserialize-for-hash" => &mut policy.reserialize_for_hash,
+                "require-full-order-pk" => &mut policy.require_full_order_pk,
+                _ => {
+                    return Err(anyhow!(
+                        "unknown check {}, expected one of {}",
+                        check,
+                        CHECK_NAMES.join(", ")
+                    ))
+                }
+            };
+            *flag = true;
+        }
+        if policy.prereduce && !policy.cofactored {
+            return Err(anyhow!("prereduce only applies along with cofactored"));
+        }
+        Ok(policy)
+    }
+}
+
+/// Whether `pt` has order exactly L, i.e. `[L]pt = 0` without `pt` being the identity. This
+/// catches the mixed-order points which pass any small-order blocklist. The multiplication by
+/// L runs in variable time, as the points checked are public, at about half the cost of the
+/// constant-time `EdwardsPoint::is_torsion_free`.
+pub fn has_full_order(pt: &EdwardsPoint) -> bool {
+    !pt.is_identity()
+        && EdwardsPoint::vartime_double_scalar_mul_basepoint(&BASEPOINT_ORDER, pt, &Scalar::zero())
+            .is_identity()
+}
+
+pub(crate) fn is_canonical_encoding(bytes: &[u8], pt: &EdwardsPoint) -> bool {
+    pt.compress().as_bytes()[..] == bytes[..]
+}
+
+/// The outcome of matching a (possibly incomplete) set of observed verdicts against
+/// every known `ValidationPolicy`.
+pub struct Inference {
+    /// Policies whose predictions agree with every observed verdict.
+    pub consistent: Vec<ValidationPolicy>,
+    /// Indices of the unobserved vectors on which the consistent policies disagree,
+    /// the ones splitting the consistent policies most evenly first.
+    pub disambiguating: Vec<usize>,
+}
+
+impl Inference {
+    /// True when observing any further vector could not narrow the consistent policies down.
+    pub fn is_conclusive(&self) -> bool {
+        self.disambiguating.is_empty()
+    }
+}
+
+/// Infers the policies consistent with `observed`, where `observed[i]` is the verdict of
+/// the verifier under test on `vectors[i]`, or `None` when that vector could not be tried
+/// (e.g. an HSM which refuses to import small-order keys). Only whether a vector was
+/// accepted is matched, not the step rejecting it, and `Error` verdicts count as untried.
+pub fn infer_from_partial(
+    vectors: &[TestVector],
+    observed: &[Option<Verdict>],
+) -> Result<Inference> {
+    if vectors.len() != observed.len() {
+        return Err(anyhow!(
+            "got {} observed verdicts for {} test vectors",
+            observed.len(),
+            vectors.len()
+        ));
+    }
+
+    let candidates: Vec<(ValidationPolicy, Vec<bool>)> = predictions(vectors)
+        .into_iter()
+        .filter(|(_, predicted)| {
+            predicted.iter().zip(observed).all(|(p, o)| match o {
+                Some(verdict) if !verdict.is_error() => verdict.is_accepted() == *p,
+                _ => true,
+            })
+        })
+        .collect();
+    let unobserved: Vec<bool> = observed
+        .iter()
+        .map(|o| o.filter(|verdict| !verdict.is_error()).is_none())
+        .collect();
+
+    Ok(Inference {
+        disambiguating: rank_disambiguating(&candidates, &unobserved),
+        consistent: candidates.into_iter().map(|(policy, _)| policy).collect(),
+    })
+}
+
+/// The policies consistent with `results`, whether a black-box library accepted each of the
+/// generated vectors it was tried on, by case id (see `test_vectors::CASE_IDS`), e.g. a
+/// column of the results table: the inverse of `ValidationPolicy::predict_generated`. The
+/// cases left out of `results` count as untried.
+pub fn infer_policy(results: &BTreeMap<String, bool>) -> Result<Vec<ValidationPolicy>> {
+    Ok(infer_by_id(results)?.consistent)
+}
+
+/// The ids of the cases missing from `results` whose verdict would narrow down the policies
+/// of `infer_policy`, the most informative first, or none if they are conclusive.
+pub fn disambiguating_cases(results: &BTreeMap<String, bool>) -> Result<Vec<&'static str>> {
+    Ok(infer_by_id(results)?
+        .disambiguating
+        .into_iter()
+        .map(|i| CASE_IDS[i])
+        .collect())
+}
+
+fn infer_by_id(results: &BTreeMap<String, bool>) -> Result<Inference> {
+    if let Some(id) = results.keys().find(|id| !CASE_IDS.contains(&id.as_str())) {
+        return Err(anyhow!("unknown case id {}", id));
+    }
+    let observed: Vec<Option<Verdict>> = CASE_IDS
+        .iter()
+        .map(|id| {
+            results
+                .get(*id)
+                .map(|accepted| Verdict::from_accepted(*accepted))
+        })
+        .collect();
+    infer_from_partial(&generate_test_vectors(), &observed)
+}
+
+fn predictions(vectors: &[TestVector]) -> Vec<(ValidationPolicy, Vec<bool>)> {
+    ValidationPolicy::all()
+        .into_iter()
+        .map(|policy| (policy, policy.acceptance(vectors)))
+        .collect()
+}
+
+// Ranks the `open` vectors on which the `candidates` disagree by how evenly they split them,
+// so that either answer eliminates as many candidates as possible.
+fn rank_disambiguating(candidates: &[(ValidationPolicy, Vec<bool>)], open: &[bool]) -> Vec<usize> {
+    let mut splits: Vec<(usize, usize)> = (0..open.len())
+        .filter(|&i| open[i])
+        .map(|i| {
+            let accepting = candidates.iter().filter(|(_, p)| p[i]).count();
+            (i, accepting.min(candidates.len() - accepting))
+        })
+        .filter(|&(_, minority)| minority > 0)
+        .collect();
+    splits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    splits.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Classifies a black-box verifier one query at a time, always picking the vector whose
+/// verdict is most informative given the previous answers. This keeps the number of
+/// queries low when they are expensive (hardware tokens, rate-limited services).
+pub struct Fingerprinter<'a> {
+    vectors: &'a [TestVector],
+    candidates: Vec<(ValidationPolicy, Vec<bool>)>,
+    open: Vec<bool>,
+    queries: usize,
+}
+
+impl<'a> Fingerprinter<'a> {
+    pub fn new(vectors: &'a [TestVector]) -> Fingerprinter<'a> {
+        Fingerprinter {
+            vectors,
+            candidates: predictions(vectors),
+            open: vec![true; vectors.len()],
+            queries: 0,
+        }
+    }
+
+    /// The index of the vector to submit next, or `None` once no remaining vector can
+    /// narrow the consistent policies down any further.
+    pub fn next_query(&self) -> Option<usize> {
+        rank_disambiguating(&self.candidates, &self.open)
+            .first()
+            .copied()
+    }
+
+    /// Records the verdict of the verifier under test on `vectors[index]`. An `Error`
+    /// verdict counts as a query, but narrows nothing down.
+    pub fn record(&mut self, index: usize, verdict: Verdict) -> Result<()> {
+        self.close(index)?;
+        self.queries += 1;
+        if !verdict.is_error() {
+            self.candidates
+                .retain(|(_, predicted)| predicted[index] == verdict.is_accepted());
+        }
+        Ok(())
+    }
+
+    /// Records that `vectors[index]` cannot be submitted to the verifier under test.
+    pub fn skip(&mut self, index: usize) -> Result<()> {
+        self.close(index)
+    }
+
+    fn close(&mut self, index: usize) -> Result<()> {
+        match self.open.get_mut(index) {
+            Some(open) if *open => {
+                *open = false;
+                Ok(())
+            }
+            Some(_) => Err(anyhow!("vector {} was already submitted or skipped", index)),
+            None => Err(anyhow!(
+                "no vector {} among {} test vectors",
+                index,
+                self.vectors.len()
+            )),
+        }
+    }
+
+    /// The number of verdicts recorded so far.
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    /// The policies consistent with every verdict recorded so far.
+    pub fn consistent(&self) -> Vec<ValidationPolicy> {
+        self.candidates.iter().map(|(policy, _)| *policy).collect()
+    }
+}
+
+/// Runs a `Fingerprinter` interactively: each chosen vector is written to `output` in the
+/// `cases.txt` layout, and the verdict of the verifier under test is read back from `input`
+/// as `y` (accepted), `p` (rejected while parsing), `n` (rejected while verifying) or `s`
+/// (cannot be submitted).
+pub fn fingerprint_interactive<R: BufRead, W: Write>(
+    vectors: &[TestVector],
+    mut input: R,
+    mut output: W,
+) -> Result<Vec<ValidationPolicy>> {
+    let mut fingerprinter = Fingerprinter::new(vectors);
+
+    while let Some(i) = fingerprinter.next_query() {
+        let tv = &vectors[i];
+        writeln!(output, "vector #{}", i)?;
+        writeln!(output, "msg={}", hex::encode(&tv.message[..]))?;
+        writeln!(output, "pbk={}", hex::encode(&tv.pub_key[..]))?;
+        writeln!(output, "sig={}", hex::encode(&tv.signature[..]))?;
+
+        loop {
+            write!(output, "accepted? [y/p/n/s] ")?;
+            output.flush()?;
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Err(anyhow!("input closed before the verifier was classified"));
+            }
+            match line.trim() {
+                "y" => fingerprinter.record(i, Verdict::Accepted)?,
+                "p" => fingerprinter.record(i, Verdict::ParseRejected)?,
+                "n" => fingerprinter.record(i, Verdict::VerifyRejected)?,
+                "s" => fingerprinter.skip(i)?,
+                _ => continue,
+            }
+            break;
+        }
+    }
+
+    let consistent = fingerprinter.consistent();
+    writeln!(
+        output,
+        "{} queries, {} consistent policies:",
+        fingerprinter.queries(),
+        consistent.len()
+    )?;
+    for policy in consistent.iter() {
+        writeln!(output, "  {}", policy)?;
+    }
+    Ok(consistent)
+}
+
+/// A subset of the test vectors selected by `prune`, along with the coverage lost.
+pub struct PrunedCorpus {
+    /// Indices of the retained vectors, in increasing order.
+    pub selected: Vec<usize>,
+    /// Indices of the dropped vectors, in increasing order.
+    pub dropped: Vec<usize>,
+    /// Policies the full set of vectors tells apart from the target policy, but the retained
+    /// ones no longer do.
+    pub undistinguished: Vec<ValidationPolicy>,
+}
+
+/// Selects at most `max_vectors` of the `vectors` which best tell the target `policy` apart
+/// from every other one, for embedding in size-constrained test suites (e.g. firmware images).
+///
+/// The selection is greedy and deterministic: each step retains the vector telling apart the
+/// most policies not yet told apart, with ties going to the lowest index. It stops early once
+/// no further vector adds coverage.
+pub fn prune(
+    vectors: &[TestVector],
+    policy: &ValidationPolicy,
+    max_vectors: usize,
+) -> PrunedCorpus {
+    let target = policy.acceptance(vectors);
+    let mut remaining: Vec<(ValidationPolicy, Vec<bool>)> = predictions(vectors)
+        .into_iter()
+        .filter(|(_, predicted)| *predicted != target)
+        .collect();
+
+    let mut selected = Vec::new();
+    while selected.len() < max_vectors {
+        let best = (0..vectors.len())
+            .filter(|i| !selected.contains(i))
+            .map(|i| {
+                let covered = remaining.iter().filter(|(_, p)| p[i] != target[i]).count();
+                (i, covered)
+            })
+            .filter(|&(_, covered)| covered > 0)
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
+        match best {
+            Some((i, _)) => {
+                selected.push(i);
+                remaining.retain(|(_, p)| p[i] == target[i]);
+            }
+            None => break,
+        }
+    }
+    selected.sort_unstable();
+
+    PrunedCorpus {
+        dropped: (0..vectors.len())
+            .filter(|i| !selected.contains(i))
+            .collect(),
+        selected,
+        undistinguished: remaining.into_iter().map(|(policy, _)| policy).collect(),
+    }
+}
@@ -0,0 +1,158 @@
+use crate::non_reducing_scalar52::BigScalar;
+use crate::test_vectors::TestVector;
+use crate::torsion::{DECODE_MODE, TORSION_ORDERS};
+use crate::{
+    compute_hram, deserialize_point_with, eight, new_rng, verify_cofactored, verify_cofactorless,
+    verify_pre_reduced_cofactored, EIGHT_TORSION,
+};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A vector telling apart the two orders of operations of cofactored verification:
+/// multiplying the point by 8, as in `[8](R - [s]B + [k]A) = 0`, or multiplying the scalars
+/// by 8 modulo L, as in `[8]R = [8s mod L]B - [8k mod L]A`. Both agree whenever A is of
+/// prime order, hence A is given a small-order component of each possible order.
+///
+/// Half of the vectors pass either way, so that a verifier rejecting all of them is seen to
+/// reject A rather than to pre-reduce. The cofactorless verdict and the S class vary
+/// independently, so that neither can stand in for the order of operations.
+#[derive(Serialize)]
+pub struct PreReductionVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// The order of the small-order component of A: 2, 4 or 8.
+    pub torsion_order: usize,
+    /// Whether S is given as S + L rather than reduced.
+    pub unreduced_s: bool,
+    pub passes_cofactored: bool,
+    pub passes_pre_reduced: bool,
+    pub passes_cofactorless: bool,
+}
+
+fn pre_reduction_vector(
+    rng: &mut impl RngCore,
+    (torsion_order, torsion_idx): (usize, usize),
+    passes_pre_reduced: bool,
+    passes_cofactorless: bool,
+    unreduced_s: bool,
+) -> PreReductionVector {
+    let small_pt = deserialize_point_with(&EIGHT_TORSION[torsion_idx], DECODE_MODE).unwrap();
+
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT + small_pt;
+
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+
+    // [8k mod L]T cancels iff the pre-reduced equation holds, [k]T iff the cofactorless one
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    loop {
+        let k = compute_hram(&message, &pub_key, &r);
+        if (eight() * k * small_pt).is_identity() == passes_pre_reduced
+            && (k * small_pt).is_identity() == passes_cofactorless
+        {
+            break;
+        }
+        rng.fill_bytes(&mut message);
+    }
+
+    let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+    let s_bytes = if unreduced_s {
+        (BigScalar::from_scalar(&s) + BigScalar::L).to_bytes()
+    } else {
+        s.to_bytes()
+    };
+    let s_prime = Scalar::from_bits(s_bytes);
+
+    debug_assert!(verify_cofactored(&message, &pub_key, &(r, s_prime)).is_ok());
+    debug_assert_eq!(
+        verify_pre_reduced_cofactored(&message, &pub_key, &(r, s_prime)).is_ok(),
+        passes_pre_reduced
+    );
+    debug_assert_eq!(
+        verify_cofactorless(&message, &pub_key, &(r, s_prime)).is_ok(),
+        passes_cofactorless
+    );
+
+    let mut signature = r.compress().as_bytes().to_vec();
+    signature.extend_from_slice(&s_bytes);
+    PreReductionVector {
+        comment: format!(
+            "A with a component of order {}, {}, {} pre-reduced, {} cofactorless",
+            torsion_order,
+            if unreduced_s { "S + L" } else { "S < L" },
+            passes_or_fails(passes_pre_reduced),
+            passes_or_fails(passes_cofactorless),
+        ),
+        vector: TestVector {
+            message: message.to_vec(),
+            pub_key: pub_key.compress().to_bytes(),
+            signature,
+            metadata: None,
+            trace: None,
+        },
+        torsion_order,
+        unreduced_s,
+        passes_cofactored: true,
+        passes_pre_reduced,
+        passes_cofactorless,
+    }
+}
+
+fn passes_or_fails(passes: bool) -> &'static str {
+    if passes {
+        "passes"
+    } else {
+        "fails"
+    }
+}
+
+/// For every order of the small-order component of A, every combination of pre-reduced and
+/// cofactorless verdicts, with S reduced then not.
+pub fn pre_reduction_vectors(rng: &mut impl RngCore) -> Vec<PreReductionVector> {
+    let mut vectors = Vec::new();
+    for torsion in TORSION_ORDERS.iter() {
+        for &passes_pre_reduced in [false, true].iter() {
+            for &passes_cofactorless in [false, true].iter() {
+                for &unreduced_s in [false, true].iter() {
+                    vectors.push(pre_reduction_vector(
+                        rng,
+                        *torsion,
+                        passes_pre_reduced,
+                        passes_cofactorless,
+                        unreduced_s,
+                    ));
+                }
+            }
+        }
+    }
+    vectors
+}
+
+/// Tells whether a cofactored verifier pre-reduces (8h), from its verdicts on two vectors
+/// under an A with a component of order 8, both failing cofactorless verification: the
+/// first passes the cofactored equation only, the second the pre-reduced one as well.
+/// `verify` takes the message, the public key and the signature, and returns whether it
+/// accepts them. This gives `Some(true)` for a verifier accepting the second vector only,
+/// `Some(false)` for one accepting the first, which pre-reducing rules out, and `None` for
+/// one rejecting both, e.g. verifying the cofactorless equation or rejecting A of mixed
+/// order, for which the question does not arise.
+pub fn detect_prereduction(mut verify: impl FnMut(&[u8], &[u8], &[u8]) -> bool) -> Option<bool> {
+    let mut rng = new_rng();
+    let mut accepts = |passes_pre_reduced: bool| {
+        let tv = pre_reduction_vector(&mut rng, (8, 1), passes_pre_reduced, false, false).vector;
+        verify(&tv.message, &tv.pub_key, &tv.signature)
+    };
+    match (accepts(false), accepts(true)) {
+        (true, _) => Some(false),
+        (false, true) => Some(true),
+        (false, false) => None,
+    }
+}
@@ -0,0 +1,67 @@
+use crate::policy::ValidationPolicy;
+use crate::test_vectors::ed25519ph::CONTEXT;
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha512};
+use signature::DigestVerifier;
+
+/// A public key verifying Ed25519ph signatures with the checks of a `ValidationPolicy`, e.g.
+/// one of `policy::NAMED_POLICIES`, through RustCrypto's `DigestVerifier<Sha512, _>`: code
+/// verifying prehashed messages through that trait can be run against the reference
+/// policies as well as against a library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrehashVerifier {
+    pub policy: ValidationPolicy,
+    pub pub_key: [u8; 32],
+    context: Vec<u8>,
+}
+
+impl PrehashVerifier {
+    /// The verifier of `pub_key` under the empty context, as that of the Ed25519ph vectors.
+    pub fn new(policy: ValidationPolicy, pub_key: [u8; 32]) -> PrehashVerifier {
+        PrehashVerifier {
+            policy,
+            pub_key,
+            context: CONTEXT.to_vec(),
+        }
+    }
+
+    /// The verifier of `pub_key` under `context`, which fails if longer than 255 bytes.
+    pub fn with_context(
+        policy: ValidationPolicy,
+        pub_key: [u8; 32],
+        context: &[u8],
+    ) -> Result<PrehashVerifier> {
+        if context.len() > 255 {
+            return Err(anyhow!(
+                "the context must be at most 255 bytes, got {}",
+                context.len()
+            ));
+        }
+        Ok(PrehashVerifier {
+            policy,
+            pub_key,
+            context: context.to_vec(),
+        })
+    }
+
+    pub fn context(&self) -> &[u8] {
+        &self.context
+    }
+}
+
+impl DigestVerifier<Sha512, ed25519::Signature> for PrehashVerifier {
+    fn verify_digest(
+        &self,
+        digest: Sha512,
+        signature: &ed25519::Signature,
+    ) -> Result<(), signature::Error> {
+        self.policy
+            .verify_prehashed(
+                &self.context,
+                &digest.finalize(),
+                &self.pub_key,
+                signature.as_ref(),
+            )
+            .map_err(|_| signature::Error::new())
+    }
+}
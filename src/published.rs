@@ -0,0 +1,97 @@
+/// This file records the results published along with [CGN20e] for the libraries the unit
+/// tests verify, so that the current behavior of those libraries can be compared against the
+/// snapshot, and the libraries whose behavior changed since publication reported.
+///
+/// References:
+/// [CGN20e] Taming the many EdDSAs; by Konstantinos Chalkias, François Garillot and Valeria Nikolaenko; https://ia.cr/2020/1244
+use crate::verdict::Verdict;
+use anyhow::{anyhow, Result};
+
+/// The published rows, one "V" (accepted) or "X" (rejected) per vector of
+/// `generate_test_vectors`. The paper does not tell rejections apart.
+pub const PUBLISHED_RESULTS: [(&str, &str); 6] = [
+    ("[CGN20e] Alg.2", "XXVVVVXXXXXX"),
+    ("BoringSSL", "VVVVXXXXXXXV"),
+    ("Dalek", "VVVVXXXXXXXV"),
+    ("Dalek strict", "XXXVXXXXXXXX"),
+    ("libra-crypto", "XXXVXXXXXXXX"),
+    ("Zebra", "VVVVVVXXXVVV"),
+];
+
+/// How the measured verdicts of a library compare to its published row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Drift {
+    pub library: &'static str,
+    pub published: &'static str,
+    /// The measured row, in the symbols of the published one, with "E" for `Verdict::Error`.
+    pub measured: String,
+    /// The vectors on which the library accepts or rejects otherwise than published, or
+    /// errored.
+    pub changed: Vec<usize>,
+}
+
+impl Drift {
+    pub fn has_changed(&self) -> bool {
+        !self.changed.is_empty()
+    }
+}
+
+fn symbol(verdict: Verdict) -> char {
+    match verdict {
+        Verdict::Accepted => 'V',
+        Verdict::Error => 'E',
+        Verdict::ParseRejected | Verdict::VerifyRejected => 'X',
+    }
+}
+
+/// Compares the verdicts of `library` on the vectors of `generate_test_vectors` to its
+/// published row, which fails if it has none or if the verdicts are not one per vector.
+pub fn compare(library: &str, verdicts: &[Verdict]) -> Result<Drift> {
+    let (library, published) = PUBLISHED_RESULTS
+        .iter()
+        .find(|(name, _)| *name == library)
+        .ok_or_else(|| anyhow!("no published results for {}", library))?;
+    if verdicts.len() != published.len() {
+        return Err(anyhow!(
+            "expected {} verdicts, got {}",
+            published.len(),
+            verdicts.len()
+        ));
+    }
+    let measured: String = verdicts.iter().copied().map(symbol).collect();
+    let changed = published
+        .chars()
+        .zip(measured.chars())
+        .enumerate()
+        .filter(|(_, (published, measured))| published != measured)
+        .map(|(index, _)| index)
+        .collect();
+    Ok(Drift {
+        library,
+        published,
+        measured,
+        changed,
+    })
+}
+
+/// A line per library, telling whether its measured verdicts still match the published ones,
+/// e.g. `Dalek: changed on 4, 5 (published VVVVXXXXXXXV, measured VVVVVVXXXXXV)`.
+pub fn drift_report(measured: &[(&str, &[Verdict])]) -> Result<String> {
+    let mut report = String::new();
+    for (library, verdicts) in measured.iter() {
+        let drift = compare(library, verdicts)?;
+        if drift.has_changed() {
+            let changed: Vec<String> = drift.changed.iter().map(usize::to_string).collect();
+            report.push_str(&format!(
+                "{}: changed on {} (published {}, measured {})\n",
+                drift.library,
+                changed.join(", "),
+                drift.published,
+                drift.measured
+            ));
+        } else {
+            report.push_str(&format!("{}: as published\n", drift.library));
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,65 @@
+use crate::matrix::{classify, Verdicts};
+use crate::non_reducing_scalar52::BigScalar;
+use crate::signing::SigningKey;
+use crate::test_vectors::TestVector;
+use rand::RngCore;
+use serde::Serialize;
+use std::convert::TryFrom;
+
+/// The multiples of L the pairs lift S by: S + 3L is below 2^255 for any S < L.
+pub const MULTIPLES_OF_L: [u64; 3] = [1, 2, 3];
+
+/// An honest signature `(R, S)` next to `(R, S + kL)`, of the same message under the same
+/// key. A verifier reducing S modulo L before using it cannot tell them apart: accepting
+/// both, it lets anyone turn a signature into another, which breaks SUF-CMA. Harnesses
+/// check that it accepts at most one of them.
+#[derive(Serialize)]
+pub struct ReducedSPair {
+    pub comment: String,
+    /// k, S being lifted to `S + kL` in `lifted`.
+    pub multiple_of_l: u64,
+    pub original: TestVector,
+    pub lifted: TestVector,
+    pub verdicts_original: Verdicts,
+    pub verdicts_lifted: Verdicts,
+}
+
+impl ReducedSPair {
+    /// The names of the policies accepting both signatures, out of `matrix::KNOWN_POLICIES`:
+    /// those reducing S rather than checking S < L.
+    pub fn accepting_both(&self) -> Vec<String> {
+        self.verdicts_original
+            .iter()
+            .filter(|(name, verdict)| {
+                verdict.is_accepted() && self.verdicts_lifted[*name].is_accepted()
+            })
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+}
+
+/// A pair for each of `MULTIPLES_OF_L`, all lifting the signature of the same random message
+/// under a fresh key.
+pub fn reduced_s_pairs(rng: &mut impl RngCore) -> Vec<ReducedSPair> {
+    let key = SigningKey::generate(rng);
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    let original = key.sign_vector(&message);
+    let s = BigScalar::from_bytes(&<[u8; 32]>::try_from(&original.signature[32..]).unwrap());
+
+    MULTIPLES_OF_L
+        .iter()
+        .map(|&k| {
+            let mut lifted = original.clone();
+            lifted.signature[32..].copy_from_slice(&s.add_multiple_of_l(k).unwrap().to_bytes());
+            ReducedSPair {
+                comment: format!("S and S + {}L", k),
+                multiple_of_l: k,
+                verdicts_original: classify(&original),
+                verdicts_lifted: classify(&lifted),
+                original: original.clone(),
+                lifted,
+            }
+        })
+        .collect()
+}
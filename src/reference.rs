@@ -0,0 +1,291 @@
+// The reference verification: decoding, the hram scalar and the verification equations,
+// along with [CGN20e] Algorithm 2 in `algorithm2`. It builds without std, for the checks to
+// run on devices next to their own Ed25519 implementations, see the `std` feature.
+
+use crate::algorithm2;
+use anyhow::{anyhow, Result};
+use core::ops::Neg;
+use curve25519_dalek::{edwards::EdwardsPoint, scalar::Scalar, traits::IsIdentity};
+use sha2::{Digest, Sha512};
+
+// 8 as a Scalar - to reflect instructions of "interpreting values as
+// integers"
+pub(crate) fn eight() -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[0] |= 8;
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+pub fn check_slice_size<'a>(
+    slice: &'a [u8],
+    expected_len: usize,
+    arg_name: &'static str,
+) -> Result<&'a [u8]> {
+    if slice.len() != expected_len {
+        return Err(anyhow!(
+            "slice length for {} must be {} characters, got {}",
+            arg_name,
+            expected_len,
+            slice.len()
+        ));
+    }
+    Ok(slice)
+}
+
+/// How strictly `deserialize_point_with` decodes points. Generators state the mode they
+/// construct each point with, as vectors built around a non-canonical encoding need the
+/// lenient one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Any 32 bytes decompressing to a point, as `CompressedEdwardsY::decompress` does.
+    Lenient,
+    /// Only canonical encodings, i.e. with y < p and no sign bit set for x = 0, as in
+    /// [CGN20e] Algorithm 2.
+    Strict,
+}
+
+/// Decodes a point leniently, see `deserialize_point_with`.
+pub fn deserialize_point(pt: &[u8]) -> Result<EdwardsPoint> {
+    deserialize_point_with(pt, DecodeMode::Lenient)
+}
+
+pub fn deserialize_point_with(pt: &[u8], mode: DecodeMode) -> Result<EdwardsPoint> {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(check_slice_size(pt, 32, "pt")?);
+
+    if mode == DecodeMode::Strict && !algorithm2::is_canonical_point_encoding(&bytes[..]) {
+        return Err(anyhow!("Non-canonical point encoding!"));
+    }
+
+    curve25519_dalek::edwards::CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or_else(|| anyhow!("Point decompression failed!"))
+}
+
+#[allow(dead_code)]
+pub(crate) fn deserialize_scalar(scalar: &[u8]) -> Result<Scalar> {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(check_slice_size(scalar, 32, "scalar")?);
+
+    // This permissive pass-through can produce large scalars!
+    Ok(curve25519_dalek::scalar::Scalar::from_bits(bytes))
+}
+
+pub(crate) fn deserialize_signature(sig_bytes: &[u8]) -> Result<(EdwardsPoint, Scalar)> {
+    let checked_sig_bytes = check_slice_size(sig_bytes, 64, "sig_bytes")?;
+    let r = deserialize_point(&checked_sig_bytes[..32])?;
+    let s = deserialize_scalar(&checked_sig_bytes[32..])?;
+    Ok((r, s))
+}
+
+/// SHA-512 having absorbed R || A, from which the hram scalar of any message follows by
+/// hashing the message alone: loops grinding the message under a fixed R and A encode and
+/// hash those once, rather than on each attempt.
+#[derive(Clone)]
+pub struct HramPrefix(Sha512);
+
+impl HramPrefix {
+    /// The prefix of R and A re-serialized, as `compute_hram` hashes them.
+    pub fn new(signature_r: &EdwardsPoint, pub_key: &EdwardsPoint) -> HramPrefix {
+        HramPrefix::from_arrays(
+            signature_r.compress().as_bytes(),
+            pub_key.compress().as_bytes(),
+        )
+    }
+
+    /// The prefix of R and A as encoded.
+    pub fn from_arrays(signature_r: &[u8], pub_key: &[u8]) -> HramPrefix {
+        HramPrefix(Sha512::default().chain(signature_r).chain(pub_key))
+    }
+
+    /// `SHA-512(R || A || message)` reduced modulo L.
+    pub fn finish(&self, message: &[u8]) -> Scalar {
+        let k_bytes = self.0.clone().chain(message);
+        // curve25519_dalek is stuck on an old digest version, so we can't do
+        // Scalar::from_hash
+        let mut k_output = [0u8; 64];
+        k_output.copy_from_slice(&k_bytes.finalize()[..]);
+        Scalar::from_bytes_mod_order_wide(&k_output)
+    }
+}
+
+pub fn compute_hram(message: &[u8], pub_key: &EdwardsPoint, signature_r: &EdwardsPoint) -> Scalar {
+    HramPrefix::new(signature_r, pub_key).finish(message)
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn compute_hram_with_r_array(
+    message: &[u8],
+    pub_key: &EdwardsPoint,
+    signature_r: &[u8],
+) -> Scalar {
+    HramPrefix::from_arrays(signature_r, pub_key.compress().as_bytes()).finish(message)
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn compute_hram_with_arrays(
+    message: &[u8],
+    pub_key_arr: &[u8],
+    signature_r: &[u8],
+) -> Scalar {
+    HramPrefix::from_arrays(signature_r, pub_key_arr).finish(message)
+}
+
+/// Cofactored verification multiplying the point by 8: `[8](R - [s]B + [k]A) = 0`.
+pub fn verify_cofactored(
+    message: &[u8],
+    pub_key: &EdwardsPoint,
+    unpacked_signature: &(EdwardsPoint, Scalar),
+) -> Result<()> {
+    let k = compute_hram(message, pub_key, &unpacked_signature.0);
+    verify_final_cofactored(pub_key, unpacked_signature, &k)
+}
+
+pub fn verify_cofactorless(
+    message: &[u8],
+    pub_key: &EdwardsPoint,
+    unpacked_signature: &(EdwardsPoint, Scalar),
+) -> Result<()> {
+    let k = compute_hram(message, pub_key, &unpacked_signature.0);
+    verify_final_cofactorless(pub_key, unpacked_signature, &k)
+}
+
+/// Cofactored verification multiplying the scalars by 8 modulo L before the points:
+/// `[8]R = [8s mod L]B - [8k mod L]A`. It disagrees with `verify_cofactored` when A has a
+/// small-order component T and `[8k mod L]T` is not the identity.
+pub fn verify_pre_reduced_cofactored(
+    message: &[u8],
+    pub_key: &EdwardsPoint,
+    unpacked_signature: &(EdwardsPoint, Scalar),
+) -> Result<()> {
+    let k = compute_hram(message, pub_key, &unpacked_signature.0);
+    verify_final_pre_reduced_cofactored(pub_key, unpacked_signature, &k)
+}
+
+/// The equation a `StreamingVerifier` checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Equation {
+    /// That of `verify_cofactored`.
+    Cofactored,
+    /// That of `verify_cofactorless`.
+    Cofactorless,
+    /// That of `verify_pre_reduced_cofactored`.
+    PreReducedCofactored,
+}
+
+/// A verification fed the message in chunks, as libraries verifying messages too large to
+/// hold in memory do: R and A are hashed, re-serialized as in `compute_hram`, when it is
+/// created, then each chunk as it comes, and the equation is checked once the message ends.
+/// However the message is split, it reaches the verdict of the function of its `Equation`
+/// on the whole message.
+#[derive(Clone)]
+pub struct StreamingVerifier {
+    equation: Equation,
+    pub_key: EdwardsPoint,
+    unpacked_signature: (EdwardsPoint, Scalar),
+    hram: HramPrefix,
+}
+
+impl StreamingVerifier {
+    pub fn new(
+        equation: Equation,
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> StreamingVerifier {
+        StreamingVerifier {
+            equation,
+            pub_key: *pub_key,
+            unpacked_signature: *unpacked_signature,
+            hram: HramPrefix::new(&unpacked_signature.0, pub_key),
+        }
+    }
+
+    /// The verification of the serialized `signature` under the serialized `pub_key`,
+    /// decoded as the verifiers of the results tables do: A and R leniently, S unreduced.
+    pub fn from_serialized(
+        equation: Equation,
+        pub_key: &[u8],
+        signature: &[u8],
+    ) -> Result<StreamingVerifier> {
+        Ok(StreamingVerifier::new(
+            equation,
+            &deserialize_point(pub_key)?,
+            &deserialize_signature(signature)?,
+        ))
+    }
+
+    /// Hashes the next `chunk` of the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hram.0.update(chunk);
+    }
+
+    /// Checks the equation, once every chunk of the message has been fed.
+    pub fn finalize_verify(self) -> Result<()> {
+        let k = self.hram.finish(&[]);
+        match self.equation {
+            Equation::Cofactored => {
+                verify_final_cofactored(&self.pub_key, &self.unpacked_signature, &k)
+            }
+            Equation::Cofactorless => {
+                verify_final_cofactorless(&self.pub_key, &self.unpacked_signature, &k)
+            }
+            Equation::PreReducedCofactored => {
+                verify_final_pre_reduced_cofactored(&self.pub_key, &self.unpacked_signature, &k)
+            }
+        }
+    }
+}
+
+pub(crate) fn verify_final_cofactored(
+    pub_key: &EdwardsPoint,
+    unpacked_signature: &(EdwardsPoint, Scalar),
+    hash: &Scalar,
+) -> Result<()> {
+    let rprime = EdwardsPoint::vartime_double_scalar_mul_basepoint(
+        hash,
+        &pub_key.neg(),
+        &unpacked_signature.1,
+    );
+    if (unpacked_signature.0 - rprime)
+        .mul_by_cofactor()
+        .is_identity()
+    {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid cofactored signature"))
+    }
+}
+
+pub(crate) fn verify_final_pre_reduced_cofactored(
+    pub_key: &EdwardsPoint,
+    unpacked_signature: &(EdwardsPoint, Scalar),
+    hash: &Scalar,
+) -> Result<()> {
+    let eight_hash = eight() * hash;
+    let eight_s = eight() * unpacked_signature.1;
+
+    let rprime =
+        EdwardsPoint::vartime_double_scalar_mul_basepoint(&eight_hash, &pub_key.neg(), &eight_s);
+    if (unpacked_signature.0.mul_by_cofactor() - rprime).is_identity() {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid pre-reduced cofactored signature"))
+    }
+}
+
+pub(crate) fn verify_final_cofactorless(
+    pub_key: &EdwardsPoint,
+    unpacked_signature: &(EdwardsPoint, Scalar),
+    hash: &Scalar,
+) -> Result<()> {
+    let rprime = EdwardsPoint::vartime_double_scalar_mul_basepoint(
+        hash,
+        &pub_key.neg(),
+        &unpacked_signature.1,
+    );
+    if (unpacked_signature.0 - rprime).is_identity() {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid cofactorless signature"))
+    }
+}
@@ -0,0 +1,130 @@
+use crate::report::ResultsTable;
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::verifier::{table_with_health, Verifier};
+use std::panic::{self, AssertUnwindSafe};
+
+/// A library under test, as a row of the results table: its name, and its verdict on a
+/// vector. Adapters decoding the key and the signature separately from verifying tell
+/// rejections apart, as `Verdict::from_results` does.
+pub trait LibraryAdapter {
+    fn name(&self) -> &str;
+
+    fn verdict(&self, tv: &TestVector) -> Verdict;
+}
+
+/// The adapter of a library given by its name and a function reaching its verdicts.
+pub struct Adapter<F> {
+    name: String,
+    verdict: F,
+}
+
+impl<F: Fn(&TestVector) -> Verdict> Adapter<F> {
+    pub fn new(name: &str, verdict: F) -> Adapter<F> {
+        Adapter {
+            name: name.to_string(),
+            verdict,
+        }
+    }
+}
+
+impl<F: Fn(&TestVector) -> Verdict> LibraryAdapter for Adapter<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn verdict(&self, tv: &TestVector) -> Verdict {
+        (self.verdict)(tv)
+    }
+}
+
+// A registered library, by name, or the reason its integration could not be set up
+type Entry = (String, Result<Box<dyn LibraryAdapter>, String>);
+
+/// The libraries compared in the results table, in their order of registration, each run
+/// through the same harness: a panic on a vector gets an `Error` verdict on it, and a
+/// library whose integration failed to be set up an `Error` verdict on every vector.
+#[derive(Default)]
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn register(&mut self, adapter: impl LibraryAdapter + 'static) -> &mut Registry {
+        self.entries
+            .push((adapter.name().to_string(), Ok(Box::new(adapter))));
+        self
+    }
+
+    /// Registers a `Verifier`, e.g. one of `upstream::crate_verifiers` or a closure, under
+    /// `name`.
+    pub fn register_verifier(
+        &mut self,
+        name: &str,
+        verifier: impl Verifier + 'static,
+    ) -> &mut Registry {
+        self.register(Adapter::new(name, move |tv: &TestVector| {
+            verifier.verdict(&tv.message, &tv.pub_key, &tv.signature)
+        }))
+    }
+
+    /// Registers a library whose integration could not be set up, e.g. behind a disabled
+    /// feature, for the table to report it rather than leave it out.
+    pub fn register_unavailable(&mut self, name: &str, error: anyhow::Error) -> &mut Registry {
+        self.entries
+            .push((name.to_string(), Err(error.to_string())));
+        self
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// The verdict of every registered library on `tv`, in their order of registration.
+    pub fn verdicts(&self, tv: &TestVector) -> Vec<(&str, Verdict)> {
+        self.entries
+            .iter()
+            .map(|(name, adapter)| {
+                let verdict = match adapter {
+                    Ok(adapter) => panic::catch_unwind(AssertUnwindSafe(|| adapter.verdict(tv)))
+                        .unwrap_or(Verdict::Error),
+                    Err(_) => Verdict::Error,
+                };
+                (name.as_str(), verdict)
+            })
+            .collect()
+    }
+
+    /// The verdicts of every registered library on the `vectors`.
+    pub fn results(&self, vectors: &[TestVector]) -> ResultsTable {
+        let mut table = ResultsTable::new();
+        let rows: Vec<Vec<(&str, Verdict)>> = vectors.iter().map(|tv| self.verdicts(tv)).collect();
+        for (i, name) in self.names().into_iter().enumerate() {
+            table.push(name, rows.iter().map(|row| row[i].1).collect());
+        }
+        table
+    }
+
+    /// As `results`, laid out as `verifier::integrations_table`: the table is followed by
+    /// the health of each library which was unavailable or panicked.
+    pub fn table(&self, vectors: &[TestVector]) -> String {
+        let results = self.results(vectors);
+        let rows: Vec<(&str, &[Verdict])> = results
+            .rows()
+            .iter()
+            .map(|(name, verdicts)| (name.as_str(), &verdicts[..]))
+            .collect();
+        let failures: Vec<(&str, String)> = self
+            .entries
+            .iter()
+            .filter_map(|(name, adapter)| {
+                adapter.as_ref().err().map(|e| (name.as_str(), e.clone()))
+            })
+            .collect();
+        table_with_health(&rows, &failures)
+    }
+}
@@ -0,0 +1,155 @@
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::verifier::{verdicts, verdicts_table, Verifier};
+use std::fmt;
+
+/// The verdicts of named libraries on the vectors, a row per library and a column per
+/// vector, rendered as the results table of the README (`Display`), or as Markdown, HTML and
+/// CSV for CI artifacts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResultsTable {
+    rows: Vec<(String, Vec<Verdict>)>,
+}
+
+impl ResultsTable {
+    pub fn new() -> ResultsTable {
+        ResultsTable::default()
+    }
+
+    /// The table of the verdicts of each of the named `verifiers` on the `vectors`, e.g. of
+    /// `upstream::crate_verifiers`.
+    pub fn from_verifiers(
+        verifiers: &[(&str, &dyn Verifier)],
+        vectors: &[TestVector],
+    ) -> ResultsTable {
+        let mut table = ResultsTable::new();
+        for (name, verifier) in verifiers.iter() {
+            table.push(name, verdicts(*verifier, vectors));
+        }
+        table
+    }
+
+    /// Appends the row of `library`.
+    pub fn push(&mut self, library: &str, verdicts: Vec<Verdict>) {
+        self.rows.push((library.to_string(), verdicts));
+    }
+
+    pub fn rows(&self) -> &[(String, Vec<Verdict>)] {
+        &self.rows
+    }
+
+    /// The same table with rejections no longer told apart, as in the README, where only
+    /// "V" and "X" appear.
+    pub fn collapse_rejections(&self) -> ResultsTable {
+        let rows = self
+            .rows
+            .iter()
+            .map(|(name, verdicts)| {
+                let collapsed = verdicts
+                    .iter()
+                    .map(|verdict| match verdict {
+                        Verdict::ParseRejected => Verdict::VerifyRejected,
+                        verdict => *verdict,
+                    })
+                    .collect();
+                (name.clone(), collapsed)
+            })
+            .collect();
+        ResultsTable { rows }
+    }
+
+    fn columns(&self) -> usize {
+        self.rows.iter().map(|(_, v)| v.len()).max().unwrap_or(0)
+    }
+
+    /// The table in Markdown, with the layout of the README table.
+    pub fn to_markdown(&self) -> String {
+        let columns = self.columns();
+        let mut markdown = format!("|{:<15}|", "Library");
+        for i in 0..columns {
+            markdown += &format!(" {:<2}|", i);
+        }
+        markdown += &format!("\n|{}|", "-".repeat(15));
+        markdown += &"---|".repeat(columns);
+        markdown += "\n";
+        for (name, verdicts) in self.rows.iter() {
+            markdown += &format!("|{:<15}|", name.replace('|', "\\|"));
+            for verdict in verdicts.iter() {
+                markdown += &format!(" {} |", verdict);
+            }
+            markdown += "\n";
+        }
+        markdown
+    }
+
+    /// The table in HTML, each verdict cell with the class of its verdict (`accepted`,
+    /// `parse_rejected`, `verify_rejected` or `error`) for style sheets to color.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table>\n<thead>\n<tr><th>Library</th>");
+        for i in 0..self.columns() {
+            html += &format!("<th>{}</th>", i);
+        }
+        html += "</tr>\n</thead>\n<tbody>\n";
+        for (name, verdicts) in self.rows.iter() {
+            html += &format!("<tr><th>{}</th>", escape_html(name));
+            for verdict in verdicts.iter() {
+                html += &format!("<td class=\"{}\">{}</td>", class(*verdict), verdict);
+            }
+            html += "</tr>\n";
+        }
+        html += "</tbody>\n</table>\n";
+        html
+    }
+
+    /// The table in CSV, with a header row and the library in the first column.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("library");
+        for i in 0..self.columns() {
+            csv += &format!(",{}", i);
+        }
+        csv += "\n";
+        for (name, verdicts) in self.rows.iter() {
+            csv += &escape_csv(name);
+            for verdict in verdicts.iter() {
+                csv += &format!(",{}", verdict);
+            }
+            csv += "\n";
+        }
+        csv
+    }
+}
+
+impl fmt::Display for ResultsTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<(&str, &[Verdict])> = self
+            .rows
+            .iter()
+            .map(|(name, verdicts)| (name.as_str(), &verdicts[..]))
+            .collect();
+        write!(f, "{}", verdicts_table(&rows))
+    }
+}
+
+fn class(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Accepted => "accepted",
+        Verdict::ParseRejected => "parse_rejected",
+        Verdict::VerifyRejected => "verify_rejected",
+        Verdict::Error => "error",
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// Quotes a field holding a separator, a quote or a line break, doubling its quotes
+fn escape_csv(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,256 @@
+use crate::corpus_version::CorpusVersion;
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+
+/// The SHA-256 digests of the files of each corpus version, as released. Generation only
+/// depends on the seeded RNG and on exact arithmetic, hence these hold on any platform: a
+/// mismatch means the files at hand were not produced by this code, or that a change of the
+/// generators altered a frozen version.
+const EXPECTED_DIGESTS: &[(CorpusVersion, &[(&str, &str)])] = &[
+    (
+        CorpusVersion::V1,
+        &[
+            (
+                "cases.json",
+                "a1cba7a0af86b77dea1e8058df8e5aead33cd5d95f21632fdc4583169612c4de",
+            ),
+            (
+                "cases.txt",
+                "db14ccd6953ffa79c81586da8f4ab41dd893f7efd07af97d027a27c179b3ab6b",
+            ),
+            (
+                "corpus.json",
+                "5172e8be19d6e9a6d02b07a7845d98a4fa1ac3271cca47a5807852c5b152a39a",
+            ),
+        ],
+    ),
+    (
+        CorpusVersion::V2,
+        &[
+            (
+                "cases.json",
+                "7073c9b5c05b38a4574d7cb1737f583ddd8605be881e1a8e14cf4c0d0a0b5360",
+            ),
+            (
+                "cases.txt",
+                "0866f55bc7aef0e6f969fe3a356c14ee9390ab94e82aae32c874fa453abbf37a",
+            ),
+            (
+                "cases_ph.json",
+                "40873580e0dcec5141bcc9775a8a4d47ab1abef2a19478dc0566633f0be741b6",
+            ),
+            (
+                "cases_ctx.json",
+                "f072985982e1a7f8dc914eba4bdbe7881bbd1179baff010a6bf8bf0b8c74f813",
+            ),
+            (
+                "batches.json",
+                "33f32e2b0489e5fea8fd04fe54897b37d202e453d15a29c67d5849af306be1e3",
+            ),
+            (
+                "batch_equations.json",
+                "96cf15197f5b80c9ebc5d9b5c70f7c37a40651fe21e94288a0a3863f86cb10de",
+            ),
+            (
+                "hram_pairs.json",
+                "acadfaa27a7488f25f64e4b6cfc1d7ec486e7e43574f2f43ed8e40845f53bd27",
+            ),
+            (
+                "pre_reduction.json",
+                "4a4782e0ff9fe5f7e48a40eee1604e9a5ff64207d603ed35b2ca9e36532c0206",
+            ),
+            (
+                "tiny_scalars.json",
+                "3090cd61eae0d19a1e268299bf4900921f57de19233f4813d5daf1194dca3515",
+            ),
+            (
+                "full_order.json",
+                "8d57b37848387075a4263d9875af297d56515f19796d5a8d9890d2a0650f9d69",
+            ),
+            (
+                "torsion_clearing.json",
+                "3c9c78ab6c8a5894826439c73fabaddb6cd5682a5d2d115a7fd2c92aee08f556",
+            ),
+            (
+                "cases_wycheproof.json",
+                "7f562b869067489bf5c3fa39dc7a005fd50c5d7c9f53419af7c427895a43b443",
+            ),
+            (
+                "non_canonical.json",
+                "87aa0e00655bbfa46da6df47fca4675203463f04f35c3e30d76828a3f6116940",
+            ),
+            (
+                "s_high_bits.json",
+                "79668786d71861c63b0852bf740209ad7c06bbfedb9aa3542852416e98a21053",
+            ),
+            (
+                "large_s.json",
+                "5d53b1ce60454e0500b4fbdda618b8f405338309d490bc3d8f18a6be2ee4f353",
+            ),
+            (
+                "identity_r.json",
+                "fd1a21ef74bd7d5bed2696383c90f027016da65a18b85779c751cbba820578cc",
+            ),
+            (
+                "identity_pubkey.json",
+                "784b71e551078844fd4c37999c93c9c9782402193a39908a07940e0fae8fb3c5",
+            ),
+            (
+                "one_sided_non_canonical.json",
+                "2a220a12231dc490029b643e2f344b06f260bdffbded9943dd528834b8551c5a",
+            ),
+            (
+                "non_canonical_encodings.json",
+                "3f3d3881c23ab374d6ceae2f3875a9cfe4942aeed903a49fed666e99640cf84f",
+            ),
+            (
+                "torsion_sweep.json",
+                "1703a9627575325f767c94f43f34e63d0c6b187e16f37bf1a8a512556bc254a2",
+            ),
+            (
+                "key_substitution.json",
+                "ad82ee538085c7e422863ce95b8bf3b9c34c8051f59e4dc5d4d4251fb55adf96",
+            ),
+            (
+                "repudiation.json",
+                "03f52729b95cd9f8c003db82f59880c33a3969273d787181a87f28f1e9029247",
+            ),
+            (
+                "batch_inconsistency.json",
+                "d5f261774d55ff22dc3a1021daa06f765a707a7b7c14c2ae63fc2a2a3a684100",
+            ),
+            (
+                "controls.json",
+                "e99edfaf3e61a2336bd1c36e9bdecf34e369d73b957b0e8c1ddcd1917f6ec9ae",
+            ),
+            (
+                "negative_controls.json",
+                "b17cce70d6c8dc4358d3650b03d03ff353643b438e461776775e1572057b9d10",
+            ),
+            (
+                "message_lengths.json",
+                "bfd8668804466938041a65f127322e4248d401f8057ea2549664650004a2c0e1",
+            ),
+            (
+                "message_lengths.bin",
+                "c6bc6850888f0d9d831f41fad940be69e3d006a0dfe18a023bbb89c4450384ba",
+            ),
+            (
+                "normalization.json",
+                "25d615601f4a46176b109319f1aba0df8f918b591a6d9d210222777d3f129498",
+            ),
+            (
+                "s_boundary.json",
+                "d7b7357b2a3b44f82f19a5697a2d9d998e0f87d4be40664d556ab16d92453bab",
+            ),
+            (
+                "reduced_s.json",
+                "437330b3ada90c063199621597b5136860145845d4cccc5cd00f67f6a66d0b7a",
+            ),
+            (
+                "malleability.json",
+                "2ec2d79b1aa7de2fbb749108dc9656c0ab320a261595aa6f7fb8c4e99b7daa40",
+            ),
+            (
+                "corpus.json",
+                "31cd32e606ff82bc78408f1ceb3e8c9c3354cd1895cc6183a07a5b2e4de1b04d",
+            ),
+        ],
+    ),
+];
+
+/// A generated file whose digest differs from the expected one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub file: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// The hex-encoded SHA-256 digest of `contents`.
+pub fn digest(contents: &[u8]) -> String {
+    hex::encode(Sha256::digest(contents))
+}
+
+/// The digests of the files of `version` generated on this platform, by file name.
+pub fn digests(version: CorpusVersion) -> Result<Vec<(&'static str, String)>> {
+    Ok(version
+        .artifacts()?
+        .into_iter()
+        .map(|(name, contents)| (name, digest(&contents)))
+        .collect())
+}
+
+/// The expected digests of the files of `version`, if known.
+pub fn expected_digests(version: CorpusVersion) -> Option<&'static [(&'static str, &'static str)]> {
+    EXPECTED_DIGESTS
+        .iter()
+        .find(|(v, _)| *v == version)
+        .map(|(_, digests)| *digests)
+}
+
+/// Generates the files of `version` on this platform and compares their digests against
+/// those it was released with, returning the files which differ.
+pub fn check(version: CorpusVersion) -> Result<Vec<Mismatch>> {
+    compare(version, &digests(version)?)
+}
+
+fn compare(version: CorpusVersion, actual: &[(&'static str, String)]) -> Result<Vec<Mismatch>> {
+    let expected =
+        expected_digests(version).ok_or_else(|| anyhow!("no expected digests for {}", version))?;
+    // files generated without an expected digest, or expected but no longer generated,
+    // mismatch as well
+    let mut mismatches: Vec<Mismatch> = actual
+        .iter()
+        .map(|(file, actual_digest)| Mismatch {
+            file,
+            expected: lookup(expected, file),
+            actual: actual_digest.clone(),
+        })
+        .collect();
+    mismatches.extend(
+        expected
+            .iter()
+            .filter(|(file, _)| actual.iter().all(|(name, _)| name != file))
+            .map(|(file, expected_digest)| Mismatch {
+                file,
+                expected: expected_digest.to_string(),
+                actual: String::new(),
+            }),
+    );
+    mismatches.retain(|m| m.expected != m.actual);
+    Ok(mismatches)
+}
+
+fn lookup(digests: &[(&str, &str)], file: &str) -> String {
+    digests
+        .iter()
+        .find(|(name, _)| *name == file)
+        .map(|(_, digest)| digest.to_string())
+        .unwrap_or_default()
+}
+
+/// Runs `check`, writing a line per generated file to `output`, and fails on any mismatch.
+pub fn report<W: Write>(version: CorpusVersion, output: &mut W) -> Result<()> {
+    let actual = digests(version)?;
+    let mismatches = compare(version, &actual)?;
+    let width = actual.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, actual) in actual.iter() {
+        let status = if mismatches.iter().any(|m| m.file == *name) {
+            "MISMATCH"
+        } else {
+            "ok"
+        };
+        writeln!(output, "{:<w$} {} {}", name, actual, status, w = width)?;
+    }
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} of the generated files differ from those of {}",
+            mismatches.len(),
+            version
+        ))
+    }
+}
@@ -0,0 +1,89 @@
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::{
+    compute_hram, deserialize_point_with, new_rng, serialize_signature, DecodeMode, EIGHT_TORSION,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// A single signature valid for two messages under a public key of small order, which lets
+/// the signer repudiate either message: the verifier cannot tell which one was signed.
+pub struct RepudiationVector {
+    pub pub_key: [u8; 32],
+    pub signature: Vec<u8>,
+    pub message1: Vec<u8>,
+    pub message2: Vec<u8>,
+}
+
+// Hex-encoded, as `TestVector`
+impl Serialize for RepudiationVector {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RepudiationVector", 4)?;
+        state.serialize_field("pub_key", &hex::encode(self.pub_key))?;
+        state.serialize_field("signature", &hex::encode(&self.signature))?;
+        state.serialize_field("message1", &hex::encode(&self.message1))?;
+        state.serialize_field("message2", &hex::encode(&self.message2))?;
+        state.end()
+    }
+}
+
+impl RepudiationVector {
+    /// The signature under each message, `message1` then `message2`, to run verifiers on.
+    pub fn vectors(&self) -> [TestVector; 2] {
+        let vector = |message: &[u8]| TestVector {
+            message: message.to_vec(),
+            pub_key: self.pub_key,
+            signature: self.signature.clone(),
+            metadata: None,
+            trace: None,
+        };
+        [vector(&self.message1), vector(&self.message2)]
+    }
+}
+
+// The index in `EIGHT_TORSION` of the point of order 2, (0, -1)
+const ORDER_TWO: usize = 4;
+
+/// Two payment orders under the point of order 2 as public key, with `R = [s]B - A` and
+/// `S = s`, s drawn until k is odd for both messages, so that `[k]A = A` and both equations
+/// hold for each.
+pub fn repudiation() -> RepudiationVector {
+    let mut rng = new_rng();
+    let pub_key = deserialize_point_with(&EIGHT_TORSION[ORDER_TWO], DecodeMode::Strict).unwrap();
+    let message1: &[u8] = b"Send 100 USD to Alice";
+    let message2: &[u8] = b"Send 100000 USD to Alice";
+    loop {
+        let mut scalar_bytes = [0u8; 32];
+        rng.fill_bytes(&mut scalar_bytes);
+        let s = Scalar::from_bytes_mod_order(scalar_bytes);
+        let r = s * ED25519_BASEPOINT_POINT - pub_key;
+        let holds = |message: &[u8]| {
+            (compute_hram(message, &pub_key, &r) * pub_key - pub_key).is_identity()
+        };
+        if s != Scalar::zero() && holds(message1) && holds(message2) {
+            return RepudiationVector {
+                pub_key: EIGHT_TORSION[ORDER_TWO],
+                signature: serialize_signature(&r, &s),
+                message1: message1.to_vec(),
+                message2: message2.to_vec(),
+            };
+        }
+    }
+}
+
+/// Whether a verifier accepts the signature under both messages, given its `verdicts` on
+/// `RepudiationVector::vectors`: such a verifier lets signers repudiate their signatures.
+pub fn accepts_both(verdicts: &[Verdict]) -> Result<bool> {
+    match verdicts {
+        [first, second] => Ok(first.is_accepted() && second.is_accepted()),
+        _ => Err(anyhow!("expected 2 verdicts, got {}", verdicts.len())),
+    }
+}
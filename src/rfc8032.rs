@@ -0,0 +1,44 @@
+/// This file implements the verification of RFC 8032, section 5.1.7, to the letter: A and R
+/// are decoded as in section 5.1.3 and S must be canonical, then the cofactorless equation
+/// is checked, which the RFC allows in place of the cofactored one.
+///
+/// References:
+/// [RFC8032] Edwards-Curve Digital Signature Algorithm (EdDSA); by Simon Josefsson and Ilari Liusvaara; https://www.rfc-editor.org/rfc/rfc8032
+use crate::algorithm2::deserialize_s;
+use crate::verdict::Verdict;
+use crate::{
+    check_slice_size, compute_hram_with_arrays, deserialize_point_with, verify_final_cofactorless,
+    DecodeMode,
+};
+use anyhow::Result;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+
+/// Decodes a point as in section 5.1.3, rejecting y >= p, and x = 0 with its sign bit set.
+pub fn deserialize_point(pt: &[u8]) -> Result<EdwardsPoint> {
+    deserialize_point_with(pt, DecodeMode::Strict)
+}
+
+#[allow(non_snake_case)]
+fn decode(pk_bytes: &[u8], sig_bytes: &[u8]) -> Result<(EdwardsPoint, EdwardsPoint, Scalar)> {
+    let checked_sig_bytes = check_slice_size(sig_bytes, 64, "sig_bytes")?;
+    let A = deserialize_point(pk_bytes)?;
+    let R = deserialize_point(&checked_sig_bytes[..32])?;
+    let s = deserialize_s(&checked_sig_bytes[32..])?;
+    Ok((A, R, s))
+}
+
+/// Deserializes and verifies a signature as in section 5.1.7: `[S]B = R + [k]A`, with k the
+/// hash of the encodings of R and A, which decoding ensured are canonical.
+pub fn verify(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> bool {
+    verdict(msg_bytes, pk_bytes, sig_bytes).is_accepted()
+}
+
+/// As `verify`, telling apart the step rejecting the signature.
+#[allow(non_snake_case)]
+pub fn verdict(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> Verdict {
+    Verdict::from_results(decode(pk_bytes, sig_bytes), |(A, R, s)| {
+        let k = compute_hram_with_arrays(msg_bytes, pk_bytes, &sig_bytes[..32]);
+        verify_final_cofactorless(&A, &(R, s), &k)
+    })
+}
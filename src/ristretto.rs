@@ -0,0 +1,297 @@
+use crate::check_slice_size;
+use crate::non_reducing_scalar52::BigScalar;
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::verifier::Verifier;
+use anyhow::{anyhow, Result};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, IsIdentity};
+use serde::Serialize;
+use sha2::{Digest, Sha512};
+use std::convert::TryFrom;
+
+/// Schnorr signatures over ristretto255, shaped as Ed25519 ones: a 32-byte public key
+/// A = [a]B, a 64-byte signature R || S, S = r + k * a, with k the SHA-512 digest of
+/// R || A || M reduced modulo L. Only the encodings differ, and with them the group: that
+/// of ristretto255 has prime order L, and each element a single encoding, which decoding
+/// checks.
+pub struct SchnorrKey {
+    secret: Scalar,
+    pub_key: RistrettoPoint,
+}
+
+impl SchnorrKey {
+    /// The key of the secret scalar, the 32 bytes of `seed` reduced modulo L.
+    pub fn from_seed(seed: &[u8; 32]) -> SchnorrKey {
+        let secret = Scalar::from_bytes_mod_order(*seed);
+        SchnorrKey {
+            secret,
+            pub_key: secret * RISTRETTO_BASEPOINT_POINT,
+        }
+    }
+
+    pub fn pub_key(&self) -> [u8; 32] {
+        self.pub_key.compress().to_bytes()
+    }
+
+    /// The signature of `message`, with the nonce r derived from the secret and the message
+    /// as RFC 8032 derives it from the prefix.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let mut r_bytes = [0u8; 64];
+        r_bytes.copy_from_slice(
+            &Sha512::default()
+                .chain(self.secret.as_bytes())
+                .chain(message)
+                .finalize()[..],
+        );
+        let r = Scalar::from_bytes_mod_order_wide(&r_bytes);
+        let r_pt = r * RISTRETTO_BASEPOINT_POINT;
+        let k = compute_hram(message, &self.pub_key(), &r_pt.compress().to_bytes());
+        let s = r + k * self.secret;
+        [&r_pt.compress().to_bytes()[..], &s.to_bytes()[..]].concat()
+    }
+
+    pub fn sign_vector(&self, message: &[u8]) -> TestVector {
+        TestVector {
+            message: message.to_vec(),
+            pub_key: self.pub_key(),
+            signature: self.sign(message),
+            metadata: None,
+            trace: None,
+        }
+    }
+}
+
+// k = SHA-512(R || A || M) mod L, over the encodings, which are unique
+fn compute_hram(message: &[u8], pub_key: &[u8; 32], r: &[u8; 32]) -> Scalar {
+    let mut k_bytes = [0u8; 64];
+    k_bytes.copy_from_slice(
+        &Sha512::default()
+            .chain(r)
+            .chain(pub_key)
+            .chain(message)
+            .finalize()[..],
+    );
+    Scalar::from_bytes_mod_order_wide(&k_bytes)
+}
+
+/// Decodes a ristretto255 element, which only its canonical encoding decodes to.
+pub fn deserialize_element(bytes: &[u8]) -> Result<RistrettoPoint> {
+    CompressedRistretto::from_slice(check_slice_size(bytes, 32, "element")?)
+        .decompress()
+        .ok_or_else(|| anyhow!("Invalid ristretto255 encoding!"))
+}
+
+/// Decodes A and R, and S, which must be canonical as in RFC 8032.
+pub fn deserialize(
+    pub_key: &[u8],
+    signature: &[u8],
+) -> Result<(RistrettoPoint, RistrettoPoint, Scalar)> {
+    let signature = check_slice_size(signature, 64, "signature")?;
+    let a = deserialize_element(pub_key)?;
+    let r = deserialize_element(&signature[..32])?;
+    let s = Scalar::from_canonical_bytes(<[u8; 32]>::try_from(&signature[32..])?)
+        .ok_or_else(|| anyhow!("Non-canonical S!"))?;
+    Ok((a, r, s))
+}
+
+// [S]B - R - [k]A, hashing A and R as encoded: the decoded elements re-encode to them
+fn residue(message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<RistrettoPoint> {
+    let (a, r, s) = deserialize(pub_key, signature)?;
+    let k = compute_hram(message, &a.compress().to_bytes(), &r.compress().to_bytes());
+    Ok(s * RISTRETTO_BASEPOINT_POINT - r - k * a)
+}
+
+/// The verification equation `[S]B = R + [k]A`.
+pub fn verify(message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+    if residue(message, pub_key, signature)?.is_identity() {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid Schnorr signature!"))
+    }
+}
+
+/// The equation `[8][S]B = [8]R + [8][k]A`, multiplied by the cofactor of Curve25519 for
+/// the sake of the contrast: in a group of prime order it holds iff the other one does.
+pub fn verify_cofactored(message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+    if (Scalar::from(8u8) * residue(message, pub_key, signature)?).is_identity() {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid Schnorr signature!"))
+    }
+}
+
+/// `verify`, as a verifier of the results tables.
+pub struct RistrettoSchnorr;
+
+impl Verifier for RistrettoSchnorr {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        verify(message, pub_key, signature)
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        Verdict::from_results(deserialize(pub_key, signature), |_| {
+            verify(message, pub_key, signature)
+        })
+    }
+}
+
+/// Whether `[L]P` is the identity, computed as `[L - 1]P + P`: true of every element of
+/// ristretto255, false of the Edwards points with a torsion component.
+pub fn has_order_dividing_l(pt: &RistrettoPoint) -> bool {
+    (-Scalar::one() * pt + pt).is_identity()
+}
+
+// p = 2^255 - 19, little-endian
+const P: [u8; 32] = [
+    0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+
+// a - b, little-endian, for a >= b
+fn sub(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut difference = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in 0..32 {
+        let d = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = (d < 0) as i16;
+        difference[i] = (d + 256 * borrow) as u8;
+    }
+    difference
+}
+
+/// The other encodings of the field element s encoding an element, all rejected by
+/// decoding: with the top bit set, the negative p - s (for s != 0), and s + p where it fits
+/// in 255 bits. Ed25519 encodings admit the analogues of the first and the last, which
+/// cases 8 to 11 build on.
+pub fn non_canonical_variants(encoding: &[u8; 32]) -> Vec<[u8; 32]> {
+    let mut variants = Vec::new();
+    let mut top_bit = *encoding;
+    top_bit[31] |= 128;
+    variants.push(top_bit);
+    if *encoding != [0u8; 32] {
+        variants.push(sub(&P, encoding));
+    }
+    // s + p < 2^255 iff s < 19
+    if encoding[1..].iter().all(|b| *b == 0) && encoding[0] < 19 {
+        let mut plus_p = P;
+        plus_p[0] += encoding[0];
+        variants.push(plus_p);
+    }
+    variants
+}
+
+/// Why each case of `generate_test_vectors` has no counterpart over ristretto255, by index,
+/// or which check still matters there.
+pub const CASE_CONTRASTS: [&str; 12] = [
+    "small A and R: the identity is the only element of small order, and A = R = identity \
+     with S = 0 verifies as it does in Ed25519, unless A is checked against it",
+    "small A: the identity A is the only one left, and verifies as in Ed25519",
+    "small R: the identity R is the only one left, and verifies as in Ed25519",
+    "mixed A and R: every element has order L, there is no torsion component to add",
+    "mixed A and R parting the equations: the cofactored and cofactorless equations are \
+     equivalent in a group of prime order",
+    "pre-reduced k: k and 8k mod L act alike on elements of order L",
+    "S > L: expressible, S must still be checked canonical",
+    "S >> L: expressible, S must still be checked canonical",
+    "non-canonical R, reduced for hash: decoding accepts the canonical encoding only",
+    "non-canonical R, not reduced for hash: decoding accepts the canonical encoding only, \
+     the hash input is unique",
+    "non-canonical A, reduced for hash: decoding accepts the canonical encoding only",
+    "non-canonical A, not reduced for hash: decoding accepts the canonical encoding only, \
+     the hash input is unique",
+];
+
+/// A vector of the contrast, over ristretto255.
+#[derive(Serialize)]
+pub struct ContrastVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// Whether `verify` and `verify_cofactored`, which always agree, accept it.
+    pub valid: bool,
+}
+
+/// The vectors transposing the cases of Ed25519 to ristretto255, signed by `key` over
+/// `message`: an honest signature; those of the identity, the only small-order element,
+/// as A and R; S + L; and the non-canonical encodings of A and R of the honest signature
+/// and of the identity, which decoding rejects where the Ed25519 ones decode.
+pub fn contrast_vectors(key: &SchnorrKey, message: &[u8]) -> Vec<ContrastVector> {
+    let honest = key.sign_vector(message);
+    let identity = RistrettoPoint::identity().compress().to_bytes();
+    let mut vectors = vec![(honest.clone(), "honest signature".to_string())];
+    let mut s = [0u8; 32];
+    s.copy_from_slice(&honest.signature[32..]);
+
+    // A = identity, R = [r]B, S = r: [S]B = R + [k]A whatever k, with r that S
+    let r = Scalar::from_bytes_mod_order(s);
+    let r_pt = (r * RISTRETTO_BASEPOINT_POINT).compress().to_bytes();
+    let identity_pub_key = TestVector {
+        pub_key: identity,
+        signature: [&r_pt[..], &s[..]].concat(),
+        ..honest.clone()
+    };
+    vectors.push((identity_pub_key.clone(), "identity A, S = r".to_string()));
+    vectors.push((
+        TestVector {
+            pub_key: identity,
+            signature: [&identity[..], &[0u8; 32][..]].concat(),
+            ..honest.clone()
+        },
+        "identity A and R, S = 0".to_string(),
+    ));
+
+    // S + L, 2^252 < S + L < 2^253
+    let large_s = (BigScalar::from_bytes(&s) + BigScalar::L).to_bytes();
+    let mut signature = honest.signature.clone();
+    signature[32..].copy_from_slice(&large_s);
+    vectors.push((
+        TestVector {
+            signature,
+            ..honest.clone()
+        },
+        "S + L".to_string(),
+    ));
+
+    for variant in non_canonical_variants(&honest.pub_key) {
+        vectors.push((
+            TestVector {
+                pub_key: variant,
+                ..honest.clone()
+            },
+            "non-canonical A".to_string(),
+        ));
+    }
+    let mut r_bytes = [0u8; 32];
+    r_bytes.copy_from_slice(&honest.signature[..32]);
+    for variant in non_canonical_variants(&r_bytes) {
+        let mut signature = honest.signature.clone();
+        signature[..32].copy_from_slice(&variant);
+        vectors.push((
+            TestVector {
+                signature,
+                ..honest.clone()
+            },
+            "non-canonical R".to_string(),
+        ));
+    }
+    for variant in non_canonical_variants(&identity) {
+        vectors.push((
+            TestVector {
+                pub_key: variant,
+                ..identity_pub_key.clone()
+            },
+            "non-canonical identity A, S = r".to_string(),
+        ));
+    }
+
+    vectors
+        .into_iter()
+        .map(|(vector, comment)| ContrastVector {
+            valid: verify(&vector.message, &vector.pub_key, &vector.signature).is_ok(),
+            comment,
+            vector,
+        })
+        .collect()
+}
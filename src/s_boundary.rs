@@ -0,0 +1,83 @@
+use crate::matrix::{classify, Verdicts};
+use crate::non_reducing_scalar52::BigScalar;
+use crate::s_high_bits::S_CHECKS;
+use crate::test_vectors::TestVector;
+use crate::{deserialize_point, HramPrefix, EIGHT_TORSION};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::traits::IsIdentity;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A signature whose S is next to L, the bound of the checks of `S < L`: L - 1, the
+/// largest canonical S, or L, the smallest non-canonical one, each paired with the S it
+/// differs from by L, 2L - 1 and 0, which the signature verifies with all the same. S being
+/// fixed modulo L, the equation only holds under a public key of small order, `[S]B = R` with
+/// `[k]A` the identity: R is -B for S = -1 mod L, and the identity for S = 0 mod L.
+#[derive(Serialize)]
+pub struct SBoundaryVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// S as `j * L + remainder`: j, 0 or 1.
+    pub multiple_of_l: u64,
+    /// Whether S < L.
+    pub canonical_s: bool,
+    pub expected_verdicts: Verdicts,
+    /// Whether each of `s_high_bits::S_CHECKS`, by name, lets S through.
+    pub passes_s_checks: BTreeMap<String, bool>,
+}
+
+/// The vectors of S = L - 1 and 2L - 1, then of S = 0 and L, under the identity as public
+/// key, then under a point of order 8, for which the message is ground until 8 divides k.
+pub fn s_boundary_vectors(rng: &mut impl RngCore) -> Vec<SBoundaryVector> {
+    let minus_one = BigScalar::L - BigScalar::from_u64(1);
+    let public_keys = [
+        ("the identity", EIGHT_TORSION[0]),
+        ("of order 8", EIGHT_TORSION[1]),
+    ];
+    let remainders = [
+        (minus_one, ["L - 1", "2L - 1"]),
+        (BigScalar::ZERO, ["0", "L"]),
+    ];
+    let mut vectors = Vec::new();
+    for (key_name, pub_key) in public_keys.iter() {
+        let a = deserialize_point(pub_key).unwrap();
+        for (remainder, s_names) in remainders.iter() {
+            let r = (remainder.reduce() * ED25519_BASEPOINT_POINT)
+                .compress()
+                .to_bytes();
+            let prefix = HramPrefix::from_arrays(&r, pub_key);
+            let mut message = [0u8; 32];
+            loop {
+                rng.fill_bytes(&mut message);
+                if (prefix.finish(&message) * a).is_identity() {
+                    break;
+                }
+            }
+            for (j, s_name) in s_names.iter().enumerate() {
+                let s = remainder.add_multiple_of_l(j as u64).unwrap();
+                let vector = TestVector {
+                    message: message.to_vec(),
+                    pub_key: *pub_key,
+                    signature: [&r[..], &s.to_bytes()[..]].concat(),
+                    metadata: None,
+                    trace: None,
+                };
+                let expected_verdicts = classify(&vector);
+                let passes_s_checks = S_CHECKS
+                    .iter()
+                    .map(|check| (check.name.to_string(), (check.passes)(&s.to_bytes())))
+                    .collect();
+                vectors.push(SBoundaryVector {
+                    comment: format!("S = {}, A {}", s_name, key_name),
+                    vector,
+                    multiple_of_l: j as u64,
+                    canonical_s: s.is_canonical(),
+                    expected_verdicts,
+                    passes_s_checks,
+                });
+            }
+        }
+    }
+    vectors
+}
@@ -0,0 +1,125 @@
+use crate::matrix::{classify, Verdicts};
+use crate::non_reducing_scalar52::BigScalar;
+use crate::test_vectors::TestVector;
+use crate::{compute_hram, serialize_signature, verify_cofactorless};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A valid signature with S replaced by `S + jL`, for the smallest `j >= 1` setting bits
+/// 253 to 255 of S to `top_bits`. The equation holds modulo L for every variant, so that
+/// a verifier accepts one exactly when its check of S lets it through, which localizes the
+/// bit mask it applies in place of `S < L`.
+#[derive(Serialize)]
+pub struct HighBitsVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// Bits 253 (least significant) to 255 of S.
+    pub top_bits: u8,
+    pub multiple_of_l: u8,
+    /// The policies not requiring S < L read S as dalek's `Scalar::from_bits`, dropping bit
+    /// 255.
+    pub expected_verdicts: Verdicts,
+    /// Whether each of `S_CHECKS`, by name, lets S through.
+    pub passes_s_checks: BTreeMap<String, bool>,
+}
+
+/// A check of S, in place of or as `S < L`, applied to its encoding.
+#[derive(Clone, Copy)]
+pub struct SCheck {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub passes: fn(&[u8; 32]) -> bool,
+}
+
+/// The checks of S found in implementations, from the strictest to none at all.
+pub const S_CHECKS: [SCheck; 4] = [
+    SCheck {
+        name: "canonical",
+        description: "S < L, as RFC 8032 requires",
+        passes: |s| Scalar::from_canonical_bytes(*s).is_some(),
+    },
+    SCheck {
+        name: "top-three-bits",
+        description: "bits 253 to 255 cleared, as ref10's `s[31] & 224`",
+        passes: |s| s[31] & 0xe0 == 0,
+    },
+    SCheck {
+        name: "top-bit",
+        description: "bit 255 cleared",
+        passes: |s| s[31] & 0x80 == 0,
+    },
+    SCheck {
+        name: "none",
+        description: "any S, reduced modulo L",
+        passes: |_| true,
+    },
+];
+
+// The smallest multiple j >= 1 such that S + jL has `top_bits` as bits 253 to 255
+fn variant(s: &[u8; 32], top_bits: u8) -> Option<(u8, [u8; 32])> {
+    (1..=16)
+        .filter_map(|j| {
+            BigScalar::from_bytes(s)
+                .add_multiple_of_l(j.into())
+                .map(|sum| (j, sum.to_bytes()))
+        })
+        .find(|(_, sum)| sum[31] >> 5 == top_bits)
+}
+
+/// The 8 variants of a valid signature under A and R of order L, by increasing `top_bits`.
+/// The signature is drawn again in the rare cases where some combination of the top bits
+/// has no variant.
+pub fn s_high_bits_vectors(rng: &mut impl RngCore) -> Vec<HighBitsVector> {
+    let mut scalar_bytes = [0u8; 32];
+    let mut message = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut scalar_bytes);
+        let a = Scalar::from_bytes_mod_order(scalar_bytes);
+        let pub_key = a * ED25519_BASEPOINT_POINT;
+        rng.fill_bytes(&mut scalar_bytes);
+        let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+        let r = r_scalar * ED25519_BASEPOINT_POINT;
+        rng.fill_bytes(&mut message);
+        let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+        debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
+
+        let variants: Option<Vec<(u8, [u8; 32])>> = (0..8)
+            .map(|top_bits| variant(s.as_bytes(), top_bits))
+            .collect();
+        let variants = match variants {
+            Some(variants) => variants,
+            None => continue,
+        };
+        return variants
+            .into_iter()
+            .zip(0u8..)
+            .map(|((j, s_bytes), top_bits)| {
+                let mut signature = serialize_signature(&r, &s);
+                signature[32..].copy_from_slice(&s_bytes);
+                let vector = TestVector {
+                    message: message.to_vec(),
+                    pub_key: pub_key.compress().to_bytes(),
+                    signature,
+                    metadata: None,
+                    trace: None,
+                };
+                let expected_verdicts = classify(&vector);
+                let passes_s_checks = S_CHECKS
+                    .iter()
+                    .map(|check| (check.name.to_string(), (check.passes)(&s_bytes)))
+                    .collect();
+                HighBitsVector {
+                    comment: format!("S + {}L, bits 255 to 253 of S = {:03b}", j, top_bits),
+                    vector,
+                    top_bits,
+                    multiple_of_l: j,
+                    expected_verdicts,
+                    passes_s_checks,
+                }
+            })
+            .collect();
+    }
+}
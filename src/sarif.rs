@@ -0,0 +1,166 @@
+use crate::policy::ValidationPolicy;
+use crate::test_vectors::{generate_test_vectors, TestVector, CASE_IDS};
+use crate::verdict::Verdict;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::ops::Range;
+
+/// A family of the cases of `generate_test_vectors`, reported as one SARIF rule.
+pub struct CaseFamily {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub cases: Range<usize>,
+}
+
+/// The families of cases, in the order of the cases.
+pub const CASE_FAMILIES: [CaseFamily; 8] = [
+    CaseFamily {
+        id: "small-order-a-and-r",
+        name: "SmallOrderAAndR",
+        description: "A and R of small order, with S = 0: the signature is repudiable",
+        cases: 0..1,
+    },
+    CaseFamily {
+        id: "small-order-a",
+        name: "SmallOrderA",
+        description: "A of small order, with R of mixed order: the signature is repudiable",
+        cases: 1..2,
+    },
+    CaseFamily {
+        id: "small-order-r",
+        name: "SmallOrderR",
+        description: "R of small order, with A of mixed order",
+        cases: 2..3,
+    },
+    CaseFamily {
+        id: "mixed-order",
+        name: "MixedOrder",
+        description: "A and R of mixed order, telling apart cofactored verification",
+        cases: 3..5,
+    },
+    CaseFamily {
+        id: "pre-reduction",
+        name: "PreReduction",
+        description: "A of mixed order, failing cofactored verification iff (8h) is pre-reduced",
+        cases: 5..6,
+    },
+    CaseFamily {
+        id: "s-out-of-bounds",
+        name: "SOutOfBounds",
+        description: "S >= L, breaking strong unforgeability",
+        cases: 6..8,
+    },
+    CaseFamily {
+        id: "non-canonical-r",
+        name: "NonCanonicalR",
+        description: "R of small order with a non-canonical encoding",
+        cases: 8..10,
+    },
+    CaseFamily {
+        id: "non-canonical-a",
+        name: "NonCanonicalA",
+        description: "A of small order with a non-canonical encoding",
+        cases: 10..12,
+    },
+];
+
+/// The family of the `case`-th vector of `generate_test_vectors`.
+pub fn family_of(case: usize) -> Option<&'static CaseFamily> {
+    CASE_FAMILIES
+        .iter()
+        .find(|family| family.cases.contains(&case))
+}
+
+/// The indices of the `vectors` accepted in `observed`, although `policy` rejects them.
+pub fn failed_expectations(
+    policy: &ValidationPolicy,
+    vectors: &[TestVector],
+    observed: &[Verdict],
+) -> Result<Vec<usize>> {
+    if vectors.len() != observed.len() {
+        return Err(anyhow!(
+            "got {} observed verdicts for {} test vectors",
+            observed.len(),
+            vectors.len()
+        ));
+    }
+    Ok(policy
+        .predict(vectors)
+        .iter()
+        .zip(observed.iter())
+        .enumerate()
+        .filter(|(_, (expected, observed))| !expected.is_accepted() && observed.is_accepted())
+        .map(|(i, _)| i)
+        .collect())
+}
+
+/// A SARIF 2.1.0 log of the failed expectations of each library, given its verdicts on the
+/// vectors of `generate_test_vectors`: each vector a library accepts while `policy`
+/// rejects it is an error of the rule of its family, located at the vector in `cases.txt`.
+pub fn export(policy: &ValidationPolicy, observations: &[(&str, &[Verdict])]) -> Result<Value> {
+    let vectors = generate_test_vectors();
+    let mut results = Vec::new();
+    for (library, observed) in observations.iter() {
+        for case in failed_expectations(policy, &vectors, observed)? {
+            let rule_index = CASE_FAMILIES
+                .iter()
+                .position(|family| family.cases.contains(&case))
+                .ok_or_else(|| anyhow!("case {} belongs to no family", case))?;
+            let family = &CASE_FAMILIES[rule_index];
+            let id = CASE_IDS[case];
+            // cases.txt starts with the number of cases, then 4 lines per case, from its id
+            let start_line = 2 + 4 * case;
+            results.push(json!({
+                "ruleId": family.id,
+                "ruleIndex": rule_index,
+                "level": "error",
+                "message": {
+                    "text": format!(
+                        "{} accepts case {} ({}: {}), which the policy ({}) rejects",
+                        library, case, id, family.description, policy
+                    )
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "cases.txt" },
+                        "region": { "startLine": start_line, "endLine": start_line + 3 }
+                    }
+                }],
+                "partialFingerprints": {
+                    "speccheckCase/v1": format!("{}:{}", library, case),
+                    "speccheckCase/v2": format!("{}:{}", library, id)
+                },
+                "properties": { "library": library, "case": case, "caseId": id }
+            }));
+        }
+    }
+
+    let rules: Vec<Value> = CASE_FAMILIES
+        .iter()
+        .map(|family| {
+            json!({
+                "id": family.id,
+                "name": family.name,
+                "shortDescription": { "text": family.description },
+                "defaultConfiguration": { "level": "error" }
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules
+                }
+            },
+            "results": results
+        }]
+    }))
+}
@@ -0,0 +1,125 @@
+use crate::classification::multiple_of_eight_le;
+use crate::generator::VectorGenerator;
+use crate::test_vectors::TestVector;
+use crate::{
+    compute_hram, deserialize_point, deserialize_signature, verify_cofactored, verify_cofactorless,
+    verify_pre_reduced_cofactored,
+};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use serde::Serialize;
+use std::ops::Range;
+
+/// A property rarer than the cases themselves, which an instance of a case may happen to
+/// have. Sweeping seeds finds such instances, which may then be added to the canonical set
+/// as cases of their own.
+#[derive(Clone, Copy)]
+pub struct RareProperty {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub holds: fn(&TestVector) -> bool,
+}
+
+/// The properties `sweep` looks for by default. The hram scalar k is that of the
+/// re-serialized A and R.
+pub const RARE_PROPERTIES: [RareProperty; 3] = [
+    RareProperty {
+        name: "order-8-cofactorless-even-hram",
+        description: "A or R has a component of order 8, the cofactorless equation holds \
+                      and k is even",
+        holds: order_8_cofactorless_even_hram,
+    },
+    RareProperty {
+        name: "pre-reduction-agrees-on-order-8",
+        description: "A has a component of order 8, the cofactored equation holds whether or \
+                      not (8h) is pre-reduced, and the cofactorless one fails",
+        holds: pre_reduction_agrees_on_order_8,
+    },
+    RareProperty {
+        name: "hram-multiple-of-8",
+        description: "A has a component of small order, and k is a multiple of 8, so that \
+                      [k]A has none",
+        holds: hram_multiple_of_8,
+    },
+];
+
+pub use crate::classification::torsion_order;
+
+// A, (R, S) and k of `tv`, if A and R decode
+fn decode(tv: &TestVector) -> Option<(EdwardsPoint, (EdwardsPoint, Scalar), Scalar)> {
+    let pub_key = deserialize_point(&tv.pub_key).ok()?;
+    let signature = deserialize_signature(&tv.signature).ok()?;
+    let k = compute_hram(&tv.message, &pub_key, &signature.0);
+    Some((pub_key, signature, k))
+}
+
+fn order_8_cofactorless_even_hram(tv: &TestVector) -> bool {
+    matches!(decode(tv), Some((pub_key, signature, k))
+        if (torsion_order(&pub_key) == 8 || torsion_order(&signature.0) == 8)
+            && k.as_bytes()[0] & 1 == 0
+            && verify_cofactorless(&tv.message, &pub_key, &signature).is_ok())
+}
+
+fn pre_reduction_agrees_on_order_8(tv: &TestVector) -> bool {
+    matches!(decode(tv), Some((pub_key, signature, _))
+        if torsion_order(&pub_key) == 8
+            && verify_cofactored(&tv.message, &pub_key, &signature).is_ok()
+            && verify_pre_reduced_cofactored(&tv.message, &pub_key, &signature).is_ok()
+            && verify_cofactorless(&tv.message, &pub_key, &signature).is_err())
+}
+
+fn hram_multiple_of_8(tv: &TestVector) -> bool {
+    matches!(decode(tv), Some((pub_key, _, k))
+        if torsion_order(&pub_key) > 1 && multiple_of_eight_le(k.as_bytes()))
+}
+
+/// An instance of a case with a `RareProperty`, drawn from the generator with the given
+/// `seed`, derived as the `index`-th one from the generator swept.
+pub struct Finding {
+    pub property: &'static str,
+    pub index: u64,
+    pub seed: [u8; 32],
+    pub case: usize,
+    pub vector: TestVector,
+}
+
+impl Serialize for Finding {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("Finding", 5)?;
+        state.serialize_field("property", self.property)?;
+        state.serialize_field("index", &self.index)?;
+        state.serialize_field("seed", &hex::encode(self.seed))?;
+        state.serialize_field("case", &self.case)?;
+        state.serialize_field("vector", &self.vector)?;
+        state.end()
+    }
+}
+
+/// Draws the cases from the generators derived from `base` at each of the `indices`, and
+/// reports the instances having any of the `properties`, in order.
+pub fn sweep(
+    base: &VectorGenerator,
+    indices: Range<u64>,
+    properties: &[RareProperty],
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for index in indices {
+        let generator = base.derive(index);
+        for (case, tv) in generator.test_vectors().into_iter().enumerate() {
+            for property in properties.iter().filter(|property| (property.holds)(&tv)) {
+                findings.push(Finding {
+                    property: property.name,
+                    index,
+                    seed: generator.seed(),
+                    case,
+                    vector: tv.clone(),
+                });
+            }
+        }
+    }
+    findings
+}
@@ -0,0 +1,93 @@
+use crate::test_vectors::TestVector;
+use crate::{compute_hram, serialize_signature};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use sha2::{Digest, Sha512};
+use std::convert::TryFrom;
+
+/// An Ed25519 key pair signing as RFC 8032 does, for known-good control vectors next to the
+/// adversarial ones: its signatures have A and R of order L, canonical, and S < L, so that
+/// every verifier, whatever the checks it performs, accepts them.
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; 32],
+    secret: Scalar,
+    prefix: [u8; 32],
+    pub_key: EdwardsPoint,
+}
+
+impl SigningKey {
+    /// The key pair of the 32-byte secret key `seed`, expanded as in RFC 8032, Section
+    /// 5.1.5: the first half of its SHA-512 digest, clamped, is the secret scalar a, and the
+    /// second half the prefix the nonces are derived from.
+    pub fn from_seed(seed: &[u8; 32]) -> SigningKey {
+        let h = Sha512::digest(seed);
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes.copy_from_slice(&h[..32]);
+        scalar_bytes[0] &= 248;
+        scalar_bytes[31] &= 127;
+        scalar_bytes[31] |= 64;
+        let secret = Scalar::from_bytes_mod_order(scalar_bytes);
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&h[32..]);
+        SigningKey {
+            seed: *seed,
+            secret,
+            prefix,
+            pub_key: secret * ED25519_BASEPOINT_POINT,
+        }
+    }
+
+    /// The key pair of a seed drawn from `rng`.
+    pub fn generate(rng: &mut impl RngCore) -> SigningKey {
+        let mut seed = [0u8; 32];
+        rng.fill_bytes(&mut seed);
+        SigningKey::from_seed(&seed)
+    }
+
+    pub fn seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// The encoding of A = [a]B.
+    pub fn pub_key(&self) -> [u8; 32] {
+        self.pub_key.compress().to_bytes()
+    }
+
+    pub(crate) fn pub_key_point(&self) -> EdwardsPoint {
+        self.pub_key
+    }
+
+    pub(crate) fn secret(&self) -> Scalar {
+        self.secret
+    }
+
+    /// The nonce r of `message`, SHA-512(prefix || message) reduced modulo L, as in RFC
+    /// 8032, Section 5.1.6.
+    pub(crate) fn nonce(&self, message: &[u8]) -> Scalar {
+        let h = Sha512::new().chain(self.prefix).chain(message).finalize();
+        Scalar::from_bytes_mod_order_wide(&<[u8; 64]>::try_from(&h[..]).unwrap())
+    }
+
+    /// The 64-byte signature of `message`, R = [r]B followed by S = r + ka mod L, as in RFC
+    /// 8032, Section 5.1.6.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let nonce = self.nonce(message);
+        let r = nonce * ED25519_BASEPOINT_POINT;
+        let s = nonce + compute_hram(message, &self.pub_key, &r) * self.secret;
+        serialize_signature(&r, &s)
+    }
+
+    /// The signature of `message` as a vector, for the verifiers of this crate.
+    pub fn sign_vector(&self, message: &[u8]) -> TestVector {
+        TestVector {
+            message: message.to_vec(),
+            pub_key: self.pub_key(),
+            signature: self.sign(message),
+            metadata: None,
+            trace: None,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::generator::VectorGenerator;
+use crate::non_canonical_encodings::non_canonical_encodings;
+use crate::non_reducing_scalar52::BigScalar;
+use crate::signing::SigningKey;
+use crate::test_vectors::TestVector;
+use crate::{compute_hram, deserialize_point_with, serialize_signature, DecodeMode, EIGHT_TORSION};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use proptest::prelude::*;
+
+// The messages of the vectors, of up to 64 bytes
+fn message() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..=64)
+}
+
+fn torsion_point(index: usize) -> EdwardsPoint {
+    // the points of `EIGHT_TORSION` are canonical
+    deserialize_point_with(&EIGHT_TORSION[index], DecodeMode::Strict).unwrap()
+}
+
+/// One of the 8 points of small order, the identity included.
+pub fn small_order_point() -> impl Strategy<Value = EdwardsPoint> {
+    (0..8usize).prop_map(torsion_point)
+}
+
+/// [a]B for a uniform scalar a, of order L but for a = 0.
+pub fn full_order_point() -> impl Strategy<Value = EdwardsPoint> {
+    canonical_scalar().prop_map(|a| a * ED25519_BASEPOINT_POINT)
+}
+
+/// [a]B + T for a uniform scalar a and T of order 2, 4 or 8.
+pub fn mixed_order_point() -> impl Strategy<Value = EdwardsPoint> {
+    (canonical_scalar(), 1..8usize)
+        .prop_map(|(a, index)| a * ED25519_BASEPOINT_POINT + torsion_point(index))
+}
+
+/// A point of small, full or mixed order, as any of the above.
+pub fn point() -> impl Strategy<Value = EdwardsPoint> {
+    prop_oneof![small_order_point(), full_order_point(), mixed_order_point()]
+}
+
+/// The canonical encodings of the points of `points`.
+pub fn canonical_encoding(
+    points: impl Strategy<Value = EdwardsPoint>,
+) -> impl Strategy<Value = [u8; 32]> {
+    points.prop_map(|pt| pt.compress().to_bytes())
+}
+
+/// One of the non-canonical encodings of a point, out of
+/// `non_canonical_encodings::non_canonical_encodings`.
+pub fn non_canonical_encoding() -> impl Strategy<Value = [u8; 32]> {
+    let encodings: Vec<[u8; 32]> = non_canonical_encodings()
+        .into_iter()
+        .map(|encoding| encoding.bytes)
+        .collect();
+    prop::sample::select(encodings)
+}
+
+/// S < L, uniform, as RFC 8032 requires.
+pub fn canonical_scalar() -> impl Strategy<Value = Scalar> {
+    any::<[u8; 32]>().prop_map(Scalar::from_bytes_mod_order)
+}
+
+/// S + L for a canonical S, kept below 2^253 so that the checks of the 3 high bits of S let
+/// it through, while those of S < L do not.
+pub fn large_scalar() -> impl Strategy<Value = Scalar> {
+    canonical_scalar().prop_filter_map("S + L sets a high bit", |s| {
+        let bytes = (BigScalar::from_scalar(&s) + BigScalar::L).to_bytes();
+        if bytes[31] & 224 == 0 {
+            Some(Scalar::from_bits(bytes))
+        } else {
+            None
+        }
+    })
+}
+
+/// 2^253 <= S < 2^255, far beyond L: the checks of the high bits of S reject it, unlike the
+/// verifiers reducing S modulo L.
+pub fn really_large_scalar() -> impl Strategy<Value = Scalar> {
+    any::<[u8; 32]>().prop_map(|mut bytes| {
+        bytes[31] = (bytes[31] | 32) & 127;
+        Scalar::from_bits(bytes)
+    })
+}
+
+/// A vector of a random message, public key and R drawn from the given encodings, and S
+/// from `s`: most such vectors pass no verification equation, and check how a verifier
+/// decodes and checks the components against e.g. `ValidationPolicy::verdict`.
+pub fn vector_from(
+    pub_key: impl Strategy<Value = [u8; 32]>,
+    r: impl Strategy<Value = [u8; 32]>,
+    s: impl Strategy<Value = Scalar>,
+) -> impl Strategy<Value = TestVector> {
+    (message(), pub_key, r, s).prop_map(|(message, pub_key, r, s)| TestVector {
+        message,
+        pub_key,
+        signature: [&r[..], &s.as_bytes()[..]].concat(),
+        metadata: None,
+        trace: None,
+    })
+}
+
+/// A signature of a random message under a fresh key, made as RFC 8032 does: every
+/// verifier accepts it.
+pub fn honest_vector() -> impl Strategy<Value = TestVector> {
+    (any::<[u8; 32]>(), message())
+        .prop_map(|(seed, message)| SigningKey::from_seed(&seed).sign_vector(&message))
+}
+
+/// A signature of a random message by the secret key `[a]B + T`, with a random nonce, for
+/// small-order T: it passes the cofactored equation, and the cofactorless one only for the
+/// challenges k making `[k]T` vanish.
+pub fn mixed_order_key_vector() -> impl Strategy<Value = TestVector> {
+    (canonical_scalar(), 1..8usize, canonical_scalar(), message()).prop_map(
+        |(a, index, r, message)| {
+            let pub_key = a * ED25519_BASEPOINT_POINT + torsion_point(index);
+            let r_pt = r * ED25519_BASEPOINT_POINT;
+            let s = r + compute_hram(&message, &pub_key, &r_pt) * a;
+            TestVector {
+                message,
+                pub_key: pub_key.compress().to_bytes(),
+                signature: serialize_signature(&r_pt, &s),
+                metadata: None,
+                trace: None,
+            }
+        },
+    )
+}
+
+/// An instance of the `index`-th case of `generate_test_vectors`, out of 12, drawn from a
+/// random seed, with its metadata: drawing one grinds every case, which takes a few
+/// milliseconds.
+pub fn case_vector(index: usize) -> impl Strategy<Value = TestVector> {
+    any::<[u8; 32]>()
+        .prop_map(move |seed| VectorGenerator::from_seed(seed).test_vectors()[index].clone())
+}
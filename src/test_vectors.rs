@@ -1,32 +1,188 @@
-use crate::non_reducing_scalar52::Scalar52;
+use crate::algorithm2::is_canonical_point_encoding;
+use crate::classification::{point_order, torsion_order, PointOrder};
+use crate::generator::VectorGenerator;
+use crate::message_source::{MessageSource, RandomBytes};
+use crate::non_reducing_scalar52::BigScalar;
+use crate::policy::NAMED_POLICIES;
+use crate::sarif::family_of;
+use crate::verdict::Verdict;
+use crate::verifier::{self, Verifier};
 use crate::{
-    compute_hram, compute_hram_with_pk_array, compute_hram_with_r_array, deserialize_point,
-    deserialize_scalar, eight, multiple_of_eight_le, new_rng, non_reducing_scalar52,
+    check_slice_size, compute_hram, deserialize_point_with, deserialize_scalar, eight, new_rng,
     pick_small_nonzero_point, serialize_signature, verify_cofactored, verify_cofactorless,
-    verify_pre_reduced_cofactored, EIGHT_TORSION_NON_CANONICAL,
+    verify_pre_reduced_cofactored, DecodeMode, HramPrefix, EIGHT_TORSION,
+    EIGHT_TORSION_NON_CANONICAL,
 };
 use anyhow::{anyhow, Result};
 use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
 use curve25519_dalek::scalar::Scalar;
 use curve25519_dalek::traits::IsIdentity;
 use rand::RngCore;
+use serde::de::Error as _;
 use serde::ser::SerializeStruct;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sha2::{Digest, Sha512};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::ops::Neg;
+use std::path::Path;
 use string_builder::Builder;
 
 ///////////
 // Cases //
 ///////////
 
+/// The small-order point a generator builds its vectors around: either drawn from the
+/// generator's RNG, as in `generate_test_vectors`, or a given one.
+#[derive(Clone, Copy)]
+pub enum SmallPoint {
+    Random,
+    Fixed(EdwardsPoint),
+}
+
+impl SmallPoint {
+    fn pick(self, rng: &mut impl RngCore) -> Result<EdwardsPoint> {
+        match self {
+            SmallPoint::Random => {
+                let small_idx: usize = rng.next_u64() as usize;
+                Ok(pick_small_nonzero_point(small_idx + 1))
+            }
+            // no message grinding can make the verification equations depend on the identity
+            SmallPoint::Fixed(pt) if pt.is_identity() => {
+                Err(anyhow!("the small-order point must not be the identity"))
+            }
+            SmallPoint::Fixed(pt) => Ok(pt),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct TestVector {
     #[allow(dead_code)]
-    pub message: [u8; 32],
+    pub message: Vec<u8>,
     #[allow(dead_code)]
     pub pub_key: [u8; 32],
     #[allow(dead_code)]
     pub signature: Vec<u8>,
+    /// What the vector exercises, for the cases of `generate_test_vectors` and of its
+    /// Ed25519ph and Ed25519ctx variants.
+    #[allow(dead_code)]
+    pub metadata: Option<CaseMetadata>,
+    /// How the vector was built, for the cases of `generate_test_vectors`. Left out of its
+    /// JSON, see `TestVector::explained`.
+    #[allow(dead_code)]
+    pub trace: Option<ConstructionTrace>,
+}
+
+/// The class of S of a signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SClass {
+    Zero,
+    /// 0 < S < L.
+    Canonical,
+    /// S >= L.
+    Large,
+}
+
+/// The row of a vector in the table of cases: what its A, R and S are, and the verdicts of
+/// the cofactored and cofactorless equations. These are the verdicts of verifiers hashing A
+/// and R the way the case intends, re-serialized for cases 8 and 10 and as provided for 9
+/// and 11, and applying no check on A, R or S besides decoding them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaseMetadata {
+    /// The stable id of the case, see `CASE_IDS`, empty in the files of corpus-v1.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// The name of the family of the case, see `sarif::CASE_FAMILIES`.
+    pub name: String,
+    pub index: usize,
+    pub s_class: SClass,
+    /// The order of A: 1, 2, 4 or 8 if small, L, or 2L, 4L or 8L if mixed.
+    pub a_order: String,
+    /// The order of R, as for A.
+    pub r_order: String,
+    pub canonical_a: bool,
+    pub canonical_r: bool,
+    pub expected_cofactored: bool,
+    pub expected_cofactorless: bool,
+    pub comment: String,
+    /// The verdict of each of `policy::NAMED_POLICIES` on the vector, by name. The policies
+    /// model Ed25519 verifiers: the Ed25519ph and Ed25519ctx variants have none.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub expected_verdicts: BTreeMap<String, Verdict>,
+}
+
+/// Whether each reference verifier of this crate accepts a vector, see
+/// `TestVector::evaluate`: the row of the vector in the results table, for consumers
+/// classifying vectors without going through the verifiers one by one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PolicyResults {
+    /// `verify_cofactored`.
+    pub cofactored: bool,
+    /// `verify_cofactorless`.
+    pub cofactorless: bool,
+    /// `verify_pre_reduced_cofactored`, multiplying k by 8 modulo L.
+    pub pre_reduced_cofactored: bool,
+    /// [CGN20e] Algorithm 2, `algorithm2::verify`.
+    pub algorithm2: bool,
+    /// The rules of ZIP-215, `zip215::verify`.
+    pub zip215: bool,
+    /// RFC 8032 to the letter, `rfc8032::verify`.
+    pub rfc8032: bool,
+}
+
+/// The steps a generator took to build a case, for auditors to redo its algebra without
+/// reading the generator: the small-order point it is built around, the relation it ground
+/// messages for, and the scalars of the signature. The points follow from them: A and R as
+/// given in `target`, B being the base point and T the small-order point.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConstructionTrace {
+    /// The classes of S, A and R, e.g. "S > 0, small A, mixed R".
+    pub construction: &'static str,
+    /// The verdicts the construction aims for, and what the vector breaks.
+    pub outcome: &'static str,
+    /// The index in `torsion::EIGHT_TORSION` of T, if any.
+    pub torsion_index: Option<usize>,
+    /// The relation the message was ground for, k being the challenge hash, or None if the
+    /// first message drawn does.
+    pub target: Option<&'static str>,
+    /// The number of messages drawn until one met `target`, 0 without grinding.
+    pub ground_messages: usize,
+    /// The scalars by name, as the 32 bytes of their little-endian encoding in hex: a the
+    /// secret scalar, r the nonce, k the challenge and s, as in the signature.
+    pub scalars: BTreeMap<&'static str, String>,
+}
+
+impl ConstructionTrace {
+    fn new(construction: &'static str, outcome: &'static str) -> ConstructionTrace {
+        ConstructionTrace {
+            construction,
+            outcome,
+            torsion_index: None,
+            target: None,
+            ground_messages: 0,
+            scalars: BTreeMap::new(),
+        }
+    }
+
+    fn ground(mut self, target: &'static str, ground_messages: usize) -> ConstructionTrace {
+        self.target = Some(target);
+        self.ground_messages = ground_messages;
+        self
+    }
+
+    fn torsion(mut self, small_pt: &EdwardsPoint) -> ConstructionTrace {
+        let encoding = small_pt.compress().to_bytes();
+        self.torsion_index = EIGHT_TORSION.iter().position(|pt| *pt == encoding);
+        self
+    }
+
+    fn scalar(mut self, name: &'static str, value: &Scalar) -> ConstructionTrace {
+        self.scalars.insert(name, hex::encode(value.as_bytes()));
+        self
+    }
 }
 
 impl Serialize for TestVector {
@@ -34,69 +190,269 @@ impl Serialize for TestVector {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Color", 3)?;
+        let mut state = serializer.serialize_struct("Color", 4)?;
         state.serialize_field("message", &hex::encode(&self.message))?;
-        state.serialize_field("pub_key", &hex::encode(&self.pub_key))?;
+        state.serialize_field("pub_key", &hex::encode(self.pub_key))?;
         state.serialize_field("signature", &hex::encode(&self.signature))?;
+        match &self.metadata {
+            Some(metadata) => state.serialize_field("metadata", metadata)?,
+            None => state.skip_field("metadata")?,
+        }
         state.end()
     }
 }
 
+// Reads back the hex-encoded layout of `cases.json`, e.g. to check vectors generated elsewhere.
+impl<'de> Deserialize<'de> for TestVector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Encoded {
+            message: String,
+            pub_key: String,
+            signature: String,
+            #[serde(default)]
+            metadata: Option<CaseMetadata>,
+        }
+
+        let encoded = Encoded::deserialize(deserializer)?;
+        let decode = |field: &str, value: &str| {
+            hex::decode(value).map_err(|e| D::Error::custom(format!("{}: {}", field, e)))
+        };
+        let pub_key = decode("pub_key", &encoded.pub_key)?;
+        Ok(TestVector {
+            message: decode("message", &encoded.message)?,
+            pub_key: <[u8; 32]>::try_from(&pub_key[..]).map_err(|_| {
+                D::Error::custom(format!("pub_key must be 32 bytes, got {}", pub_key.len()))
+            })?,
+            signature: decode("signature", &encoded.signature)?,
+            metadata: encoded.metadata,
+            trace: None,
+        })
+    }
+}
+
+impl TestVector {
+    /// Runs the vector through the reference verifiers of this crate. The cofactored,
+    /// cofactorless and pre-reduced equations decode A and R leniently, as the rows of
+    /// `verifier::Cofactored` and the like.
+    pub fn evaluate(&self) -> PolicyResults {
+        let accepts = |verifier: &dyn Verifier| {
+            verifier
+                .verify(&self.message, &self.pub_key, &self.signature)
+                .is_ok()
+        };
+        PolicyResults {
+            cofactored: accepts(&verifier::Cofactored),
+            cofactorless: accepts(&verifier::Cofactorless),
+            pre_reduced_cofactored: accepts(&verifier::PreReducedCofactored),
+            algorithm2: accepts(&verifier::Algorithm2),
+            zip215: accepts(&verifier::Zip215),
+            rfc8032: accepts(&verifier::Rfc8032),
+        }
+    }
+
+    /// The stable id of the case the vector is an instance of, see `CASE_IDS`.
+    pub fn id(&self) -> Option<&str> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.id.as_str())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// The case of `generate_test_vectors` with the stable id `id`, wherever it sits among
+    /// the cases.
+    pub fn by_id(id: &str) -> Option<TestVector> {
+        generate_test_vectors()
+            .into_iter()
+            .find(|tv| tv.id() == Some(id))
+    }
+
+    /// The JSON of the vector, with its trace under `trace` if it has one.
+    pub fn explained(&self) -> Result<serde_json::Value> {
+        let mut json = serde_json::to_value(self)?;
+        if let (Some(trace), Some(fields)) = (&self.trace, json.as_object_mut()) {
+            fields.insert("trace".to_string(), serde_json::to_value(trace)?);
+        }
+        Ok(json)
+    }
+
+    /// Reads the vectors of a file in the layout of `cases.json`.
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Vec<TestVector>> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| anyhow!("{}: {}", path.display(), e))
+    }
+
+    /// Reads the vectors of a file in the layout of `cases.txt`, see `from_txt`.
+    pub fn from_txt_file(path: impl AsRef<Path>) -> Result<Vec<TestVector>> {
+        let path = path.as_ref();
+        TestVector::from_txt(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("{}: {}", path.display(), e))
+    }
+
+    /// Parses vectors in the layout of `cases.txt`: their number, then the message, public
+    /// key and signature of each in hex, on lines prefixed with `msg=`, `pbk=` and `sig=`,
+    /// each preceded by the id of its case on a line prefixed with `id=` since corpus-v2.
+    /// The ids are checked against `CASE_IDS` and skipped: the rest of the metadata is only
+    /// in `cases.json`.
+    pub fn from_txt(txt: &str) -> Result<Vec<TestVector>> {
+        let mut lines = txt.lines().enumerate().peekable();
+        let count: usize = match lines.next() {
+            Some((_, line)) => line
+                .trim()
+                .parse()
+                .map_err(|e| anyhow!("line 1: invalid number of vectors: {}", e))?,
+            None => return Err(anyhow!("missing the number of vectors")),
+        };
+        let mut field = |prefix: &str| -> Result<Vec<u8>> {
+            // the id of the case, if any, precedes the message
+            if prefix == "msg=" {
+                if let Some((i, line)) = lines.peek().map(|(i, line)| (*i, *line)) {
+                    if let Some(id) = line.trim().strip_prefix("id=") {
+                        if !CASE_IDS.contains(&id) {
+                            return Err(anyhow!("line {}: unknown case id {}", i + 1, id));
+                        }
+                        lines.next();
+                    }
+                }
+            }
+            let (i, line) = lines
+                .next()
+                .ok_or_else(|| anyhow!("missing {}, expected {} vectors", prefix, count))?;
+            let value = line
+                .trim()
+                .strip_prefix(prefix)
+                .ok_or_else(|| anyhow!("line {}: expected {}", i + 1, prefix))?;
+            hex::decode(value).map_err(|e| anyhow!("line {}: {}", i + 1, e))
+        };
+
+        let mut vectors = Vec::with_capacity(count);
+        for _ in 0..count {
+            let message = field("msg=")?;
+            let pub_key = field("pbk=")?;
+            let signature = field("sig=")?;
+            vectors.push(TestVector {
+                message,
+                pub_key: <[u8; 32]>::try_from(&pub_key[..])
+                    .map_err(|_| anyhow!("pub_key must be 32 bytes, got {}", pub_key.len()))?,
+                signature,
+                metadata: None,
+                trace: None,
+            });
+        }
+        if let Some((i, _)) = lines.find(|(_, line)| !line.trim().is_empty()) {
+            return Err(anyhow!("line {}: expected only {} vectors", i + 1, count));
+        }
+        Ok(vectors)
+    }
+}
+
+// Counts the messages drawn from a source, for the traces of the vectors ground over them
+struct Counted<'a, M> {
+    messages: &'a mut M,
+    drawn: usize,
+}
+
+impl<'a, M: MessageSource> Counted<'a, M> {
+    fn new(messages: &'a mut M) -> Counted<'a, M> {
+        Counted { messages, drawn: 0 }
+    }
+
+    // The messages drawn since the last call
+    fn take(&mut self) -> usize {
+        std::mem::replace(&mut self.drawn, 0)
+    }
+}
+
+impl<M: MessageSource> MessageSource for Counted<'_, M> {
+    fn next_message<R: RngCore>(&mut self, rng: &mut R) -> Vec<u8> {
+        self.drawn += 1;
+        self.messages.next_message(rng)
+    }
+}
+
+// The vector of a case along with its trace, which is logged
+fn traced(
+    message: Vec<u8>,
+    pub_key: [u8; 32],
+    signature: Vec<u8>,
+    trace: ConstructionTrace,
+) -> TestVector {
+    let tv = TestVector {
+        message,
+        pub_key,
+        signature,
+        metadata: None,
+        trace: Some(trace),
+    };
+    if let Ok(json) = tv.explained() {
+        debug!("{}", json);
+    }
+    tv
+}
+
 //////////////////////
 // 0 (cofactored)   //
 // 1 (cofactorless) //
 //////////////////////
 
-pub fn zero_small_small() -> Result<(TestVector, TestVector), anyhow::Error> {
-    let mut rng = new_rng();
+pub fn zero_small_small(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    small: SmallPoint,
+) -> Result<(TestVector, TestVector), anyhow::Error> {
+    let mut messages = Counted::new(messages);
     // Pick a torsion point
-    let small_idx: usize = rng.next_u64() as usize;
-
-    let pub_key = pick_small_nonzero_point(small_idx + 1);
+    let pub_key = small.pick(rng)?;
     let r = pub_key.neg();
     let s = Scalar::zero();
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
-    if (r + compute_hram(&message, &pub_key, &r) * pub_key).is_identity() {
-        return Err(anyhow!("wrong rng seed"));
+    let hram = HramPrefix::new(&r, &pub_key);
+    let mut message = messages.next_message(rng);
+    while (r + hram.finish(&message) * pub_key).is_identity() {
+        message = messages.next_message(rng);
     }
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
-    debug!(
-        "S=0, small A, small R\n\
-             passes cofactored, fails cofactorless, repudiable\n\
-             \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
+    let trace = ConstructionTrace::new(
+        "S=0, small A, small R",
+        "passes cofactored, fails cofactorless, repudiable",
+    )
+    .ground("R + [k]A != 0", messages.take())
+    .torsion(&pub_key)
+    .scalar("k", &hram.finish(&message))
+    .scalar("s", &s);
+    let tv1 = traced(
+        message.clone(),
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
     );
-    let tv1 = TestVector {
-        message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
 
-    while !(r + compute_hram(&message, &pub_key, &r) * pub_key).is_identity() {
-        rng.fill_bytes(&mut message);
+    while !(r + hram.finish(&message) * pub_key).is_identity() {
+        message = messages.next_message(rng);
     }
 
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
 
-    debug!(
-        "S=0, small A, small R\n\
-         passes cofactored, passes cofactorless, repudiable\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
-    );
-    let tv2 = TestVector {
+    let trace = ConstructionTrace::new(
+        "S=0, small A, small R",
+        "passes cofactored, passes cofactorless, repudiable",
+    )
+    .ground("R + [k]A = 0", messages.take())
+    .torsion(&pub_key)
+    .scalar("k", &hram.finish(&message))
+    .scalar("s", &s);
+    let tv2 = traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
+    );
 
     Ok((tv1, tv2))
 }
@@ -106,8 +462,12 @@ pub fn zero_small_small() -> Result<(TestVector, TestVector), anyhow::Error> {
 // 3 (cofactorless) //
 //////////////////////
 
-pub fn non_zero_mixed_small() -> Result<(TestVector, TestVector)> {
-    let mut rng = new_rng();
+pub fn non_zero_mixed_small(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    small: SmallPoint,
+) -> Result<(TestVector, TestVector)> {
+    let mut messages = Counted::new(messages);
     // Pick a random Scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -118,50 +478,51 @@ pub fn non_zero_mixed_small() -> Result<(TestVector, TestVector)> {
     let r0 = s * ED25519_BASEPOINT_POINT;
 
     // Pick a torsion point
-    let small_idx: usize = rng.next_u64() as usize;
-    let pub_key = pick_small_nonzero_point(small_idx + 1);
+    let pub_key = small.pick(rng)?;
 
     let r = r0 + pub_key.neg();
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
-    if (pub_key.neg() + compute_hram(&message, &pub_key, &r) * pub_key).is_identity() {
-        return Err(anyhow!("wrong rng seed"));
+    let hram = HramPrefix::new(&r, &pub_key);
+    let mut message = messages.next_message(rng);
+    while (pub_key.neg() + hram.finish(&message) * pub_key).is_identity() {
+        message = messages.next_message(rng);
     }
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
-    debug!(
-        "S > 0, small A, mixed R\n\
-             passes cofactored, fails cofactorless, repudiable\n\
-             \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
+    let trace = ConstructionTrace::new(
+        "S > 0, small A, mixed R",
+        "passes cofactored, fails cofactorless, repudiable",
+    )
+    .ground("[k]A - A != 0, with R = [s]B - A", messages.take())
+    .torsion(&pub_key)
+    .scalar("k", &hram.finish(&message))
+    .scalar("s", &s);
+    let tv1 = traced(
+        message.clone(),
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
     );
-    let tv1 = TestVector {
-        message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
 
-    while !(pub_key.neg() + compute_hram(&message, &pub_key, &r) * pub_key).is_identity() {
-        rng.fill_bytes(&mut message);
+    while !(pub_key.neg() + hram.finish(&message) * pub_key).is_identity() {
+        message = messages.next_message(rng);
     }
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
-    debug!(
-        "S > 0, small A, mixed R\n\
-         passes cofactored, passes cofactorless, repudiable\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
-    );
-    let tv2 = TestVector {
+    let trace = ConstructionTrace::new(
+        "S > 0, small A, mixed R",
+        "passes cofactored, passes cofactorless, repudiable",
+    )
+    .ground("[k]A - A = 0, with R = [s]B - A", messages.take())
+    .torsion(&pub_key)
+    .scalar("k", &hram.finish(&message))
+    .scalar("s", &s);
+    let tv2 = traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
+    );
 
     Ok((tv1, tv2))
 }
@@ -172,8 +533,12 @@ pub fn non_zero_mixed_small() -> Result<(TestVector, TestVector)> {
 //////////////////////
 
 // The symmetric case from non_zero_mixed_small
-pub fn non_zero_small_mixed() -> Result<(TestVector, TestVector)> {
-    let mut rng = new_rng();
+pub fn non_zero_small_mixed(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    small: SmallPoint,
+) -> Result<(TestVector, TestVector)> {
+    let mut messages = Counted::new(messages);
     // Pick a random scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -184,53 +549,58 @@ pub fn non_zero_small_mixed() -> Result<(TestVector, TestVector)> {
     let pub_key_component = a * ED25519_BASEPOINT_POINT;
 
     // Pick a torsion point
-    let small_idx: usize = rng.next_u64() as usize;
-    let r = pick_small_nonzero_point(small_idx + 1);
+    let r = small.pick(rng)?;
 
     let pub_key = pub_key_component + r.neg();
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
-    if (r + compute_hram(&message, &pub_key, &r) * r.neg()).is_identity() {
-        return Err(anyhow!("wrong rng seed"));
+    let hram = HramPrefix::new(&r, &pub_key);
+    let mut message = messages.next_message(rng);
+    while (r + hram.finish(&message) * r.neg()).is_identity() {
+        message = messages.next_message(rng);
     }
-    let s = compute_hram(&message, &pub_key, &r) * a;
+    let k = hram.finish(&message);
+    let s = k * a;
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
-    debug!(
-        "S > 0, mixed A, small R\n\
-             passes cofactored, fails cofactorless, leaks private key\n\
-             \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, small R",
+        "passes cofactored, fails cofactorless, leaks private key",
+    )
+    .ground("R - [k]R != 0, with A = [a]B - R", messages.take())
+    .torsion(&r)
+    .scalar("a", &a)
+    .scalar("k", &k)
+    .scalar("s", &s);
+
+    let tv1 = traced(
+        message.clone(),
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
     );
 
-    let tv1 = TestVector {
-        message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
-
-    while !(r + compute_hram(&message, &pub_key, &r) * r.neg()).is_identity() {
-        rng.fill_bytes(&mut message);
+    while !(r + hram.finish(&message) * r.neg()).is_identity() {
+        message = messages.next_message(rng);
     }
-    let s = compute_hram(&message, &pub_key, &r) * a;
+    let k = hram.finish(&message);
+    let s = k * a;
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
-    debug!(
-        "S > 0, mixed A, small R\n\
-         passes cofactored, passes cofactorless, leaks private key\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
-    );
-    let tv2 = TestVector {
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, small R",
+        "passes cofactored, passes cofactorless, leaks private key",
+    )
+    .ground("R - [k]R = 0, with A = [a]B - R", messages.take())
+    .torsion(&r)
+    .scalar("a", &a)
+    .scalar("k", &k)
+    .scalar("s", &s);
+    let tv2 = traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
+    );
 
     Ok((tv1, tv2))
 }
@@ -240,8 +610,12 @@ pub fn non_zero_small_mixed() -> Result<(TestVector, TestVector)> {
 // 7 (cofactorless) //
 //////////////////////
 
-pub fn non_zero_mixed_mixed() -> Result<(TestVector, TestVector)> {
-    let mut rng = new_rng();
+pub fn non_zero_mixed_mixed(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    small: SmallPoint,
+) -> Result<(TestVector, TestVector)> {
+    let mut messages = Counted::new(messages);
     // Pick a random scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -253,74 +627,95 @@ pub fn non_zero_mixed_mixed() -> Result<(TestVector, TestVector)> {
     rng.fill_bytes(&mut scalar_bytes);
 
     // Pick a torsion point
-    let small_idx: usize = rng.next_u64() as usize;
-    let small_pt = pick_small_nonzero_point(small_idx + 1);
+    let small_pt = small.pick(rng)?;
 
     // generate the r of a "normal" signature
     let prelim_pub_key = a * ED25519_BASEPOINT_POINT;
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
+    let mut message = messages.next_message(rng);
     let mut h = Sha512::new();
-    h.update(&nonce_bytes);
+    h.update(nonce_bytes);
     h.update(&message);
 
     let mut output = [0u8; 64];
-    output.copy_from_slice(h.finalize().as_slice());
+    output.copy_from_slice(&h.finalize()[..]);
     let mut prelim_r = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
 
     let pub_key = prelim_pub_key + small_pt;
     let mut r = prelim_r * ED25519_BASEPOINT_POINT + small_pt.neg();
 
-    if (small_pt.neg() + compute_hram(&message, &pub_key, &r) * small_pt).is_identity() {
-        return Err(anyhow!("wrong rng seed"));
+    while (small_pt.neg() + compute_hram(&message, &pub_key, &r) * small_pt).is_identity() {
+        message = messages.next_message(rng);
+        let mut h = Sha512::new();
+        h.update(nonce_bytes);
+        h.update(&message);
+
+        let mut output = [0u8; 64];
+        output.copy_from_slice(&h.finalize()[..]);
+        prelim_r = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
+
+        r = prelim_r * ED25519_BASEPOINT_POINT + small_pt.neg();
     }
-    let s = prelim_r + compute_hram(&message, &pub_key, &r) * a;
+    let k = compute_hram(&message, &pub_key, &r);
+    let s = prelim_r + k * a;
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
-    debug!(
-        "S > 0, mixed A, mixed R\n\
-             passes cofactored, fails cofactorless\n\
-             \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, mixed R",
+        "passes cofactored, fails cofactorless",
+    )
+    .ground(
+        "[k]T - T != 0, with A = [a]B + T and R = [r]B - T",
+        messages.take(),
+    )
+    .torsion(&small_pt)
+    .scalar("a", &a)
+    .scalar("r", &prelim_r)
+    .scalar("k", &k)
+    .scalar("s", &s);
+
+    let tv1 = traced(
+        message.clone(),
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
     );
 
-    let tv1 = TestVector {
-        message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
-
     while !(small_pt.neg() + compute_hram(&message, &pub_key, &r) * small_pt).is_identity() {
-        rng.fill_bytes(&mut message);
+        message = messages.next_message(rng);
         let mut h = Sha512::new();
-        h.update(&nonce_bytes);
+        h.update(nonce_bytes);
         h.update(&message);
 
         let mut output = [0u8; 64];
-        output.copy_from_slice(h.finalize().as_slice());
+        output.copy_from_slice(&h.finalize()[..]);
         prelim_r = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
 
         r = prelim_r * ED25519_BASEPOINT_POINT + small_pt.neg();
     }
-    let s = prelim_r + compute_hram(&message, &pub_key, &r) * a;
+    let k = compute_hram(&message, &pub_key, &r);
+    let s = prelim_r + k * a;
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
-    debug!(
-        "S > 0, mixed A, mixed R\n\
-         passes cofactored, passes cofactorless\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
-    );
-    let tv2 = TestVector {
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, mixed R",
+        "passes cofactored, passes cofactorless",
+    )
+    .ground(
+        "[k]T - T = 0, with A = [a]B + T and R = [r]B - T",
+        messages.take(),
+    )
+    .torsion(&small_pt)
+    .scalar("a", &a)
+    .scalar("r", &prelim_r)
+    .scalar("k", &k)
+    .scalar("s", &s);
+    let tv2 = traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    };
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
+    );
 
     Ok((tv1, tv2))
 }
@@ -329,9 +724,12 @@ pub fn non_zero_mixed_mixed() -> Result<(TestVector, TestVector)> {
 // 8 (pre-reduced scalar) //
 ////////////////////////////
 
-fn pre_reduced_scalar() -> TestVector {
-    let mut rng = new_rng();
-
+pub fn pre_reduced_scalar(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    small: SmallPoint,
+) -> Result<TestVector> {
+    let mut messages = Counted::new(messages);
     // Pick a random scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -346,29 +744,30 @@ fn pre_reduced_scalar() -> TestVector {
     let prelim_pub_key = a * ED25519_BASEPOINT_POINT;
 
     // Pick a torsion point
-    let small_idx: usize = rng.next_u64() as usize;
-    let small_pt = pick_small_nonzero_point(small_idx + 1);
+    let small_pt = small.pick(rng)?;
     let pub_key = prelim_pub_key + small_pt;
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
+    let mut message = messages.next_message(rng);
     let mut h = Sha512::new();
-    h.update(&nonce_bytes);
+    h.update(nonce_bytes);
     h.update(&message);
 
     let mut output = [0u8; 64];
-    output.copy_from_slice(h.finalize().as_slice());
+    output.copy_from_slice(&h.finalize()[..]);
     let r_scalar = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
     let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let hram = HramPrefix::new(&r, &pub_key);
 
-    // grind a k so that 8*k gets reduced to a number NOT multiple of eight,
-    // and add a small order component to the public key.
-    while multiple_of_eight_le(eight() * compute_hram(&message, &pub_key, &r)) {
-        rng.fill_bytes(&mut message);
+    // grind a k so that neither k nor 8*k reduced mod L are multiples of the
+    // order of the small order component added to the public key.
+    while (eight() * hram.finish(&message) * small_pt).is_identity()
+        || (hram.finish(&message) * small_pt).is_identity()
+    {
+        message = messages.next_message(rng);
     }
 
-    let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
-
+    let k = hram.finish(&message);
+    let s = r_scalar + k * a;
     // that's because we do cofactored verification without pre-reducing scalars
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
 
@@ -377,27 +776,33 @@ fn pre_reduced_scalar() -> TestVector {
 
     // as expected
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
-    debug!(
-        "S > 0, mixed A, large order R\n\
-         passes cofactored, fails pre-reducing cofactored, fails cofactorless\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
-    );
-    TestVector {
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, large order R",
+        "passes cofactored, fails pre-reducing cofactored, fails cofactorless",
+    )
+    .ground(
+        "[k]T != 0 and [8k mod L]T != 0, with A = [a]B + T and R = [r]B",
+        messages.take(),
+    )
+    .torsion(&small_pt)
+    .scalar("a", &a)
+    .scalar("r", &r_scalar)
+    .scalar("k", &k)
+    .scalar("s", &s);
+    Ok(traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s),
-    }
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s),
+        trace,
+    ))
 }
 
 ////////
 // 9  //
 ////////
 
-fn large_s() -> Result<TestVector> {
-    let mut rng = new_rng();
+pub fn large_s(rng: &mut impl RngCore, messages: &mut impl MessageSource) -> Result<TestVector> {
+    let mut messages = Counted::new(messages);
     // Pick a random scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -411,24 +816,27 @@ fn large_s() -> Result<TestVector> {
     // generate the r of a "normal" signature
     let pub_key = a * ED25519_BASEPOINT_POINT;
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
-    let mut h = Sha512::new();
-    h.update(&nonce_bytes);
-    h.update(&message);
+    // grind until S + L passes the high bits checks, whatever the seed: case 7 goes beyond them
+    let (message, r_scalar, r, s, s_prime_bytes) = loop {
+        let message = messages.next_message(rng);
+        let mut h = Sha512::new();
+        h.update(nonce_bytes);
+        h.update(&message);
 
-    let mut output = [0u8; 64];
-    output.copy_from_slice(h.finalize().as_slice());
-    let r_scalar = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
+        let mut output = [0u8; 64];
+        output.copy_from_slice(&h.finalize()[..]);
+        let r_scalar = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
 
-    let r = r_scalar * ED25519_BASEPOINT_POINT;
+        let r = r_scalar * ED25519_BASEPOINT_POINT;
 
-    let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+        let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+        let s_prime_bytes = (BigScalar::from_scalar(&s) + BigScalar::L).to_bytes();
+        if s_prime_bytes[31] & 224u8 == 0u8 {
+            break (message, r_scalar, r, s, s_prime_bytes);
+        }
+    };
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
-
-    let s_nonreducing = Scalar52::from_bytes(&s.to_bytes());
-    let s_prime_bytes = Scalar52::add(&s_nonreducing, &non_reducing_scalar52::L).to_bytes();
     // using deserialize_scalar is key here, we use `from_bits` to represent
     // the scalar
     let s_prime = deserialize_scalar(&s_prime_bytes)?;
@@ -437,19 +845,22 @@ fn large_s() -> Result<TestVector> {
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s_prime)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s_prime)).is_ok());
 
-    debug!(
-        "S > L, large order A, large order R\n\
-         passes cofactored, passes  cofactorless, often excluded from both, breaks strong unforgeability\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s_prime))
-    );
-    let tv = TestVector {
+    let trace = ConstructionTrace::new(
+        "S > L, large order A, large order R",
+        "passes cofactored, passes cofactorless, often excluded from both, breaks strong \
+         unforgeability",
+    )
+    .ground("the 3 high bits of S + L unset", messages.take())
+    .scalar("a", &a)
+    .scalar("r", &r_scalar)
+    .scalar("k", &compute_hram(&message, &pub_key, &r))
+    .scalar("s", &s_prime);
+    let tv = traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s_prime),
-    };
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s_prime),
+        trace,
+    );
 
     Ok(tv)
 }
@@ -458,8 +869,10 @@ fn large_s() -> Result<TestVector> {
 // 10 //
 ////////
 
-fn really_large_s() -> Result<TestVector> {
-    let mut rng = new_rng();
+pub fn really_large_s(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+) -> Result<TestVector> {
     // Pick a random scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -473,26 +886,26 @@ fn really_large_s() -> Result<TestVector> {
     // generate the r of a "normal" signature
     let pub_key = a * ED25519_BASEPOINT_POINT;
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
+    let message = messages.next_message(rng);
     let mut h = Sha512::new();
-    h.update(&nonce_bytes);
+    h.update(nonce_bytes);
     h.update(&message);
 
     let mut output = [0u8; 64];
-    output.copy_from_slice(h.finalize().as_slice());
+    output.copy_from_slice(&h.finalize()[..]);
     let r_scalar = curve25519_dalek::scalar::Scalar::from_bytes_mod_order_wide(&output);
 
     let r = r_scalar * ED25519_BASEPOINT_POINT;
 
-    let s = r_scalar + compute_hram(&message, &pub_key, &r) * a;
+    let k = compute_hram(&message, &pub_key, &r);
+    let s = r_scalar + k * a;
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
 
-    let mut s_nonreducing = Scalar52::from_bytes(&s.to_bytes());
+    let mut s_nonreducing = BigScalar::from_scalar(&s);
     // perform the incomplete higher-bits check often used in place of s<L
-    while (s_nonreducing.to_bytes()[31] as u8 & 224u8) == 0u8 {
-        s_nonreducing = Scalar52::add(&s_nonreducing, &non_reducing_scalar52::L);
+    while s_nonreducing < BigScalar::pow2(253) {
+        s_nonreducing = s_nonreducing + BigScalar::L;
     }
     let s_prime_bytes = s_nonreducing.to_bytes();
 
@@ -504,19 +917,22 @@ fn really_large_s() -> Result<TestVector> {
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s_prime)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s_prime)).is_ok());
 
-    debug!(
-        "S much larger than L, large order A, large order R\n\
-         passes cofactored, passes  cofactorless, often excluded from both due to high bit checks, breaks strong unforgeability\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s_prime))
-    );
-    let tv = TestVector {
+    // no grinding: S + L is added to until a high bit is set
+    let trace = ConstructionTrace::new(
+        "S much larger than L, large order A, large order R",
+        "passes cofactored, passes cofactorless, often excluded from both due to high bit \
+         checks, breaks strong unforgeability",
+    )
+    .scalar("a", &a)
+    .scalar("r", &r_scalar)
+    .scalar("k", &k)
+    .scalar("s", &s_prime);
+    let tv = traced(
         message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature: serialize_signature(&r, &s_prime),
-    };
+        pub_key.compress().to_bytes(),
+        serialize_signature(&r, &s_prime),
+        trace,
+    );
 
     Ok(tv)
 }
@@ -525,16 +941,21 @@ fn really_large_s() -> Result<TestVector> {
 // 11-12 //
 ///////////
 
+/// The decoding of the non-canonical R and A the following vectors are built around.
+pub const NON_CANONICAL_DECODE_MODE: DecodeMode = DecodeMode::Lenient;
+
 // This test vector has R = (-0, 2^255 - 20) of order 2 in non-canonical form, serialialized as ECFFFF..FFFF.
 // Libraries that reject non-canonical encodings of R or small-order R would reject both vectors.
 // The first vector will pass cofactored and cofactorless verifications that reserialize R prior to hashing and fail those that do not reserialize R for the hash.
 // The second vector will behave in an opposite way.
-pub fn non_zero_small_non_canonical_mixed() -> Result<Vec<TestVector>> {
+pub fn non_zero_small_non_canonical_mixed(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    r_arr: [u8; 32],
+) -> Result<Vec<TestVector>> {
+    let mut messages = Counted::new(messages);
     let mut vec = Vec::new();
 
-    // r not identity, with incorrect x sign and y coordinate larger than p
-    let r_arr = EIGHT_TORSION_NON_CANONICAL[2];
-    let mut rng = new_rng();
     // Pick a random scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -543,57 +964,74 @@ pub fn non_zero_small_non_canonical_mixed() -> Result<Vec<TestVector>> {
     debug_assert!(a != Scalar::zero());
 
     let pub_key_component = a * ED25519_BASEPOINT_POINT;
-    let r = deserialize_point(&r_arr[..32]).unwrap();
+    let r = deserialize_point_with(&r_arr[..32], NON_CANONICAL_DECODE_MODE)?;
 
-    let small_idx: usize = rng.next_u64() as usize;
+    // the small-order component of A must generate R for some k to cancel it, which only
+    // R of order 4 or 8 may prevent
+    let mut small_idx: usize = rng.next_u64() as usize;
+    while !(0..8u8)
+        .any(|j| (r + Scalar::from(j) * pick_small_nonzero_point(small_idx + 1)).is_identity())
+    {
+        small_idx = rng.next_u64() as usize;
+    }
     let r2 = pick_small_nonzero_point(small_idx + 1);
     let pub_key = pub_key_component + r2.neg();
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
+    let hram = HramPrefix::new(&r, &pub_key);
+    let hram_provided = HramPrefix::from_arrays(&r_arr[..32], pub_key.compress().as_bytes());
+    let mut message = messages.next_message(rng);
 
-    while !(r + compute_hram(&message, &pub_key, &r) * r2.neg()).is_identity()
-        || !(r + compute_hram_with_r_array(&message, &pub_key, &r_arr[..32]) * r2.neg())
-            .is_identity()
+    while !(r + hram.finish(&message) * r2.neg()).is_identity()
+        || !(r + hram_provided.finish(&message) * r2.neg()).is_identity()
     {
-        rng.fill_bytes(&mut message);
+        message = messages.next_message(rng);
     }
-    let s = compute_hram(&message, &pub_key, &r) * a;
+    let ground_messages = messages.take();
+    let k = hram.finish(&message);
+    let s = k * a;
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
     let mut signature = serialize_signature(&r, &s);
     signature[..32].clone_from_slice(&r_arr[..32]);
-    debug!(
-        "S > 0, mixed A, small non-canonical R\n\
-         passes cofactored, passes cofactorless, leaks private key\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&signature)
-    );
-    let tv1 = TestVector {
-        message,
-        pub_key: pub_key.compress().to_bytes(),
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, small non-canonical R",
+        "passes cofactored, passes cofactorless, leaks private key, S for k hashing R \
+         re-serialized",
+    )
+    .ground(
+        "R - [k]T = 0 for k hashing R re-serialized and as provided, with A = [a]B - T",
+        ground_messages,
+    )
+    .torsion(&r2)
+    .scalar("a", &a)
+    .scalar("k", &k)
+    .scalar("s", &s);
+    let tv1 = traced(
+        message.clone(),
+        pub_key.compress().to_bytes(),
         signature,
-    };
+        trace,
+    );
     vec.push(tv1);
 
-    let s = compute_hram_with_r_array(&message, &pub_key, &r_arr[..32]) * a;
+    let k = hram_provided.finish(&message);
+    let s = k * a;
     let mut signature = serialize_signature(&r, &s);
     signature[..32].clone_from_slice(&r_arr[..32]);
-    debug!(
-        "S > 0, mixed A, small non-canonical R\n\
-         passes cofactored, passes cofactorless, leaks private key\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&signature)
-    );
-    let tv2 = TestVector {
-        message,
-        pub_key: pub_key.compress().to_bytes(),
-        signature,
-    };
+    let trace = ConstructionTrace::new(
+        "S > 0, mixed A, small non-canonical R",
+        "passes cofactored, passes cofactorless, leaks private key, S for k hashing R as \
+         provided",
+    )
+    .ground(
+        "R - [k]T = 0 for k hashing R re-serialized and as provided, with A = [a]B - T",
+        ground_messages,
+    )
+    .torsion(&r2)
+    .scalar("a", &a)
+    .scalar("k", &k)
+    .scalar("s", &s);
+    let tv2 = traced(message, pub_key.compress().to_bytes(), signature, trace);
     vec.push(tv2);
 
     Ok(vec)
@@ -611,13 +1049,14 @@ pub fn non_zero_small_non_canonical_mixed() -> Result<Vec<TestVector>> {
 // but accept the second do not reduce A prior to hashing.
 // Both vectors pass for cofactored verification.
 #[allow(dead_code)]
-pub fn non_zero_mixed_small_non_canonical() -> Result<Vec<TestVector>> {
+pub fn non_zero_mixed_small_non_canonical(
+    rng: &mut impl RngCore,
+    messages: &mut impl MessageSource,
+    pub_key_arr: [u8; 32],
+) -> Result<Vec<TestVector>> {
+    let mut messages = Counted::new(messages);
     let mut vec = Vec::new();
 
-    // pk not identity, with only incorrect x sign
-    let pub_key_arr = EIGHT_TORSION_NON_CANONICAL[2];
-
-    let mut rng = new_rng();
     // Pick a random Scalar
     let mut scalar_bytes = [0u8; 32];
     rng.fill_bytes(&mut scalar_bytes);
@@ -626,147 +1065,895 @@ pub fn non_zero_mixed_small_non_canonical() -> Result<Vec<TestVector>> {
     debug_assert!(s != Scalar::zero());
 
     let r0 = s * ED25519_BASEPOINT_POINT;
-    let pub_key = deserialize_point(&pub_key_arr[..32]).unwrap();
+    let pub_key = deserialize_point_with(&pub_key_arr[..32], NON_CANONICAL_DECODE_MODE)?;
+    // no message grinding can make the hash of A matter when it is the identity
+    if pub_key.is_identity() {
+        return Err(anyhow!("the non-canonical A must not encode the identity"));
+    }
     let r = r0 + pub_key.neg();
 
-    let mut message = [0u8; 32];
-    rng.fill_bytes(&mut message);
+    let hram = HramPrefix::new(&r, &pub_key);
+    let hram_provided = HramPrefix::from_arrays(r.compress().as_bytes(), &pub_key_arr[..32]);
+    let mut message = messages.next_message(rng);
 
     // succeeds when public key is reserialized
-    while !(pub_key.neg() + compute_hram(&message, &pub_key, &r) * pub_key).is_identity()
-        || (pub_key.neg() + compute_hram_with_pk_array(&message, &pub_key_arr[..32], &r) * pub_key)
-            .is_identity()
+    while !(pub_key.neg() + hram.finish(&message) * pub_key).is_identity()
+        || (pub_key.neg() + hram_provided.finish(&message) * pub_key).is_identity()
     {
-        rng.fill_bytes(&mut message);
+        message = messages.next_message(rng);
     }
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
-    debug!(
-        "S > 0, non-canonical A, mixed R\n\
-         passes cofactored, passes cofactorless, repudiable\n\
-         reserializes A\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
+    let trace = ConstructionTrace::new(
+        "S > 0, non-canonical A, mixed R",
+        "passes cofactored, passes cofactorless, repudiable, passes cofactorless iff A is \
+         re-serialized",
+    )
+    .ground(
+        "[k]A - A = 0 for k hashing A re-serialized, != 0 for k hashing A as provided, with \
+         R = [s]B - A",
+        messages.take(),
+    )
+    .torsion(&pub_key)
+    .scalar("k", &hram.finish(&message))
+    .scalar("s", &s);
+    let tv1 = traced(
+        message.clone(),
+        pub_key_arr,
+        serialize_signature(&r, &s),
+        trace,
     );
-    let tv1 = TestVector {
-        message,
-        pub_key: pub_key_arr,
-        signature: serialize_signature(&r, &s),
-    };
     vec.push(tv1);
 
     // succeeds when public key is not-reserialized
-    while !(pub_key.neg() + compute_hram_with_pk_array(&message, &pub_key_arr[..32], &r) * pub_key)
-        .is_identity()
-        || (pub_key.neg() + compute_hram(&message, &pub_key, &r) * pub_key).is_identity()
+    while !(pub_key.neg() + hram_provided.finish(&message) * pub_key).is_identity()
+        || (pub_key.neg() + hram.finish(&message) * pub_key).is_identity()
     {
-        rng.fill_bytes(&mut message);
+        message = messages.next_message(rng);
     }
     debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
     debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
-    debug!(
-        "S > 0, non-canonical A, mixed R\n\
-         passes cofactored, passes cofactorless, repudiable\n\
-         does not reserialize A\n\
-         \"message\": \"{}\", \"pub_key\": \"{}\", \"signature\": \"{}\"",
-        hex::encode(&message),
-        hex::encode(&pub_key.compress().as_bytes()),
-        hex::encode(&serialize_signature(&r, &s))
-    );
-    let tv2 = TestVector {
-        message,
-        pub_key: pub_key_arr,
-        signature: serialize_signature(&r, &s),
-    };
+    let trace = ConstructionTrace::new(
+        "S > 0, non-canonical A, mixed R",
+        "passes cofactored, passes cofactorless, repudiable, passes cofactorless iff A is \
+         not re-serialized",
+    )
+    .ground(
+        "[k]A - A = 0 for k hashing A as provided, != 0 for k hashing A re-serialized, with \
+         R = [s]B - A",
+        messages.take(),
+    )
+    .torsion(&pub_key)
+    .scalar("k", &hram_provided.finish(&message))
+    .scalar("s", &s);
+    let tv2 = traced(message, pub_key_arr, serialize_signature(&r, &s), trace);
     vec.push(tv2);
 
     Ok(vec)
 }
 
+// The last two bytes of a message, in hex, to identify it in the table of cases
+fn hex_tail(message: &[u8]) -> String {
+    hex::encode(&message[message.len().saturating_sub(2)..])
+}
+
+// The comment and the expected cofactored and cofactorless verdicts of each case, as in the
+// table of cases
+const CASES: [(&str, bool, bool); 12] = [
+    ("small A and R", true, true),
+    ("small A only", true, true),
+    ("small R only", true, true),
+    ("succeeds unless full-order is checked", true, true),
+    ("mixed A and R, fails cofactorless", true, false),
+    ("fails cofactored iff (8h) prereduced", true, false),
+    ("S out of bounds", true, true),
+    ("S out of bounds, beyond the high bit checks", true, true),
+    ("non-canonical R, reduced for hash", true, true),
+    ("non-canonical R, not reduced for hash", true, true),
+    ("non-canonical A, reduced for hash", true, true),
+    ("non-canonical A, not reduced for hash", true, true),
+];
+
+/// The stable id of each case, by index: the id of its family in `sarif::CASE_FAMILIES`,
+/// then what tells it apart within the family. Unlike indices, ids are never reassigned, so
+/// that results keyed by them survive cases being added.
+pub const CASE_IDS: [&str; 12] = [
+    "small-order-a-and-r/zero-s",
+    "small-order-a/mixed-r",
+    "small-order-r/mixed-a",
+    "mixed-order/passes-cofactorless",
+    "mixed-order/fails-cofactorless",
+    "pre-reduction/fails-pre-reduced-cofactored",
+    "s-out-of-bounds/within-high-bits",
+    "s-out-of-bounds/beyond-high-bits",
+    "non-canonical-r/reduced-for-hash",
+    "non-canonical-r/not-reduced-for-hash",
+    "non-canonical-a/reduced-for-hash",
+    "non-canonical-a/not-reduced-for-hash",
+];
+
+// The order of `pt`, as in `CaseMetadata`: [L]pt has the order of the small-order component
+// of `pt`, as L is odd
+pub(crate) fn order_name(pt: &EdwardsPoint) -> String {
+    match (point_order(pt), torsion_order(pt)) {
+        (PointOrder::Prime, _) => "L".to_string(),
+        (PointOrder::Mixed, order) => format!("{}L", order),
+        (_, order) => order.to_string(),
+    }
+}
+
+// The metadata of `tv` following from its bytes (the class of S, the orders and encodings of
+// A and R), completed with the rest, and without verdicts of the named policies. Fails on a
+// signature of another size than 64 bytes, or on A or R not decompressing.
+pub(crate) fn case_metadata(
+    tv: &TestVector,
+    name: &str,
+    index: usize,
+    comment: &str,
+    (expected_cofactored, expected_cofactorless): (bool, bool),
+) -> Result<CaseMetadata> {
+    let signature = check_slice_size(&tv.signature, 64, "signature")?;
+    let pub_key = deserialize_point_with(&tv.pub_key, DecodeMode::Lenient)?;
+    let r = deserialize_point_with(&signature[..32], DecodeMode::Lenient)?;
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature[32..]);
+    let s_class = if s_bytes == [0u8; 32] {
+        SClass::Zero
+    } else if Scalar::from_canonical_bytes(s_bytes).is_some() {
+        SClass::Canonical
+    } else {
+        SClass::Large
+    };
+    Ok(CaseMetadata {
+        id: String::new(),
+        name: name.to_string(),
+        index,
+        s_class,
+        a_order: order_name(&pub_key),
+        r_order: order_name(&r),
+        canonical_a: is_canonical_point_encoding(&tv.pub_key),
+        canonical_r: is_canonical_point_encoding(&signature[..32]),
+        expected_cofactored,
+        expected_cofactorless,
+        comment: comment.to_string(),
+        expected_verdicts: BTreeMap::new(),
+    })
+}
+
+// Fills in the metadata of the cases of `generate_test_vectors`, or of one of its variants
+fn annotate_cases(vec: &mut [TestVector]) {
+    for (index, tv) in vec.iter_mut().enumerate() {
+        let (comment, expected_cofactored, expected_cofactorless) = CASES[index];
+        let name = family_of(index).unwrap().name;
+        let metadata = case_metadata(
+            tv,
+            name,
+            index,
+            comment,
+            (expected_cofactored, expected_cofactorless),
+        );
+        tv.metadata = Some(CaseMetadata {
+            id: CASE_IDS[index].to_string(),
+            ..metadata.unwrap()
+        });
+    }
+}
+
+// Fills in the verdicts of the named policies on the annotated cases of Ed25519
+fn annotate_verdicts(vec: &mut [TestVector]) {
+    for (name, policy) in NAMED_POLICIES.iter() {
+        let verdicts = policy.predict(vec);
+        for (tv, verdict) in vec.iter_mut().zip(verdicts) {
+            if let Some(metadata) = tv.metadata.as_mut() {
+                metadata.expected_verdicts.insert(name.to_string(), verdict);
+            }
+        }
+    }
+}
+
 pub fn generate_test_vectors() -> Vec<TestVector> {
+    generate_test_vectors_with(new_rng)
+}
+
+/// `count` independent instances of every case, with their own keys and messages: the i-th
+/// 12 vectors are the cases drawn from the i-th generator derived from
+/// `VectorGenerator::from_u64(seed)`. Fuzzing harnesses sample a case many times over, for
+/// verifiers whose verdicts depend on the bytes drawn rather than on the case alone.
+pub fn generate_test_vectors_n(count: usize, seed: u64) -> Vec<TestVector> {
+    let generator = VectorGenerator::from_u64(seed);
+    (0..count as u64)
+        .flat_map(|index| generator.derive(index).test_vectors())
+        .collect()
+}
+
+// Generates the test vectors, drawing the randomness for each case from a fresh `case_rng()`.
+pub fn generate_test_vectors_with<R: RngCore>(case_rng: impl FnMut() -> R) -> Vec<TestVector> {
+    generate_test_vectors_from(case_rng, &mut RandomBytes::default())
+}
+
+// As `generate_test_vectors_with`, grinding over the messages drawn from `messages`.
+pub fn generate_test_vectors_from<R: RngCore, M: MessageSource>(
+    mut case_rng: impl FnMut() -> R,
+    messages: &mut M,
+) -> Vec<TestVector> {
     let mut info = Builder::default();
     info.append("|  |    msg |    sig |  S   |    A  |    R  | cof-ed | cof-less |        comment        |\n");
     info.append("|---------------------------------------------------------------------------------------|\n");
     let mut vec = Vec::new();
 
     // #0: canonical S, small R, small A
-    let (_tv1, tv2) = zero_small_small().unwrap();
+    let (_tv1, tv2) = zero_small_small(&mut case_rng(), messages, SmallPoint::Random).unwrap();
     info.append(format!(
         "| 0| ..{:} | ..{:} |  = 0 | small | small |    V   |    V     | small A and R |\n",
-        &hex::encode(&tv2.message)[60..],
+        hex_tail(&tv2.message),
         &hex::encode(&tv2.signature)[124..]
     ));
     vec.push(tv2); // passes cofactored, passes cofactorless
 
     // #1: canonical S, mixed R, small A
-    let (_tv1, tv2) = non_zero_mixed_small().unwrap();
+    let (_tv1, tv2) = non_zero_mixed_small(&mut case_rng(), messages, SmallPoint::Random).unwrap();
     info.append(format!(
         "| 1| ..{:} | ..{:} |  < L | small | mixed |    V   |    V     | small A only |\n",
-        &hex::encode(&tv2.message)[60..],
+        hex_tail(&tv2.message),
         &hex::encode(&tv2.signature)[124..]
     ));
     vec.push(tv2); // passes cofactored, passes cofactorless
 
     // #2: canonical S, small R, mixed A
-    let (_tv1, tv2) = non_zero_small_mixed().unwrap();
+    let (_tv1, tv2) = non_zero_small_mixed(&mut case_rng(), messages, SmallPoint::Random).unwrap();
     info.append(format!(
         "| 2| ..{:} | ..{:} |  < L | mixed | small |    V   |    V     | small R only |\n",
-        &hex::encode(&tv2.message)[60..],
+        hex_tail(&tv2.message),
         &hex::encode(&tv2.signature)[124..]
     ));
     vec.push(tv2); // passes cofactored, passes cofactorless
 
     // #3-4: canonical S, mixed R, mixed A
-    let (tv1, tv2) = non_zero_mixed_mixed().unwrap();
-    info.append(format!("| 3| ..{:} | ..{:} |  < L | mixed | mixed |    V   |    V     | succeeds unless full-order is checked |\n", &hex::encode(&tv2.message)[60..], &hex::encode(&tv2.signature)[124..]));
+    let (tv1, tv2) = non_zero_mixed_mixed(&mut case_rng(), messages, SmallPoint::Random).unwrap();
+    info.append(format!("| 3| ..{:} | ..{:} |  < L | mixed | mixed |    V   |    V     | succeeds unless full-order is checked |\n", hex_tail(&tv2.message), &hex::encode(&tv2.signature)[124..]));
     vec.push(tv2); // passes cofactored, passes cofactorless
     info.append(format!(
         "| 4| ..{:} | ..{:} |  < L | mixed | mixed |    V   |    X     |  |\n",
-        &hex::encode(&tv1.message)[60..],
+        hex_tail(&tv1.message),
         &hex::encode(&tv1.signature)[124..]
     ));
     vec.push(tv1); // passes cofactored, fails cofactorless
 
     // #5 Prereduce scalar which fails cofactorless
-    let tv1 = pre_reduced_scalar();
-    info.append(format!("| 5| ..{:} | ..{:} |  < L | mixed |   L   |    V*  |    X     | fails cofactored iff (8h) prereduced |\n", &hex::encode(&tv1.message)[60..], &hex::encode(&tv1.signature)[124..]));
+    let tv1 = pre_reduced_scalar(&mut case_rng(), messages, SmallPoint::Random).unwrap();
+    info.append(format!("| 5| ..{:} | ..{:} |  < L | mixed |   L   |    V*  |    X     | fails cofactored iff (8h) prereduced |\n", hex_tail(&tv1.message), &hex::encode(&tv1.signature)[124..]));
     vec.push(tv1);
 
     // #6 Large S
-    let tv1 = large_s().unwrap();
+    let tv1 = large_s(&mut case_rng(), messages).unwrap();
     info.append(format!(
         "| 6| ..{:} | ..{:} |  > L |   L   |   L   |    V   |    V     |  |\n",
-        &hex::encode(&tv1.message)[60..],
+        hex_tail(&tv1.message),
         &hex::encode(&tv1.signature)[124..]
     ));
     vec.push(tv1);
 
     // #7 Large S beyond the high bit checks (i.e. non-canonical representation)
-    let tv1 = really_large_s().unwrap();
+    let tv1 = really_large_s(&mut case_rng(), messages).unwrap();
     info.append(format!(
         "| 7| ..{:} | ..{:} | >> L |   L   |   L   |    V   |    V     |  |\n",
-        &hex::encode(&tv1.message)[60..],
+        hex_tail(&tv1.message),
         &hex::encode(&tv1.signature)[124..]
     ));
     vec.push(tv1);
 
-    // #8-9 Non canonical R
-    let mut tv_vec = non_zero_small_non_canonical_mixed().unwrap();
+    // #8-9 Non canonical R, not identity, with incorrect x sign and y coordinate larger than p
+    let mut tv_vec = non_zero_small_non_canonical_mixed(
+        &mut case_rng(),
+        messages,
+        EIGHT_TORSION_NON_CANONICAL[2],
+    )
+    .unwrap();
     assert!(tv_vec.len() == 2);
-    info.append(format!("| 8| ..{:} | ..{:} |  < L | mixed | small*|    V   |    V     | non-canonical R, reduced for hash |\n", &hex::encode(&tv_vec[0].message)[60..], &hex::encode(&tv_vec[0].signature)[124..]));
-    info.append(format!("| 9| ..{:} | ..{:} |  < L | mixed | small*|    V   |    V     | non-canonical R, not reduced for hash |\n", &hex::encode(&tv_vec[1].message)[60..], &hex::encode(&tv_vec[1].signature)[124..]));
+    info.append(format!("| 8| ..{:} | ..{:} |  < L | mixed | small*|    V   |    V     | non-canonical R, reduced for hash |\n", hex_tail(&tv_vec[0].message), &hex::encode(&tv_vec[0].signature)[124..]));
+    info.append(format!("| 9| ..{:} | ..{:} |  < L | mixed | small*|    V   |    V     | non-canonical R, not reduced for hash |\n", hex_tail(&tv_vec[1].message), &hex::encode(&tv_vec[1].signature)[124..]));
     vec.append(&mut tv_vec);
 
-    // #10-11 Non canonical A
-    let mut tv_vec = non_zero_mixed_small_non_canonical().unwrap();
+    // #10-11 Non canonical A, not identity, with only incorrect x sign
+    let mut tv_vec = non_zero_mixed_small_non_canonical(
+        &mut case_rng(),
+        messages,
+        EIGHT_TORSION_NON_CANONICAL[2],
+    )
+    .unwrap();
     assert!(tv_vec.len() == 2);
-    info.append(format!("|10| ..{:} | ..{:} |  < L | small*| mixed |    V   |    V     | non-canonical A, reduced for hash |\n", &hex::encode(&tv_vec[0].message)[60..], &hex::encode(&tv_vec[0].signature)[124..]));
-    info.append(format!("|11| ..{:} | ..{:} |  < L | small*| mixed |    V   |    V     | non-canonical A, not reduced for hash |\n", &hex::encode(&tv_vec[1].message)[60..], &hex::encode(&tv_vec[1].signature)[124..]));
+    info.append(format!("|10| ..{:} | ..{:} |  < L | small*| mixed |    V   |    V     | non-canonical A, reduced for hash |\n", hex_tail(&tv_vec[0].message), &hex::encode(&tv_vec[0].signature)[124..]));
+    info.append(format!("|11| ..{:} | ..{:} |  < L | small*| mixed |    V   |    V     | non-canonical A, not reduced for hash |\n", hex_tail(&tv_vec[1].message), &hex::encode(&tv_vec[1].signature)[124..]));
     vec.append(&mut tv_vec);
 
     // print!("{}", info.string().unwrap());
 
+    annotate_cases(&mut vec);
+    annotate_verdicts(&mut vec);
     vec
 }
+
+/// The edge cases of `generate_test_vectors`, for Ed25519ph: the pre-hashed variant of
+/// RFC 8032 (Section 5.1), signing the SHA-512 digest of the message, with the challenge
+/// hash prefixed by `dom2(1, context)`. The vectors carry the message before pre-hashing.
+// The cases of `generate_test_vectors` for the variants of Ed25519 hashing a dom2 prefix,
+// Ed25519ph and Ed25519ctx
+mod dom2 {
+    use super::TestVector;
+    use crate::message_source::{MessageSource, RandomBytes};
+    use crate::non_reducing_scalar52::BigScalar;
+    use crate::{
+        deserialize_point_with, eight, new_rng, pick_small_nonzero_point, serialize_signature,
+        verify_final_cofactored, verify_final_cofactorless, verify_final_pre_reduced_cofactored,
+        EIGHT_TORSION_NON_CANONICAL,
+    };
+    use anyhow::{anyhow, Result};
+    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use curve25519_dalek::traits::IsIdentity;
+    use rand::RngCore;
+    use sha2::{Digest, Sha512};
+    use std::ops::Neg;
+
+    const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+
+    // The variant of Ed25519, as the flag and the context of `dom2(flag, context)`
+    #[derive(Clone, Copy)]
+    pub(super) struct Dom2<'a> {
+        // Ed25519ph, hashing SHA-512(message) rather than the message as Ed25519ctx does
+        pub(super) prehashed: bool,
+        pub(super) context: &'a [u8],
+    }
+
+    impl Dom2<'_> {
+        pub(super) fn compute_hram_with_arrays(
+            &self,
+            message: &[u8],
+            pub_key: &[u8],
+            signature_r: &[u8],
+        ) -> Result<Scalar> {
+            if self.prehashed {
+                self.compute_hram_with_input(&Sha512::digest(message), pub_key, signature_r)
+            } else {
+                self.compute_hram_with_input(message, pub_key, signature_r)
+            }
+        }
+
+        // The challenge hashing `input` last: PH(M) for Ed25519ph, M for Ed25519ctx
+        pub(super) fn compute_hram_with_input(
+            &self,
+            input: &[u8],
+            pub_key: &[u8],
+            signature_r: &[u8],
+        ) -> Result<Scalar> {
+            if self.context.len() > 255 {
+                return Err(anyhow!(
+                    "the context must be at most 255 bytes, got {}",
+                    self.context.len()
+                ));
+            }
+            let hasher = Sha512::default()
+                .chain(DOM2_PREFIX)
+                .chain([self.prehashed as u8, self.context.len() as u8])
+                .chain(self.context)
+                .chain(signature_r)
+                .chain(pub_key)
+                .chain(input);
+            let mut k_output = [0u8; 64];
+            k_output.copy_from_slice(&hasher.finalize()[..]);
+            Ok(Scalar::from_bytes_mod_order_wide(&k_output))
+        }
+
+        pub(super) fn compute_hram(
+            &self,
+            message: &[u8],
+            pub_key: &EdwardsPoint,
+            signature_r: &EdwardsPoint,
+        ) -> Result<Scalar> {
+            self.compute_hram_with_arrays(
+                message,
+                pub_key.compress().as_bytes(),
+                signature_r.compress().as_bytes(),
+            )
+        }
+
+        pub(super) fn verify_cofactored(
+            &self,
+            message: &[u8],
+            pub_key: &EdwardsPoint,
+            unpacked_signature: &(EdwardsPoint, Scalar),
+        ) -> Result<()> {
+            let k = self.compute_hram(message, pub_key, &unpacked_signature.0)?;
+            verify_final_cofactored(pub_key, unpacked_signature, &k)
+        }
+
+        pub(super) fn verify_cofactorless(
+            &self,
+            message: &[u8],
+            pub_key: &EdwardsPoint,
+            unpacked_signature: &(EdwardsPoint, Scalar),
+        ) -> Result<()> {
+            let k = self.compute_hram(message, pub_key, &unpacked_signature.0)?;
+            verify_final_cofactorless(pub_key, unpacked_signature, &k)
+        }
+
+        pub(super) fn verify_pre_reduced_cofactored(
+            &self,
+            message: &[u8],
+            pub_key: &EdwardsPoint,
+            unpacked_signature: &(EdwardsPoint, Scalar),
+        ) -> Result<()> {
+            let k = self.compute_hram(message, pub_key, &unpacked_signature.0)?;
+            verify_final_pre_reduced_cofactored(pub_key, unpacked_signature, &k)
+        }
+    }
+
+    // Draws messages from `messages` until `accept` holds for one
+    fn grind(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        mut accept: impl FnMut(&[u8]) -> Result<bool>,
+    ) -> Result<Vec<u8>> {
+        loop {
+            let message = messages.next_message(rng);
+            if accept(&message)? {
+                return Ok(message);
+            }
+        }
+    }
+
+    fn random_scalar(rng: &mut impl RngCore) -> Scalar {
+        let mut scalar_bytes = [0u8; 32];
+        rng.fill_bytes(&mut scalar_bytes);
+        Scalar::from_bytes_mod_order(scalar_bytes)
+    }
+
+    fn small_point(rng: &mut impl RngCore) -> EdwardsPoint {
+        pick_small_nonzero_point(rng.next_u64() as usize + 1)
+    }
+
+    fn vector(message: Vec<u8>, pub_key: &EdwardsPoint, signature: Vec<u8>) -> TestVector {
+        TestVector {
+            message,
+            pub_key: pub_key.compress().to_bytes(),
+            signature,
+            metadata: None,
+            trace: None,
+        }
+    }
+
+    // #0: S = 0, small A, small R, passing cofactorless
+    fn zero_small_small(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+    ) -> Result<TestVector> {
+        let pub_key = small_point(rng);
+        let r = pub_key.neg();
+        let s = Scalar::zero();
+        let message = grind(rng, messages, |m| {
+            Ok((r + dom.compute_hram(m, &pub_key, &r)? * pub_key).is_identity())
+        })?;
+        debug_assert!(dom.verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
+        Ok(vector(message, &pub_key, serialize_signature(&r, &s)))
+    }
+
+    // #1: small A, mixed R, passing cofactorless
+    fn non_zero_mixed_small(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+    ) -> Result<TestVector> {
+        let s = random_scalar(rng);
+        let pub_key = small_point(rng);
+        let r = s * ED25519_BASEPOINT_POINT + pub_key.neg();
+        let message = grind(rng, messages, |m| {
+            Ok((pub_key.neg() + dom.compute_hram(m, &pub_key, &r)? * pub_key).is_identity())
+        })?;
+        debug_assert!(dom.verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
+        Ok(vector(message, &pub_key, serialize_signature(&r, &s)))
+    }
+
+    // #2: mixed A, small R, passing cofactorless
+    fn non_zero_small_mixed(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+    ) -> Result<TestVector> {
+        let a = random_scalar(rng);
+        let r = small_point(rng);
+        let pub_key = a * ED25519_BASEPOINT_POINT + r.neg();
+        let message = grind(rng, messages, |m| {
+            Ok((r + dom.compute_hram(m, &pub_key, &r)? * r.neg()).is_identity())
+        })?;
+        let s = dom.compute_hram(&message, &pub_key, &r)? * a;
+        debug_assert!(dom.verify_cofactorless(&message, &pub_key, &(r, s)).is_ok());
+        Ok(vector(message, &pub_key, serialize_signature(&r, &s)))
+    }
+
+    // #3-4: mixed A, mixed R, passing then failing cofactorless
+    fn non_zero_mixed_mixed(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+        passes_cofactorless: bool,
+    ) -> Result<TestVector> {
+        let a = random_scalar(rng);
+        let r_scalar = random_scalar(rng);
+        let small_pt = small_point(rng);
+        let pub_key = a * ED25519_BASEPOINT_POINT + small_pt;
+        let r = r_scalar * ED25519_BASEPOINT_POINT + small_pt.neg();
+        let message = grind(rng, messages, |m| {
+            let k = dom.compute_hram(m, &pub_key, &r)?;
+            Ok((small_pt.neg() + k * small_pt).is_identity() == passes_cofactorless)
+        })?;
+        let s = r_scalar + dom.compute_hram(&message, &pub_key, &r)? * a;
+        debug_assert!(dom.verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
+        debug_assert_eq!(
+            dom.verify_cofactorless(&message, &pub_key, &(r, s)).is_ok(),
+            passes_cofactorless
+        );
+        Ok(vector(message, &pub_key, serialize_signature(&r, &s)))
+    }
+
+    // #5: mixed A, large order R, failing pre-reduced cofactored and cofactorless
+    fn pre_reduced_scalar(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+    ) -> Result<TestVector> {
+        let a = random_scalar(rng);
+        let r_scalar = random_scalar(rng);
+        let small_pt = small_point(rng);
+        let pub_key = a * ED25519_BASEPOINT_POINT + small_pt;
+        let r = r_scalar * ED25519_BASEPOINT_POINT;
+        let message = grind(rng, messages, |m| {
+            let k = dom.compute_hram(m, &pub_key, &r)?;
+            Ok(!(eight() * k * small_pt).is_identity() && !(k * small_pt).is_identity())
+        })?;
+        let s = r_scalar + dom.compute_hram(&message, &pub_key, &r)? * a;
+        debug_assert!(dom.verify_cofactored(&message, &pub_key, &(r, s)).is_ok());
+        debug_assert!(dom
+            .verify_pre_reduced_cofactored(&message, &pub_key, &(r, s))
+            .is_err());
+        debug_assert!(dom
+            .verify_cofactorless(&message, &pub_key, &(r, s))
+            .is_err());
+        Ok(vector(message, &pub_key, serialize_signature(&r, &s)))
+    }
+
+    // #6-7: large order A and R, S + L then S + nL with its 3 high bits not all unset
+    fn large_s(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+        beyond_high_bits: bool,
+    ) -> Result<TestVector> {
+        let a = random_scalar(rng);
+        let r_scalar = random_scalar(rng);
+        let pub_key = a * ED25519_BASEPOINT_POINT;
+        let r = r_scalar * ED25519_BASEPOINT_POINT;
+        let message = messages.next_message(rng);
+        let s = r_scalar + dom.compute_hram(&message, &pub_key, &r)? * a;
+
+        let mut s_nonreducing = BigScalar::from_scalar(&s) + BigScalar::L;
+        while beyond_high_bits && s_nonreducing < BigScalar::pow2(253) {
+            s_nonreducing = s_nonreducing + BigScalar::L;
+        }
+        let s_prime = Scalar::from_bits(s_nonreducing.to_bytes());
+        debug_assert!(dom
+            .verify_cofactored(&message, &pub_key, &(r, s_prime))
+            .is_ok());
+        debug_assert!(dom
+            .verify_cofactorless(&message, &pub_key, &(r, s_prime))
+            .is_ok());
+        Ok(vector(message, &pub_key, serialize_signature(&r, &s_prime)))
+    }
+
+    // #8-9: mixed A, small non-canonical R, passing cofactorless when hashing R re-serialized
+    // then as provided
+    fn non_zero_small_non_canonical_mixed(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+        r_arr: [u8; 32],
+    ) -> Result<Vec<TestVector>> {
+        let a = random_scalar(rng);
+        let r = deserialize_point_with(&r_arr, super::NON_CANONICAL_DECODE_MODE)?;
+        let r2 = small_point(rng);
+        let pub_key = a * ED25519_BASEPOINT_POINT + r2.neg();
+        let message = grind(rng, messages, |m| {
+            let k = dom.compute_hram(m, &pub_key, &r)?;
+            let k_raw = dom.compute_hram_with_arrays(m, pub_key.compress().as_bytes(), &r_arr)?;
+            Ok((r + k * r2.neg()).is_identity() && (r + k_raw * r2.neg()).is_identity())
+        })?;
+
+        let mut vec = Vec::new();
+        for k in [
+            dom.compute_hram(&message, &pub_key, &r)?,
+            dom.compute_hram_with_arrays(&message, pub_key.compress().as_bytes(), &r_arr)?,
+        ]
+        .iter()
+        {
+            let mut signature = serialize_signature(&r, &(k * a));
+            signature[..32].copy_from_slice(&r_arr);
+            vec.push(vector(message.clone(), &pub_key, signature));
+        }
+        Ok(vec)
+    }
+
+    // #10-11: small non-canonical A, mixed R, passing cofactorless when hashing A
+    // re-serialized then as provided
+    fn non_zero_mixed_small_non_canonical(
+        rng: &mut impl RngCore,
+        messages: &mut impl MessageSource,
+        dom: Dom2<'_>,
+        pub_key_arr: [u8; 32],
+    ) -> Result<Vec<TestVector>> {
+        let s = random_scalar(rng);
+        let pub_key = deserialize_point_with(&pub_key_arr, super::NON_CANONICAL_DECODE_MODE)?;
+        if pub_key.is_identity() {
+            return Err(anyhow!("the non-canonical A must not encode the identity"));
+        }
+        let r = s * ED25519_BASEPOINT_POINT + pub_key.neg();
+        let r_arr = r.compress().to_bytes();
+
+        let mut vec = Vec::new();
+        for &reserialized in [true, false].iter() {
+            let message = grind(rng, messages, |m| {
+                let k = dom.compute_hram(m, &pub_key, &r)?;
+                let k_raw = dom.compute_hram_with_arrays(m, &pub_key_arr, &r_arr)?;
+                let passes = |k: Scalar| (pub_key.neg() + k * pub_key).is_identity();
+                Ok(passes(k) == reserialized && passes(k_raw) != reserialized)
+            })?;
+            vec.push(TestVector {
+                message,
+                pub_key: pub_key_arr,
+                signature: serialize_signature(&r, &s),
+                metadata: None,
+                trace: None,
+            });
+        }
+        Ok(vec)
+    }
+
+    // The 12 cases of `generate_test_vectors`, in the same order and with the same expected
+    // verdicts, signed in the variant `dom`
+    pub(super) fn generate_test_vectors(dom: Dom2<'_>) -> Result<Vec<TestVector>> {
+        let messages = &mut RandomBytes::default();
+        let mut vec = vec![
+            zero_small_small(&mut new_rng(), messages, dom)?,
+            non_zero_mixed_small(&mut new_rng(), messages, dom)?,
+            non_zero_small_mixed(&mut new_rng(), messages, dom)?,
+            non_zero_mixed_mixed(&mut new_rng(), messages, dom, true)?,
+            non_zero_mixed_mixed(&mut new_rng(), messages, dom, false)?,
+            pre_reduced_scalar(&mut new_rng(), messages, dom)?,
+            large_s(&mut new_rng(), messages, dom, false)?,
+            large_s(&mut new_rng(), messages, dom, true)?,
+        ];
+        vec.append(&mut non_zero_small_non_canonical_mixed(
+            &mut new_rng(),
+            messages,
+            dom,
+            EIGHT_TORSION_NON_CANONICAL[2],
+        )?);
+        vec.append(&mut non_zero_mixed_small_non_canonical(
+            &mut new_rng(),
+            messages,
+            dom,
+            EIGHT_TORSION_NON_CANONICAL[2],
+        )?);
+        super::annotate_cases(&mut vec);
+        Ok(vec)
+    }
+}
+
+pub mod ed25519ph {
+    use super::dom2::{self, Dom2};
+    use super::TestVector;
+    use anyhow::Result;
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::scalar::Scalar;
+
+    /// The context `generate_test_vectors` signs with, empty as in the Ed25519ph test vector
+    /// of RFC 8032.
+    pub const CONTEXT: &[u8] = b"";
+
+    fn dom2(context: &[u8]) -> Dom2<'_> {
+        Dom2 {
+            prehashed: true,
+            context,
+        }
+    }
+
+    /// The challenge `SHA-512(dom2(1, context) || R || A || SHA-512(message))`, hashing A and
+    /// R as provided. Fails on contexts longer than 255 bytes.
+    pub fn compute_hram_with_arrays(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &[u8],
+        signature_r: &[u8],
+    ) -> Result<Scalar> {
+        dom2(context).compute_hram_with_arrays(message, pub_key, signature_r)
+    }
+
+    /// As `compute_hram_with_arrays`, given the SHA-512 digest of the message rather than the
+    /// message, as verifiers of prehashed messages are.
+    pub fn compute_hram_with_prehash(
+        context: &[u8],
+        prehash: &[u8],
+        pub_key: &[u8],
+        signature_r: &[u8],
+    ) -> Result<Scalar> {
+        dom2(context).compute_hram_with_input(prehash, pub_key, signature_r)
+    }
+
+    /// As `compute_hram_with_arrays`, hashing the re-serialized A and R.
+    pub fn compute_hram(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        signature_r: &EdwardsPoint,
+    ) -> Result<Scalar> {
+        dom2(context).compute_hram(message, pub_key, signature_r)
+    }
+
+    /// Cofactored Ed25519ph verification, see `crate::verify_cofactored`.
+    pub fn verify_cofactored(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> Result<()> {
+        dom2(context).verify_cofactored(message, pub_key, unpacked_signature)
+    }
+
+    /// Cofactorless Ed25519ph verification, see `crate::verify_cofactorless`.
+    pub fn verify_cofactorless(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> Result<()> {
+        dom2(context).verify_cofactorless(message, pub_key, unpacked_signature)
+    }
+
+    /// Pre-reduced cofactored Ed25519ph verification, see
+    /// `crate::verify_pre_reduced_cofactored`.
+    pub fn verify_pre_reduced_cofactored(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> Result<()> {
+        dom2(context).verify_pre_reduced_cofactored(message, pub_key, unpacked_signature)
+    }
+
+    /// The 12 cases of `generate_test_vectors`, in the same order and with the same expected
+    /// verdicts, signed in Ed25519ph under `CONTEXT`.
+    pub fn generate_test_vectors() -> Vec<TestVector> {
+        generate_test_vectors_with_context(CONTEXT).unwrap()
+    }
+
+    /// As `generate_test_vectors`, under `context`, which fails if longer than 255 bytes.
+    pub fn generate_test_vectors_with_context(context: &[u8]) -> Result<Vec<TestVector>> {
+        dom2::generate_test_vectors(dom2(context))
+    }
+}
+
+pub mod ed25519ctx {
+    use super::dom2::{self, Dom2};
+    use super::TestVector;
+    use anyhow::Result;
+    use curve25519_dalek::edwards::EdwardsPoint;
+    use curve25519_dalek::scalar::Scalar;
+    use serde::ser::SerializeStruct;
+    use serde::{Serialize, Serializer};
+
+    /// The lengths of the contexts `generate_test_vectors` signs under: the empty context,
+    /// which RFC 8032 discourages for Ed25519ctx but which still differs from Ed25519 as it
+    /// is hashed with a dom2 prefix, a single byte, and the longest context dom2 encodes.
+    pub const CONTEXT_LENGTHS: [usize; 3] = [0, 1, 255];
+
+    /// The cases of `generate_test_vectors` signed in Ed25519ctx under `context`.
+    pub struct ContextVectors {
+        pub context: Vec<u8>,
+        pub vectors: Vec<TestVector>,
+    }
+
+    impl Serialize for ContextVectors {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("ContextVectors", 2)?;
+            state.serialize_field("context", &hex::encode(&self.context))?;
+            state.serialize_field("vectors", &self.vectors)?;
+            state.end()
+        }
+    }
+
+    fn dom2(context: &[u8]) -> Dom2<'_> {
+        Dom2 {
+            prehashed: false,
+            context,
+        }
+    }
+
+    /// The context of `len` bytes the vectors are signed under, counting up from 0.
+    pub fn context(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    /// The challenge `SHA-512(dom2(0, context) || R || A || message)`, hashing A and R as
+    /// provided. Fails on contexts longer than 255 bytes.
+    pub fn compute_hram_with_arrays(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &[u8],
+        signature_r: &[u8],
+    ) -> Result<Scalar> {
+        dom2(context).compute_hram_with_arrays(message, pub_key, signature_r)
+    }
+
+    /// As `compute_hram_with_arrays`, hashing the re-serialized A and R.
+    pub fn compute_hram(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        signature_r: &EdwardsPoint,
+    ) -> Result<Scalar> {
+        dom2(context).compute_hram(message, pub_key, signature_r)
+    }
+
+    /// Cofactored Ed25519ctx verification, see `crate::verify_cofactored`.
+    pub fn verify_cofactored(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> Result<()> {
+        dom2(context).verify_cofactored(message, pub_key, unpacked_signature)
+    }
+
+    /// Cofactorless Ed25519ctx verification, see `crate::verify_cofactorless`.
+    pub fn verify_cofactorless(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> Result<()> {
+        dom2(context).verify_cofactorless(message, pub_key, unpacked_signature)
+    }
+
+    /// Pre-reduced cofactored Ed25519ctx verification, see
+    /// `crate::verify_pre_reduced_cofactored`.
+    pub fn verify_pre_reduced_cofactored(
+        context: &[u8],
+        message: &[u8],
+        pub_key: &EdwardsPoint,
+        unpacked_signature: &(EdwardsPoint, Scalar),
+    ) -> Result<()> {
+        dom2(context).verify_pre_reduced_cofactored(message, pub_key, unpacked_signature)
+    }
+
+    /// The 12 cases of `generate_test_vectors`, in the same order and with the same expected
+    /// verdicts, signed in Ed25519ctx under the context of each of `CONTEXT_LENGTHS`.
+    pub fn generate_test_vectors() -> Vec<ContextVectors> {
+        CONTEXT_LENGTHS
+            .iter()
+            .map(|&len| {
+                let context = context(len);
+                let vectors = generate_test_vectors_with_context(&context).unwrap();
+                ContextVectors { context, vectors }
+            })
+            .collect()
+    }
+
+    /// The 12 cases of `generate_test_vectors` signed in Ed25519ctx under `context`, which
+    /// fails if longer than 255 bytes.
+    pub fn generate_test_vectors_with_context(context: &[u8]) -> Result<Vec<TestVector>> {
+        dom2::generate_test_vectors(dom2(context))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::test_vectors::TestVector;
+use crate::torsion::DECODE_MODE;
+use crate::{
+    compute_hram, deserialize_point_with, serialize_signature, verify_cofactored,
+    verify_cofactorless, EIGHT_TORSION,
+};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+
+/// A vector whose S is a tiny scalar, with R crafted as `[S]B - [k]A` so that the equation
+/// holds, probing verifiers special-casing small multiples of B (e.g. through table
+/// lookups). The challenge k depends on R, hence only a small-order A lets R be computed
+/// before k: `[k]A` is then one of at most 8 points, which grinding the message selects.
+#[derive(Serialize)]
+pub struct TinyScalarVector {
+    pub comment: String,
+    pub vector: TestVector,
+    pub s: u8,
+    /// The order of A: 1 for the identity, 8 otherwise.
+    pub pub_key_order: usize,
+    pub passes_cofactored: bool,
+    pub passes_cofactorless: bool,
+}
+
+/// The tiny values of S covered: the smallest ones, and those around the cofactor.
+pub const TINY_SCALARS: [u8; 4] = [1, 2, 7, 8];
+
+// The identity, and a point of order 8, from `EIGHT_TORSION`
+const PUB_KEYS: [(usize, usize); 2] = [(1, 0), (8, 1)];
+
+fn tiny_scalar_vector(
+    rng: &mut impl RngCore,
+    s: u8,
+    (pub_key_order, pub_key_idx): (usize, usize),
+    passes_cofactorless: bool,
+) -> TinyScalarVector {
+    let pub_key = deserialize_point_with(&EIGHT_TORSION[pub_key_idx], DECODE_MODE).unwrap();
+    let s_scalar = Scalar::from(s);
+    // R is crafted for [k]A = A, i.e. k = 1 mod 8 unless A is the identity
+    let r: EdwardsPoint = s_scalar * ED25519_BASEPOINT_POINT - pub_key;
+
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    while (compute_hram(&message, &pub_key, &r) * pub_key == pub_key) != passes_cofactorless {
+        rng.fill_bytes(&mut message);
+    }
+
+    debug_assert!(verify_cofactored(&message, &pub_key, &(r, s_scalar)).is_ok());
+    debug_assert_eq!(
+        verify_cofactorless(&message, &pub_key, &(r, s_scalar)).is_ok(),
+        passes_cofactorless
+    );
+
+    TinyScalarVector {
+        comment: format!(
+            "S = {}, A of order {}, R = [S]B - A, {} cofactorless",
+            s,
+            pub_key_order,
+            if passes_cofactorless {
+                "passes"
+            } else {
+                "fails"
+            }
+        ),
+        vector: TestVector {
+            message: message.to_vec(),
+            pub_key: pub_key.compress().to_bytes(),
+            signature: serialize_signature(&r, &s_scalar),
+            metadata: None,
+            trace: None,
+        },
+        s,
+        pub_key_order,
+        passes_cofactored: true,
+        passes_cofactorless,
+    }
+}
+
+/// For every tiny S: A the identity, then A of order 8 with the message ground so that the
+/// cofactorless equation holds, then so that it fails.
+pub fn tiny_scalar_vectors(rng: &mut impl RngCore) -> Vec<TinyScalarVector> {
+    let mut vectors = Vec::new();
+    for &s in TINY_SCALARS.iter() {
+        vectors.push(tiny_scalar_vector(rng, s, PUB_KEYS[0], true));
+        vectors.push(tiny_scalar_vector(rng, s, PUB_KEYS[1], true));
+        vectors.push(tiny_scalar_vector(rng, s, PUB_KEYS[1], false));
+    }
+    vectors
+}
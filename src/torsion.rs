@@ -0,0 +1,84 @@
+use crate::DecodeMode;
+
+// The 8-torsion subgroup E[8].
+//
+// In the case of Curve25519, it is cyclic; the i-th element of
+// the array is [i]P, where P is a point of order 8
+// generating E[8].
+//
+// Thus E[4] is the points indexed by `0,2,4,6`, and
+// E[2] is the points indexed by `0,4`.
+//
+// The following byte arrays have been ported from curve25519-dalek /backend/serial/u64/constants.rs
+// and they represent the serialised version of the CompressedEdwardsY points.
+pub const EIGHT_TORSION: [[u8; 32]; 8] = [
+    [
+        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ], // (0,1), order 1, neutral element
+    [
+        199, 23, 106, 112, 61, 77, 216, 79, 186, 60, 11, 118, 13, 16, 103, 15, 42, 32, 83, 250, 44,
+        57, 204, 198, 78, 199, 253, 119, 146, 172, 3, 122,
+    ], // order 8
+    [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 128,
+    ], // order 4
+    [
+        38, 232, 149, 143, 194, 178, 39, 176, 69, 195, 244, 137, 242, 239, 152, 240, 213, 223, 172,
+        5, 211, 198, 51, 57, 177, 56, 2, 136, 109, 83, 252, 5,
+    ], // order 8
+    [
+        236, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 127,
+    ], // order 2
+    [
+        38, 232, 149, 143, 194, 178, 39, 176, 69, 195, 244, 137, 242, 239, 152, 240, 213, 223, 172,
+        5, 211, 198, 51, 57, 177, 56, 2, 136, 109, 83, 252, 133,
+    ], // order 8
+    [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ], // order 4
+    [
+        199, 23, 106, 112, 61, 77, 216, 79, 186, 60, 11, 118, 13, 16, 103, 15, 42, 32, 83, 250, 44,
+        57, 204, 198, 78, 199, 253, 119, 146, 172, 3, 250,
+    ], // order 8
+];
+
+// The decoding of the points of `EIGHT_TORSION`, strict as it holds canonical encodings only
+pub(crate) const DECODE_MODE: DecodeMode = DecodeMode::Strict;
+
+// A point of each order a small-order component may have: its order, and its index in
+// `EIGHT_TORSION`
+pub(crate) const TORSION_ORDERS: [(usize, usize); 3] = [(2, 4), (4, 2), (8, 1)];
+
+// Non canonical representations of those torsion points
+// for which the non-canonical serialization exist
+// First 3 elements are neutral elements
+pub const EIGHT_TORSION_NON_CANONICAL: [[u8; 32]; 6] = [
+    [
+        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 128,
+    ], // neutral element, incorrect x-sign : (-0, 1) order 1
+    [
+        238, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+    ], // neutral element, incorrect x-sign : (-0, 2^255 - 18) order 1
+    [
+        236, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+    ], // incorrect x-sign : (-0, -1) order 2
+    [
+        238, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 127,
+    ], // neutral element with large y component : (0, 2^255 - 18) order 1
+    [
+        237, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+    ], // (-sqrt(-1), 2^255 - 19) order 4
+    [
+        237, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 127,
+    ], // (sqrt(-1), 2^255 - 19) order 4
+];
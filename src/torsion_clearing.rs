@@ -0,0 +1,97 @@
+use crate::policy::has_full_order;
+use crate::test_vectors::TestVector;
+use crate::torsion::{DECODE_MODE, TORSION_ORDERS};
+use crate::{
+    compute_hram, deserialize_point, deserialize_point_with, serialize_signature,
+    verify_cofactored, verify_cofactorless, EIGHT_TORSION,
+};
+use anyhow::Result;
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use rand::RngCore;
+use serde::Serialize;
+
+/// Clears the small-order component of `pt`, as `[8 * (8^-1 mod L)]pt`: the result is the
+/// projection of `pt` on the subgroup of order L, and `pt` itself if it has no torsion.
+/// Protocols which must accept arbitrary keys can verify against the cleared key, so that
+/// no verifier can tell keys differing by a small-order point apart.
+pub fn clear_torsion(pt: &EdwardsPoint) -> EdwardsPoint {
+    Scalar::from(8u8).invert() * pt.mul_by_cofactor()
+}
+
+/// Decodes `pub_key` leniently and encodes it with its torsion cleared, see `clear_torsion`.
+pub fn clear_torsion_encoded(pub_key: &[u8]) -> Result<[u8; 32]> {
+    Ok(clear_torsion(&deserialize_point(pub_key)?)
+        .compress()
+        .to_bytes())
+}
+
+/// The same message and signature, made with the secret scalar of a mixed-order A under the
+/// torsion-cleared A' it hashes, against A and against A'. The signature fails against A
+/// under any verification equation, as the challenge hashes A', and passes against A'.
+#[derive(Serialize)]
+pub struct TorsionClearingVector {
+    pub comment: String,
+    /// The vector under A, which fails.
+    pub before: TestVector,
+    /// The vector under A', which passes.
+    pub after: TestVector,
+    /// The order of the small-order component of A: 2, 4 or 8.
+    pub torsion_order: usize,
+}
+
+fn torsion_clearing_vector(
+    rng: &mut impl RngCore,
+    (torsion_order, torsion_idx): (usize, usize),
+) -> TorsionClearingVector {
+    let torsion = deserialize_point_with(&EIGHT_TORSION[torsion_idx], DECODE_MODE).unwrap();
+    let mut scalar_bytes = [0u8; 32];
+    rng.fill_bytes(&mut scalar_bytes);
+    let a = Scalar::from_bytes_mod_order(scalar_bytes);
+    let pub_key = a * ED25519_BASEPOINT_POINT + torsion;
+    let cleared = clear_torsion(&pub_key);
+
+    rng.fill_bytes(&mut scalar_bytes);
+    let r_scalar = Scalar::from_bytes_mod_order(scalar_bytes);
+    let r = r_scalar * ED25519_BASEPOINT_POINT;
+    let mut message = [0u8; 32];
+    rng.fill_bytes(&mut message);
+    let s = r_scalar + compute_hram(&message, &cleared, &r) * a;
+
+    debug_assert!(cleared == a * ED25519_BASEPOINT_POINT && has_full_order(&cleared));
+    debug_assert!(verify_cofactored(&message, &pub_key, &(r, s)).is_err());
+    debug_assert!(verify_cofactorless(&message, &pub_key, &(r, s)).is_err());
+    debug_assert!(verify_cofactorless(&message, &cleared, &(r, s)).is_ok());
+
+    let signature = serialize_signature(&r, &s);
+    TorsionClearingVector {
+        comment: format!(
+            "A with a component of order {}, signed under A' = [8 * (8^-1 mod L)]A",
+            torsion_order
+        ),
+        before: TestVector {
+            message: message.to_vec(),
+            pub_key: pub_key.compress().to_bytes(),
+            signature: signature.clone(),
+            metadata: None,
+            trace: None,
+        },
+        after: TestVector {
+            message: message.to_vec(),
+            pub_key: cleared.compress().to_bytes(),
+            signature,
+            metadata: None,
+            trace: None,
+        },
+        torsion_order,
+    }
+}
+
+/// A pair of vectors for each order of the small-order component of A.
+pub fn torsion_clearing_vectors(rng: &mut impl RngCore) -> Vec<TorsionClearingVector> {
+    TORSION_ORDERS
+        .iter()
+        .map(|&torsion| torsion_clearing_vector(rng, torsion))
+        .collect()
+}
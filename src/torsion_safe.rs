@@ -0,0 +1,106 @@
+use crate::policy::NAMED_POLICIES;
+use crate::test_vectors::{generate_test_vectors, TestVector, CASE_IDS};
+use crate::torsion_clearing::clear_torsion;
+use crate::verdict::Verdict;
+use crate::{check_slice_size, deserialize_point};
+use anyhow::Result;
+use curve25519_dalek::edwards::EdwardsPoint;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// `pt` with its small-order component removed, `[8 * (8^-1 mod L)]pt`, see
+/// `torsion_clearing::clear_torsion`: a point of order L is unchanged, a point of small
+/// order becomes the identity.
+pub fn normalize(pt: &EdwardsPoint) -> EdwardsPoint {
+    clear_torsion(pt)
+}
+
+/// The public key and signature a verifier which cannot be changed checks in place of
+/// `pub_key` and `signature`: A and R decoded leniently, normalized and re-encoded
+/// canonically, S as provided. Honest signatures are left as they are. On the others, the
+/// verifiers no longer differ by their equation, nor by how they hash or check the
+/// encodings of A and R: only the checks on S and those rejecting A or R of small order,
+/// which normalization turns into the identity, still tell them apart.
+pub fn normalize_signature(pub_key: &[u8], signature: &[u8]) -> Result<([u8; 32], Vec<u8>)> {
+    let signature = check_slice_size(signature, 64, "signature")?;
+    let pub_key = normalize(&deserialize_point(pub_key)?)
+        .compress()
+        .to_bytes();
+    let r = normalize(&deserialize_point(&signature[..32])?).compress();
+    Ok((pub_key, [r.as_bytes(), &signature[32..]].concat()))
+}
+
+/// A case of `cases.json` before and after `normalize_signature`, with the verdicts of the
+/// named policies on both.
+#[derive(Serialize)]
+pub struct NormalizationVector {
+    /// The id of the case, see `test_vectors::CASE_IDS`.
+    pub comment: String,
+    pub before: TestVector,
+    pub after: TestVector,
+    /// The verdicts of `policy::NAMED_POLICIES` on `before`, by name.
+    pub verdicts_before: BTreeMap<String, Verdict>,
+    /// The verdicts of `policy::NAMED_POLICIES` on `after`, by name.
+    pub verdicts_after: BTreeMap<String, Verdict>,
+}
+
+impl NormalizationVector {
+    /// Whether the named policies disagree on `before`, on whether to accept it.
+    pub fn diverges_before(&self) -> bool {
+        diverges(&self.verdicts_before)
+    }
+
+    /// Whether the named policies disagree on `after`.
+    pub fn diverges_after(&self) -> bool {
+        diverges(&self.verdicts_after)
+    }
+}
+
+fn diverges(verdicts: &BTreeMap<String, Verdict>) -> bool {
+    let mut accepted = verdicts.values().map(|verdict| verdict.is_accepted());
+    let first = accepted.next().unwrap_or(false);
+    accepted.any(|a| a != first)
+}
+
+fn verdicts(tv: &TestVector) -> BTreeMap<String, Verdict> {
+    NAMED_POLICIES
+        .iter()
+        .map(|(name, policy)| {
+            (
+                name.to_string(),
+                policy.verdict(&tv.message, &tv.pub_key, &tv.signature),
+            )
+        })
+        .collect()
+}
+
+/// Each case of `cases.json`, normalized: divergences left after normalization are those
+/// of the checks on small-order points and on S.
+pub fn normalization_vectors() -> Result<Vec<NormalizationVector>> {
+    generate_test_vectors()
+        .into_iter()
+        .zip(CASE_IDS.iter())
+        .map(|(tv, id)| {
+            let before = TestVector {
+                metadata: None,
+                trace: None,
+                ..tv
+            };
+            let (pub_key, signature) = normalize_signature(&before.pub_key, &before.signature)?;
+            let after = TestVector {
+                message: before.message.clone(),
+                pub_key,
+                signature,
+                metadata: None,
+                trace: None,
+            };
+            Ok(NormalizationVector {
+                comment: id.to_string(),
+                verdicts_before: verdicts(&before),
+                verdicts_after: verdicts(&after),
+                before,
+                after,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use crate::matrix::{classify, Verdicts};
+use crate::message_source::RandomBytes;
+use crate::test_vectors::{
+    non_zero_mixed_small, non_zero_small_mixed, order_name, SmallPoint, TestVector,
+};
+use crate::verdict::Verdict;
+use crate::{deserialize_point_with, DecodeMode, EIGHT_TORSION};
+use anyhow::{anyhow, Result};
+use rand::RngCore;
+use serde::Serialize;
+
+/// The vector of case 1 (small A, mixed R) or of case 2 (mixed A, small R) of
+/// `generate_test_vectors` built around a given small-order point rather than a random one,
+/// valid under both equations. A verifier checking the order of A or R against a partial
+/// blocklist, e.g. of the points of order 2 only, rejects the vectors of some of the points
+/// only, where the single random point of the cases may miss it.
+#[derive(Serialize)]
+pub struct TorsionSweepVector {
+    pub comment: String,
+    pub vector: TestVector,
+    /// The index of the small-order point in `EIGHT_TORSION`, from 1 to 7.
+    pub torsion_index: usize,
+    /// The order of the small-order point: 2, 4 or 8.
+    pub torsion_order: String,
+    /// Whether the small-order point is A, with R mixed, rather than R, with A mixed.
+    pub in_pub_key: bool,
+    pub expected_verdicts: Verdicts,
+}
+
+/// For each of the 7 points of `EIGHT_TORSION` but the identity, the vector where it is A,
+/// then the one where it is R.
+pub fn sweep_torsion(rng: &mut impl RngCore) -> Result<Vec<TorsionSweepVector>> {
+    let mut vectors = Vec::new();
+    for (torsion_index, bytes) in EIGHT_TORSION.iter().enumerate().skip(1) {
+        let torsion = deserialize_point_with(bytes, DecodeMode::Strict)?;
+        let small = SmallPoint::Fixed(torsion);
+        for &in_pub_key in [true, false].iter() {
+            // the second vector of each pair passes both equations
+            let (_, vector) = if in_pub_key {
+                non_zero_mixed_small(rng, &mut RandomBytes::default(), small)?
+            } else {
+                non_zero_small_mixed(rng, &mut RandomBytes::default(), small)?
+            };
+            let expected_verdicts = classify(&vector);
+            let torsion_order = order_name(&torsion);
+            vectors.push(TorsionSweepVector {
+                comment: format!(
+                    "{} of order {} (EIGHT_TORSION[{}]), {} mixed",
+                    if in_pub_key { "A" } else { "R" },
+                    torsion_order,
+                    torsion_index,
+                    if in_pub_key { "R" } else { "A" }
+                ),
+                vector,
+                torsion_index,
+                torsion_order,
+                in_pub_key,
+                expected_verdicts,
+            });
+        }
+    }
+    Ok(vectors)
+}
+
+/// The indices in `EIGHT_TORSION` of the small-order points whose vectors a verifier
+/// rejects, given its `verdicts` on the `vectors` of `sweep_torsion` in the same order, and
+/// with the small-order point in A or in R. A verifier checking the order of that point
+/// rejects all 7 of them, and one checking nothing none of them: any other set exposes a
+/// check covering some small-order points only.
+pub fn rejected_torsion(
+    vectors: &[TorsionSweepVector],
+    verdicts: &[Verdict],
+    in_pub_key: bool,
+) -> Result<Vec<usize>> {
+    if vectors.len() != verdicts.len() {
+        return Err(anyhow!(
+            "got {} verdicts for {} vectors",
+            verdicts.len(),
+            vectors.len()
+        ));
+    }
+    Ok(vectors
+        .iter()
+        .zip(verdicts.iter())
+        .filter(|(v, verdict)| v.in_pub_key == in_pub_key && !verdict.is_accepted())
+        .map(|(v, _)| v.torsion_index)
+        .collect())
+}
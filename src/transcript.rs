@@ -0,0 +1,141 @@
+use crate::test_vectors::{generate_test_vectors_with, TestVector};
+use anyhow::Result;
+use rand::rngs::OsRng;
+use rand::{Error, RngCore};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Every byte of randomness drawn while generating test vectors, in order. Replaying it
+/// regenerates the very same vectors, so that vectors generated from fresh OS randomness
+/// remain fully reproducible for audits.
+#[derive(Clone, Default)]
+pub struct Transcript(Rc<RefCell<Vec<u8>>>);
+
+impl Transcript {
+    pub fn from_bytes(bytes: Vec<u8>) -> Transcript {
+        Transcript(Rc::new(RefCell::new(bytes)))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.borrow().clone()
+    }
+
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> Result<Transcript> {
+        Ok(Transcript::from_bytes(fs::read(path)?))
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, &self.0.borrow()[..])?;
+        Ok(())
+    }
+}
+
+/// Forwards the randomness of `inner`, appending every byte drawn to a `Transcript`.
+pub struct RecordingRng<R> {
+    inner: R,
+    transcript: Transcript,
+}
+
+impl<R: RngCore> RecordingRng<R> {
+    pub fn new(inner: R, transcript: Transcript) -> RecordingRng<R> {
+        RecordingRng { inner, transcript }
+    }
+}
+
+impl<R: RngCore> RngCore for RecordingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest);
+        self.transcript.0.borrow_mut().extend_from_slice(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.inner.try_fill_bytes(dest)?;
+        self.transcript.0.borrow_mut().extend_from_slice(dest);
+        Ok(())
+    }
+}
+
+/// Plays back the bytes of a `Transcript` in order. Clones share their position in the
+/// transcript, so that a clone handed to each case carries on where the previous one stopped.
+#[derive(Clone)]
+pub struct ReplayRng {
+    transcript: Transcript,
+    position: Rc<Cell<usize>>,
+}
+
+impl ReplayRng {
+    pub fn new(transcript: Transcript) -> ReplayRng {
+        ReplayRng {
+            transcript,
+            position: Rc::new(Cell::new(0)),
+        }
+    }
+}
+
+impl RngCore for ReplayRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    // Panics once the transcript is exhausted: the grinding loops of the generators would
+    // otherwise never terminate.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("RNG transcript exhausted during replay")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        let transcript = self.transcript.0.borrow();
+        let start = self.position.get();
+        if transcript.len() - start < dest.len() {
+            return Err(Error::new(format!(
+                "transcript of {} bytes exhausted at byte {}",
+                transcript.len(),
+                start
+            )));
+        }
+        dest.copy_from_slice(&transcript[start..start + dest.len()]);
+        self.position.set(start + dest.len());
+        Ok(())
+    }
+}
+
+/// Generates the test vectors from OS-provided randomness, along with the transcript
+/// needed to replay their construction.
+pub fn generate_recorded() -> (Vec<TestVector>, Transcript) {
+    let transcript = Transcript::default();
+    let vec = generate_test_vectors_with(|| RecordingRng::new(OsRng, transcript.clone()));
+    (vec, transcript)
+}
+
+/// Regenerates the test vectors recorded in `transcript` by `generate_recorded`.
+///
+/// # Panics
+///
+/// Panics if the transcript is shorter than what the construction of the vectors draws.
+pub fn generate_replayed(transcript: &Transcript) -> Vec<TestVector> {
+    let rng = ReplayRng::new(transcript.clone());
+    generate_test_vectors_with(|| rng.clone())
+}
@@ -0,0 +1,513 @@
+use crate::classification::{point_order, scalar_class, PointOrder, ScalarClass};
+use crate::policy::{is_canonical_encoding, ValidationPolicy};
+use crate::test_vectors::{generate_test_vectors, TestVector};
+use crate::torsion::{EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL};
+use crate::verdict::Verdict;
+use crate::verifier::{
+    Algorithm2, Cofactored, Cofactorless, Fips186_5, Libsodium, Rfc8032, Verifier, Zip215,
+};
+use crate::{check_slice_size, deserialize_point};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::io::Write;
+
+/// The suites of Ed25519 vectors compared and merged here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Suite {
+    /// The vectors of `generate_test_vectors`.
+    Speccheck,
+    /// Project Wycheproof's EdDSA verification vectors, as imported by `import_wycheproof`.
+    Wycheproof,
+    /// The small-order vectors of ZIP-215, as rebuilt by `zip215_vectors`.
+    Zip215,
+}
+
+impl fmt::Display for Suite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Suite::Speccheck => "speccheck",
+                Suite::Wycheproof => "Wycheproof",
+                Suite::Zip215 => "ZIP-215",
+            }
+        )
+    }
+}
+
+/// A vector of one of the suites, along with the verdict its authors expect, if any:
+/// Wycheproof deems some vectors "acceptable" either way, and speccheck prescribes none.
+#[derive(Clone, Serialize)]
+pub struct SuiteVector {
+    pub suite: Suite,
+    pub id: String,
+    pub comment: String,
+    pub vector: TestVector,
+    pub expected: Option<bool>,
+}
+
+/// The vectors of `generate_test_vectors`, identified by their index.
+pub fn speccheck_vectors() -> Vec<SuiteVector> {
+    generate_test_vectors()
+        .into_iter()
+        .enumerate()
+        .map(|(i, vector)| SuiteVector {
+            suite: Suite::Speccheck,
+            id: i.to_string(),
+            comment: String::new(),
+            vector,
+            expected: None,
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct WycheproofFile {
+    #[serde(rename = "testGroups")]
+    test_groups: Vec<WycheproofGroup>,
+}
+
+#[derive(Deserialize)]
+struct WycheproofGroup {
+    // renamed in the later versions of the schema
+    #[serde(alias = "publicKey")]
+    key: WycheproofKey,
+    tests: Vec<WycheproofTest>,
+}
+
+#[derive(Deserialize)]
+struct WycheproofKey {
+    curve: Option<String>,
+    pk: String,
+}
+
+#[derive(Deserialize)]
+struct WycheproofTest {
+    #[serde(rename = "tcId")]
+    tc_id: u64,
+    comment: String,
+    msg: String,
+    sig: String,
+    result: String,
+}
+
+/// Imports the vectors of a Wycheproof EdDSA verification file, e.g. `eddsa_test.json`.
+/// Fails on keys of other curves, and on results other than "valid", "invalid" and
+/// "acceptable", the latter being imported without an expected verdict.
+pub fn import_wycheproof(json: &str) -> Result<Vec<SuiteVector>> {
+    let file: WycheproofFile = serde_json::from_str(json)?;
+    let mut vectors = Vec::new();
+    for group in file.test_groups.into_iter() {
+        if let Some(curve) = group.key.curve.as_deref().filter(|c| *c != "edwards25519") {
+            return Err(anyhow!("unsupported curve {}", curve));
+        }
+        let mut pub_key = [0u8; 32];
+        pub_key.copy_from_slice(check_slice_size(&hex::decode(&group.key.pk)?, 32, "pk")?);
+        for test in group.tests.into_iter() {
+            let expected = match test.result.as_str() {
+                "valid" => Some(true),
+                "invalid" => Some(false),
+                "acceptable" => None,
+                result => return Err(anyhow!("test {} has unknown result {}", test.tc_id, result)),
+            };
+            vectors.push(SuiteVector {
+                suite: Suite::Wycheproof,
+                id: test.tc_id.to_string(),
+                comment: test.comment,
+                vector: TestVector {
+                    message: hex::decode(&test.msg)?,
+                    pub_key,
+                    signature: hex::decode(&test.sig)?,
+                    metadata: None,
+                    trace: None,
+                },
+                expected,
+            });
+        }
+    }
+    Ok(vectors)
+}
+
+/// The vectors of ZIP-215, as built by ed25519-zebra: for every encoding of a small-order
+/// point as A, and every one as R, the signature `(R, 0)` of "Zcash". ZIP-215 requires all of
+/// them to pass, while they tell apart the legacy verifiers.
+pub fn zip215_vectors() -> Vec<SuiteVector> {
+    let encodings: Vec<[u8; 32]> = EIGHT_TORSION
+        .iter()
+        .chain(EIGHT_TORSION_NON_CANONICAL.iter())
+        .copied()
+        .collect();
+    let mut vectors = Vec::new();
+    for (i, pub_key) in encodings.iter().enumerate() {
+        for (j, r) in encodings.iter().enumerate() {
+            let mut signature = r.to_vec();
+            signature.extend_from_slice(&[0u8; 32]);
+            vectors.push(SuiteVector {
+                suite: Suite::Zip215,
+                id: format!("{}/{}", i, j),
+                comment: format!(
+                    "A encoded as {}, R as {}",
+                    hex::encode(pub_key),
+                    hex::encode(r)
+                ),
+                vector: TestVector {
+                    message: b"Zcash".to_vec(),
+                    pub_key: *pub_key,
+                    signature,
+                    metadata: None,
+                    trace: None,
+                },
+                expected: Some(true),
+            });
+        }
+    }
+    vectors
+}
+
+/// The behavioral axes a vector may probe: the structural properties of its points and
+/// scalar which verifiers may check, and the choices of verification equation and hashing
+/// on which its verdict hinges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    /// The signature is not 64 bytes long, or A or R does not decode to a point.
+    Malformed,
+    SmallOrderA,
+    MixedOrderA,
+    NonCanonicalA,
+    SmallOrderR,
+    MixedOrderR,
+    NonCanonicalR,
+    /// L <= S < 2^253, so that S passes the check on its 3 high bits only.
+    UnreducedS,
+    /// S >= 2^253.
+    OversizedS,
+    /// The cofactored and cofactorless equations disagree.
+    Cofactor,
+    /// Cofactored verification multiplying the point by 8 and pre-reducing disagree.
+    PreReduction,
+    /// Hashing A and R as provided and re-serialized disagree.
+    HashEncoding,
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Axis::Malformed => "malformed",
+                Axis::SmallOrderA => "small-order A",
+                Axis::MixedOrderA => "mixed-order A",
+                Axis::NonCanonicalA => "non-canonical A",
+                Axis::SmallOrderR => "small-order R",
+                Axis::MixedOrderR => "mixed-order R",
+                Axis::NonCanonicalR => "non-canonical R",
+                Axis::UnreducedS => "L <= S < 2^253",
+                Axis::OversizedS => "S >= 2^253",
+                Axis::Cofactor => "cofactored vs. cofactorless",
+                Axis::PreReduction => "(8h) pre-reduction",
+                Axis::HashEncoding => "hashing re-serialized points",
+            }
+        )
+    }
+}
+
+/// The axes probed by `tv`.
+pub fn axes(tv: &TestVector) -> BTreeSet<Axis> {
+    let mut axes = BTreeSet::new();
+    let pub_key = deserialize_point(&tv.pub_key);
+    let r = tv.signature.get(..32).map(deserialize_point);
+    let (pub_key, r) = match (pub_key, r) {
+        (Ok(pub_key), Some(Ok(r))) if tv.signature.len() == 64 => (pub_key, r),
+        _ => {
+            axes.insert(Axis::Malformed);
+            return axes;
+        }
+    };
+
+    for (pt, bytes, small, mixed, non_canonical) in [
+        (
+            pub_key,
+            &tv.pub_key[..],
+            Axis::SmallOrderA,
+            Axis::MixedOrderA,
+            Axis::NonCanonicalA,
+        ),
+        (
+            r,
+            &tv.signature[..32],
+            Axis::SmallOrderR,
+            Axis::MixedOrderR,
+            Axis::NonCanonicalR,
+        ),
+    ]
+    .iter()
+    {
+        match point_order(pt) {
+            PointOrder::Prime => {}
+            PointOrder::Mixed => {
+                axes.insert(*mixed);
+            }
+            _ => {
+                axes.insert(*small);
+            }
+        }
+        if !is_canonical_encoding(bytes, pt) {
+            axes.insert(*non_canonical);
+        }
+    }
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&tv.signature[32..]);
+    match scalar_class(&s_bytes) {
+        ScalarClass::HighBitsSet => {
+            axes.insert(Axis::OversizedS);
+        }
+        ScalarClass::GeL => {
+            axes.insert(Axis::UnreducedS);
+        }
+        ScalarClass::Canonical => {}
+    }
+
+    let accepts = |policy: ValidationPolicy| {
+        policy
+            .verify(&tv.message, &tv.pub_key, &tv.signature)
+            .is_ok()
+    };
+    let cofactorless = ValidationPolicy::default();
+    let cofactored = ValidationPolicy {
+        cofactored: true,
+        ..Default::default()
+    };
+    let reserialized = |policy: ValidationPolicy| ValidationPolicy {
+        reserialize_for_hash: true,
+        ..policy
+    };
+    if accepts(cofactored) != accepts(cofactorless) {
+        axes.insert(Axis::Cofactor);
+    }
+    if accepts(cofactored)
+        != accepts(ValidationPolicy {
+            prereduce: true,
+            ..cofactored
+        })
+    {
+        axes.insert(Axis::PreReduction);
+    }
+    if accepts(cofactored) != accepts(reserialized(cofactored))
+        || accepts(cofactorless) != accepts(reserialized(cofactorless))
+    {
+        axes.insert(Axis::HashEncoding);
+    }
+    axes
+}
+
+/// How many vectors of each suite probe each axis.
+pub struct Coverage {
+    pub suites: Vec<Suite>,
+    pub counts: BTreeMap<Axis, BTreeMap<Suite, usize>>,
+}
+
+impl Coverage {
+    pub fn new(vectors: &[SuiteVector]) -> Coverage {
+        let mut suites: Vec<Suite> = vectors.iter().map(|sv| sv.suite).collect();
+        suites.sort_unstable();
+        suites.dedup();
+
+        let mut counts: BTreeMap<Axis, BTreeMap<Suite, usize>> = BTreeMap::new();
+        for sv in vectors.iter() {
+            for axis in axes(&sv.vector).into_iter() {
+                *counts.entry(axis).or_default().entry(sv.suite).or_default() += 1;
+            }
+        }
+        Coverage { suites, counts }
+    }
+
+    /// The axes `suite` probes, and none of the other suites does.
+    pub fn exclusive(&self, suite: Suite) -> Vec<Axis> {
+        self.counts
+            .iter()
+            .filter(|(_, by_suite)| by_suite.len() == 1 && by_suite.contains_key(&suite))
+            .map(|(axis, _)| *axis)
+            .collect()
+    }
+
+    /// Prints the number of vectors of each suite probing each axis, then the axes probed
+    /// by a single suite.
+    pub fn report<W: Write>(&self, output: &mut W) -> Result<()> {
+        write!(output, "{:30}", "")?;
+        for suite in self.suites.iter() {
+            write!(output, "|{:>11}", suite.to_string())?;
+        }
+        writeln!(output)?;
+        for (axis, by_suite) in self.counts.iter() {
+            write!(output, "{:30}", axis.to_string())?;
+            for suite in self.suites.iter() {
+                write!(output, "|{:>11}", by_suite.get(suite).copied().unwrap_or(0))?;
+            }
+            writeln!(output)?;
+        }
+        for suite in self.suites.iter() {
+            let exclusive: Vec<String> =
+                self.exclusive(*suite).iter().map(Axis::to_string).collect();
+            writeln!(
+                output,
+                "only in {}: {}",
+                suite,
+                if exclusive.is_empty() {
+                    "-".to_string()
+                } else {
+                    exclusive.join(", ")
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a vector of the merged corpus comes from, with the verdict expected there.
+#[derive(Clone, Serialize)]
+pub struct Source {
+    pub suite: Suite,
+    pub id: String,
+    pub comment: String,
+    pub expected: Option<bool>,
+}
+
+/// A vector of the merged corpus, along with every suite it appears in (the suites may
+/// disagree on the expected verdict) and the axes it probes.
+#[derive(Serialize)]
+pub struct MergedVector {
+    pub vector: TestVector,
+    pub sources: Vec<Source>,
+    pub axes: BTreeSet<Axis>,
+}
+
+// The message, public key and signature bytes of a vector
+type VectorBytes = (Vec<u8>, [u8; 32], Vec<u8>);
+
+/// Merges the `vectors` of all suites, keeping a single copy of those carrying the same
+/// message, public key and signature bytes, in order of first appearance.
+pub fn merge(vectors: Vec<SuiteVector>) -> Vec<MergedVector> {
+    let mut merged: Vec<MergedVector> = Vec::new();
+    let mut positions: HashMap<VectorBytes, usize> = HashMap::new();
+    for sv in vectors.into_iter() {
+        let source = Source {
+            suite: sv.suite,
+            id: sv.id,
+            comment: sv.comment,
+            expected: sv.expected,
+        };
+        let key = (
+            sv.vector.message.clone(),
+            sv.vector.pub_key,
+            sv.vector.signature.clone(),
+        );
+        match positions.get(&key) {
+            Some(&i) => merged[i].sources.push(source),
+            None => {
+                positions.insert(key, merged.len());
+                merged.push(MergedVector {
+                    axes: axes(&sv.vector),
+                    vector: sv.vector,
+                    sources: vec![source],
+                });
+            }
+        }
+    }
+    merged
+}
+
+/// A vector on which a verifier departs from the verdict its suite expects.
+pub struct Divergence {
+    pub verifier: String,
+    pub suite: Suite,
+    pub id: String,
+    pub comment: String,
+    pub expected: bool,
+    pub verdict: Verdict,
+}
+
+/// The verifiers of this crate checked against the suites by default: `verify_cofactored`,
+/// `verify_cofactorless`, [CGN20e] Algorithm 2, RFC 8032, FIPS 186-5, the rules of ZIP-215
+/// and the emulation of libsodium.
+pub fn crate_verifiers() -> [(&'static str, &'static dyn Verifier); 7] {
+    [
+        ("cofactored", &Cofactored),
+        ("cofactorless", &Cofactorless),
+        ("Algorithm 2", &Algorithm2),
+        ("RFC 8032", &Rfc8032),
+        ("FIPS 186-5", &Fips186_5),
+        ("ZIP-215", &Zip215),
+        ("libsodium", &Libsodium),
+    ]
+}
+
+/// Runs each of the `vectors` with an expected verdict through each of the `verifiers`,
+/// returning the divergences, by verifier then in the order of the vectors.
+pub fn cross_check(
+    vectors: &[SuiteVector],
+    verifiers: &[(&str, &dyn Verifier)],
+) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+    for (name, verifier) in verifiers.iter() {
+        for sv in vectors.iter() {
+            let expected = match sv.expected {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let tv = &sv.vector;
+            let verdict = verifier.verdict(&tv.message, &tv.pub_key, &tv.signature);
+            if verdict.is_accepted() != expected {
+                divergences.push(Divergence {
+                    verifier: name.to_string(),
+                    suite: sv.suite,
+                    id: sv.id.clone(),
+                    comment: sv.comment.clone(),
+                    expected,
+                    verdict,
+                });
+            }
+        }
+    }
+    divergences
+}
+
+/// Runs `cross_check` and prints, for each verifier, how many of the vectors with an
+/// expected verdict it departs from, then each of them.
+pub fn cross_check_report<W: Write>(
+    vectors: &[SuiteVector],
+    verifiers: &[(&str, &dyn Verifier)],
+    output: &mut W,
+) -> Result<()> {
+    let divergences = cross_check(vectors, verifiers);
+    let checked = vectors.iter().filter(|sv| sv.expected.is_some()).count();
+    for (name, _) in verifiers.iter() {
+        let diverging: Vec<&Divergence> =
+            divergences.iter().filter(|d| d.verifier == *name).collect();
+        writeln!(
+            output,
+            "{}: {} of {} vectors diverge",
+            name,
+            diverging.len(),
+            checked
+        )?;
+        for d in diverging.iter() {
+            writeln!(
+                output,
+                "  {} {} ({}): expected {}, got {}",
+                d.suite,
+                d.id,
+                d.comment,
+                if d.expected { "valid" } else { "invalid" },
+                d.verdict
+            )?;
+        }
+    }
+    Ok(())
+}
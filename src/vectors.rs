@@ -0,0 +1,15 @@
+// The test vectors and the ways of generating them covered by semver. The individual
+// generators they are built from are internals, in `test_vectors` with the `unstable`
+// feature.
+pub use crate::test_vectors::{
+    generate_test_vectors, generate_test_vectors_from, generate_test_vectors_n,
+    generate_test_vectors_with, CaseMetadata, ConstructionTrace, PolicyResults, SClass, TestVector,
+    CASE_IDS,
+};
+
+// The same cases in Ed25519ph and Ed25519ctx, along with their challenge hash and
+// verification functions.
+pub use crate::test_vectors::{ed25519ctx, ed25519ph};
+
+// Generating them from other seeds.
+pub use crate::generator::VectorGenerator;
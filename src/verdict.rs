@@ -0,0 +1,65 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// The outcome of submitting a test vector to a verifier. Rejections are told apart by the
+/// step refusing the vector, as libraries differ in which checks they perform while decoding
+/// keys and signatures (e.g. dalek's `Signature::try_from`) and which during verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Accepted,
+    /// The public key or the signature was refused while decoding, before verifying.
+    ParseRejected,
+    /// The public key and the signature decoded, but the signature did not verify.
+    VerifyRejected,
+    /// The verifier panicked on the vector, or its integration could not be set up: this
+    /// tells nothing of the checks it performs.
+    Error,
+}
+
+impl Verdict {
+    /// Combines the outcome of decoding the inputs with that of verifying them, the latter
+    /// being only attempted once decoding succeeded.
+    pub fn from_results<T, E, F, G>(parsed: Result<T, E>, verify: F) -> Verdict
+    where
+        F: FnOnce(T) -> Result<(), G>,
+    {
+        match parsed {
+            Err(_) => Verdict::ParseRejected,
+            Ok(decoded) => match verify(decoded) {
+                Ok(()) => Verdict::Accepted,
+                Err(_) => Verdict::VerifyRejected,
+            },
+        }
+    }
+
+    pub fn from_accepted(accepted: bool) -> Verdict {
+        if accepted {
+            Verdict::Accepted
+        } else {
+            Verdict::VerifyRejected
+        }
+    }
+
+    pub fn is_accepted(self) -> bool {
+        self == Verdict::Accepted
+    }
+
+    pub fn is_error(self) -> bool {
+        self == Verdict::Error
+    }
+}
+
+// The symbols of the result tables: V accepted, P rejected while parsing, X rejected while
+// verifying, E errored.
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Verdict::Accepted => "V",
+            Verdict::ParseRejected => "P",
+            Verdict::VerifyRejected => "X",
+            Verdict::Error => "E",
+        };
+        write!(f, "{}", symbol)
+    }
+}
@@ -0,0 +1,305 @@
+use crate::algorithm2;
+use crate::fips186_5;
+use crate::libsodium;
+use crate::policy::ValidationPolicy;
+use crate::rfc8032;
+use crate::test_vectors::TestVector;
+use crate::verdict::Verdict;
+use crate::zip215;
+use crate::{
+    deserialize_point, deserialize_signature, verify_cofactored, verify_cofactorless,
+    verify_pre_reduced_cofactored,
+};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use std::panic::{self, AssertUnwindSafe};
+
+/// A verification backend, taking the public key and the signature as serialized in the
+/// test vectors. Library adapters implementing it (or given as closures) can be evaluated
+/// on the vectors along with the verifiers of this crate, see `results_table`.
+pub trait Verifier {
+    /// Verifies the serialized `signature` of `message` under the serialized `pub_key`.
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()>;
+
+    /// As `verify`, telling apart the step rejecting the vector. Backends which do not
+    /// expose their decoding step separately report every rejection as a verification one.
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        Verdict::from_accepted(self.verify(message, pub_key, signature).is_ok())
+    }
+}
+
+impl<F> Verifier for F
+where
+    F: Fn(&[u8], &[u8], &[u8]) -> Result<()>,
+{
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        self(message, pub_key, signature)
+    }
+}
+
+// The verification functions of this crate taking unpacked points and scalars
+type UnpackedVerify = fn(&[u8], &EdwardsPoint, &(EdwardsPoint, Scalar)) -> Result<()>;
+
+// Decodes A and R, and S without reducing it, as the unpacked verification functions do
+fn decode(pub_key: &[u8], signature: &[u8]) -> Result<(EdwardsPoint, (EdwardsPoint, Scalar))> {
+    Ok((
+        deserialize_point(pub_key)?,
+        deserialize_signature(signature)?,
+    ))
+}
+
+fn verify_unpacked(
+    verify: UnpackedVerify,
+    message: &[u8],
+    pub_key: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    let (pub_key, signature) = decode(pub_key, signature)?;
+    verify(message, &pub_key, &signature)
+}
+
+fn verdict_unpacked(
+    verify: UnpackedVerify,
+    message: &[u8],
+    pub_key: &[u8],
+    signature: &[u8],
+) -> Verdict {
+    Verdict::from_results(decode(pub_key, signature), |(pub_key, signature)| {
+        verify(message, &pub_key, &signature)
+    })
+}
+
+/// `verify_cofactored`, on serialized inputs.
+pub struct Cofactored;
+
+impl Verifier for Cofactored {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        verify_unpacked(verify_cofactored, message, pub_key, signature)
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        verdict_unpacked(verify_cofactored, message, pub_key, signature)
+    }
+}
+
+/// `verify_cofactorless`, on serialized inputs.
+pub struct Cofactorless;
+
+impl Verifier for Cofactorless {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        verify_unpacked(verify_cofactorless, message, pub_key, signature)
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        verdict_unpacked(verify_cofactorless, message, pub_key, signature)
+    }
+}
+
+/// `verify_pre_reduced_cofactored`, on serialized inputs.
+pub struct PreReducedCofactored;
+
+impl Verifier for PreReducedCofactored {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        verify_unpacked(verify_pre_reduced_cofactored, message, pub_key, signature)
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        verdict_unpacked(verify_pre_reduced_cofactored, message, pub_key, signature)
+    }
+}
+
+/// The individual verification of [CGN20e], `algorithm2::verify`.
+pub struct Algorithm2;
+
+impl Verifier for Algorithm2 {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        if algorithm2::verify(message, pub_key, signature) {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid signature under Algorithm 2"))
+        }
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        algorithm2::verdict(message, pub_key, signature)
+    }
+}
+
+/// The verifier of RFC 8032, `rfc8032::verify`.
+pub struct Rfc8032;
+
+impl Verifier for Rfc8032 {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        if rfc8032::verify(message, pub_key, signature) {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid signature under RFC 8032"))
+        }
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        rfc8032::verdict(message, pub_key, signature)
+    }
+}
+
+/// The verifier of FIPS 186-5, `fips186_5::verify`.
+pub struct Fips186_5;
+
+impl Verifier for Fips186_5 {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        if fips186_5::verify(message, pub_key, signature) {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid signature under FIPS 186-5"))
+        }
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        fips186_5::verdict(message, pub_key, signature)
+    }
+}
+
+/// The reference verifier of the ZIP-215 rules, `zip215::verify`.
+pub struct Zip215;
+
+impl Verifier for Zip215 {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        if zip215::verify(message, pub_key, signature) {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid signature under ZIP-215"))
+        }
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        zip215::verdict(message, pub_key, signature)
+    }
+}
+
+/// The emulation of libsodium, `libsodium::verify`.
+pub struct Libsodium;
+
+impl Verifier for Libsodium {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        if libsodium::verify(message, pub_key, signature) {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid signature under libsodium"))
+        }
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        libsodium::verdict(message, pub_key, signature)
+    }
+}
+
+impl Verifier for ValidationPolicy {
+    fn verify(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Result<()> {
+        ValidationPolicy::verify(self, message, pub_key, signature)
+    }
+
+    fn verdict(&self, message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        ValidationPolicy::verdict(self, message, pub_key, signature)
+    }
+}
+
+/// The verdicts of `verifier` on each of the `vectors`. A panic on a vector is recorded as
+/// an `Error` verdict on it, rather than aborting the whole run.
+pub fn verdicts(verifier: &dyn Verifier, vectors: &[TestVector]) -> Vec<Verdict> {
+    vectors
+        .iter()
+        .map(|tv| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                verifier.verdict(&tv.message, &tv.pub_key, &tv.signature)
+            }))
+            .unwrap_or(Verdict::Error)
+        })
+        .collect()
+}
+
+/// A named column of the results table: a verifier, or the error its integration failed to
+/// be set up with (e.g. a missing shared library or an unsupported key format).
+pub type Integration<'a> = (&'a str, Result<&'a dyn Verifier>);
+
+/// The verdicts of each of the named `verifiers` on the `vectors`, laid out as the results
+/// table of the README.
+pub fn results_table(verifiers: &[(&str, &dyn Verifier)], vectors: &[TestVector]) -> String {
+    let integrations: Vec<Integration<'_>> = verifiers
+        .iter()
+        .map(|(name, verifier)| (*name, Ok(*verifier)))
+        .collect();
+    integrations_table(&integrations, vectors)
+}
+
+/// As `results_table`, for integrations some of which may have failed to be set up: those
+/// get an `Error` verdict on every vector, and the table is followed by the health of each
+/// integration which failed or panicked.
+pub fn integrations_table(integrations: &[Integration<'_>], vectors: &[TestVector]) -> String {
+    let rows: Vec<(&str, Vec<Verdict>)> = integrations
+        .iter()
+        .map(|(name, verifier)| match verifier {
+            Ok(verifier) => (*name, verdicts(*verifier, vectors)),
+            Err(_) => (*name, vec![Verdict::Error; vectors.len()]),
+        })
+        .collect();
+    let rows: Vec<(&str, &[Verdict])> = rows.iter().map(|(name, v)| (*name, &v[..])).collect();
+    let failures: Vec<(&str, String)> = integrations
+        .iter()
+        .filter_map(|(name, verifier)| verifier.as_ref().err().map(|e| (*name, e.to_string())))
+        .collect();
+    table_with_health(&rows, &failures)
+}
+
+/// The health of an integration, from its verdicts: the indices of the vectors it errored
+/// on, none for a healthy one.
+pub fn errored(verdicts: &[Verdict]) -> Vec<usize> {
+    verdicts
+        .iter()
+        .enumerate()
+        .filter(|(_, verdict)| verdict.is_error())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// As `results_table`, from verdicts observed beforehand, e.g. on another machine.
+pub fn verdicts_table(rows: &[(&str, &[Verdict])]) -> String {
+    table_with_health(rows, &[])
+}
+
+// The table of `rows`, followed by a line for each of the `failures` to set up an integration
+// and for each row with errored verdicts
+pub(crate) fn table_with_health(
+    rows: &[(&str, &[Verdict])],
+    failures: &[(&str, String)],
+) -> String {
+    let columns = rows.iter().map(|(_, v)| v.len()).max().unwrap_or(0);
+    let width = 16 + 4 * columns;
+    let mut table = format!(" {}\n|{:<15}|", "-".repeat(width - 1), "Library");
+    for i in 0..columns {
+        table += &format!(" {:<2}|", i);
+    }
+    table += &format!("\n|{}+", "-".repeat(15));
+    table += &vec!["---"; columns].join("+");
+    table += "|\n";
+    for (name, verdicts) in rows.iter() {
+        table += &format!("|{:<15}|", name);
+        for verdict in verdicts.iter() {
+            table += &format!(" {} |", verdict);
+        }
+        table += "\n";
+    }
+    table += &format!(" {}\n", "-".repeat(width - 1));
+    for (name, verdicts) in rows.iter() {
+        if let Some((_, e)) = failures.iter().find(|(failed, _)| failed == name) {
+            table += &format!("{}: unavailable, {}\n", name, e);
+            continue;
+        }
+        let errored = errored(verdicts);
+        if !errored.is_empty() {
+            let indices: Vec<String> = errored.iter().map(usize::to_string).collect();
+            table += &format!("{}: errored on vectors {}\n", name, indices.join(", "));
+        }
+    }
+    table
+}
@@ -0,0 +1,56 @@
+use crate::matrix::KNOWN_POLICIES;
+use crate::test_vectors;
+use crate::verdict::Verdict;
+use crate::verifier::{Cofactored, Cofactorless, Verifier};
+use wasm_bindgen::prelude::*;
+
+// The JavaScript interface, built by `wasm-pack build --features wasm`, for JavaScript
+// libraries to be checked in the browser or in Node on the vectors generated in place rather
+// than on a copy of `cases.json`. Verdicts are named as in the json files, e.g.
+// `expected_verdicts`: accepted, parse_rejected or verify_rejected.
+
+fn name(verdict: Verdict) -> String {
+    let name = match verdict {
+        Verdict::Accepted => "accepted",
+        Verdict::ParseRejected => "parse_rejected",
+        Verdict::VerifyRejected => "verify_rejected",
+        Verdict::Error => "error",
+    };
+    name.to_string()
+}
+
+/// The vectors of `cases.json`, as the same json, for `JSON.parse`: the message, public key
+/// and signature of each in hex, along with its metadata.
+#[wasm_bindgen(js_name = generateTestVectors)]
+pub fn generate_test_vectors() -> String {
+    // serializing hex strings and plain metadata does not fail
+    serde_json::to_string(&test_vectors::generate_test_vectors()).unwrap()
+}
+
+/// The verdict of `verify_cofactored` on the signature of `message` under `pub_key`.
+#[wasm_bindgen(js_name = verifyCofactored)]
+pub fn verify_cofactored(message: &[u8], pub_key: &[u8], signature: &[u8]) -> String {
+    name(Cofactored.verdict(message, pub_key, signature))
+}
+
+/// The verdict of `verify_cofactorless` on the signature of `message` under `pub_key`.
+#[wasm_bindgen(js_name = verifyCofactorless)]
+pub fn verify_cofactorless(message: &[u8], pub_key: &[u8], signature: &[u8]) -> String {
+    name(Cofactorless.verdict(message, pub_key, signature))
+}
+
+/// The verdict of the policy of `matrix::KNOWN_POLICIES` named `policy`, e.g. algorithm2 or
+/// zip215, or an exception for an unknown name.
+#[wasm_bindgen(js_name = verifyWithPolicy)]
+pub fn verify_with_policy(
+    policy: &str,
+    message: &[u8],
+    pub_key: &[u8],
+    signature: &[u8],
+) -> Result<String, JsValue> {
+    let (_, policy) = KNOWN_POLICIES
+        .iter()
+        .find(|(name, _)| *name == policy)
+        .ok_or_else(|| JsValue::from_str(&format!("unknown policy {}", policy)))?;
+    Ok(name(policy.verdict(message, pub_key, signature)))
+}
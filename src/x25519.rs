@@ -0,0 +1,381 @@
+use crate::report::ResultsTable;
+use crate::verdict::Verdict;
+use crate::{deserialize_point_with, DecodeMode, EIGHT_TORSION};
+use anyhow::{anyhow, Result};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::scalar::Scalar;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// The scalar every input of the suite is multiplied by, that of Alice in RFC 7748,
+/// section 6.1, clamped when used.
+pub const SCALAR: [u8; 32] = [
+    0x77, 0x07, 0x6d, 0x0a, 0x73, 0x18, 0xa5, 0x7d, 0x3c, 0x16, 0xc1, 0x72, 0x51, 0xb2, 0x66, 0x45,
+    0xdf, 0x4c, 0x2f, 0x87, 0xeb, 0xc0, 0x99, 0x2a, 0xb1, 0x77, 0xfb, 0xa5, 0x1d, 0xb9, 0x2c, 0x2a,
+];
+
+// p = 2^255 - 19, little-endian
+const P: [u8; 32] = [
+    0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+
+/// X25519 as RFC 7748, section 5, specifies it: the scalar is clamped, the top bit of u
+/// masked and u reduced modulo p, and no check made on the result, all-zero for the
+/// low-order inputs.
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut clamped = *scalar;
+    clamped[0] &= 248;
+    clamped[31] &= 127;
+    clamped[31] |= 64;
+    // the ladder of dalek reads the 255 low bits of u, and of the clamped scalar unreduced
+    (MontgomeryPoint(*u) * Scalar::from_bits(clamped)).to_bytes()
+}
+
+/// The groups of inputs of the suite, each telling apart one choice left to
+/// implementations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Family {
+    /// Canonical u-coordinates of points of order dividing 8, on the curve or its twist,
+    /// for which the shared secret is all-zero whatever the scalar: returned as is, or
+    /// aborted on, as section 6.1 allows.
+    LowOrder,
+    /// u-coordinates at least p, with the top bit clear, which RFC 7748 reduces modulo p.
+    NonCanonical,
+    /// u-coordinates with the top bit set, which RFC 7748 masks.
+    HighBit,
+    /// The base point, which every implementation must get right: a harness getting it
+    /// wrong is broken.
+    Control,
+}
+
+impl fmt::Display for Family {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Family::LowOrder => "low-order u",
+            Family::NonCanonical => "u >= p",
+            Family::HighBit => "top bit of u set",
+            Family::Control => "base point",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// An input of the suite: the public key of the peer, and the shared secret of RFC 7748
+/// with `SCALAR`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct X25519Vector {
+    pub comment: String,
+    pub family: Family,
+    #[serde(serialize_with = "hex_bytes")]
+    pub u: [u8; 32],
+    #[serde(serialize_with = "hex_bytes")]
+    pub shared_secret: [u8; 32],
+}
+
+fn hex_bytes<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(bytes))
+}
+
+impl X25519Vector {
+    fn new(comment: String, family: Family, u: [u8; 32]) -> X25519Vector {
+        X25519Vector {
+            comment,
+            family,
+            u,
+            shared_secret: x25519(&SCALAR, &u),
+        }
+    }
+
+    /// Whether RFC 7748 computes the all-zero shared secret on the input.
+    pub fn is_all_zero(&self) -> bool {
+        self.shared_secret == [0u8; 32]
+    }
+}
+
+// u + n, for n small enough not to overflow 255 bits from p
+fn add_small(u: &[u8; 32], n: u8) -> [u8; 32] {
+    let mut sum = *u;
+    let mut carry = n as u16;
+    for byte in sum.iter_mut() {
+        carry += *byte as u16;
+        *byte = carry as u8;
+        carry >>= 8;
+    }
+    sum
+}
+
+fn with_top_bit(u: &[u8; 32]) -> [u8; 32] {
+    let mut u = *u;
+    u[31] |= 128;
+    u
+}
+
+/// The low-order inputs: the u-coordinates of the points of `EIGHT_TORSION`, u = 0 for the
+/// identity and the point of order 2, 1 for those of order 4, and the two of the points of
+/// order 8, along with p - 1, of order 4 on the twist; then p and p + 1, the non-canonical
+/// encodings of 0 and 1, and p + 9, of the base point; then those of 0, 1, the first of
+/// order 8 and the base point with the top bit set; then the base point itself. These are
+/// the inputs of the blocklist of libsodium, and their variants it does not list.
+pub fn x25519_vectors() -> Vec<X25519Vector> {
+    let mut low_order: Vec<[u8; 32]> = Vec::new();
+    for bytes in EIGHT_TORSION.iter() {
+        // the points of `EIGHT_TORSION` are canonical
+        let u = deserialize_point_with(bytes, DecodeMode::Strict)
+            .unwrap()
+            .to_montgomery()
+            .to_bytes();
+        if !low_order.contains(&u) {
+            low_order.push(u);
+        }
+    }
+    let one = add_small(&[0u8; 32], 1);
+    let nine = add_small(&[0u8; 32], 9);
+    let mut p_minus_one = P;
+    p_minus_one[0] -= 1;
+
+    let mut vectors: Vec<X25519Vector> = low_order
+        .iter()
+        .map(|u| {
+            let comment = match u {
+                u if *u == [0u8; 32] => "u = 0, of the identity and the point of order 2",
+                u if *u == one => "u = 1, of the points of order 4",
+                _ => "of points of order 8",
+            };
+            X25519Vector::new(comment.to_string(), Family::LowOrder, *u)
+        })
+        .collect();
+    vectors.push(X25519Vector::new(
+        "u = p - 1, of order 4 on the twist".to_string(),
+        Family::LowOrder,
+        p_minus_one,
+    ));
+    for (n, comment) in [
+        (0, "u = p, encoding 0"),
+        (1, "u = p + 1, encoding 1"),
+        (9, "u = p + 9, encoding the base point"),
+    ]
+    .iter()
+    {
+        vectors.push(X25519Vector::new(
+            comment.to_string(),
+            Family::NonCanonical,
+            add_small(&P, *n),
+        ));
+    }
+    let order_8 = *low_order
+        .iter()
+        .find(|u| **u != [0u8; 32] && **u != one)
+        .unwrap();
+    for (u, of) in [
+        (&[0u8; 32], "u = 0"),
+        (&one, "u = 1"),
+        (&order_8, "the first u of order 8"),
+        (&nine, "the base point"),
+    ]
+    .iter()
+    {
+        vectors.push(X25519Vector::new(
+            format!("{} with the top bit set", of),
+            Family::HighBit,
+            with_top_bit(u),
+        ));
+    }
+    vectors.push(X25519Vector::new(
+        "u = 9, the base point".to_string(),
+        Family::Control,
+        nine,
+    ));
+    vectors
+}
+
+/// An X25519 backend, as `verifier::Verifier` is for signatures: library adapters
+/// implementing it (or given as closures) run through the suite along with the references
+/// of this crate.
+pub trait KeyAgreement {
+    /// The shared secret of `scalar` with the public key `u`, or an error if the backend
+    /// aborts.
+    fn diffie_hellman(&self, scalar: &[u8; 32], u: &[u8; 32]) -> Result<[u8; 32]>;
+}
+
+impl<F> KeyAgreement for F
+where
+    F: Fn(&[u8; 32], &[u8; 32]) -> Result<[u8; 32]>,
+{
+    fn diffie_hellman(&self, scalar: &[u8; 32], u: &[u8; 32]) -> Result<[u8; 32]> {
+        self(scalar, u)
+    }
+}
+
+/// `x25519`, returning the all-zero shared secret of the low-order inputs.
+pub struct Rfc7748;
+
+impl KeyAgreement for Rfc7748 {
+    fn diffie_hellman(&self, scalar: &[u8; 32], u: &[u8; 32]) -> Result<[u8; 32]> {
+        Ok(x25519(scalar, u))
+    }
+}
+
+/// `x25519`, aborting on the all-zero shared secret, as RFC 7748, section 6.1, allows and
+/// e.g. libsodium's `crypto_scalarmult` does.
+pub struct RejectAllZero;
+
+impl KeyAgreement for RejectAllZero {
+    fn diffie_hellman(&self, scalar: &[u8; 32], u: &[u8; 32]) -> Result<[u8; 32]> {
+        let shared = x25519(scalar, u);
+        if shared == [0u8; 32] {
+            return Err(anyhow!("all-zero shared secret"));
+        }
+        Ok(shared)
+    }
+}
+
+/// What a backend did with an input of the suite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    /// It returned the shared secret of RFC 7748, all-zero for the low-order inputs.
+    Rfc7748,
+    /// It aborted.
+    Aborted,
+    /// It returned another shared secret, e.g. reading the top bit of u.
+    Departed,
+    /// It panicked.
+    Error,
+}
+
+impl Outcome {
+    /// The outcome as a verdict of the results tables: V when the backend returned a shared
+    /// secret, whichever, X when it aborted and E when it panicked.
+    pub fn verdict(self) -> Verdict {
+        match self {
+            Outcome::Rfc7748 | Outcome::Departed => Verdict::Accepted,
+            Outcome::Aborted => Verdict::VerifyRejected,
+            Outcome::Error => Verdict::Error,
+        }
+    }
+}
+
+/// The outcome of `backend` on each of the `vectors`. A panic on a vector is recorded as an
+/// `Error` outcome on it, rather than aborting the whole run.
+pub fn outcomes(backend: &dyn KeyAgreement, vectors: &[X25519Vector]) -> Vec<Outcome> {
+    vectors
+        .iter()
+        .map(|v| {
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                match backend.diffie_hellman(&SCALAR, &v.u) {
+                    Ok(shared) if shared == v.shared_secret => Outcome::Rfc7748,
+                    Ok(_) => Outcome::Departed,
+                    Err(_) => Outcome::Aborted,
+                }
+            }))
+            .unwrap_or(Outcome::Error)
+        })
+        .collect()
+}
+
+/// The table of the outcomes of each of the named `backends` on the `vectors`, as verdicts
+/// (see `Outcome::verdict`).
+pub fn results(backends: &[(&str, &dyn KeyAgreement)], vectors: &[X25519Vector]) -> ResultsTable {
+    let mut table = ResultsTable::new();
+    for (name, backend) in backends.iter() {
+        let verdicts = outcomes(*backend, vectors)
+            .into_iter()
+            .map(Outcome::verdict)
+            .collect();
+        table.push(name, verdicts);
+    }
+    table
+}
+
+/// How a backend handles a family of inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Handling {
+    /// As RFC 7748: it returns the all-zero secret of the low-order inputs, reduces u modulo
+    /// p and masks its top bit.
+    Computes,
+    /// It aborts on every input of the family.
+    Aborts,
+    /// It returns other secrets on every input of the family, or the outcomes are mixed.
+    Departs,
+}
+
+/// The behavior of a backend on the suite, by family, e.g. whether it returns all-zero
+/// shared secrets, aborts on them, or masks the top bit of u.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Behavior {
+    pub low_order: Handling,
+    pub non_canonical: Handling,
+    pub high_bit: Handling,
+}
+
+impl Behavior {
+    /// The behavior from the outcomes on `x25519_vectors`, or an error if the backend got the
+    /// base point wrong or panicked: its harness is then broken, and the rest tells nothing.
+    pub fn from_outcomes(vectors: &[X25519Vector], outcomes: &[Outcome]) -> Result<Behavior> {
+        if vectors.len() != outcomes.len() {
+            return Err(anyhow!(
+                "{} outcomes for {} vectors",
+                outcomes.len(),
+                vectors.len()
+            ));
+        }
+        let control = vectors
+            .iter()
+            .zip(outcomes.iter())
+            .all(|(v, o)| v.family != Family::Control || *o == Outcome::Rfc7748);
+        if outcomes.contains(&Outcome::Error) || !control {
+            return Err(anyhow!("the backend fails on the base point, or panicked"));
+        }
+        // aborting on an all-zero secret is computing it, for a backend aborting on every
+        // low-order input: the other families then only tell how u is read
+        let handling = |family: Family, aborts_on_zero: bool| {
+            let outcomes: Vec<(&X25519Vector, Outcome)> = vectors
+                .iter()
+                .zip(outcomes.iter().cloned())
+                .filter(|(v, _)| v.family == family)
+                .collect();
+            if outcomes.iter().all(|(v, o)| {
+                *o == Outcome::Rfc7748
+                    || (aborts_on_zero && *o == Outcome::Aborted && v.is_all_zero())
+            }) {
+                Handling::Computes
+            } else if outcomes.iter().all(|(_, o)| *o == Outcome::Aborted) {
+                Handling::Aborts
+            } else {
+                Handling::Departs
+            }
+        };
+        let low_order = handling(Family::LowOrder, false);
+        let aborts_on_zero = low_order == Handling::Aborts;
+        Ok(Behavior {
+            low_order,
+            non_canonical: handling(Family::NonCanonical, aborts_on_zero),
+            high_bit: handling(Family::HighBit, aborts_on_zero),
+        })
+    }
+}
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let low_order = match self.low_order {
+            Handling::Computes => "returns all-zero shared secrets",
+            Handling::Aborts => "aborts on low-order u",
+            Handling::Departs => "departs from RFC 7748 on low-order u",
+        };
+        let non_canonical = match self.non_canonical {
+            Handling::Computes => "reduces u >= p",
+            Handling::Aborts => "rejects u >= p",
+            Handling::Departs => "departs from RFC 7748 on u >= p",
+        };
+        let high_bit = match self.high_bit {
+            Handling::Computes => "masks the top bit of u",
+            Handling::Aborts => "rejects the top bit of u set",
+            Handling::Departs => "reads the top bit of u, or departs otherwise",
+        };
+        write!(f, "{}, {}, {}", low_order, non_canonical, high_bit)
+    }
+}
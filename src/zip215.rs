@@ -0,0 +1,39 @@
+/// This file implements the validation rules of ZIP-215 as a reference verifier, to compare
+/// libraries against the rules themselves rather than against one implementation of them.
+///
+/// References:
+/// [ZIP215] Explicitly defining and modifying Ed25519 validation rules; by Henry de Valence; https://zips.z.cash/zip-0215
+use crate::algorithm2::deserialize_s;
+use crate::verdict::Verdict;
+use crate::{
+    check_slice_size, compute_hram_with_arrays, deserialize_point, verify_final_cofactored,
+};
+use anyhow::Result;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+
+// Decodes A and R leniently, accepting their non-canonical encodings (y >= p, or the sign
+// bit set with x = 0) as well as the points of small order, and S strictly
+#[allow(non_snake_case)]
+fn decode(pk_bytes: &[u8], sig_bytes: &[u8]) -> Result<(EdwardsPoint, EdwardsPoint, Scalar)> {
+    let checked_sig_bytes = check_slice_size(sig_bytes, 64, "sig_bytes")?;
+    let A = deserialize_point(pk_bytes)?;
+    let R = deserialize_point(&checked_sig_bytes[..32])?;
+    let s = deserialize_s(&checked_sig_bytes[32..])?;
+    Ok((A, R, s))
+}
+
+/// Deserializes and verifies a signature under the rules of ZIP-215: the cofactored equation
+/// `[8][S]B = [8]R + [8][k]A`, with k the hash of A and R as encoded in the inputs.
+pub fn verify(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> bool {
+    verdict(msg_bytes, pk_bytes, sig_bytes).is_accepted()
+}
+
+/// As `verify`, telling apart the step rejecting the signature.
+#[allow(non_snake_case)]
+pub fn verdict(msg_bytes: &[u8], pk_bytes: &[u8], sig_bytes: &[u8]) -> Verdict {
+    Verdict::from_results(decode(pk_bytes, sig_bytes), |(A, R, s)| {
+        let k = compute_hram_with_arrays(msg_bytes, pk_bytes, &sig_bytes[..32]);
+        verify_final_cofactored(&A, &(R, s), &k)
+    })
+}
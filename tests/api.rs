@@ -0,0 +1,245 @@
+// Pins the stable API: any change to the items below is a breaking change for downstream
+// crates, and must come with a major version bump.
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use curve25519_dalek::{edwards::EdwardsPoint, scalar::Scalar};
+    use ed25519_speccheck::{
+        algorithm2,
+        classification::{
+            has_torsion_component, multiple_of_eight_le, point_order, scalar_class, torsion_order,
+            PointOrder, ScalarClass,
+        },
+        compute_hram,
+        corpus_version::{self, CorpusVersion, Manifest, ManifestEntry},
+        decoded::DecodedVector,
+        deserialize_point, deserialize_point_with, fips186_5, libsodium,
+        matrix::{classify, ExpectedMatrix, Verdicts, KNOWN_POLICIES},
+        policy::{
+            self, disambiguating_cases, infer_from_partial, infer_policy, prune, Fingerprinter,
+            Inference, PrunedCorpus, ValidationPolicy,
+        },
+        rfc8032, serialize_signature,
+        vectors::{
+            generate_test_vectors, generate_test_vectors_n, CaseMetadata, ConstructionTrace,
+            PolicyResults, TestVector, VectorGenerator, CASE_IDS,
+        },
+        verdict::Verdict,
+        verify_cofactored, verify_cofactorless, verify_pre_reduced_cofactored, zip215, DecodeMode,
+        Equation, HramPrefix, StreamingVerifier,
+    };
+    use std::collections::BTreeMap;
+
+    type VerifyFn = fn(&[u8], &EdwardsPoint, &(EdwardsPoint, Scalar)) -> Result<()>;
+
+    #[test]
+    fn test_vectors_api() {
+        let generate: fn() -> Vec<TestVector> = generate_test_vectors;
+        let vec = generate();
+
+        let tv: &TestVector = &vec[0];
+        let _: &Vec<u8> = &tv.message;
+        let _: &[u8; 32] = &tv.pub_key;
+        let _: &Vec<u8> = &tv.signature;
+        let _: &Option<CaseMetadata> = &tv.metadata;
+        let _: &Option<ConstructionTrace> = &tv.trace;
+        let _: Option<&str> = tv.id();
+        let _: &String = &tv.metadata.as_ref().unwrap().id;
+        let _: fn(&str) -> Option<TestVector> = TestVector::by_id;
+        let _: [&str; 12] = CASE_IDS;
+        let results: PolicyResults = tv.evaluate();
+        let _: [bool; 6] = [
+            results.cofactored,
+            results.cofactorless,
+            results.pre_reduced_cofactored,
+            results.algorithm2,
+            results.zip215,
+            results.rfc8032,
+        ];
+
+        let generator: VectorGenerator = VectorGenerator::from_seed([0u8; 32]);
+        let _: VectorGenerator = VectorGenerator::from_u64(0);
+        let _: fn() -> VectorGenerator = VectorGenerator::from_entropy;
+        let _: [u8; 32] = generator.derive(1).seed();
+        let _: rand::rngs::StdRng = generator.rng();
+        let _: fn(&VectorGenerator) -> Vec<TestVector> = VectorGenerator::test_vectors;
+        let _: fn(usize, u64) -> Vec<TestVector> = generate_test_vectors_n;
+    }
+
+    #[test]
+    fn test_verify_api() {
+        let verifiers: [VerifyFn; 3] = [
+            verify_cofactored,
+            verify_cofactorless,
+            verify_pre_reduced_cofactored,
+        ];
+        let _: fn(&[u8], &EdwardsPoint, &EdwardsPoint) -> Scalar = compute_hram;
+        let _: fn(&[u8; 32]) -> ScalarClass = scalar_class;
+        match ScalarClass::Canonical {
+            ScalarClass::Canonical | ScalarClass::GeL | ScalarClass::HighBitsSet => {}
+        }
+        let _: fn(&[u8; 32]) -> bool = multiple_of_eight_le;
+        let _: fn(&EdwardsPoint) -> PointOrder = point_order;
+        match PointOrder::Prime {
+            PointOrder::One
+            | PointOrder::Two
+            | PointOrder::Four
+            | PointOrder::Eight
+            | PointOrder::Mixed
+            | PointOrder::Prime => {}
+        }
+        let _: fn(&EdwardsPoint) -> bool = has_torsion_component;
+        let _: fn(&EdwardsPoint) -> usize = torsion_order;
+        let _: fn(&EdwardsPoint, &EdwardsPoint) -> HramPrefix = HramPrefix::new;
+        let _: fn(&[u8], &[u8]) -> HramPrefix = HramPrefix::from_arrays;
+        let _: fn(&HramPrefix, &[u8]) -> Scalar = HramPrefix::finish;
+        let _: fn(&[u8]) -> Result<EdwardsPoint> = deserialize_point;
+        let _: fn(&[u8], DecodeMode) -> Result<EdwardsPoint> = deserialize_point_with;
+        match DecodeMode::Strict {
+            DecodeMode::Lenient | DecodeMode::Strict => {}
+        }
+        let _: fn(&EdwardsPoint, &Scalar) -> Vec<u8> = serialize_signature;
+        match Equation::Cofactored {
+            Equation::Cofactored | Equation::Cofactorless | Equation::PreReducedCofactored => {}
+        }
+        let _: fn(Equation, &EdwardsPoint, &(EdwardsPoint, Scalar)) -> StreamingVerifier =
+            StreamingVerifier::new;
+        let _: fn(Equation, &[u8], &[u8]) -> Result<StreamingVerifier> =
+            StreamingVerifier::from_serialized;
+        let _: fn(&mut StreamingVerifier, &[u8]) = StreamingVerifier::update;
+        let _: fn(StreamingVerifier) -> Result<()> = StreamingVerifier::finalize_verify;
+        let _: fn(&[u8], &[u8], &[u8]) -> bool = algorithm2::verify;
+        let _: fn(&[u8], &[u8], &[u8]) -> Verdict = algorithm2::verdict;
+        let _: fn(&[u8], &[u8], &[u8]) -> bool = rfc8032::verify;
+        let _: fn(&[u8], &[u8], &[u8]) -> Verdict = rfc8032::verdict;
+        let _: fn(&[u8], &[u8], &[u8]) -> bool = fips186_5::verify;
+        let _: fn(&[u8], &[u8], &[u8]) -> Verdict = fips186_5::verdict;
+        let _: fn(&[u8], &[u8], &[u8]) -> bool = zip215::verify;
+        let _: fn(&[u8], &[u8], &[u8]) -> Verdict = zip215::verdict;
+        let _: fn(&[u8], &[u8], &[u8]) -> bool = libsodium::verify;
+        let _: fn(&[u8], &[u8], &[u8]) -> Verdict = libsodium::verdict;
+
+        // a vector all verifiers accept, with A and R of order L
+        let tv = &generate_test_vectors()[6];
+        let pub_key = deserialize_point(&tv.pub_key).unwrap();
+        let r = deserialize_point(&tv.signature[..32]).unwrap();
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&tv.signature[32..]);
+        let s = Scalar::from_bits(s_bytes);
+        for verify in verifiers.iter() {
+            assert!(verify(&tv.message, &pub_key, &(r, s)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_policies_api() {
+        let policy = ValidationPolicy {
+            reject_small_order_pk: false,
+            reject_noncanonical_pk: false,
+            reject_small_order_r: false,
+            reject_noncanonical_r: false,
+            require_canonical_s: false,
+            cofactored: true,
+            prereduce: false,
+            reserialize_for_hash: false,
+            require_full_order_pk: false,
+        };
+        let _: fn() -> Vec<ValidationPolicy> = ValidationPolicy::all;
+        let _: ValidationPolicy = policy::LIBSODIUM;
+        let _: ValidationPolicy = policy::COFACTORED;
+        let _: ValidationPolicy = policy::COFACTORLESS;
+
+        let vec = generate_test_vectors();
+        let tv = &vec[3];
+        let _: Result<()> = policy.verify(&tv.message, &tv.pub_key, &tv.signature);
+        let verdict: Verdict = policy.verdict(&tv.message, &tv.pub_key, &tv.signature);
+        let _: Vec<Verdict> = policy.predict(&vec);
+        let decoded: DecodedVector =
+            DecodedVector::new(&tv.message, &tv.pub_key, &tv.signature).unwrap();
+        let _: DecodedVector =
+            DecodedVector::from_points(&tv.message, &decoded.pub_key, &decoded.r, &decoded.s);
+        let _: Result<()> = policy.verify_decoded(&decoded);
+        let _: Verdict = policy.verdict_decoded(&decoded);
+        let _: String = policy.to_string();
+        let built: ValidationPolicy = ValidationPolicy::new()
+            .reject_small_order_pk()
+            .reject_noncanonical_pk()
+            .reject_small_order_r()
+            .reject_noncanonical_r()
+            .require_canonical_s()
+            .require_full_order_pk()
+            .reserialize_for_hash()
+            .cofactored(true)
+            .prereduce(false);
+        let _: Vec<Verdict> = built.predict_generated();
+        let _: Vec<&str> = built.differing_checks(&policy);
+        let _: Result<Option<ValidationPolicy>> = built.minimal_change(&vec, &built.predict(&vec));
+
+        match verdict {
+            Verdict::Accepted
+            | Verdict::ParseRejected
+            | Verdict::VerifyRejected
+            | Verdict::Error => {}
+        }
+        let _: bool = verdict.is_accepted();
+        let _: bool = verdict.is_error();
+        let _: Verdict = Verdict::from_accepted(true);
+    }
+
+    #[test]
+    fn test_classify_api() {
+        let vec = generate_test_vectors();
+        let observed: Vec<Option<Verdict>> = vec![None; vec.len()];
+
+        let inference: Inference = infer_from_partial(&vec, &observed).unwrap();
+        let _: &Vec<ValidationPolicy> = &inference.consistent;
+        let _: &Vec<usize> = &inference.disambiguating;
+        let _: bool = inference.is_conclusive();
+        let results: BTreeMap<String, bool> = BTreeMap::new();
+        let _: Result<Vec<ValidationPolicy>> = infer_policy(&results);
+        let _: Result<Vec<&str>> = disambiguating_cases(&results);
+
+        let mut fingerprinter = Fingerprinter::new(&vec);
+        let _: Option<usize> = fingerprinter.next_query();
+        let _: Result<()> = fingerprinter.record(0, Verdict::Accepted);
+        let _: Result<()> = fingerprinter.skip(1);
+        let _: usize = fingerprinter.queries();
+        let _: Vec<ValidationPolicy> = fingerprinter.consistent();
+
+        let pruned: PrunedCorpus = prune(&vec, &ValidationPolicy::default(), 4);
+        let _: &Vec<usize> = &pruned.selected;
+        let _: &Vec<usize> = &pruned.dropped;
+        let _: &Vec<ValidationPolicy> = &pruned.undistinguished;
+
+        let matrix = ExpectedMatrix::new(&vec);
+        let _: &[(&str, ValidationPolicy)] = &KNOWN_POLICIES;
+        let verdicts: Verdicts = classify(&vec[0]);
+        let _: Option<&Verdicts> = matrix.row(0);
+        let _: Option<Verdict> = matrix.expected(0, "zip215");
+        let observed: Vec<Verdict> = verdicts.values().copied().collect();
+        let _: Result<()> = matrix.check("zip215", &observed);
+        let _: Vec<&str> = matrix.matching(&observed);
+        let _: usize = matrix.len();
+        let _: bool = matrix.is_empty();
+    }
+
+    #[test]
+    fn test_corpus_version_api() {
+        match corpus_version::LATEST {
+            CorpusVersion::V1 | CorpusVersion::V2 => {}
+        }
+        let version: CorpusVersion = "corpus-v1".parse().unwrap();
+        let _: &[CorpusVersion] = &CorpusVersion::ALL;
+        let _: &str = version.name();
+        let _: Option<CorpusVersion> = version.previous();
+        let _: Vec<&str> = version.files();
+        let files: Vec<(&str, Vec<u8>)> = version.artifacts().unwrap();
+        let _: &str = corpus_version::MANIFEST;
+
+        let manifest: Manifest = Manifest::from_json(&files[2].1).unwrap();
+        let _: CorpusVersion = manifest.corpus;
+        let entry: &ManifestEntry = &manifest.files[0];
+        let _: (&String, &String) = (&entry.name, &entry.sha256);
+        let _: Result<()> = manifest.check(version, &[]);
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::{constants::BASEPOINT_ORDER, scalar::Scalar};
+    use ed25519_speccheck::{
+        algorithm2, deserialize_point, deserialize_point_with,
+        message_source::{Counter, RandomBytes, Template},
+        new_rng,
+        non_reducing_scalar52::BigScalar,
+        policy::{ValidationPolicy, COFACTORED, COFACTORLESS},
+        test_vectors::{
+            generate_test_vectors, generate_test_vectors_from, large_s, non_zero_mixed_mixed,
+            non_zero_mixed_small, non_zero_mixed_small_non_canonical, non_zero_small_mixed,
+            non_zero_small_non_canonical_mixed, pre_reduced_scalar, really_large_s,
+            zero_small_small, SmallPoint, TestVector,
+        },
+        torsion::{EIGHT_TORSION, EIGHT_TORSION_NON_CANONICAL},
+        DecodeMode,
+    };
+
+    // Verification hashing A and R as provided, rather than re-serialized as `COFACTORED` and
+    // `COFACTORLESS` do
+    const RAW_COFACTORED: ValidationPolicy = ValidationPolicy {
+        reserialize_for_hash: false,
+        ..COFACTORED
+    };
+    const RAW_COFACTORLESS: ValidationPolicy = ValidationPolicy {
+        reserialize_for_hash: false,
+        ..COFACTORLESS
+    };
+
+    fn passes(policy: &ValidationPolicy, tv: &TestVector) -> bool {
+        policy
+            .verify(&tv.message, &tv.pub_key, &tv.signature)
+            .is_ok()
+    }
+
+    fn nonzero_small_points() -> Vec<SmallPoint> {
+        EIGHT_TORSION[1..]
+            .iter()
+            .map(|bytes| SmallPoint::Fixed(deserialize_point(bytes).unwrap()))
+            .collect()
+    }
+
+    fn identity() -> SmallPoint {
+        SmallPoint::Fixed(deserialize_point(&EIGHT_TORSION[0]).unwrap())
+    }
+
+    #[test]
+    fn test_decode_modes() {
+        for bytes in EIGHT_TORSION.iter() {
+            let lenient = deserialize_point_with(bytes, DecodeMode::Lenient).unwrap();
+            let strict = deserialize_point_with(bytes, DecodeMode::Strict).unwrap();
+            assert_eq!(lenient, strict);
+        }
+        for bytes in EIGHT_TORSION_NON_CANONICAL.iter() {
+            assert!(deserialize_point_with(bytes, DecodeMode::Lenient).is_ok());
+            assert!(deserialize_point_with(bytes, DecodeMode::Strict).is_err());
+            // as Algorithm 2 decodes
+            assert!(algorithm2::deserialize_point(bytes).is_err());
+        }
+        // the modes only differ in their acceptance of the encodings
+        let not_a_point = [2u8; 32];
+        assert!(deserialize_point(&not_a_point).is_err());
+        assert!(deserialize_point_with(&not_a_point, DecodeMode::Strict).is_err());
+    }
+
+    // The generators of pairs of vectors where the first one passes cofactored and fails
+    // cofactorless verification, and the second one passes both
+    #[test]
+    fn test_pair_generators_for_every_small_point() {
+        let generators = [
+            zero_small_small,
+            non_zero_mixed_small,
+            non_zero_small_mixed,
+            non_zero_mixed_mixed,
+        ];
+        for generator in generators.iter() {
+            for small in nonzero_small_points() {
+                let (tv1, tv2) =
+                    generator(&mut new_rng(), &mut RandomBytes::default(), small).unwrap();
+                assert!(passes(&COFACTORED, &tv1));
+                assert!(!passes(&COFACTORLESS, &tv1));
+                assert!(passes(&COFACTORED, &tv2));
+                assert!(passes(&COFACTORLESS, &tv2));
+            }
+            assert!(generator(&mut new_rng(), &mut RandomBytes::default(), identity()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_pre_reduced_scalar_for_every_small_point() {
+        for small in nonzero_small_points() {
+            let tv =
+                pre_reduced_scalar(&mut new_rng(), &mut RandomBytes::default(), small).unwrap();
+            assert!(passes(&COFACTORED, &tv));
+            assert!(!passes(&COFACTORED.prereduce(true), &tv));
+            assert!(!passes(&COFACTORLESS, &tv));
+        }
+        assert!(
+            pre_reduced_scalar(&mut new_rng(), &mut RandomBytes::default(), identity()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_large_s() {
+        let canonical_s = ValidationPolicy {
+            require_canonical_s: true,
+            ..COFACTORED
+        };
+        for tv in [
+            large_s(&mut new_rng(), &mut RandomBytes::default()).unwrap(),
+            really_large_s(&mut new_rng(), &mut RandomBytes::default()).unwrap(),
+        ]
+        .iter()
+        {
+            assert!(passes(&COFACTORED, tv));
+            assert!(passes(&COFACTORLESS, tv));
+            assert!(!passes(&canonical_s, tv));
+        }
+    }
+
+    #[test]
+    fn test_big_scalar() {
+        assert_eq!(BigScalar::L.to_bytes(), BASEPOINT_ORDER.to_bytes());
+        let s = Scalar::from_bytes_mod_order([0xa5; 32]);
+        let big = BigScalar::from_scalar(&s);
+        assert_eq!(BigScalar::from_bytes(&[0xa5; 32]).to_bytes(), [0xa5; 32]);
+        assert!(big.is_canonical());
+        assert!(!BigScalar::L.is_canonical());
+        assert!((BigScalar::L - BigScalar::from_u64(1)).is_canonical());
+
+        // S + jL reduces to S for every j fitting in 256 bits, 15 at most
+        for j in 0..16 {
+            let lifted = big.add_multiple_of_l(j).unwrap();
+            assert_eq!(lifted.reduce(), s);
+            assert_eq!(lifted.div_rem_l(), (j, s));
+            assert_eq!(lifted - BigScalar::L * BigScalar::from_u64(j), big);
+        }
+        assert_eq!(BigScalar::ZERO.add_multiple_of_l(16), None);
+
+        // nothing wraps around
+        let top = BigScalar::pow2(255);
+        assert_eq!(top.checked_add(&top), None);
+        assert_eq!(BigScalar::ZERO.checked_sub(&BigScalar::from_u64(1)), None);
+        assert_eq!(BigScalar::pow2(128) * BigScalar::pow2(127), top);
+        assert_eq!(
+            BigScalar::pow2(128).checked_mul(&BigScalar::pow2(128)),
+            None
+        );
+        assert_eq!(
+            BigScalar::from_u64(u64::MAX) * BigScalar::from_u64(u64::MAX),
+            BigScalar::pow2(128) - BigScalar::pow2(65) + BigScalar::from_u64(1)
+        );
+
+        // bit patterns above 2^252
+        assert!(BigScalar::L > BigScalar::pow2(252));
+        assert!(BigScalar::L < BigScalar::pow2(253));
+        assert!(top.bit(255) && !top.bit(254));
+        assert_eq!(BigScalar::ZERO.with_bit(253, true), BigScalar::pow2(253));
+        assert_eq!(top.with_bit(255, false), BigScalar::ZERO);
+        assert_eq!(BigScalar::pow2(253).to_bytes()[31], 0x20);
+    }
+
+    // The first vector passes only when R is re-serialized for hashing, the second one only
+    // when it is not
+    #[test]
+    fn test_non_canonical_r_for_every_encoding() {
+        for r_arr in EIGHT_TORSION_NON_CANONICAL.iter() {
+            let tv_vec = non_zero_small_non_canonical_mixed(
+                &mut new_rng(),
+                &mut RandomBytes::default(),
+                *r_arr,
+            )
+            .unwrap();
+            for tv in tv_vec.iter() {
+                assert_eq!(&tv.signature[..32], &r_arr[..]);
+            }
+            assert!(passes(&COFACTORED, &tv_vec[0]));
+            assert!(passes(&COFACTORLESS, &tv_vec[0]));
+            assert!(!passes(&RAW_COFACTORED, &tv_vec[0]));
+            assert!(passes(&RAW_COFACTORED, &tv_vec[1]));
+            assert!(passes(&RAW_COFACTORLESS, &tv_vec[1]));
+            assert!(!passes(&COFACTORED, &tv_vec[1]));
+        }
+    }
+
+    // Both vectors pass cofactored verification, while cofactorless verification accepts the
+    // first one only when A is re-serialized for hashing, and the second one only when not
+    #[test]
+    fn test_non_canonical_a_for_every_encoding() {
+        for pub_key_arr in EIGHT_TORSION_NON_CANONICAL.iter() {
+            let generated = non_zero_mixed_small_non_canonical(
+                &mut new_rng(),
+                &mut RandomBytes::default(),
+                *pub_key_arr,
+            );
+            if deserialize_point(pub_key_arr).unwrap()
+                == deserialize_point(&EIGHT_TORSION[0]).unwrap()
+            {
+                assert!(generated.is_err());
+                continue;
+            }
+
+            let tv_vec = generated.unwrap();
+            for tv in tv_vec.iter() {
+                assert_eq!(tv.pub_key, *pub_key_arr);
+                assert!(passes(&COFACTORED, tv));
+                assert!(passes(&RAW_COFACTORED, tv));
+            }
+            assert!(passes(&COFACTORLESS, &tv_vec[0]));
+            assert!(!passes(&RAW_COFACTORLESS, &tv_vec[0]));
+            assert!(!passes(&COFACTORLESS, &tv_vec[1]));
+            assert!(passes(&RAW_COFACTORLESS, &tv_vec[1]));
+        }
+    }
+
+    // Whatever the messages ground over, the vectors exhibit the documented behaviors
+    #[test]
+    fn test_message_sources() {
+        let payload = b"{\"to\":\"alice\",\"amount\":100,\"memo\":\"________\"}";
+        let memo = 35..43;
+        let mut template = Template::new(payload, memo.clone()).unwrap();
+        let templated = generate_test_vectors_from(new_rng, &mut template);
+        let counted = generate_test_vectors_from(new_rng, &mut Counter::new(b"nonce:"));
+        let reference = generate_test_vectors();
+
+        for tv in templated.iter() {
+            assert_eq!(tv.message[..memo.start], payload[..memo.start]);
+            assert_eq!(tv.message[memo.end..], payload[memo.end..]);
+        }
+        for tv in counted.iter() {
+            assert!(tv.message.starts_with(b"nonce:"));
+        }
+        for policy in [COFACTORED, COFACTORLESS, RAW_COFACTORED, RAW_COFACTORLESS].iter() {
+            assert_eq!(policy.predict(&templated), policy.predict(&reference));
+            assert_eq!(policy.predict(&counted), policy.predict(&reference));
+        }
+
+        assert!(Template::new(payload, 40..40).is_err());
+        assert!(Template::new(payload, 40..payload.len() + 1).is_err());
+    }
+}
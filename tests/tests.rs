@@ -1,27 +1,124 @@
 #[cfg(test)]
 mod tests {
     use anyhow::{anyhow, Result};
-    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
-    use curve25519_dalek::{scalar::Scalar, traits::IsIdentity};
+    use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+    use curve25519_dalek::{
+        edwards::EdwardsPoint, ristretto::RistrettoPoint, scalar::Scalar, traits::IsIdentity,
+    };
 
+    #[cfg(feature = "dalek")]
     use ed25519_dalek::{PublicKey, Signature, Verifier};
+    #[cfg(feature = "ed448")]
+    use ed25519_speccheck::ed448::{self, ed448_vectors, Ed448Key, Ed448Vector, ED448_POLICIES};
+    #[cfg(feature = "ring")]
+    use ed25519_speccheck::report::ResultsTable;
     use ed25519_speccheck::{
-        algorithm2, compute_hram, deserialize_point, new_rng, serialize_signature,
-        test_vectors::{generate_test_vectors, TestVector},
-        verify_cofactored, verify_cofactorless, EIGHT_TORSION,
+        algorithm2, algorithm3, artifacts,
+        batch::{self, Coefficients},
+        batch_inconsistency::batch_inconsistency_vectors,
+        batch_vectors::{
+            batch_equation_vectors, interleaved_batches, misattribution_batches,
+            verify_batch_cofactored, verify_batch_cofactorless, BatchOutcome, BatchVector,
+        },
+        classification::{
+            has_torsion_component, multiple_of_eight_le, point_order, scalar_class, PointOrder,
+            ScalarClass,
+        },
+        cli::{self, Command, Format},
+        compute_hram,
+        contribution::{self, Contribution},
+        controls::{self, control_vectors, negative_control_vectors},
+        corpus::{self, CorpusReader, Layout},
+        corpus_version::{self, CorpusVersion, Manifest},
+        decoded::DecodedVector,
+        deserialize_point, deserialize_point_with,
+        differential::{differential_fuzz, random_vector, Minimizer, VectorKind},
+        explain::{explain, explain_verifier, Property},
+        export,
+        external::{External, Input},
+        full_order::full_order_vectors,
+        hram_pairs::hram_linked_pairs,
+        identity_pubkey::identity_pubkey,
+        identity_r::identity_r,
+        io::{self, VectorFormat},
+        key_substitution::{key_substitution_pairs, substituted, substitutes},
+        large_messages::{
+            large_message_vectors, message_length_vectors, store_message_vectors,
+            INLINE_MESSAGE_LIMIT, MESSAGE_LENGTHS, MESSAGE_SIZES,
+        },
+        large_s::{large_s_family, s_check_of, PLACEMENTS},
+        malicious_signer::{MaliciousSigner, SignedMessage},
+        malleability,
+        matrix::{classify, ExpectedMatrix, KNOWN_POLICIES},
+        metrics::{self, Metrics},
+        new_rng,
+        non_canonical::non_canonical_vectors,
+        non_canonical_encodings::{
+            non_canonical_encoding_vectors, non_canonical_encodings, per_encoding_table,
+        },
+        one_sided_non_canonical::{one_sided_non_canonical_vectors, AcceptancePattern},
+        policy::{
+            disambiguating_cases, has_full_order, infer_from_partial, infer_policy, prune,
+            Fingerprinter, ValidationPolicy, ALGORITHM_2, COFACTORED, COFACTORLESS, FIPS_186_5,
+            LIBSODIUM, NAMED_POLICIES, RFC_8032, ZIP_215,
+        },
+        pre_reduction::{detect_prereduction, pre_reduction_vectors},
+        published,
+        reduced_s::{reduced_s_pairs, MULTIPLES_OF_L},
+        registry::{Adapter, Registry},
+        repro,
+        repudiation::{self, repudiation},
+        ristretto::{self, contrast_vectors, RistrettoSchnorr, SchnorrKey},
+        s_boundary::s_boundary_vectors,
+        s_high_bits::{s_high_bits_vectors, S_CHECKS},
+        sarif,
+        search::{sweep, RARE_PROPERTIES},
+        serialize_signature,
+        signing::SigningKey,
+        tiny_scalars::{tiny_scalar_vectors, TINY_SCALARS},
+        torsion_clearing::{clear_torsion, clear_torsion_encoded, torsion_clearing_vectors},
+        torsion_safe::{normalization_vectors, normalize, normalize_signature},
+        torsion_sweep::{rejected_torsion, sweep_torsion},
+        transcript::{generate_recorded, generate_replayed, Transcript},
+        upstream::{
+            crate_verifiers, cross_check, cross_check_report, import_wycheproof, merge,
+            speccheck_vectors, zip215_vectors, Axis, Coverage, Suite,
+        },
+        vectors::{
+            ed25519ctx, ed25519ph, generate_test_vectors, generate_test_vectors_n, SClass,
+            TestVector, VectorGenerator, CASE_IDS,
+        },
+        verdict::Verdict,
+        verifier::{
+            self, integrations_table, results_table, verdicts, Algorithm2, Cofactored,
+            Cofactorless, Fips186_5, Libsodium, PreReducedCofactored, Rfc8032, Verifier as _,
+            Zip215,
+        },
+        verify_cofactored, verify_cofactorless, verify_pre_reduced_cofactored,
+        x25519::{
+            outcomes, results as x25519_results, x25519, x25519_vectors, Behavior, Family,
+            Handling, KeyAgreement, Outcome, RejectAllZero, Rfc7748, SCALAR,
+        },
+        DecodeMode, Equation, HramPrefix, StreamingVerifier,
     };
+    #[cfg(feature = "zebra")]
     use ed25519_zebra::{Signature as ZSignature, VerificationKey as ZPublicKey};
     use rand::RngCore;
+    #[cfg(feature = "ring")]
     use ring::signature;
+    use sha2::{Digest, Sha256, Sha512};
+    use std::collections::{BTreeMap, HashSet};
     use std::convert::TryFrom;
     use std::ops::Neg;
 
+    #[cfg(feature = "dalek")]
     fn unpack_test_vector_dalek(t: &TestVector) -> (PublicKey, Signature) {
         let pk = PublicKey::from_bytes(&t.pub_key[..]).unwrap();
         let sig = Signature::try_from(&t.signature[..]).unwrap();
         (pk, sig)
     }
 
+    #[cfg(feature = "hacl")]
     fn unpack_test_vector_hacl(
         t: &TestVector,
     ) -> (hacl_star::ed25519::PublicKey, hacl_star::ed25519::Signature) {
@@ -33,12 +130,14 @@ mod tests {
         (pk, sig)
     }
 
+    #[cfg(feature = "zebra")]
     fn unpack_test_vector_zebra(t: &TestVector) -> (ZPublicKey, ZSignature) {
         let pk = ZPublicKey::try_from(&t.pub_key[..]).unwrap();
         let sig = ZSignature::try_from(&t.signature[..]).unwrap();
         (pk, sig)
     }
 
+    #[cfg(feature = "ring")]
     fn ring_verify(t: &TestVector) -> Result<()> {
         let pk = untrusted::Input::from(&t.pub_key[..]);
         let sig = untrusted::Input::from(&t.signature[..]);
@@ -52,279 +151,4960 @@ mod tests {
         .map_err(|_| anyhow!("signature verification failed"))
     }
 
-    #[test]
-    #[allow(non_snake_case)]
-    fn test_CGN20_algorithm2() {
-        let vec = generate_test_vectors();
+    // libsodium does not tell parsing from verifying either, and `crypto_sign_open` checks
+    // the same as `crypto_sign_verify_detached`, over the signature followed by the message.
+    // Both read 64 bytes of signature and 32 of public key whatever their length, hence the
+    // lengths checked first, as `ffi` does
+    #[cfg(feature = "sodium")]
+    fn sodium_verify_detached(t: &TestVector) -> bool {
+        if t.signature.len() != 64 || t.pub_key.len() != 32 {
+            return false;
+        }
+        assert!(unsafe { libsodium_sys::sodium_init() } >= 0);
+        let verified = unsafe {
+            libsodium_sys::crypto_sign_verify_detached(
+                t.signature.as_ptr(),
+                t.message.as_ptr(),
+                t.message.len() as u64,
+                t.pub_key.as_ptr(),
+            )
+        };
+        verified == 0
+    }
 
-        print!("\n|[CGN20e] Alg.2 |");
-        for tv in vec.iter() {
-            let pk = match algorithm2::deserialize_pk(&tv.pub_key) {
-                Ok(pk) => pk,
-                Err(_) => {
-                    print!(" X |");
-                    continue;
-                }
-            };
+    #[cfg(feature = "sodium")]
+    fn sodium_open(t: &TestVector) -> bool {
+        if t.signature.len() != 64 || t.pub_key.len() != 32 {
+            return false;
+        }
+        assert!(unsafe { libsodium_sys::sodium_init() } >= 0);
+        let signed = [&t.signature[..], &t.message[..]].concat();
+        let mut opened = vec![0u8; signed.len()];
+        let mut opened_len = 0u64;
+        let verified = unsafe {
+            libsodium_sys::crypto_sign_open(
+                opened.as_mut_ptr(),
+                &mut opened_len,
+                signed.as_ptr(),
+                signed.len() as u64,
+                t.pub_key.as_ptr(),
+            )
+        };
+        verified == 0 && opened[..opened_len as usize] == t.message[..]
+    }
 
-            let (s, R) = match algorithm2::deserialize_signature(&tv.signature) {
-                Ok(sR) => sR,
-                Err(_) => {
-                    print!(" X |");
-                    continue;
+    // Prints a row of the results table, e.g. `|Dalek          | V | P | X |`
+    fn print_row(library: &str, verdicts: impl Iterator<Item = Verdict>) {
+        print!("\n|{:<15}|", library);
+        for verdict in verdicts {
+            print!(" {} |", verdict);
+        }
+        println!();
+    }
+
+    // The libraries under test, and the reference Algorithm 2, as rows of the results table:
+    // those whose feature is enabled
+    fn library_registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Adapter::new("[CGN20e] Alg.2", |tv: &TestVector| {
+            algorithm2::verdict(&tv.message, &tv.pub_key, &tv.signature)
+        }));
+        #[cfg(feature = "aptos")]
+        registry
+            .register(Adapter::new("aptos-crypto", aptos_verdict))
+            .register(Adapter::new("aptos-crypto-st", |tv: &TestVector| {
+                // We are just manually checking the pubkey and the signature's R encodings
+                // are canonical
+                if !algorithm2::is_canonical_point_encoding(&tv.pub_key)
+                    || !algorithm2::is_canonical_point_encoding(&tv.signature[..32])
+                {
+                    return Verdict::ParseRejected;
                 }
-            };
+                aptos_verdict(tv)
+            }));
+        // ring does not expose the decoding step separately
+        #[cfg(feature = "ring")]
+        registry.register(Adapter::new("BoringSSL", |tv: &TestVector| {
+            Verdict::from_accepted(ring_verify(tv).is_ok())
+        }));
+        #[cfg(feature = "dalek")]
+        registry
+            .register(Adapter::new("Dalek", |tv: &TestVector| {
+                Verdict::from_results(Signature::try_from(&tv.signature[..]), |_| {
+                    let (pk, sig) = unpack_test_vector_dalek(tv);
+                    pk.verify(&tv.message[..], &sig)
+                })
+            }))
+            .register(Adapter::new("Dalek strict", |tv: &TestVector| {
+                Verdict::from_results(Signature::try_from(&tv.signature[..]), |_| {
+                    let (pk, sig) = unpack_test_vector_dalek(tv);
+                    pk.verify_strict(&tv.message[..], &sig)
+                })
+            }));
+        #[cfg(feature = "hacl")]
+        registry.register(Adapter::new("Hacl*", |tv: &TestVector| {
+            let (pk, sig) = unpack_test_vector_hacl(tv);
+            Verdict::from_accepted(pk.verify(&tv.message[..], &sig))
+        }));
+        #[cfg(feature = "diem")]
+        registry.register(Adapter::new("libra-crypto", diem_verdict));
+        #[cfg(feature = "sodium")]
+        registry
+            .register(Adapter::new("libsodium", |tv: &TestVector| {
+                Verdict::from_accepted(sodium_verify_detached(tv))
+            }))
+            .register(Adapter::new("libsodium open", |tv: &TestVector| {
+                Verdict::from_accepted(sodium_open(tv))
+            }));
+        #[cfg(feature = "openssl")]
+        registry.register(Adapter::new("OpenSSL", openssl_verdict));
+        #[cfg(feature = "zebra")]
+        registry.register(Adapter::new("Zebra", |tv: &TestVector| {
+            Verdict::from_results(ZSignature::try_from(&tv.signature[..]), |_| {
+                let (pk, sig) = unpack_test_vector_zebra(tv);
+                pk.verify(&sig, &tv.message[..])
+            })
+        }));
+        registry
+    }
 
-            if algorithm2::verify_signature(&s, &R, &tv.message, &pk) {
-                print!(" V |");
-            } else {
-                print!(" X |");
-            }
+    #[cfg(feature = "diem")]
+    fn diem_verdict(tv: &TestVector) -> Verdict {
+        let pk = match diem_crypto::ed25519::Ed25519PublicKey::try_from(&tv.pub_key[..]) {
+            Ok(pk) => pk,
+            Err(_e) => return Verdict::ParseRejected,
+        };
+        let sig = match diem_crypto::ed25519::Ed25519Signature::try_from(&tv.signature[..]) {
+            Ok(sig) => sig,
+            Err(_e) => return Verdict::ParseRejected,
+        };
+        match diem_crypto::traits::Signature::verify_arbitrary_msg(&sig, &tv.message[..], &pk) {
+            Ok(_v) => Verdict::Accepted,
+            Err(_e) => Verdict::VerifyRejected,
+        }
+    }
+
+    // OpenSSL only decodes the public key when verifying: building it from raw bytes only
+    // checks its length
+    #[cfg(feature = "openssl")]
+    fn openssl_verdict(tv: &TestVector) -> Verdict {
+        let pk = match openssl::pkey::PKey::public_key_from_raw_bytes(
+            &tv.pub_key[..],
+            openssl::pkey::Id::ED25519,
+        ) {
+            Ok(pk) => pk,
+            Err(_e) => return Verdict::ParseRejected,
+        };
+        let mut verifier = match openssl::sign::Verifier::new_without_digest(&pk) {
+            Ok(verifier) => verifier,
+            Err(_e) => return Verdict::Error,
+        };
+        match verifier.verify_oneshot(&tv.signature[..], &tv.message[..]) {
+            Ok(true) => Verdict::Accepted,
+            _ => Verdict::VerifyRejected,
+        }
+    }
+
+    #[cfg(feature = "aptos")]
+    fn aptos_verdict(tv: &TestVector) -> Verdict {
+        let pk = match aptos_crypto::ed25519::Ed25519PublicKey::try_from(&tv.pub_key[..]) {
+            Ok(pk) => pk,
+            Err(_e) => return Verdict::ParseRejected,
+        };
+        let sig = match aptos_crypto::ed25519::Ed25519Signature::try_from(&tv.signature[..]) {
+            Ok(sig) => sig,
+            Err(_e) => return Verdict::ParseRejected,
+        };
+        match aptos_crypto::traits::Signature::verify_arbitrary_msg(&sig, &tv.message[..], &pk) {
+            Ok(_v) => Verdict::Accepted,
+            Err(_e) => Verdict::VerifyRejected,
         }
-        println!();
     }
 
     #[test]
-    fn test_diem() {
+    fn test_libraries() {
         let vec = generate_test_vectors();
+        let results = library_registry().results(&vec);
+        for (name, verdicts) in results.rows().iter() {
+            print_row(name, verdicts.iter().copied());
+        }
 
-        print!("\n|libra-crypto   |");
-        for tv in vec.iter() {
-            let pk = match diem_crypto::ed25519::Ed25519PublicKey::try_from(&tv.pub_key[..]) {
-                Ok(pk) => pk,
-                Err(_e) => {
-                    print!(" X |");
-                    continue;
-                }
-            };
-            let sig = match diem_crypto::ed25519::Ed25519Signature::try_from(&tv.signature[..]) {
-                Ok(sig) => sig,
-                Err(_e) => {
-                    print!(" X |");
-                    continue;
-                }
-            };
-            match diem_crypto::traits::Signature::verify_arbitrary_msg(&sig, &tv.message[..], &pk) {
-                Ok(_v) => print!(" V |"),
-                Err(_e) => print!(" X |"),
+        // the pinned versions behave as when the paper was published
+        let measured: Vec<(&str, &[Verdict])> = results
+            .rows()
+            .iter()
+            .filter(|(name, _)| {
+                published::PUBLISHED_RESULTS
+                    .iter()
+                    .any(|(library, _)| library == name)
+            })
+            .map(|(name, verdicts)| (name.as_str(), &verdicts[..]))
+            .collect();
+        let report = published::drift_report(&measured).unwrap();
+        println!("{}", report);
+        assert!(report.lines().all(|l| l.ends_with("as published")));
+
+        // libsodium behaves as its emulation, whichever entry point
+        #[cfg(feature = "sodium")]
+        for name in ["libsodium", "libsodium open"].iter() {
+            let row = &results.rows().iter().find(|(n, _)| n == name).unwrap().1;
+            for (tv, verdict) in vec.iter().zip(row.iter()) {
+                assert_eq!(
+                    verdict.is_accepted(),
+                    Libsodium
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok()
+                );
             }
         }
-        println!();
+
+        let alg2 = &results
+            .rows()
+            .iter()
+            .find(|(name, _)| name == "[CGN20e] Alg.2")
+            .unwrap()
+            .1;
+        let mut changed = alg2.clone();
+        changed[0] = Verdict::Accepted;
+        changed[11] = Verdict::Error;
+        let drift = published::compare("[CGN20e] Alg.2", &changed).unwrap();
+        assert_eq!(drift.changed, vec![0, 11]);
+        assert_eq!(drift.measured, "VXVVVVXXXXXE");
+        assert!(published::drift_report(&[("[CGN20e] Alg.2", &changed)])
+            .unwrap()
+            .starts_with("[CGN20e] Alg.2: changed on 0, 11 (published XXVVVVXXXXXX"));
+        assert!(published::compare("OpenSSL", alg2).is_err());
+        assert!(published::compare("[CGN20e] Alg.2", &alg2[1..]).is_err());
+
+        // in-house verifiers join the same harness, and failed integrations are reported
+        let mut registry = Registry::new();
+        registry
+            .register_verifier("cofactored", Cofactored)
+            .register(Adapter::new("flaky", |tv: &TestVector| {
+                assert!(tv.signature[63] != 0, "flaky integration");
+                Verdict::Accepted
+            }))
+            .register_unavailable("in-house", anyhow!("not built"));
+        assert_eq!(registry.names(), ["cofactored", "flaky", "in-house"]);
+        let table = registry.table(&vec);
+        print!("{}", table);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[3],
+            results_table(&[("cofactored", &Cofactored)], &vec)
+                .lines()
+                .nth(3)
+                .unwrap()
+        );
+        assert_eq!(lines[7], "flaky: errored on vectors 0");
+        assert_eq!(lines[8], "in-house: unavailable, not built");
     }
 
+    #[cfg(unix)]
     #[test]
-    fn test_aptos() {
+    fn test_external_verifier() {
         let vec = generate_test_vectors();
+        let run = |external: External| verdicts(&external, &vec);
+        assert!(run(External::new("true", &[], Input::Args))
+            .iter()
+            .all(|v| *v == Verdict::Accepted));
+        assert!(run(External::new("false", &[], Input::Stdin))
+            .iter()
+            .all(|v| *v == Verdict::VerifyRejected));
+        assert!(run(External::new("sh", &["-c", "exit 2"], Input::Args))
+            .iter()
+            .all(|v| *v == Verdict::ParseRejected));
+        assert!(run(External::new("speccheck-missing", &[], Input::Args))
+            .iter()
+            .all(|v| *v == Verdict::Error));
 
-        print!("\n|aptos-crypto   |");
-        for tv in vec.iter() {
-            if !test_aptos_helper(tv) {
-                continue
+        // the vector reaches the command as arguments, or on standard input
+        let pub_key = hex::encode(vec[0].pub_key);
+        let expected: Vec<Verdict> = vec
+            .iter()
+            .map(|tv| Verdict::from_accepted(tv.pub_key == vec[0].pub_key))
+            .collect();
+        let in_args = format!("test $2 = {} && test ${{#3}} = 128", pub_key);
+        assert_eq!(
+            run(External::new("sh", &["-c", &in_args, "sh"], Input::Args)),
+            expected
+        );
+        let on_stdin = format!("read m && read p && read s && test $p = pbk={}", pub_key);
+        assert_eq!(
+            run(External::new("sh", &["-c", &on_stdin], Input::Stdin)),
+            expected
+        );
+        let in_placeholders = format!("test {{pk}} = {} && test ${{#1}} = 128", pub_key);
+        assert_eq!(
+            run(External::new(
+                "sh",
+                &["-c", &in_placeholders, "sh", "{sig}"],
+                Input::Args
+            )),
+            expected
+        );
+        assert_eq!(
+            External::parse(" sh  -c  true ", Input::Args).unwrap(),
+            External::new("sh", &["-c", "true"], Input::Args)
+        );
+        assert!(External::parse(" ", Input::Args).is_err());
+
+        // and joins the report as a row
+        let command = cli::parse(
+            "report --external ok=true --external-stdin ko=false"
+                .split(' ')
+                .map(String::from),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(&format!("|ok{}|{}", " ".repeat(13), " V |".repeat(12))));
+        assert!(output.contains(&format!("|ko{}|{}", " ".repeat(13), " X |".repeat(12))));
+        assert!(cli::parse(vec!["report".into(), "--external".into(), "true".into()]).is_err());
+
+        // or is checked on its own, with a warning per property it breaks
+        let script = std::env::temp_dir().join("speccheck-check.sh");
+        // accepts the vectors whose S ends in 0 or 1, and rejects the others
+        std::fs::write(&script, "case $3 in *0|*1) exit 0;; *) exit 1;; esac\n").unwrap();
+        let command = cli::parse(vec![
+            "check".into(),
+            "--cmd".into(),
+            format!("sh {} {{msg}} {{pk}} {{sig}}", script.display()),
+        ])
+        .unwrap();
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("verdicts: VXXXXXXXXXXX\nno consistent policy"));
+        assert!(output.contains(&format!(
+            "warning: {} (cases 0);",
+            Property::StronglyBinding.warning()
+        )));
+        assert!(!output.contains(Property::StrongUnforgeability.warning()));
+        let command = cli::parse("check --cmd false".split(' ').map(String::from)).unwrap();
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("\n  performing: require-canonical-s\n"));
+        assert!(!output.contains("warning"));
+        let command = cli::parse("check --cmd speccheck-missing".split(' ').map(String::from));
+        assert!(cli::run(&command.unwrap(), &mut Vec::new()).is_err());
+        assert!(cli::parse(vec!["check".into()]).is_err());
+
+        // or of the differential fuzzing, which needs two of them
+        let command = cli::parse(
+            "differential --external ok=true --external-stdin ko=false --iterations 6 --seed 1"
+                .split(' ')
+                .map(String::from),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("honest: ok V, ko X\n  msg="));
+        assert!(output.ends_with("6 disagreements in 6 signatures\n"));
+        let command = cli::parse(
+            "differential --external ok=true --external ko=false --iterations 1 --minimize"
+                .split(' ')
+                .map(String::from),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("  minimized: ok V, ko X\n  msg=\n"));
+        assert!(output.contains("\n  novel\n"));
+        assert!(cli::parse(
+            "differential --external ok=true"
+                .split(' ')
+                .map(String::from)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_differential_fuzz() {
+        let mut rng = new_rng();
+        for kind in VectorKind::ALL.iter() {
+            let tv = random_vector(*kind, &mut rng);
+            let cofactored = Cofactored.verdict(&tv.message, &tv.pub_key, &tv.signature);
+            match kind {
+                VectorKind::Honest => assert!(Rfc8032
+                    .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_accepted()),
+                VectorKind::LargeS => assert!(!Rfc8032
+                    .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_accepted()),
+                VectorKind::FlippedBit => {}
+                _ => assert!(cofactored.is_accepted(), "{}", kind),
             }
         }
-        println!();
-    }
 
-    fn test_aptos_helper(tv: &TestVector) -> bool {
-        let pk = match aptos_crypto::ed25519::Ed25519PublicKey::try_from(&tv.pub_key[..]) {
-            Ok(pk) => pk,
-            Err(_e) => {
-                print!(" X |");
-                return false;
+        // the cofactored and cofactorless equations part on mixed-order keys, about 3 times
+        // out of 4, and on non-canonical small-order ones
+        let mut registry_of_equations = Registry::new();
+        registry_of_equations
+            .register_verifier("cofactored", Cofactored)
+            .register_verifier("cofactorless", Cofactorless);
+        let disagreements = differential_fuzz(&registry_of_equations, &mut rng, 120);
+        for kind in [
+            VectorKind::MixedOrderKey,
+            VectorKind::MixedOrderKeyAndR,
+            VectorKind::NonCanonicalKey,
+        ]
+        .iter()
+        {
+            assert!(disagreements.iter().any(|d| d.kind == *kind), "{}", kind);
+        }
+        for disagreement in disagreements.iter() {
+            assert_eq!(
+                disagreement.verdicts,
+                [
+                    ("cofactored".to_string(), Verdict::Accepted),
+                    ("cofactorless".to_string(), Verdict::VerifyRejected)
+                ]
+            );
+        }
+
+        // a library agrees with itself
+        let mut registry = Registry::new();
+        registry
+            .register_verifier("ZIP-215", Zip215)
+            .register_verifier("again", Zip215);
+        assert!(differential_fuzz(&registry, &mut rng, 60).is_empty());
+
+        // minimized, a disagreement keeps the verdicts of each library, and one on a
+        // mixed-order key falls in the class of a case
+        let minimizer = Minimizer::new(&registry_of_equations, &generate_test_vectors());
+        for disagreement in disagreements.iter().take(6) {
+            let minimized = minimizer.minimize(&disagreement.vector);
+            assert_eq!(minimized.verdicts, disagreement.verdicts);
+            assert!(minimized.vector.message.len() <= disagreement.vector.message.len());
+            if disagreement.kind == VectorKind::MixedOrderKey {
+                assert!(!minimized.is_novel());
             }
+        }
+
+        // while accepting garbage is a class of its own, whatever the message
+        let mut registry = Registry::new();
+        registry
+            .register_verifier("ZIP-215", Zip215)
+            .register(Adapter::new("lax", |_: &TestVector| Verdict::Accepted));
+        let disagreements = differential_fuzz(&registry, &mut rng, 6);
+        let flipped = disagreements
+            .iter()
+            .find(|d| d.kind == VectorKind::FlippedBit)
+            .unwrap();
+        let minimized =
+            Minimizer::new(&registry, &generate_test_vectors()).minimize(&flipped.vector);
+        assert!(minimized.vector.message.is_empty());
+        assert!(minimized.is_novel());
+    }
+
+    #[test]
+    fn test_ffi() {
+        use curve25519_dalek::edwards::EdwardsPoint;
+        use ed25519_speccheck::ffi;
+
+        fn unpack(tv: &TestVector) -> (EdwardsPoint, (EdwardsPoint, Scalar)) {
+            let mut s = [0u8; 32];
+            s.copy_from_slice(&tv.signature[32..]);
+            let r = deserialize_point(&tv.signature[..32]).unwrap();
+            (
+                deserialize_point(&tv.pub_key).unwrap(),
+                (r, Scalar::from_bits(s)),
+            )
+        }
+        type VerifyFn =
+            unsafe extern "C" fn(*const u8, usize, *const u8, usize, *const u8, usize) -> i32;
+        let verify = |verify: VerifyFn, message: &[u8], pub_key: &[u8], signature: &[u8]| unsafe {
+            verify(
+                message.as_ptr(),
+                message.len(),
+                pub_key.as_ptr(),
+                pub_key.len(),
+                signature.as_ptr(),
+                signature.len(),
+            )
         };
-        let sig = match aptos_crypto::ed25519::Ed25519Signature::try_from(&tv.signature[..]) {
-            Ok(sig) => sig,
-            Err(_e) => {
-                print!(" X |");
-                return false;
+        type AcceptsFn = fn(&TestVector) -> bool;
+        let verifiers: [(VerifyFn, AcceptsFn); 2] = [
+            (ffi::speccheck_verify_cofactored, |tv| {
+                let (pub_key, signature) = unpack(tv);
+                verify_cofactored(&tv.message, &pub_key, &signature).is_ok()
+            }),
+            (ffi::speccheck_verify_cofactorless, |tv| {
+                let (pub_key, signature) = unpack(tv);
+                verify_cofactorless(&tv.message, &pub_key, &signature).is_ok()
+            }),
+        ];
+
+        let vec = generate_test_vectors();
+        for tv in vec.iter() {
+            // the inputs at an offset into a larger buffer, surrounded by other bytes, get the
+            // verdict of the vector: no byte is read before or past the lengths given
+            let mut buffer = vec![0xffu8; 3];
+            buffer.extend_from_slice(&tv.message);
+            buffer.extend_from_slice(&tv.pub_key);
+            buffer.extend_from_slice(&tv.signature);
+            buffer.extend_from_slice(&[0xffu8; 3]);
+            let (message, rest) = buffer[3..].split_at(tv.message.len());
+            let (pub_key, rest) = rest.split_at(32);
+            let signature = &rest[..64];
+            for (f, accepts) in verifiers.iter() {
+                let expected = if accepts(tv) {
+                    ffi::SPECCHECK_ACCEPTED
+                } else {
+                    ffi::SPECCHECK_VERIFY_REJECTED
+                };
+                assert_eq!(
+                    verify(*f, &tv.message, &tv.pub_key, &tv.signature),
+                    expected
+                );
+                assert_eq!(verify(*f, message, pub_key, signature), expected);
+                // short lengths into the same buffer are rejected while parsing, though the
+                // bytes past them are readable
+                for len in [0, 1, 31].iter() {
+                    assert_eq!(
+                        verify(*f, message, &pub_key[..*len], signature),
+                        ffi::SPECCHECK_PARSE_REJECTED
+                    );
+                }
+                for len in [0, 32, 63].iter() {
+                    assert_eq!(
+                        verify(*f, message, pub_key, &signature[..*len]),
+                        ffi::SPECCHECK_PARSE_REJECTED
+                    );
+                }
             }
+        }
+
+        // the vectors read back through the interface as generated, each buffer with its length
+        let vectors = ffi::speccheck_generate_vectors();
+        assert!(!vectors.is_null());
+        let empty = || ffi::SpeccheckVector {
+            message: std::ptr::null(),
+            message_len: 0,
+            pub_key: std::ptr::null(),
+            pub_key_len: 0,
+            signature: std::ptr::null(),
+            signature_len: 0,
         };
+        unsafe {
+            assert_eq!(ffi::speccheck_vectors_len(vectors), vec.len());
+            for (i, tv) in vec.iter().enumerate() {
+                let mut out = empty();
+                assert_eq!(ffi::speccheck_vector_get(vectors, i, &mut out), 0);
+                let message = std::slice::from_raw_parts(out.message, out.message_len);
+                let pub_key = std::slice::from_raw_parts(out.pub_key, out.pub_key_len);
+                let signature = std::slice::from_raw_parts(out.signature, out.signature_len);
+                assert_eq!(message, &tv.message[..]);
+                assert_eq!(pub_key, &tv.pub_key[..]);
+                assert_eq!(signature, &tv.signature[..]);
+                for (f, accepts) in verifiers.iter() {
+                    assert_eq!(
+                        f(
+                            out.message,
+                            out.message_len,
+                            out.pub_key,
+                            out.pub_key_len,
+                            out.signature,
+                            out.signature_len
+                        ),
+                        if accepts(tv) {
+                            ffi::SPECCHECK_ACCEPTED
+                        } else {
+                            ffi::SPECCHECK_VERIFY_REJECTED
+                        }
+                    );
+                }
+            }
+            // an index out of range, or a null output, gets an error and leaves it unset
+            let mut out = empty();
+            assert_eq!(
+                ffi::speccheck_vector_get(vectors, vec.len(), &mut out),
+                ffi::SPECCHECK_ERROR
+            );
+            assert!(out.message.is_null() && out.pub_key_len == 0);
+            assert_eq!(
+                ffi::speccheck_vector_get(vectors, 0, std::ptr::null_mut()),
+                ffi::SPECCHECK_ERROR
+            );
+            assert_eq!(
+                ffi::speccheck_vector_get(std::ptr::null(), 0, &mut out),
+                ffi::SPECCHECK_ERROR
+            );
+            ffi::speccheck_vectors_free(vectors);
+            assert_eq!(ffi::speccheck_vectors_len(std::ptr::null()), 0);
+            ffi::speccheck_vectors_free(std::ptr::null_mut());
+        }
+
+        // a null pointer with a non-zero length gets an error, with a zero length the verdict
+        // on an empty input
+        let tv = &vec[0];
+        let (message, message_len) = (tv.message.as_ptr(), tv.message.len());
+        assert!(message_len > 0);
+        let (pub_key, signature) = (tv.pub_key.as_ptr(), tv.signature.as_ptr());
+        let null = std::ptr::null();
+        for (f, _) in verifiers.iter() {
+            unsafe {
+                for &(message, pub_key, signature) in [
+                    (null, pub_key, signature),
+                    (message, null, signature),
+                    (message, pub_key, null),
+                    (null, null, null),
+                ]
+                .iter()
+                {
+                    assert_eq!(
+                        f(message, message_len, pub_key, 32, signature, 64),
+                        ffi::SPECCHECK_ERROR
+                    );
+                }
+                assert_eq!(
+                    f(message, message_len, null, 0, signature, 64),
+                    ffi::SPECCHECK_PARSE_REJECTED
+                );
+                assert_eq!(
+                    f(message, message_len, pub_key, 32, null, 0),
+                    ffi::SPECCHECK_PARSE_REJECTED
+                );
+                assert_ne!(f(null, 0, pub_key, 32, signature, 64), ffi::SPECCHECK_ERROR);
+            }
+        }
 
-        match aptos_crypto::traits::Signature::verify_arbitrary_msg(&sig, &tv.message[..], &pk)
+        // the header declares the interface
+        let header = std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("include/speccheck.h"),
+        )
+        .unwrap();
+        for name in [
+            "speccheck_generate_vectors(void)",
+            "speccheck_vectors_free(",
+            "speccheck_vectors_len(",
+            "speccheck_vector_get(",
+            "speccheck_verify_cofactored(",
+            "speccheck_verify_cofactorless(",
+        ]
+        .iter()
         {
-            Ok(_v) => {
-                print!(" V |");
-            },
-            Err(_e) => {
-                print!(" X |");
-                return false;
+            assert!(header.contains(name), "{}", name);
+        }
+
+        // no input gets an error rather than a verdict: random and truncated signatures and
+        // keys, empty messages
+        let mut rng = new_rng();
+        for round in 0..256 {
+            let mut message = vec![0u8; round % 40];
+            let mut pub_key = vec![0u8; if round % 8 == 1 { round % 40 } else { 32 }];
+            let mut signature = vec![0u8; if round % 8 == 0 { round % 70 } else { 64 }];
+            rng.fill_bytes(&mut message);
+            rng.fill_bytes(&mut pub_key);
+            rng.fill_bytes(&mut signature);
+            if round % 3 == 0 {
+                pub_key = vec[round % vec.len()].pub_key.to_vec();
             }
+            for (f, _) in verifiers.iter() {
+                let status = verify(*f, &message, &pub_key, &signature);
+                assert!(status != ffi::SPECCHECK_ERROR, "round {}", round);
+                assert!(
+                    (pub_key.len() == 32 && signature.len() == 64)
+                        || status == ffi::SPECCHECK_PARSE_REJECTED,
+                    "round {}",
+                    round
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_wasm() {
+        use ed25519_speccheck::wasm;
+
+        let vec = generate_test_vectors();
+        let json = wasm::generate_test_vectors();
+        assert_eq!(json, serde_json::to_string(&vec).unwrap());
+        let name = |verdict: Verdict| serde_json::to_value(verdict).unwrap();
+        for tv in vec.iter() {
+            let (message, pub_key, signature) = (&tv.message, &tv.pub_key, &tv.signature);
+            assert_eq!(
+                wasm::verify_cofactored(message, pub_key, signature),
+                name(Cofactored.verdict(message, pub_key, signature))
+            );
+            assert_eq!(
+                wasm::verify_cofactorless(message, pub_key, signature),
+                name(Cofactorless.verdict(message, pub_key, signature))
+            );
+            assert_eq!(
+                wasm::verify_with_policy("zip215", message, pub_key, signature).unwrap(),
+                name(ZIP_215.verdict(message, pub_key, signature))
+            );
+        }
+        assert_eq!(
+            wasm::verify_cofactored(b"", &[0u8; 31], &[0u8; 64]),
+            "parse_rejected"
+        );
+    }
+
+    #[test]
+    fn test_repudiation_dalek() {
+        let rv = repudiation();
+        assert_ne!(rv.message1, rv.message2);
+        let pub_key = deserialize_point(&rv.pub_key).unwrap();
+        assert!(pub_key.is_small_order() && !pub_key.is_identity());
+        let r = deserialize_point(&rv.signature[..32]).unwrap();
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&rv.signature[32..]);
+        let s = Scalar::from_canonical_bytes(s_bytes).unwrap();
+        assert!(s != Scalar::zero());
+        for message in [&rv.message1, &rv.message2].iter() {
+            assert!(verify_cofactored(message, &pub_key, &(r, s)).is_ok());
+            assert!(verify_cofactorless(message, &pub_key, &(r, s)).is_ok());
+        }
+
+        let json = serde_json::to_value(&rv).unwrap();
+        assert_eq!(json["pub_key"], hex::encode(rv.pub_key));
+        assert_eq!(json["message1"], hex::encode(b"Send 100 USD to Alice"));
+        assert_eq!(json["message2"], hex::encode(b"Send 100000 USD to Alice"));
+
+        // policies checking the order of A reject both messages, the others accept both
+        let vectors = rv.vectors();
+        for (name, policy) in KNOWN_POLICIES.iter() {
+            let both = repudiation::accepts_both(&policy.predict(&vectors)).unwrap();
+            let checks_order = policy.reject_small_order_pk || policy.require_full_order_pk;
+            assert_eq!(both, !checks_order, "{}", name);
+        }
+        assert!(repudiation::accepts_both(&[Verdict::Accepted]).is_err());
+        let results = library_registry().results(&vectors);
+        for (name, verdicts) in results.rows().iter() {
+            println!(
+                "{}: accepts both messages: {}",
+                name,
+                repudiation::accepts_both(verdicts).unwrap()
+            );
         }
 
-        return true;
+        #[cfg(feature = "dalek")]
+        {
+            let pk = PublicKey::from_bytes(&rv.pub_key[..]).unwrap();
+            let sig = Signature::try_from(&rv.signature[..]).unwrap();
+            assert!(pk.verify(&rv.message1, &sig).is_ok());
+            assert!(pk.verify(&rv.message2, &sig).is_ok());
+        }
     }
 
     #[test]
-    fn test_aptos_strong() {
+    fn test_policy_verdicts() {
         let vec = generate_test_vectors();
+        let strict = ValidationPolicy {
+            reject_small_order_pk: true,
+            reject_noncanonical_r: true,
+            require_canonical_s: true,
+            ..Default::default()
+        };
 
-        print!("\n|aptos-crypto-st|");
-        for tv in vec.iter() {
-            // We are just manually checking the pubkey encoding is canonical
-            if !algorithm2::is_canonical_point_encoding(&tv.pub_key) {
-                print!(" X |");
-                continue;
-            }
+        let verdicts = strict.predict(&vec);
+        // small-order A is only rejected while verifying, as dalek's `verify_strict` does
+        assert_eq!(verdicts[0], Verdict::VerifyRejected);
+        // cofactorless verification fails on case 4
+        assert_eq!(verdicts[4], Verdict::VerifyRejected);
+        // S > L and the non-canonical R are refused while decoding
+        assert_eq!(verdicts[6], Verdict::ParseRejected);
+        assert_eq!(verdicts[8], Verdict::ParseRejected);
+        assert_eq!(verdicts[3], Verdict::Accepted);
+        for (tv, verdict) in vec.iter().zip(verdicts.iter()) {
+            assert_eq!(
+                strict
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                verdict.is_accepted()
+            );
+        }
+    }
 
-            // We are just manually checking the signature's R encoding is canonical
-            if !algorithm2::is_canonical_point_encoding(&tv.signature[..32]) {
-                print!(" X |");
-                continue;
-            }
+    #[test]
+    fn test_pre_reduced_cofactored_accepts_valid_signatures() {
+        use curve25519_dalek::edwards::EdwardsPoint;
+
+        let pre_reduced = ValidationPolicy {
+            cofactored: true,
+            prereduce: true,
+            ..Default::default()
+        };
+        // `eight()` once set the top byte rather than the bottom one, multiplying the scalars
+        // by 2^251 mod L rather than by 8
+        let mut bytes = [0u8; 32];
+        bytes[31] |= 8;
+        let wrong_eight = Scalar::from_bytes_mod_order(bytes);
+        assert_ne!(wrong_eight, Scalar::from(8u8));
+
+        let mut rng = new_rng();
+        for _ in 0..8 {
+            let mut a_bytes = [0u8; 32];
+            let mut r_bytes = [0u8; 32];
+            let mut message = [0u8; 32];
+            rng.fill_bytes(&mut a_bytes);
+            rng.fill_bytes(&mut r_bytes);
+            rng.fill_bytes(&mut message);
+            let a = Scalar::from_bytes_mod_order(a_bytes);
+            let r = Scalar::from_bytes_mod_order(r_bytes);
+            let pub_key = a * ED25519_BASEPOINT_POINT;
+            let big_r = r * ED25519_BASEPOINT_POINT;
+            let k = compute_hram(&message, &pub_key, &big_r);
+            let s = r + k * a;
+
+            assert!(pre_reduced
+                .verify(
+                    &message,
+                    pub_key.compress().as_bytes(),
+                    &serialize_signature(&big_r, &s)
+                )
+                .is_ok());
+            // with the wrong value, `[8]R = [8s mod L]B - [8k mod L]A` fails on the same valid
+            // signature
+            let rprime = EdwardsPoint::vartime_double_scalar_mul_basepoint(
+                &(wrong_eight * k),
+                &pub_key.neg(),
+                &(wrong_eight * s),
+            );
+            assert!(!(big_r.mul_by_cofactor() - rprime).is_identity());
+        }
+    }
+
+    // Checks the vectors of `generate_test_vectors`, or drawn from a `VectorGenerator`, hold
+    // the cases their metadata describes
+    fn assert_cases_hold(vec: &[TestVector]) {
+        assert_eq!(vec.len(), 12);
+        for (i, tv) in vec.iter().enumerate() {
+            let metadata = tv.metadata.as_ref().unwrap();
+            assert_eq!(metadata.index, i);
+            assert_eq!(metadata.name, sarif::family_of(i).unwrap().name);
+
+            let (r, s) = tv.signature.split_at(32);
+            let s_zero = s.iter().all(|&b| b == 0);
+            assert_eq!(metadata.s_class == SClass::Zero, s_zero);
+            assert_eq!(
+                metadata.s_class == SClass::Large,
+                Scalar::from_canonical_bytes(<[u8; 32]>::try_from(s).unwrap()).is_none()
+            );
+            // only case 7 fails the checks on the high bits of S
+            assert_eq!(s[31] & 224 != 0, i == 7);
+            let pub_key = deserialize_point(&tv.pub_key).unwrap();
+            assert_eq!(
+                metadata.canonical_a,
+                pub_key.compress().to_bytes() == tv.pub_key
+            );
+            let r = deserialize_point(r).unwrap();
+            assert_eq!(
+                metadata.canonical_r,
+                r.compress().as_bytes()[..] == tv.signature[..32]
+            );
+            assert_eq!(metadata.a_order.ends_with('L'), !pub_key.is_small_order());
+            assert_eq!(metadata.r_order == "L", has_full_order(&r));
 
-            if !test_aptos_helper(tv) {
-                continue;
+            // the expected verdicts are those of the equations alone, hashing A and R as the
+            // case intends
+            for &cofactored in [true, false].iter() {
+                let policy = ValidationPolicy {
+                    cofactored,
+                    reserialize_for_hash: i != 9 && i != 11,
+                    ..Default::default()
+                };
+                let expected = if cofactored {
+                    metadata.expected_cofactored
+                } else {
+                    metadata.expected_cofactorless
+                };
+                assert_eq!(
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok(),
+                    expected,
+                    "case {}: {}",
+                    i,
+                    policy
+                );
             }
         }
-        println!();
     }
 
     #[test]
-    fn test_hacl() {
+    fn test_case_metadata() {
         let vec = generate_test_vectors();
+        assert_cases_hold(&vec);
+        let ph = ed25519ph::generate_test_vectors();
+        for (tv, tv_ph) in vec.iter().zip(ph.iter()) {
+            let (metadata, metadata_ph) = (tv.metadata.as_ref(), tv_ph.metadata.as_ref());
+            assert_eq!(metadata.unwrap().a_order, metadata_ph.unwrap().a_order);
+            assert!(metadata_ph.unwrap().expected_verdicts.is_empty());
+        }
+        assert!(zip215_vectors().iter().all(|v| v.vector.metadata.is_none()));
+
+        // the verdicts of the named policies, which the reference verifiers reach
+        let references: [(&str, &dyn verifier::Verifier); 5] = [
+            ("algorithm2", &Algorithm2),
+            ("fips186_5", &Fips186_5),
+            ("libsodium", &Libsodium),
+            ("rfc8032", &Rfc8032),
+            ("zip215", &Zip215),
+        ];
+        let accepted = |verdicts: &[Verdict]| -> Vec<bool> {
+            verdicts
+                .iter()
+                .map(|verdict| verdict.is_accepted())
+                .collect()
+        };
+        for ((name, reference), (policy_name, _)) in references.iter().zip(NAMED_POLICIES.iter()) {
+            assert_eq!(name, policy_name);
+            let expected: Vec<Verdict> = vec
+                .iter()
+                .map(|tv| tv.metadata.as_ref().unwrap().expected_verdicts[*name])
+                .collect();
+            assert_eq!(accepted(&expected), accepted(&verdicts(*reference, &vec)));
+        }
+        let json = serde_json::to_string(&vec).unwrap();
+        let read: Vec<TestVector> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read[5].metadata, vec[5].metadata);
+    }
 
-        print!("\n|Hacl*          |");
+    #[test]
+    fn test_construction_traces() {
+        let vec = generate_test_vectors();
         for tv in vec.iter() {
-            let (pk, sig) = unpack_test_vector_hacl(tv);
-            if pk.verify(&tv.message[..], &sig) {
-                print!(" V |");
-            } else {
-                print!(" X |");
-            }
+            let trace = tv.trace.as_ref().unwrap();
+            assert_eq!(trace.scalars["s"], hex::encode(&tv.signature[32..]));
         }
-        println!();
+
+        // case 0 grinds the message for R + [k]A = 0, A being small, and k redone from the
+        // vector is that of the trace
+        let (tv, trace) = (&vec[0], vec[0].trace.as_ref().unwrap());
+        assert_eq!(trace.target, Some("R + [k]A = 0"));
+        assert!(trace.torsion_index.is_some());
+        assert!(trace.ground_messages >= 1);
+        let pub_key = deserialize_point(&tv.pub_key).unwrap();
+        let r = deserialize_point(&tv.signature[..32]).unwrap();
+        assert_eq!(
+            trace.scalars["k"],
+            hex::encode(compute_hram(&tv.message, &pub_key, &r).as_bytes())
+        );
+        // case 7 draws a single message, and has no small-order component
+        let trace = vec[7].trace.as_ref().unwrap();
+        assert_eq!((trace.target, trace.ground_messages), (None, 0));
+        assert_eq!(trace.torsion_index, None);
+
+        // the traces are left out of the JSON of the vectors, but for their explanation
+        assert!(!serde_json::to_string(&vec).unwrap().contains("trace"));
+        let explained = vec[0].explained().unwrap();
+        assert_eq!(explained["signature"], hex::encode(&vec[0].signature));
+        assert_eq!(explained["trace"]["construction"], "S=0, small A, small R");
+        assert!(ed25519ph::generate_test_vectors()[0].trace.is_none());
     }
 
     #[test]
-    fn test_dalek() {
+    fn test_vector_generator() {
+        let release = VectorGenerator::default();
         let vec = generate_test_vectors();
+        for (tv, generated) in vec.iter().zip(release.test_vectors().iter()) {
+            assert_eq!(tv.signature, generated.signature);
+        }
+        assert_eq!(VectorGenerator::from_seed(release.seed()), release);
+        assert_eq!(VectorGenerator::from_u64(7), VectorGenerator::from_u64(7));
+        assert_ne!(
+            VectorGenerator::from_entropy(),
+            VectorGenerator::from_entropy()
+        );
 
-        print!("\n|Dalek          |");
-        for tv in vec.iter() {
-            match Signature::try_from(&tv.signature[..]) {
-                Ok(_v) => {}
-                Err(_e) => {
-                    print!(" X |");
-                    continue;
-                }
-            }
+        // any seed yields the same cases
+        let mut vectors = HashSet::new();
+        for index in 0..16 {
+            let generator = release.derive(index);
+            assert_ne!(generator, release);
+            assert_eq!(generator, release.derive(index));
+            let vec = generator.test_vectors();
+            assert_cases_hold(&vec);
+            vectors.extend(vec.into_iter().map(|tv| (tv.message, tv.signature)));
+        }
+        assert_eq!(vectors.len(), 16 * 12);
 
-            let (pk, sig) = unpack_test_vector_dalek(tv);
-            match pk.verify(&tv.message[..], &sig) {
-                Ok(_v) => print!(" V |"),
-                Err(_e) => print!(" X |"),
+        // as many instances of the cases, one after the other
+        let instances = generate_test_vectors_n(3, 7);
+        assert_eq!(instances.len(), 3 * 12);
+        for instance in instances.chunks(12) {
+            assert_cases_hold(instance);
+        }
+        let second = VectorGenerator::from_u64(7).derive(1).test_vectors();
+        assert_eq!(instances[12 + 5].signature, second[5].signature);
+        let distinct: HashSet<(&Vec<u8>, &Vec<u8>)> = instances
+            .iter()
+            .map(|tv| (&tv.message, &tv.signature))
+            .collect();
+        assert_eq!(distinct.len(), instances.len());
+        assert_eq!(
+            full_order_vectors(&mut release.derive(0).rng()).len(),
+            full_order_vectors(&mut new_rng()).len()
+        );
+    }
+
+    #[test]
+    fn test_seed_sweep() {
+        let release = VectorGenerator::default();
+        let findings = sweep(&release, 0..16, &RARE_PROPERTIES);
+        for property in RARE_PROPERTIES.iter() {
+            assert!(findings.iter().any(|f| f.property == property.name));
+        }
+        // each finding has its property, and is drawn again from its seed
+        for finding in findings.iter() {
+            let property = RARE_PROPERTIES
+                .iter()
+                .find(|p| p.name == finding.property)
+                .unwrap();
+            assert!((property.holds)(&finding.vector));
+            assert_eq!(release.derive(finding.index).seed(), finding.seed);
+            let drawn = &VectorGenerator::from_seed(finding.seed).test_vectors()[finding.case];
+            assert_eq!(drawn.signature, finding.vector.signature);
+        }
+
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+        let command = cli::parse(args(
+            "search --property hram-multiple-of-8 --from 4 --count 4",
+        ))
+        .unwrap();
+        assert_eq!(
+            command,
+            Command::Search {
+                property: Some("hram-multiple-of-8".into()),
+                indices: 4..8,
+                format: Format::Txt,
             }
+        );
+        assert!(cli::parse(args("search --property s-out-of-bounds")).is_err());
+        assert!(cli::parse(args("search --format sarif")).is_err());
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let expected = findings
+            .iter()
+            .filter(|f| f.property == "hram-multiple-of-8" && (4..8).contains(&f.index))
+            .count();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with(&format!("{} instances in 4 seeds\n", expected)));
+    }
+
+    #[test]
+    fn test_verifier_table() {
+        let vec = generate_test_vectors();
+        #[cfg(feature = "ring")]
+        {
+            let ring = |message: &[u8], pub_key: &[u8], sig: &[u8]| {
+                signature::UnparsedPublicKey::new(&signature::ED25519, pub_key)
+                    .verify(message, sig)
+                    .map_err(|_| anyhow!("signature verification failed"))
+            };
+            let table = results_table(
+                &[
+                    ("[CGN20e] Alg.2", &Algorithm2),
+                    ("BoringSSL", &ring),
+                    ("cofactored", &Cofactored),
+                    ("ZIP-215", &Zip215),
+                ],
+                &vec,
+            );
+            print!("{}", table);
+
+            let lines: Vec<&str> = table.lines().collect();
+            assert_eq!(lines.len(), 8);
+            assert_eq!(
+                lines[1],
+                "|Library        | 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10| 11|"
+            );
+            assert_eq!(
+                lines[4],
+                "|BoringSSL      | V | V | V | V | X | X | X | X | X | X | X | V |"
+            );
+            assert_eq!(
+                lines[6],
+                "|ZIP-215        | V | V | V | V | V | V | P | P | X | V | V | V |"
+            );
+            assert_eq!(lines[0].len() + 1, lines[1].len());
         }
-        println!();
+
+        // the unpacked verifiers agree with the policies hashing re-serialized points and
+        // performing no other check
+        let cofactorless = ValidationPolicy {
+            reserialize_for_hash: true,
+            ..Default::default()
+        };
+        let cofactored = ValidationPolicy {
+            cofactored: true,
+            ..cofactorless
+        };
+        let pre_reduced = ValidationPolicy {
+            prereduce: true,
+            ..cofactored
+        };
+        let pairs: [(&dyn verifier::Verifier, ValidationPolicy); 3] = [
+            (&Cofactored, cofactored),
+            (&Cofactorless, cofactorless),
+            (&PreReducedCofactored, pre_reduced),
+        ];
+        for (verifier, policy) in pairs.iter() {
+            assert_eq!(verdicts(*verifier, &vec), verdicts(policy, &vec));
+        }
+        assert_eq!(verdicts(&Algorithm2, &vec)[2], Verdict::Accepted);
+    }
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn test_results_report() {
+        let vec = generate_test_vectors();
+        let ring = |message: &[u8], pub_key: &[u8], sig: &[u8]| {
+            signature::UnparsedPublicKey::new(&signature::ED25519, pub_key)
+                .verify(message, sig)
+                .map_err(|_| anyhow!("signature verification failed"))
+        };
+        let mut table = ResultsTable::from_verifiers(
+            &[
+                ("[CGN20e] Alg.2", &Algorithm2),
+                ("BoringSSL", &ring),
+                ("libsodium", &Libsodium),
+                ("RFC 8032", &Rfc8032),
+                ("FIPS 186-5", &Fips186_5),
+                ("ZIP-215", &Zip215),
+            ],
+            &vec,
+        );
+        assert_eq!(table.to_string(), verdicts_table_of(&table));
+
+        // the rows of the README table are regenerated as they stand, rejections while
+        // parsing included
+        let markdown = table.to_markdown();
+        println!("{}", markdown);
+        let readme = std::fs::read_to_string("README.md").unwrap();
+        for line in markdown.lines().skip(2) {
+            assert!(readme.contains(line), "{} is not in the README", line);
+        }
+
+        table.push("a, \"b\" & <c>", vec![Verdict::Error; 2]);
+        let html = table.to_html();
+        assert!(html.starts_with("<table>\n<thead>\n<tr><th>Library</th><th>0</th>"));
+        assert!(html.contains("<tr><th>ZIP-215</th><td class=\"accepted\">V</td>"));
+        assert!(html.contains("<td class=\"parse_rejected\">P</td>"));
+        assert!(html.contains("<th>a, \"b\" &amp; &lt;c&gt;</th><td class=\"error\">E</td>"));
+        let csv = table.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "library,0,1,2,3,4,5,6,7,8,9,10,11");
+        assert_eq!(lines[2], "BoringSSL,V,V,V,V,X,X,X,X,X,X,X,V");
+        assert_eq!(lines[7], "\"a, \"\"b\"\" & <c>\",E,E");
+        assert_eq!(table.rows().len(), 7);
+
+        // the report command renders the same tables
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+        let command = cli::parse(args("report Dalek=VVVVXXXXXXXV --format csv")).unwrap();
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let csv = String::from_utf8(output).unwrap();
+        assert_eq!(csv.lines().nth(2), Some("Dalek,V,V,V,V,X,X,X,X,X,X,X,V"));
+        assert!(cli::parse(args("generate --format html")).is_err());
+    }
+
+    // The text table of the rows of `table`, as laid out by `verdicts_table`
+    #[cfg(feature = "ring")]
+    fn verdicts_table_of(table: &ResultsTable) -> String {
+        let rows: Vec<(&str, &[Verdict])> = table
+            .rows()
+            .iter()
+            .map(|(name, verdicts)| (name.as_str(), &verdicts[..]))
+            .collect();
+        verifier::verdicts_table(&rows)
+    }
+
+    #[test]
+    fn test_integration_health() {
+        let vec = generate_test_vectors();
+        let case_3 = vec[3].signature.clone();
+        let flaky = move |message: &[u8], pub_key: &[u8], sig: &[u8]| {
+            assert!(sig != &case_3[..], "flaky integration");
+            Cofactored.verify(message, pub_key, sig)
+        };
+        let table = integrations_table(
+            &[
+                ("cofactored", Ok(&Cofactored)),
+                ("flaky", Ok(&flaky)),
+                ("unbuilt", Err(anyhow!("feature disabled"))),
+            ],
+            &vec,
+        );
+        print!("{}", table);
+
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(
+            lines[4],
+            "|flaky          | V | V | V | E | V | V | V | V | V | X | V | V |"
+        );
+        assert_eq!(
+            lines[5],
+            "|unbuilt        | E | E | E | E | E | E | E | E | E | E | E | E |"
+        );
+        assert_eq!(lines[7], "flaky: errored on vectors 3");
+        assert_eq!(lines[8], "unbuilt: unavailable, feature disabled");
+        // healthy tables are laid out as before
+        assert_eq!(
+            results_table(&[("cofactored", &Cofactored)], &vec)
+                .lines()
+                .count(),
+            5
+        );
+
+        // errored verdicts tell nothing of the policy
+        let observed: Vec<Option<Verdict>> = verdicts(&flaky, &vec).into_iter().map(Some).collect();
+        assert_eq!(observed[3], Some(Verdict::Error));
+        let inference = infer_from_partial(&vec, &observed).unwrap();
+        let mut untried = observed.clone();
+        untried[3] = None;
+        assert_eq!(
+            inference.consistent,
+            infer_from_partial(&vec, &untried).unwrap().consistent
+        );
+        assert!(!inference.consistent.is_empty());
     }
 
+    #[cfg(feature = "ring")]
     #[test]
-    fn test_dalek_verify_strict() {
+    fn test_sarif_export() {
         let vec = generate_test_vectors();
+        let ring = |message: &[u8], pub_key: &[u8], sig: &[u8]| {
+            signature::UnparsedPublicKey::new(&signature::ED25519, pub_key)
+                .verify(message, sig)
+                .map_err(|_| anyhow!("signature verification failed"))
+        };
+        let strict = ValidationPolicy {
+            cofactored: true,
+            reject_small_order_pk: true,
+            reject_noncanonical_pk: true,
+            reject_small_order_r: true,
+            reject_noncanonical_r: true,
+            require_canonical_s: true,
+            reserialize_for_hash: false,
+            prereduce: false,
+            require_full_order_pk: false,
+        };
+        let observed = verdicts(&ring, &vec);
+        assert_eq!(
+            sarif::failed_expectations(&strict, &vec, &observed).unwrap(),
+            vec![0, 1, 2, 11]
+        );
+
+        let log = sarif::export(&strict, &[("BoringSSL", &observed)]).unwrap();
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), sarif::CASE_FAMILIES.len());
+        let results = run["results"].as_array().unwrap();
+        let rule_ids: Vec<&str> = results
+            .iter()
+            .map(|result| result["ruleId"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            rule_ids,
+            vec![
+                "small-order-a-and-r",
+                "small-order-a",
+                "small-order-r",
+                "non-canonical-a"
+            ]
+        );
+        for result in results.iter() {
+            let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+        }
+        // case 11 spans lines 46 to 49 of cases.txt, from its id
+        let region = &results[3]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 46);
+        assert_eq!(region["endLine"], 49);
+        assert_eq!(
+            results[3]["properties"]["caseId"],
+            "non-canonical-a/not-reduced-for-hash"
+        );
+        assert_eq!(
+            results[3]["partialFingerprints"]["speccheckCase/v2"],
+            "BoringSSL:non-canonical-a/not-reduced-for-hash"
+        );
+
+        // every case belongs to exactly one family
+        for i in 0..vec.len() {
+            let families = sarif::CASE_FAMILIES
+                .iter()
+                .filter(|family| family.cases.contains(&i))
+                .count();
+            assert_eq!(families, 1);
+            assert!(sarif::family_of(i).is_some());
+        }
+        assert!(sarif::export(&strict, &[("truncated", &observed[..11])]).is_err());
+    }
 
-        print!("\n|Dalek strict   |");
+    #[test]
+    fn test_decoded_vectors() {
+        let mut vec = generate_test_vectors();
+        vec.extend(
+            non_canonical_vectors(&mut new_rng())
+                .unwrap()
+                .into_iter()
+                .map(|nc| nc.vector),
+        );
+        vec.extend(large_s_family(&mut new_rng()).into_iter().map(|v| v.vector));
+        let mut truncated = vec[0].clone();
+        truncated.signature.pop();
+        vec.push(truncated);
+
+        // decoding once reaches the verdicts of every policy on the bytes
+        let policies = ValidationPolicy::all();
         for tv in vec.iter() {
-            match Signature::try_from(&tv.signature[..]) {
-                Ok(_v) => {}
-                Err(_e) => {
-                    print!(" X |");
-                    continue;
+            match DecodedVector::new(&tv.message, &tv.pub_key, &tv.signature) {
+                Ok(decoded) => {
+                    for policy in policies.iter() {
+                        assert_eq!(
+                            policy.verdict_decoded(&decoded),
+                            policy.verdict(&tv.message, &tv.pub_key, &tv.signature),
+                            "{}",
+                            policy
+                        );
+                        assert_eq!(
+                            policy.verify_decoded(&decoded).is_ok(),
+                            policy.verdict_decoded(&decoded).is_accepted()
+                        );
+                    }
                 }
+                Err(_) => assert!(policies.iter().all(|policy| policy.verdict(
+                    &tv.message,
+                    &tv.pub_key,
+                    &tv.signature
+                ) == Verdict::ParseRejected)),
             }
+        }
 
-            let (pk, sig) = unpack_test_vector_dalek(tv);
-            match pk.verify_strict(&tv.message[..], &sig) {
-                Ok(_v) => print!(" V |"),
-                Err(_e) => print!(" X |"),
+        // as do points, for the vectors encoding them canonically
+        for tv in vec[..12].iter().filter(|tv| {
+            ALGORITHM_2
+                .verify(&tv.message, &tv.pub_key, &tv.signature)
+                .is_ok()
+        }) {
+            let decoded = DecodedVector::new(&tv.message, &tv.pub_key, &tv.signature).unwrap();
+            let from_points =
+                DecodedVector::from_points(&tv.message, &decoded.pub_key, &decoded.r, &decoded.s);
+            for policy in policies.iter() {
+                assert_eq!(
+                    policy.verdict_decoded(&from_points),
+                    policy.verdict_decoded(&decoded)
+                );
             }
         }
-        println!();
     }
 
     #[test]
-    fn test_boringssl() {
+    fn test_policy_metrics() {
         let vec = generate_test_vectors();
+        let hashed: u64 = vec.iter().map(|tv| 64 + tv.message.len() as u64).sum();
 
-        print!("\n|BoringSSL      |");
-        for tv in vec.iter() {
-            match ring_verify(tv) {
-                Ok(_v) => print!(" V |"),
-                Err(_e) => print!(" X |"),
+        let lenient = ValidationPolicy::default();
+        let mut counted = Metrics::default();
+        assert_eq!(
+            lenient.predict_with_metrics(&vec, &mut counted),
+            lenient.predict(&vec)
+        );
+        // every vector decodes, then gets hashed and checked
+        assert_eq!(
+            counted,
+            Metrics {
+                verifications: 12,
+                decompressions: 24,
+                compressions: 0,
+                small_order_checks: 0,
+                full_order_checks: 0,
+                cofactor_muls: 0,
+                double_scalar_muls: 12,
+                hashed_bytes: hashed,
             }
+        );
+
+        // rejecting early saves the double-scalar multiplication, if not the decoding
+        let strict = ValidationPolicy {
+            reject_small_order_pk: true,
+            reject_noncanonical_r: true,
+            require_canonical_s: true,
+            cofactored: true,
+            ..Default::default()
+        };
+        let mut strict_counted = Metrics::default();
+        let verdicts = strict.predict_with_metrics(&vec, &mut strict_counted);
+        let checked = verdicts
+            .iter()
+            .filter(|verdict| **verdict != Verdict::ParseRejected)
+            .count() as u64;
+        assert_eq!(strict_counted.verifications, 12);
+        assert_eq!(strict_counted.compressions, 12);
+        assert_eq!(strict_counted.small_order_checks, checked);
+        assert!(strict_counted.double_scalar_muls < checked);
+        assert_eq!(
+            strict_counted.cofactor_muls,
+            strict_counted.double_scalar_muls
+        );
+        counted += strict_counted;
+        assert_eq!(counted.verifications, 24);
+
+        let mut output = Vec::new();
+        metrics::write_prometheus(
+            &mut output,
+            &[
+                (&[("policy", "lenient")], lenient_metrics(&vec)),
+                (&[("policy", "a \"b\"")], counted),
+            ],
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 8 * 4);
+        assert_eq!(
+            lines[0],
+            "# HELP speccheck_verifications_total Signatures verified."
+        );
+        assert_eq!(lines[1], "# TYPE speccheck_verifications_total counter");
+        assert_eq!(
+            lines[2],
+            "speccheck_verifications_total{policy=\"lenient\"} 12"
+        );
+        assert_eq!(
+            lines[3],
+            "speccheck_verifications_total{policy=\"a \\\"b\\\"\"} 24"
+        );
+    }
+
+    fn lenient_metrics(vectors: &[TestVector]) -> Metrics {
+        let mut counted = Metrics::default();
+        for tv in vectors.iter() {
+            ValidationPolicy::default()
+                .verify_with_metrics(&tv.message, &tv.pub_key, &tv.signature, &mut counted)
+                .ok();
         }
-        println!();
+        counted
     }
 
     #[test]
-    fn test_zebra() {
+    fn test_policy_inference_from_partial_verdicts() {
         let vec = generate_test_vectors();
+        let target = ValidationPolicy {
+            reject_small_order_pk: true,
+            require_canonical_s: true,
+            cofactored: true,
+            ..Default::default()
+        };
+        let observed: Vec<Option<Verdict>> = target.predict(&vec).into_iter().map(Some).collect();
 
-        print!("\n|Zebra          |");
-        for tv in vec.iter() {
-            match Signature::try_from(&tv.signature[..]) {
-                Ok(_v) => {}
-                Err(_e) => {
-                    print!(" X |");
-                    continue;
-                }
-            }
+        let inference = infer_from_partial(&vec, &observed).unwrap();
+        assert!(inference.consistent.contains(&target));
+        assert!(inference.is_conclusive());
 
-            let (pk, sig) = unpack_test_vector_zebra(tv);
-            match pk.verify(&sig, &tv.message[..]) {
-                Ok(_v) => print!(" V |"),
-                Err(_e) => print!(" X |"),
-            }
+        // e.g. an HSM which refuses to import the small-order keys of cases 0 and 1
+        let mut partial = observed.clone();
+        partial[0] = None;
+        partial[1] = None;
+        let partial_inference = infer_from_partial(&vec, &partial).unwrap();
+        assert!(partial_inference.consistent.contains(&target));
+        assert!(partial_inference.consistent.len() >= inference.consistent.len());
+        for i in partial_inference.disambiguating.iter() {
+            assert!(partial[*i].is_none());
         }
-        println!();
     }
 
     #[test]
-    fn test_repudiation_dalek() {
-        // Pick a random Scalar
-        let mut rng = new_rng();
-        let mut scalar_bytes = [0u8; 32];
-        rng.fill_bytes(&mut scalar_bytes);
-        let s = Scalar::from_bytes_mod_order(scalar_bytes);
-        debug_assert!(s.is_canonical());
-        debug_assert!(s != Scalar::zero());
-
-        let r0 = s * ED25519_BASEPOINT_POINT;
-        // Pick a torsion point of order 2
-        let pub_key = deserialize_point(&EIGHT_TORSION[4]).unwrap();
-        let r = r0 + pub_key.neg();
-
-        let message1 = b"Send 100 USD to Alice";
-        let message2 = b"Send 100000 USD to Alice";
-
-        debug_assert!(
-            (pub_key.neg() + compute_hram(message1, &pub_key, &r) * pub_key).is_identity()
-        );
-        debug_assert!(
-            (pub_key.neg() + compute_hram(message2, &pub_key, &r) * pub_key).is_identity()
-        );
-
-        debug_assert!(verify_cofactored(message1, &pub_key, &(r, s)).is_ok());
-        debug_assert!(verify_cofactorless(message1, &pub_key, &(r, s)).is_ok());
-        debug_assert!(verify_cofactored(message2, &pub_key, &(r, s)).is_ok());
-        debug_assert!(verify_cofactorless(message2, &pub_key, &(r, s)).is_ok());
-
-        println!(
-            "Small pk breaks non-repudiation:\n\
-             \"pub_key\": \"{}\",\n\
-             \"signature\": \"{}\",\n\
-             \"message1\": \"{}\",\n\
-             \"message2\": \"{}\"",
-            hex::encode(&pub_key.compress().as_bytes()),
-            hex::encode(&serialize_signature(&r, &s)),
-            hex::encode(&message1),
-            hex::encode(&message2),
-        );
-
-        let signature = serialize_signature(&r, &s);
-        let pk = PublicKey::from_bytes(&pub_key.compress().as_bytes()[..]).unwrap();
-        let sig = Signature::try_from(&signature[..]).unwrap();
-        debug_assert!(pk.verify(message1, &sig).is_ok());
-        debug_assert!(pk.verify(message2, &sig).is_ok());
+    fn test_policy_builder() {
+        let rfc8032 = ValidationPolicy::new()
+            .reject_noncanonical_pk()
+            .reject_noncanonical_r()
+            .require_canonical_s();
+        assert_eq!(rfc8032, RFC_8032);
+        assert_eq!(
+            ValidationPolicy::new()
+                .require_canonical_s()
+                .cofactored(true),
+            ZIP_215
+        );
+        assert_eq!(
+            ValidationPolicy::new()
+                .reject_small_order_pk()
+                .reject_small_order_r(),
+            ValidationPolicy {
+                reject_small_order_pk: true,
+                reject_small_order_r: true,
+                ..Default::default()
+            }
+        );
+        // pre-reducing only applies to the cofactored equation
+        assert!(ValidationPolicy::new().prereduce(true).cofactored);
+        assert!(
+            !ValidationPolicy::new()
+                .prereduce(true)
+                .cofactored(false)
+                .prereduce
+        );
+
+        let vec = generate_test_vectors();
+        assert_eq!(rfc8032.predict_generated(), rfc8032.predict(&vec));
+        assert_eq!(
+            rfc8032.differing_checks(&ZIP_215),
+            vec![
+                "reject-noncanonical-pk",
+                "reject-noncanonical-r",
+                "cofactored"
+            ]
+        );
+
+        // a library implementing its model needs no change
+        let observed = rfc8032.predict(&vec);
+        let unchanged = rfc8032.minimal_change(&vec, &observed).unwrap().unwrap();
+        assert!(rfc8032.differing_checks(&unchanged).is_empty());
+
+        // one behaving as ZIP-215 needs at most the checks telling the two apart
+        let observed = ZIP_215.predict(&vec);
+        let changed = rfc8032.minimal_change(&vec, &observed).unwrap().unwrap();
+        let accepted = |verdicts: Vec<Verdict>| -> Vec<bool> {
+            verdicts.into_iter().map(|v| v.is_accepted()).collect()
+        };
+        assert_eq!(accepted(changed.predict(&vec)), accepted(observed));
+        assert!(!rfc8032.differing_checks(&changed).is_empty());
+        assert!(rfc8032.differing_checks(&changed).len() <= 3);
+    }
+
+    #[test]
+    fn test_policy_inference_by_case_id() {
+        let target = ValidationPolicy::new()
+            .reject_small_order_pk()
+            .require_canonical_s()
+            .cofactored(true);
+        let mut results: BTreeMap<String, bool> = CASE_IDS
+            .iter()
+            .zip(target.predict_generated())
+            .map(|(id, verdict)| (id.to_string(), verdict.is_accepted()))
+            .collect();
+        let consistent = infer_policy(&results).unwrap();
+        assert!(consistent.contains(&target));
+        assert!(disambiguating_cases(&results).unwrap().is_empty());
+
+        // without the small-order keys, the cases on them tell the candidates apart
+        results.remove(CASE_IDS[0]);
+        results.remove(CASE_IDS[1]);
+        let partial = infer_policy(&results).unwrap();
+        assert!(partial.contains(&target));
+        assert!(partial.len() > consistent.len());
+        let suggested = disambiguating_cases(&results).unwrap();
+        assert!(!suggested.is_empty());
+        assert!(suggested
+            .iter()
+            .all(|id| *id == CASE_IDS[0] || *id == CASE_IDS[1]));
+
+        results.insert("no-such-case".to_string(), true);
+        assert!(infer_policy(&results).is_err());
+    }
+
+    #[test]
+    fn test_adaptive_fingerprinting() {
+        let vec = generate_test_vectors();
+        let target = ValidationPolicy {
+            reject_noncanonical_r: true,
+            cofactored: true,
+            ..Default::default()
+        };
+        let verdicts = target.predict(&vec);
+
+        let mut fingerprinter = Fingerprinter::new(&vec);
+        while let Some(i) = fingerprinter.next_query() {
+            fingerprinter.record(i, verdicts[i]).unwrap();
+        }
+        assert!(fingerprinter.queries() <= vec.len());
+
+        // stopping early loses nothing over submitting the whole corpus
+        let observed: Vec<Option<Verdict>> = verdicts.into_iter().map(Some).collect();
+        let inference = infer_from_partial(&vec, &observed).unwrap();
+        assert!(fingerprinter.consistent().contains(&target));
+        assert_eq!(fingerprinter.consistent(), inference.consistent);
+    }
+
+    #[test]
+    fn test_policy_aware_pruning() {
+        let vec = generate_test_vectors();
+        let target = ValidationPolicy {
+            reject_small_order_pk: true,
+            reject_noncanonical_pk: true,
+            reject_noncanonical_r: true,
+            require_canonical_s: true,
+            ..Default::default()
+        };
+
+        let full = prune(&vec, &target, vec.len());
+        assert!(full.undistinguished.is_empty());
+
+        let pruned = prune(&vec, &target, 2);
+        assert_eq!(pruned.selected.len(), 2);
+        assert_eq!(pruned.selected.len() + pruned.dropped.len(), vec.len());
+        // whatever the pruned corpus can no longer tell apart, a dropped vector did
+        let verdicts = target.predict(&vec);
+        for policy in pruned.undistinguished.iter() {
+            let predicted = policy.predict(&vec);
+            let agree = |i: usize| predicted[i].is_accepted() == verdicts[i].is_accepted();
+            assert!(pruned.selected.iter().all(|&i| agree(i)));
+            assert!(pruned.dropped.iter().any(|&i| !agree(i)));
+        }
+        // and the selection is deterministic
+        assert_eq!(prune(&vec, &target, 2).selected, pruned.selected);
+    }
+
+    #[test]
+    fn test_stored_vectors() {
+        let vec = generate_test_vectors();
+        let json = TestVector::from_json_file("cases.json").unwrap();
+        let txt = TestVector::from_txt_file("cases.txt").unwrap();
+        assert_eq!(json.len(), vec.len());
+        assert_eq!(txt.len(), vec.len());
+        for ((tv, stored_json), stored_txt) in vec.iter().zip(json.iter()).zip(txt.iter()) {
+            for stored in [stored_json, stored_txt].iter() {
+                assert_eq!(stored.message, tv.message);
+                assert_eq!(stored.pub_key, tv.pub_key);
+                assert_eq!(stored.signature, tv.signature);
+            }
+            assert_eq!(stored_json.metadata, tv.metadata);
+            assert!(stored_txt.metadata.is_none());
+        }
+        // the stored vectors can be checked in place of the generated ones
+        let policy = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+        assert_eq!(policy.predict(&txt), policy.predict(&vec));
+
+        assert!(TestVector::from_txt("").is_err());
+        assert!(TestVector::from_txt("1\nmsg=00\npbk=00\nsig=00").is_err());
+        let one = format!("1\nmsg=00\npbk={}\nsig=00", "00".repeat(32));
+        assert_eq!(TestVector::from_txt(&one).unwrap()[0].message, vec![0]);
+        assert!(TestVector::from_txt(&one.replace("1\n", "2\n")).is_err());
+        assert!(TestVector::from_txt(&one.replace("1\n", "0\n")).is_err());
+        assert!(TestVector::from_txt(&one.replace("msg=", "sig=")).is_err());
+        assert!(TestVector::from_json_file("missing.json").is_err());
+    }
+
+    #[test]
+    fn test_write_vectors() {
+        let vec = generate_test_vectors();
+        // the bytes of the stored files, written to any writer
+        for (format, file) in [
+            (VectorFormat::Json, "cases.json"),
+            (VectorFormat::Txt, "cases.txt"),
+        ]
+        .iter()
+        {
+            let mut written = Vec::new();
+            io::write_vectors(&vec, *format, &mut written).unwrap();
+            assert_eq!(written, std::fs::read(file).unwrap());
+            assert_eq!(io::encode_vectors(&vec, *format).unwrap(), written);
+        }
+        let txt = io::encode_vectors(&vec, VectorFormat::Txt).unwrap();
+        let read = TestVector::from_txt(std::str::from_utf8(&txt).unwrap()).unwrap();
+        assert_eq!(read.len(), vec.len());
+
+        let csv = String::from_utf8(io::encode_vectors(&vec, VectorFormat::Csv).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "index,id,message,pub_key,signature");
+        assert_eq!(lines.len(), vec.len() + 1);
+        for (i, (line, tv)) in lines[1..].iter().zip(vec.iter()).enumerate() {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields[0], i.to_string());
+            assert_eq!(fields[1], CASE_IDS[i]);
+            assert_eq!(hex::decode(fields[2]).unwrap(), tv.message);
+            assert_eq!(hex::decode(fields[3]).unwrap(), tv.pub_key);
+            assert_eq!(hex::decode(fields[4]).unwrap(), tv.signature);
+        }
+
+        #[cfg(feature = "cbor")]
+        {
+            let cbor = io::encode_vectors(&vec, VectorFormat::Cbor).unwrap();
+            let read: Vec<TestVector> = serde_cbor::from_slice(&cbor).unwrap();
+            // test vectors compare through their encoding
+            assert_eq!(
+                serde_json::to_string(&read).unwrap(),
+                serde_json::to_string(&vec).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_classification() {
+        // the orders of A and R agree with those of the table of cases
+        for tv in generate_test_vectors().iter() {
+            let metadata = tv.metadata.as_ref().unwrap();
+            let r = deserialize_point(&tv.signature[..32]).unwrap();
+            for (pt, order) in [
+                (deserialize_point(&tv.pub_key).unwrap(), &metadata.a_order),
+                (r, &metadata.r_order),
+            ]
+            .iter()
+            {
+                let expected = match order.as_str() {
+                    "1" => PointOrder::One,
+                    "2" => PointOrder::Two,
+                    "4" => PointOrder::Four,
+                    "8" => PointOrder::Eight,
+                    "L" => PointOrder::Prime,
+                    _ => PointOrder::Mixed,
+                };
+                assert_eq!(point_order(pt), expected);
+                assert_eq!(
+                    has_torsion_component(pt),
+                    order.as_str() != "L" && order.as_str() != "1"
+                );
+            }
+            let mut s = [0u8; 32];
+            s.copy_from_slice(&tv.signature[32..]);
+            assert_eq!(
+                scalar_class(&s) == ScalarClass::Canonical,
+                metadata.s_class != SClass::Large
+            );
+        }
+        let orders: Vec<PointOrder> = EIGHT_TORSION.iter().map(point_order).collect();
+        assert_eq!(
+            orders.iter().filter(|o| **o == PointOrder::Eight).count(),
+            4
+        );
+        assert_eq!(orders.iter().filter(|o| **o == PointOrder::Four).count(), 2);
+        assert_eq!(orders.iter().filter(|o| **o == PointOrder::Two).count(), 1);
+        assert_eq!(orders.iter().filter(|o| **o == PointOrder::One).count(), 1);
+        let mixed = ED25519_BASEPOINT_POINT + EIGHT_TORSION[2];
+        assert_eq!(point_order(&mixed), PointOrder::Mixed);
+        assert!(has_torsion_component(&mixed));
+        assert!(!has_torsion_component(&ED25519_BASEPOINT_POINT));
+
+        // S around L and the high bits
+        let l = curve25519_dalek::constants::BASEPOINT_ORDER.to_bytes();
+        let mut below = l;
+        below[0] -= 1;
+        assert_eq!(scalar_class(&below), ScalarClass::Canonical);
+        assert_eq!(scalar_class(&l), ScalarClass::GeL);
+        let mut high = [0u8; 32];
+        high[31] = 0x20;
+        assert_eq!(scalar_class(&high), ScalarClass::HighBitsSet);
+
+        assert!(multiple_of_eight_le(&[0u8; 32]));
+        assert!(multiple_of_eight_le(&Scalar::from(24u8).to_bytes()));
+        assert!(!multiple_of_eight_le(&Scalar::from(12u8).to_bytes()));
+    }
+
+    #[test]
+    fn test_case_ids() {
+        let vec = generate_test_vectors();
+        for (i, tv) in vec.iter().enumerate() {
+            let id = tv.id().unwrap();
+            assert_eq!(id, CASE_IDS[i]);
+            // ids are unique, and start with the id of the family
+            assert_eq!(CASE_IDS.iter().filter(|other| **other == id).count(), 1);
+            assert!(id.starts_with(&format!("{}/", sarif::family_of(i).unwrap().id)));
+            assert_eq!(TestVector::by_id(id).unwrap().signature, tv.signature);
+        }
+        assert!(TestVector::by_id("mixed-order").is_none());
+        for tv in ed25519ph::generate_test_vectors().iter() {
+            assert!(tv.id().is_some());
+        }
+        assert!(random_vector(VectorKind::Honest, &mut new_rng())
+            .id()
+            .is_none());
+
+        // the ids precede the vectors of cases.txt, which reads back without them
+        let txt = String::from_utf8(io::encode_vectors(&vec, VectorFormat::Txt).unwrap()).unwrap();
+        assert!(txt.starts_with(&format!("12\nid={}\nmsg=", CASE_IDS[0])));
+        let read = TestVector::from_txt(&txt).unwrap();
+        assert_eq!(read[5].signature, vec[5].signature);
+        assert!(TestVector::from_txt(&txt.replace(CASE_IDS[5], "pre-reduction")).is_err());
+        assert_eq!(
+            cli::parse_selection("mixed-order/fails-cofactorless,0").unwrap(),
+            vec![4, 0]
+        );
+        assert!(cli::parse_selection("mixed-order/passes").is_err());
+    }
+
+    #[test]
+    fn test_corpus_reader() {
+        // test vectors compare through their encoding
+        let encoded = |vectors: &[TestVector]| serde_json::to_string(vectors).unwrap();
+        for file in ["cases.json", "cases_ph.json"].iter() {
+            let stored = TestVector::from_json_file(file).unwrap();
+            let read: Vec<TestVector> = CorpusReader::open(file)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(encoded(&read), encoded(&stored));
+
+            // JSON lines are read back alike
+            let mut jsonl = Vec::new();
+            corpus::write_jsonl(&mut jsonl, &stored).unwrap();
+            let reader = CorpusReader::new(&jsonl[..], Layout::Jsonl);
+            let read: Vec<TestVector> = reader.collect::<Result<_>>().unwrap();
+            assert_eq!(encoded(&read), encoded(&stored));
+        }
+
+        // ranges are looked up by id, without decoding the vectors before them
+        let stored = TestVector::from_json_file("cases.json").unwrap();
+        let id = |i: usize| stored[i].id().unwrap();
+        let read: Vec<TestVector> = CorpusReader::open("cases.json")
+            .unwrap()
+            .range(id(4), id(5))
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(encoded(&read), encoded(&stored[4..6]));
+        let mut reader = CorpusReader::open("cases.json").unwrap();
+        reader.skip_to(id(11)).unwrap();
+        assert_eq!(reader.position(), 11);
+        assert_eq!(
+            encoded(&[reader.next().unwrap().unwrap()]),
+            encoded(&stored[11..])
+        );
+        assert!(reader.next().is_none());
+        let mut reader = CorpusReader::open("cases.json").unwrap();
+        assert!(reader.skip_to("mixed-order/passes").is_err());
+        assert!(reader.next().is_none());
+        assert_eq!(
+            CorpusReader::open("cases.json")
+                .unwrap()
+                .range(id(10), "mixed-order/passes")
+                .count(),
+            2
+        );
+        assert!(CorpusReader::open("cases.json")
+            .unwrap()
+            .range("mixed-order/passes", id(11))
+            .all(|read| read.is_err()));
+
+        let family = &stored[6].metadata.as_ref().unwrap().name;
+        let read: Vec<TestVector> = CorpusReader::open("cases.json")
+            .unwrap()
+            .family(family)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert!(!read.is_empty());
+        assert!(read
+            .iter()
+            .all(|tv| &tv.metadata.as_ref().unwrap().name == family));
+
+        // braces within strings do not end a vector, and malformed corpora fail
+        let json = encoded(&stored[..2]);
+        let reader = CorpusReader::new(json.as_bytes(), Layout::Json);
+        let read: Vec<TestVector> = reader.collect::<Result<_>>().unwrap();
+        assert_eq!(encoded(&read), json);
+        let commented = json.replacen("small A and R", "small {A} and \\\"R\\\"", 1);
+        let reader = CorpusReader::new(commented.as_bytes(), Layout::Json);
+        assert_eq!(reader.count(), 2);
+        for malformed in ["", "{}", "[{\"message\":", "[{\"message\":\"00\"}]"].iter() {
+            let mut reader = CorpusReader::new(malformed.as_bytes(), Layout::Json);
+            assert!(reader.next().unwrap().is_err());
+            assert!(reader.next().is_none());
+        }
+        assert_eq!(CorpusReader::new(&b"[]"[..], Layout::Json).count(), 0);
+    }
+
+    #[test]
+    fn test_transcript_replay() {
+        let (recorded, transcript) = generate_recorded();
+        let replayed = generate_replayed(&Transcript::from_bytes(transcript.to_bytes()));
+
+        assert_eq!(recorded.len(), replayed.len());
+        for (tv1, tv2) in recorded.iter().zip(replayed.iter()) {
+            assert_eq!(tv1.message, tv2.message);
+            assert_eq!(tv1.pub_key, tv2.pub_key);
+            assert_eq!(tv1.signature, tv2.signature);
+        }
+    }
+
+    #[test]
+    fn test_repro_check() {
+        // every version regenerates as released
+        for version in CorpusVersion::ALL.iter() {
+            assert_eq!(repro::check(*version).unwrap(), vec![], "{}", version);
+            let expected = repro::expected_digests(*version).unwrap();
+            let mut files = version.files();
+            files.push(corpus_version::MANIFEST);
+            assert!(expected.iter().map(|(file, _)| *file).eq(files));
+        }
+
+        // the files checked into the repository are those of the latest version
+        let expected = repro::expected_digests(corpus_version::LATEST).unwrap();
+        for (file, digest) in expected.iter() {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(file);
+            let contents = std::fs::read(path).unwrap();
+            assert_eq!(repro::digest(&contents), *digest, "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_corpus_versions() {
+        let v1 = CorpusVersion::V1.artifacts().unwrap();
+        let v2 = artifacts().unwrap();
+        assert_eq!(v2, CorpusVersion::V2.artifacts().unwrap());
+        assert_eq!(v1.len(), 3);
+        assert_eq!(v2.len(), 32);
+
+        // the files of corpus-v1 are the 12 vectors, and corpus-v2 adds the ids of the cases
+        // to them, and files of its own
+        let cases: Vec<TestVector> = serde_json::from_slice(&v1[0].1).unwrap();
+        assert_eq!(cases.len(), 12);
+        assert!(cases.iter().all(|tv| tv.id().is_none()));
+        let mut vec: Vec<TestVector> = serde_json::from_slice(&v2[0].1).unwrap();
+        assert_eq!(vec[3].id(), Some(CASE_IDS[3]));
+        for metadata in vec.iter_mut().filter_map(|tv| tv.metadata.as_mut()) {
+            metadata.id.clear();
+        }
+        assert_eq!(
+            serde_json::to_string(&vec).unwrap(),
+            serde_json::to_string(&cases).unwrap()
+        );
+        assert!(TestVector::from_txt(std::str::from_utf8(&v2[1].1).unwrap()).is_ok());
+        for pair in CorpusVersion::ALL.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert_eq!(pair[1].previous(), Some(pair[0]));
+            let (previous, next) = (pair[0].files(), pair[1].files());
+            assert_eq!(next[..previous.len()], previous[..]);
+        }
+        assert_eq!(CorpusVersion::V1.previous(), None);
+
+        // the manifest names the version, and pins the files next to it
+        for (version, files) in CorpusVersion::ALL.iter().zip([&v1, &v2].iter()) {
+            let (name, json) = files.last().unwrap();
+            assert_eq!(*name, corpus_version::MANIFEST);
+            let manifest = Manifest::from_json(json).unwrap();
+            assert_eq!(manifest.corpus, *version);
+            let contents: Vec<(&str, &[u8])> = files[..files.len() - 1]
+                .iter()
+                .map(|(name, contents)| (*name, &contents[..]))
+                .collect();
+            assert!(manifest.check(*version, &contents).is_ok());
+        }
+        let manifest = Manifest::from_json(&v1[2].1).unwrap();
+        assert!(manifest.check(CorpusVersion::V2, &[]).is_err());
+        let error = manifest
+            .check(CorpusVersion::V1, &[("cases.json", &v1[1].1[..])])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "cases.json differs from that of corpus-v1"
+        );
+        assert!(manifest
+            .check(CorpusVersion::V1, &[("full_order.json", &[][..])])
+            .is_err());
+        let stored = std::fs::read("corpus.json").unwrap();
+        assert_eq!(
+            Manifest::from_json(&stored).unwrap().corpus,
+            corpus_version::LATEST
+        );
+
+        assert_eq!(
+            "corpus-v1".parse::<CorpusVersion>().unwrap(),
+            CorpusVersion::V1
+        );
+        assert_eq!(CorpusVersion::V2.to_string(), "corpus-v2");
+        assert!("corpus-v3".parse::<CorpusVersion>().is_err());
+
+        // generate writes a frozen version on request
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+        let out_dir = std::env::temp_dir().join("speccheck-corpus-v1");
+        let _ = std::fs::remove_dir_all(&out_dir);
+        let command = cli::parse(args(&format!(
+            "generate --corpus corpus-v1 --out-dir {}",
+            out_dir.display()
+        )))
+        .unwrap();
+        cli::run(&command, &mut Vec::new()).unwrap();
+        let mut written: Vec<String> = std::fs::read_dir(&out_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        written.sort();
+        assert_eq!(written, vec!["cases.json", "cases.txt", "corpus.json"]);
+        assert!(cli::parse(args("generate --corpus corpus-v0")).is_err());
+    }
+
+    #[test]
+    fn test_contribution_review() {
+        let vec = generate_test_vectors();
+        let signed = large_s_family(&mut new_rng()).remove(0).vector;
+        let contribution = |tv: &TestVector, claim: serde_json::Value| {
+            let mut json = serde_json::to_value(tv).unwrap();
+            json["claim"] = claim;
+            Contribution::from_json(&json.to_string()).unwrap()
+        };
+
+        // a signature as made tells apart the policies requiring A of order L, which the
+        // cases do not, and enters the corpus with the metadata derived for it
+        let claim = serde_json::json!({
+            "comment": " a signature as made ",
+            "a_order": "L",
+            "expected_cofactorless": true,
+            "expected_verdicts": { "rfc8032": "accepted" },
+        });
+        let review = contribution::review(&contribution(&signed, claim), &vec).unwrap();
+        assert!(review.is_accepted());
+        let metadata = review.entry.metadata.unwrap();
+        assert_eq!(metadata.name, contribution::CONTRIBUTED);
+        assert_eq!(metadata.index, 12);
+        assert_eq!(metadata.comment, "a signature as made");
+        assert_eq!(
+            (metadata.s_class, &metadata.r_order[..]),
+            (SClass::Canonical, "L")
+        );
+        assert!(metadata.expected_verdicts.values().all(|v| v.is_accepted()));
+
+        // every claim is checked
+        let claim = serde_json::json!({
+            "comment": "",
+            "a_order": "8",
+            "s_class": "large",
+            "expected_verdicts": { "rfc8032": "verify_rejected", "ring": "accepted" },
+        });
+        let review = contribution::review(&contribution(&signed, claim), &vec).unwrap();
+        assert_eq!(
+            review.discrepancies,
+            vec![
+                "s_class: claimed large, derived canonical",
+                "a_order: claimed 8, derived L",
+                "expected_verdicts.rfc8032: claimed X, derived V",
+                "expected_verdicts.ring: unknown policy",
+                "comment: missing",
+            ]
+        );
+        assert_eq!(review.equivalent_to, None);
+
+        // a case of the corpus, or one drawn from another seed, adds nothing
+        let seeded = VectorGenerator::from_u64(7).test_vectors();
+        for (i, tv) in [(4, &vec[4]), (6, &seeded[6])].iter() {
+            let claim = serde_json::json!({ "comment": "case" });
+            let review = contribution::review(&contribution(tv, claim), &vec).unwrap();
+            assert!(!review.is_accepted());
+            assert_eq!(review.equivalent_to, Some(*i));
+        }
+
+        let mut truncated = signed.clone();
+        truncated.signature.pop();
+        let claim = serde_json::json!({ "comment": "truncated" });
+        assert!(contribution::review(&contribution(&truncated, claim), &vec).is_err());
+        assert!(
+            Contribution::from_json(r#"{"message": "", "pub_key": "", "signature": ""}"#).is_err()
+        );
+        let mut json = serde_json::to_value(&signed).unwrap();
+        json["claim"] = serde_json::json!({ "comment": "typo", "a_ordr": "L" });
+        assert!(Contribution::from_json(&json.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let vec = generate_test_vectors();
+
+        // the references achieve the properties of [CGN20e] Table 5
+        let achieved = |policy: ValidationPolicy| -> Vec<bool> {
+            explain("reference", &vec, &policy.predict(&vec))
+                .unwrap()
+                .findings
+                .iter()
+                .map(|finding| finding.is_achieved())
+                .collect()
+        };
+        assert_eq!(achieved(ALGORITHM_2), vec![true, true, true]);
+        assert_eq!(achieved(RFC_8032), vec![true, false, false]);
+        assert_eq!(achieved(ZIP_215), vec![true, false, true]);
+        assert_eq!(achieved(LIBSODIUM), vec![true, true, false]);
+        assert_eq!(
+            achieved(ValidationPolicy::default()),
+            vec![false, false, false]
+        );
+
+        let explanation = explain_verifier("Zip215", &Zip215, &vec).unwrap();
+        assert!(explanation.consistent.contains(&ZIP_215));
+        assert!(explanation.closest.is_none());
+        assert!(explanation.zip215_differences.is_empty());
+        assert_eq!(explanation.gaps(), vec![Property::StronglyBinding]);
+        // the small-order A accepted show the gap
+        for &i in explanation.findings[1].evidence.iter() {
+            let pub_key = deserialize_point(&vec[i].pub_key).unwrap();
+            assert!(pub_key.is_small_order());
+        }
+        let report = explanation.to_string();
+        assert!(report.contains("differences from ZIP-215: 0\n"));
+        assert!(report.contains("SBS: not achieved"));
+        assert!(report.contains(Property::StronglyBinding.recommendation()));
+
+        // a verifier no policy models is explained by the closest one
+        let mut observed = RFC_8032.predict(&vec);
+        observed[0] = Verdict::VerifyRejected;
+        observed[6] = Verdict::Accepted;
+        let explanation = explain("odd", &vec, &observed).unwrap();
+        assert!(explanation.consistent.is_empty());
+        let (_, departing) = explanation.closest.as_ref().unwrap();
+        assert!(!departing.is_empty());
+        assert!(explanation.rfc8032_differences.contains(&6));
+        assert_eq!(explanation.findings[0].evidence, vec![6]);
+        assert!(explain("short", &vec, &observed[1..]).is_err());
+    }
+
+    #[test]
+    fn test_cli() {
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+
+        let out_dir = std::env::temp_dir().join("speccheck-cli");
+        let command = cli::parse(args(&format!(
+            "generate --format json --select s-out-of-bounds,0-1,10 --seed 7 --out-dir {}",
+            out_dir.display()
+        )))
+        .unwrap();
+        assert_eq!(
+            command,
+            Command::Generate {
+                out_dir: out_dir.clone(),
+                format: Format::Json,
+                selection: Some(vec![6, 7, 0, 1, 10]),
+                seed: Some(7),
+                corpus: CorpusVersion::V2,
+                explain: false,
+            }
+        );
+        assert_eq!(
+            cli::parse(args("verify --policy=cofactored,require-canonical-s")).unwrap(),
+            Command::Verify {
+                policy: ValidationPolicy {
+                    cofactored: true,
+                    require_canonical_s: true,
+                    ..Default::default()
+                },
+                vectors: None,
+                selection: None,
+                format: Format::Txt,
+            }
+        );
+        assert!(cli::parse(args("verify --policy prereduce")).is_err());
+        // the verifiers of this crate by name, cofactored being its policy and not the check
+        assert_eq!(
+            cli::parse(args("verify --policy cofactorless --input cases.json")).unwrap(),
+            Command::Verify {
+                policy: COFACTORLESS,
+                vectors: Some(std::path::PathBuf::from("cases.json")),
+                selection: None,
+                format: Format::Txt,
+            }
+        );
+        match cli::parse(args("verify --policy cofactored")).unwrap() {
+            Command::Verify { policy, .. } => assert_eq!(policy, COFACTORED),
+            command => panic!("parsed {:?}", command),
+        }
+        assert!(cli::parse(args("verify --input a.json --vectors b.json")).is_err());
+        assert!(cli::parse(args("generate --format sarif")).is_err());
+        assert!(cli::parse(args("generate --out-dir")).is_err());
+        assert!(cli::parse(args("classify --verdicts VVX --interactive")).is_err());
+        assert!(cli::parse(args("report Dalek=VV?")).is_err());
+        // a row of verdicts must cover every vector, no more
+        for verdicts in ["VVVV", "VVVVVVVVVVVVVVVVVVVVV"].iter() {
+            for format in ["table", "csv", "sarif"].iter() {
+                let command =
+                    cli::parse(args(&format!("report a={} --format {}", verdicts, format)))
+                        .unwrap();
+                let error = cli::run(&command, &mut Vec::new()).unwrap_err();
+                assert!(error
+                    .to_string()
+                    .contains(&format!("got {} observed verdicts for 12", verdicts.len())));
+            }
+        }
+        assert!(cli::parse(args("repro-check --seed 1")).is_err());
+        assert_eq!(
+            cli::parse(args("repro-check --corpus corpus-v1")).unwrap(),
+            Command::ReproCheck {
+                corpus: CorpusVersion::V1
+            }
+        );
+        assert!(cli::parse(args("repro-check --corpus corpus-v3")).is_err());
+        assert!(cli::parse(args("validate-contribution")).is_err());
+        assert!(cli::parse(args("explain")).is_err());
+        assert!(cli::parse(args("explain Dalek=VV?")).is_err());
+
+        // the generated vectors read back, and are those drawn from the seed
+        cli::run(&command, &mut Vec::new()).unwrap();
+        let json = std::fs::read_to_string(out_dir.join("cases.json")).unwrap();
+        let written: Vec<TestVector> = serde_json::from_str(&json).unwrap();
+        let seeded = VectorGenerator::from_u64(7).test_vectors();
+        assert_eq!(written.len(), 5);
+        for (tv, i) in written.iter().zip([6, 7, 0, 1, 10].iter()) {
+            assert_eq!(tv.signature, seeded[*i].signature);
+            assert_eq!(tv.metadata, seeded[*i].metadata);
+        }
+        // with their traces when explained
+        let explained_dir = out_dir.join("explained");
+        let command = cli::parse(args(&format!(
+            "generate --format json --select 0 --explain --out-dir {}",
+            explained_dir.display()
+        )))
+        .unwrap();
+        cli::run(&command, &mut Vec::new()).unwrap();
+        let json = std::fs::read_to_string(explained_dir.join("cases.json")).unwrap();
+        let explained: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(explained[0]["trace"]["target"], "R + [k]A = 0");
+        let written: Vec<TestVector> = serde_json::from_str(&json).unwrap();
+        assert_eq!(written[0].signature, generate_test_vectors()[0].signature);
+
+        // classifying the verdicts a policy predicts finds that policy back
+        let mut output = Vec::new();
+        cli::run(
+            &cli::parse(args(&format!(
+                "verify --format json --vectors {}",
+                out_dir.join("cases.json").display()
+            )))
+            .unwrap(),
+            &mut output,
+        )
+        .unwrap();
+        let verdicts: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(verdicts[0]["index"], 0);
+        assert_eq!(verdicts.as_array().unwrap().len(), 5);
+        // in txt, the verdicts are followed by their counts
+        let mut output = Vec::new();
+        cli::run(
+            &cli::parse(args("verify --policy algorithm2 --select 0-3")).unwrap(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 6);
+        let predicted = ALGORITHM_2.predict(&generate_test_vectors()[..4]);
+        let accepted = predicted.iter().filter(|v| v.is_accepted()).count();
+        assert!(lines[5].starts_with(&format!("4 vectors: {} accepted, ", accepted)));
+
+        let policy = ValidationPolicy {
+            reject_small_order_pk: true,
+            cofactored: true,
+            ..Default::default()
+        };
+        let symbols: String = policy
+            .predict(&generate_test_vectors())
+            .iter()
+            .map(Verdict::to_string)
+            .collect();
+        let mut output = Vec::new();
+        cli::run(
+            &cli::parse(args(&format!("classify --verdicts {}", symbols))).unwrap(),
+            &mut output,
+        )
+        .unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains(&format!("  {}\n", policy)));
+
+        // explaining them infers the policy too, and reports on the properties
+        let command = cli::parse(args(&format!("explain lib={}", symbols))).unwrap();
+        assert_eq!(
+            command,
+            Command::Explain {
+                library: ("lib".to_string(), policy.predict(&generate_test_vectors())),
+                vectors: None,
+            }
+        );
+        let mut output = Vec::new();
+        cli::run(&command, &mut output).unwrap();
+        let report = String::from_utf8(output).unwrap();
+        assert!(report.starts_with("lib on 12 vectors\n"));
+        assert!(report.contains(&format!("  {}\n", policy)));
+        assert!(report.contains("consensus safety: achieved"));
+    }
+
+    #[test]
+    fn test_interleaved_batches() {
+        let vec = generate_test_vectors();
+        let batches = interleaved_batches(&mut new_rng());
+        assert_eq!(batches.len(), vec.len() + 2);
+
+        for batch in batches.iter() {
+            // whatever fails cofactored verification also fails cofactorless verification
+            for i in batch.invalid_cofactored.iter() {
+                assert!(batch.invalid_cofactorless.contains(i));
+            }
+        }
+        // the forgery follows the test vectors, and no verifier may accept it
+        let forgery = &batches[vec.len()];
+        assert_eq!(forgery.invalid_cofactored, vec![vec.len() % 8]);
+        assert_eq!(forgery.invalid_cofactorless, vec![vec.len() % 8]);
+    }
+
+    #[test]
+    fn test_batch_equation_vectors() {
+        let mut rng = new_rng();
+        let batches = batch_equation_vectors(&mut rng);
+        assert_eq!(batches.len(), 6);
+
+        // case 9 passes individually, hashing R as provided, but not in a batch
+        assert!(batches[0].batch.invalid_cofactorless.is_empty());
+        assert_eq!(batches[0].batch_cofactored, BatchOutcome::Fails);
+        // the torsion components of orders 4 and 8 cancel out under unit coefficients only
+        let cancelling = &batches[4];
+        assert_eq!(cancelling.batch.invalid_cofactorless, vec![0, 3, 7]);
+        assert_eq!(cancelling.batch_cofactorless, BatchOutcome::PassesOneIn(8));
+        let ones = vec![Scalar::one(); 8];
+        assert!(verify_batch_cofactorless(&cancelling.batch.entries, &ones).is_ok());
+        assert!(verify_batch_cofactorless(&cancelling.batch.entries, &ones[1..]).is_err());
+
+        for batch in batches.iter() {
+            let entries = &batch.batch.entries;
+            // the cofactored batch passes iff all entries pass cofactored individually, when
+            // hashing re-serialized points
+            assert_eq!(
+                batch.batch_cofactored == BatchOutcome::Passes,
+                entries.iter().all(|tv| {
+                    let pub_key = deserialize_point(&tv.pub_key).unwrap();
+                    let r = deserialize_point(&tv.signature[..32]).unwrap();
+                    let s = Scalar::from_bits(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap());
+                    verify_cofactored(&tv.message, &pub_key, &(r, s)).is_ok()
+                })
+            );
+
+            // the outcomes hold over random coefficients
+            let mut cofactorless_passes = 0;
+            for _ in 0..64 {
+                let z = algorithm3::random_coefficients(entries.len(), &mut rng);
+                assert_eq!(
+                    verify_batch_cofactored(entries, &z).is_ok(),
+                    batch.batch_cofactored == BatchOutcome::Passes
+                );
+                if verify_batch_cofactorless(entries, &z).is_ok() {
+                    cofactorless_passes += 1;
+                }
+            }
+            match batch.batch_cofactorless {
+                BatchOutcome::Passes => assert_eq!(cofactorless_passes, 64),
+                BatchOutcome::Fails => assert_eq!(cofactorless_passes, 0),
+                // fails to pass or to fail over 64 draws with probability below 2^-8
+                BatchOutcome::PassesOneIn(_) => {
+                    assert!(0 < cofactorless_passes && cofactorless_passes < 64)
+                }
+            }
+        }
+        assert_eq!(batches[5].batch_cofactorless, BatchOutcome::PassesOneIn(2));
+    }
+
+    #[test]
+    fn test_batch_inconsistency_vectors() {
+        let mut rng = new_rng();
+        let vectors = batch_inconsistency_vectors(&mut rng).unwrap();
+        assert_eq!(vectors.len(), 7);
+        for vector in vectors.iter() {
+            let batch = &vector.batch;
+            // every signature passes individually, under both equations
+            assert!(batch.invalid_cofactored.is_empty());
+            assert!(batch.invalid_cofactorless.is_empty());
+            assert!(batch.invalid_algorithm2.is_empty());
+            assert_eq!(vector.batch_cofactored, BatchOutcome::Passes);
+            assert_eq!(
+                vector.batch_cofactorless,
+                BatchOutcome::PassesOneIn(vector.torsion_order)
+            );
+
+            // fails to pass or to fail over 128 draws with probability below 2^-24
+            let (messages, pub_keys, signatures) = unpack_batch(batch);
+            let mut cofactorless_passes = 0;
+            for _ in 0..128 {
+                let z = algorithm3::random_coefficients(messages.len(), &mut rng);
+                let verify = |policy: &ValidationPolicy| {
+                    batch::verify_batch_with_coefficients(
+                        &messages,
+                        &pub_keys,
+                        &signatures,
+                        policy,
+                        &z,
+                    )
+                    .is_ok()
+                };
+                assert!(verify(&COFACTORED));
+                if verify(&COFACTORLESS) {
+                    cofactorless_passes += 1;
+                }
+            }
+            assert!(0 < cofactorless_passes && cofactorless_passes < 128);
+        }
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let cofactored = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+        let pre_reduced = ValidationPolicy {
+            prereduce: true,
+            ..cofactored
+        };
+        let mut batches = interleaved_batches(&mut new_rng());
+        batches.append(&mut misattribution_batches(&mut new_rng()));
+        for batch in batches.iter() {
+            let (messages, pub_keys, signatures) = unpack_batch(batch);
+            let verify = |policy: &ValidationPolicy, coefficients: Coefficients| {
+                batch::verify_batch(&messages, &pub_keys, &signatures, policy, coefficients).is_ok()
+            };
+            for &coefficients in [Coefficients::Random, Coefficients::Deterministic].iter() {
+                // the cofactored batch passes iff every signature does individually
+                assert_eq!(
+                    verify(&cofactored, coefficients),
+                    batch.invalid_cofactored.is_empty()
+                );
+                assert_eq!(
+                    verify(&ALGORITHM_2, coefficients),
+                    batch.invalid_algorithm2.is_empty()
+                );
+            }
+
+            // the signatures valid individually without torsion components pass under every
+            // equation
+            let honest: Vec<_> = (0..batch.entries.len())
+                .filter(|i| !batch.invalid_cofactorless.contains(i))
+                .map(|i| &batch.entries[i])
+                .filter(|tv| {
+                    [&tv.pub_key[..], &tv.signature[..32]]
+                        .iter()
+                        .all(|pt| has_full_order(&deserialize_point(pt).unwrap()))
+                })
+                .collect();
+            let messages: Vec<&[u8]> = honest.iter().map(|tv| &tv.message[..]).collect();
+            let pub_keys: Vec<&[u8]> = honest.iter().map(|tv| &tv.pub_key[..]).collect();
+            let signatures: Vec<&[u8]> = honest.iter().map(|tv| &tv.signature[..]).collect();
+            for policy in [cofactored, pre_reduced, ValidationPolicy::default()].iter() {
+                assert!(batch::verify_batch(
+                    &messages,
+                    &pub_keys,
+                    &signatures,
+                    policy,
+                    Coefficients::Deterministic
+                )
+                .is_ok());
+            }
+        }
+
+        // the deterministic coefficients reproduce the outcome of the cofactorless batch
+        // passing at random
+        let torsion = &batch_equation_vectors(&mut new_rng())[5];
+        assert_eq!(torsion.batch_cofactorless, BatchOutcome::PassesOneIn(2));
+        let (messages, pub_keys, signatures) = unpack_batch(&torsion.batch);
+        let outcome = || {
+            batch::verify_batch(
+                &messages,
+                &pub_keys,
+                &signatures,
+                &COFACTORLESS,
+                Coefficients::Deterministic,
+            )
+            .is_ok()
+        };
+        let first = outcome();
+        assert!((0..8).all(|_| outcome() == first));
+
+        assert!(batch::verify_batch(
+            &messages,
+            &pub_keys[1..],
+            &signatures,
+            &COFACTORLESS,
+            Coefficients::Random
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_hram_linked_pairs() {
+        let reserializing = ValidationPolicy {
+            cofactored: true,
+            reserialize_for_hash: true,
+            ..Default::default()
+        };
+        let raw = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+
+        let pairs = hram_linked_pairs(&mut new_rng());
+        for pair in pairs.iter() {
+            assert_eq!(pair.hram_first, pair.hram_reserialized);
+            assert_ne!(pair.hram_second, pair.hram_reserialized);
+            for tv in [&pair.first, &pair.second].iter() {
+                assert!(reserializing
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok());
+            }
+        }
+        // with R hashed as provided, the non-canonical R yields another, wrong challenge
+        for pair in pairs[..pairs.len() / 2].iter() {
+            let tv = &pair.second;
+            assert!(raw.verify(&tv.message, &tv.pub_key, &tv.signature).is_err());
+        }
+    }
+
+    #[test]
+    fn test_pre_reduction_vectors() {
+        let cofactored = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+        let pre_reduced = ValidationPolicy {
+            prereduce: true,
+            ..cofactored
+        };
+        let passes = |policy: &ValidationPolicy, tv: &TestVector| {
+            policy
+                .verify(&tv.message, &tv.pub_key, &tv.signature)
+                .is_ok()
+        };
+
+        let vectors = pre_reduction_vectors(&mut new_rng());
+        assert_eq!(vectors.len(), 24);
+        for v in vectors.iter() {
+            assert_eq!(passes(&cofactored, &v.vector), v.passes_cofactored);
+            assert_eq!(passes(&pre_reduced, &v.vector), v.passes_pre_reduced);
+            assert_eq!(
+                passes(&ValidationPolicy::default(), &v.vector),
+                v.passes_cofactorless
+            );
+        }
+        // each torsion order tells the two orders of operations apart, whatever S
+        for order in [2, 4, 8].iter() {
+            for unreduced_s in [false, true].iter() {
+                assert!(vectors.iter().any(|v| v.torsion_order == *order
+                    && v.unreduced_s == *unreduced_s
+                    && v.passes_cofactored
+                    && !v.passes_pre_reduced));
+            }
+        }
+
+        // the detector tells pre-reducing cofactored verifiers from the others
+        let detect = |policy: ValidationPolicy| {
+            detect_prereduction(|message, pub_key, signature| {
+                policy.verify(message, pub_key, signature).is_ok()
+            })
+        };
+        assert_eq!(detect(pre_reduced), Some(true));
+        assert_eq!(detect(cofactored), Some(false));
+        assert_eq!(detect(ZIP_215), Some(false));
+        assert_eq!(detect(ValidationPolicy::default()), None);
+        assert_eq!(detect(FIPS_186_5), None);
+        #[cfg(feature = "zebra")]
+        {
+            let zebra = detect_prereduction(|message, pub_key, signature| {
+                let tv = TestVector {
+                    message: message.to_vec(),
+                    pub_key: <[u8; 32]>::try_from(pub_key).unwrap(),
+                    signature: signature.to_vec(),
+                    metadata: None,
+                    trace: None,
+                };
+                let (pk, sig) = unpack_test_vector_zebra(&tv);
+                pk.verify(&sig, message).is_ok()
+            });
+            assert_eq!(zebra, Some(false));
+        }
+    }
+
+    #[test]
+    fn test_ed25519ph_vectors() {
+        // the Ed25519ph test vector of RFC 8032, Section 7.3
+        let pk = hex::decode("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf")
+            .unwrap();
+        let sig = hex::decode(
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+             31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406",
+        )
+        .unwrap();
+        let unpacked = (
+            deserialize_point(&sig[..32]).unwrap(),
+            Scalar::from_canonical_bytes(<[u8; 32]>::try_from(&sig[32..]).unwrap()).unwrap(),
+        );
+        let pub_key = deserialize_point(&pk).unwrap();
+        assert!(ed25519ph::verify_cofactorless(b"", b"abc", &pub_key, &unpacked).is_ok());
+        assert!(ed25519ph::verify_cofactorless(b"", b"abd", &pub_key, &unpacked).is_err());
+        assert!(ed25519ph::compute_hram_with_arrays(&[0; 256], b"abc", &pk, &sig[..32]).is_err());
+
+        let vec = ed25519ph::generate_test_vectors();
+        assert_eq!(vec.len(), 12);
+        let passes = |tv: &TestVector| {
+            let r = deserialize_point(&tv.signature[..32]).unwrap();
+            let s = Scalar::from_bits(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap());
+            let pub_key = deserialize_point(&tv.pub_key).unwrap();
+            (
+                ed25519ph::verify_cofactored(ed25519ph::CONTEXT, &tv.message, &pub_key, &(r, s))
+                    .is_ok(),
+                ed25519ph::verify_cofactorless(ed25519ph::CONTEXT, &tv.message, &pub_key, &(r, s))
+                    .is_ok(),
+            )
+        };
+        // with A and R re-serialized for the hash, so that vector 9, signed for R hashed as
+        // provided, fails both
+        let expected = [
+            true, true, true, true, false, false, true, true, true, false, true, false,
+        ];
+        for (i, (tv, passes_cofactorless)) in vec.iter().zip(expected.iter()).enumerate() {
+            assert_eq!(passes(tv), (i != 9, *passes_cofactorless));
+        }
+
+        // dalek agrees on the canonical vectors with S < L
+        #[cfg(feature = "dalek")]
+        for (i, tv) in vec.iter().enumerate().take(6) {
+            let (pk, sig) = unpack_test_vector_dalek(tv);
+            let prehashed = Sha512::new().chain(&tv.message);
+            assert_eq!(
+                pk.verify_prehashed(prehashed, Some(ed25519ph::CONTEXT), &sig)
+                    .is_ok(),
+                expected[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_ed25519ctx_vectors() {
+        // the Ed25519ctx test vector of RFC 8032, Section 7.2, under the context "foo"
+        let pk = hex::decode("dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292")
+            .unwrap();
+        let msg = hex::decode("f726936d19c800494e3fdaff20b276a8").unwrap();
+        let sig = hex::decode(
+            "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a\
+             8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+        )
+        .unwrap();
+        let unpacked = (
+            deserialize_point(&sig[..32]).unwrap(),
+            Scalar::from_canonical_bytes(<[u8; 32]>::try_from(&sig[32..]).unwrap()).unwrap(),
+        );
+        let pub_key = deserialize_point(&pk).unwrap();
+        assert!(ed25519ctx::verify_cofactorless(b"foo", &msg, &pub_key, &unpacked).is_ok());
+        assert!(ed25519ctx::verify_cofactorless(b"bar", &msg, &pub_key, &unpacked).is_err());
+        assert!(ed25519ph::verify_cofactorless(b"foo", &msg, &pub_key, &unpacked).is_err());
+        assert!(ed25519ctx::compute_hram_with_arrays(&[0; 256], &msg, &pk, &sig[..32]).is_err());
+
+        let all = ed25519ctx::generate_test_vectors();
+        assert_eq!(all.len(), ed25519ctx::CONTEXT_LENGTHS.len());
+        // same pattern as in Ed25519ph, see `test_ed25519ph_vectors`
+        let expected = [
+            true, true, true, true, false, false, true, true, true, false, true, false,
+        ];
+        for (cv, &len) in all.iter().zip(ed25519ctx::CONTEXT_LENGTHS.iter()) {
+            assert_eq!(cv.context, ed25519ctx::context(len));
+            assert_eq!(cv.vectors.len(), 12);
+            for (i, tv) in cv.vectors.iter().enumerate() {
+                let r = deserialize_point(&tv.signature[..32]).unwrap();
+                let s = Scalar::from_bits(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap());
+                let pub_key = deserialize_point(&tv.pub_key).unwrap();
+                let sig = (r, s);
+                assert_eq!(
+                    ed25519ctx::verify_cofactored(&cv.context, &tv.message, &pub_key, &sig).is_ok(),
+                    i != 9
+                );
+                assert_eq!(
+                    ed25519ctx::verify_cofactorless(&cv.context, &tv.message, &pub_key, &sig)
+                        .is_ok(),
+                    expected[i]
+                );
+            }
+        }
+        // the empty context still hashes a dom2 prefix, unlike Ed25519
+        let tv = &all[0].vectors[6];
+        assert!(verify_cofactorless(
+            &tv.message,
+            &deserialize_point(&tv.pub_key).unwrap(),
+            &(
+                deserialize_point(&tv.signature[..32]).unwrap(),
+                Scalar::from_bits(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap())
+            )
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_zip215_vectors() {
+        let vectors = zip215_vectors();
+        assert_eq!(vectors.len(), 196);
+        assert_eq!(merge(vectors.clone()).len(), 196);
+        // ZIP-215 is cofactored, hashes A and R as provided and only checks S
+        let zip215 = ValidationPolicy {
+            cofactored: true,
+            require_canonical_s: true,
+            ..Default::default()
+        };
+        for sv in vectors.iter() {
+            let tv = &sv.vector;
+            assert_eq!(sv.expected, Some(true));
+            assert!(zip215
+                .verify(&tv.message, &tv.pub_key, &tv.signature)
+                .is_ok());
+            #[cfg(feature = "zebra")]
+            {
+                let pk = ZPublicKey::try_from(&tv.pub_key[..]).unwrap();
+                let sig = ZSignature::try_from(&tv.signature[..]).unwrap();
+                assert!(pk.verify(&sig, &tv.message).is_ok());
+            }
+        }
+
+        // the reference verifier follows the rules, and agrees with ed25519-zebra on the
+        // vectors and on the non-canonical encodings of the points of order 4
+        let mut vec = generate_test_vectors();
+        assert_eq!(verdicts(&Zip215, &vec), verdicts(&zip215, &vec));
+        vec.extend(vectors.into_iter().map(|sv| sv.vector));
+        let non_canonical = non_canonical_vectors(&mut new_rng()).unwrap();
+        vec.extend(non_canonical.into_iter().map(|nc| nc.vector));
+        #[cfg(feature = "zebra")]
+        for tv in vec.iter() {
+            let zebra = ZSignature::try_from(&tv.signature[..])
+                .and_then(|sig| Ok((ZPublicKey::try_from(&tv.pub_key[..])?, sig)))
+                .and_then(|(pk, sig)| pk.verify(&sig, &tv.message));
+            assert_eq!(
+                Zip215
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                zebra.is_ok()
+            );
+        }
+    }
+
+    // The vectors of this crate and of ZIP-215, and the non-canonical encodings of the points
+    // of order 4, to compare the reference verifiers on
+    fn extended_vectors() -> Vec<TestVector> {
+        let mut vec = generate_test_vectors();
+        vec.extend(zip215_vectors().into_iter().map(|sv| sv.vector));
+        let non_canonical = non_canonical_vectors(&mut new_rng()).unwrap();
+        vec.extend(non_canonical.into_iter().map(|nc| nc.vector));
+        vec
+    }
+
+    #[test]
+    fn test_libsodium() {
+        let vec = generate_test_vectors();
+        let row: Vec<Verdict> = verdicts(&Libsodium, &vec);
+        print_row("libsodium", row.iter().copied());
+        let symbols: String = row.iter().map(Verdict::to_string).collect();
+        assert_eq!(symbols, "PPPVXXPPPPPP");
+        assert_eq!("libsodium".parse::<ValidationPolicy>().unwrap(), LIBSODIUM);
+
+        // the policy accepts the same vectors as the emulation, rejecting the non-canonical
+        // encodings of R while parsing rather than while comparing them
+        for tv in extended_vectors().iter() {
+            assert_eq!(
+                Libsodium
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                LIBSODIUM
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rfc8032() {
+        let vec = generate_test_vectors();
+        let row: Vec<Verdict> = verdicts(&Rfc8032, &vec);
+        print_row("RFC 8032", row.iter().copied());
+        let symbols: String = row.iter().map(Verdict::to_string).collect();
+        assert_eq!(symbols, "VVVVXXPPPPPP");
+
+        // decoding strictly is rejecting the non-canonical encodings
+        let vec = extended_vectors();
+        assert_eq!(verdicts(&Rfc8032, &vec), verdicts(&RFC_8032, &vec));
+    }
+
+    #[test]
+    fn test_fips186_5() {
+        let vec = generate_test_vectors();
+        let row: Vec<Verdict> = verdicts(&Fips186_5, &vec);
+        print_row("FIPS 186-5", row.iter().copied());
+        let symbols: String = row.iter().map(Verdict::to_string).collect();
+        assert_eq!(symbols, "XXXXXXPPPPPP");
+
+        let vec = extended_vectors();
+        assert_eq!(verdicts(&Fips186_5, &vec), verdicts(&FIPS_186_5, &vec));
+        // it departs from RFC 8032 on the vectors whose A is not of order L, or which only
+        // pass the cofactored equation
+        for (tv, verdict) in vec.iter().zip(verdicts(&Rfc8032, &vec)) {
+            let pub_key = deserialize_point(&tv.pub_key).unwrap();
+            if has_full_order(&pub_key) && verdict.is_accepted() {
+                assert!(Fips186_5
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_matrix() {
+        let vec = extended_vectors();
+        let matrix = ExpectedMatrix::new(&vec);
+        assert_eq!(matrix.len(), vec.len());
+        // the reference verifiers accept what their policies predict
+        for (index, tv) in vec.iter().enumerate() {
+            let classified = classify(tv);
+            for (name, _) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    classified[name].is_accepted(),
+                    matrix.expected(index, name).unwrap().is_accepted()
+                );
+            }
+        }
+
+        // and as `TestVector::evaluate` reports them
+        for (index, tv) in vec.iter().enumerate() {
+            let results = tv.evaluate();
+            for (name, accepted) in [
+                ("cofactored", results.cofactored),
+                ("cofactorless", results.cofactorless),
+                ("algorithm2", results.algorithm2),
+                ("zip215", results.zip215),
+                ("rfc8032", results.rfc8032),
+            ]
+            .iter()
+            {
+                assert_eq!(
+                    matrix.expected(index, name).unwrap().is_accepted(),
+                    *accepted,
+                    "{} on {}",
+                    name,
+                    index
+                );
+            }
+            assert_eq!(
+                results.pre_reduced_cofactored,
+                PreReducedCofactored
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok()
+            );
+        }
+        let rfc8032: Vec<bool> = generate_test_vectors()
+            .iter()
+            .map(|tv| tv.evaluate().rfc8032)
+            .collect();
+        assert_eq!(
+            rfc8032,
+            [true, true, true, true, false, false, false, false, false, false, false, false]
+        );
+
+        // a library is checked against a policy in one call
+        let observed = verdicts(&Zip215, &vec);
+        assert!(matrix.check("zip215", &observed).is_ok());
+        assert!(matrix.matching(&observed).contains(&"zip215"));
+        let error = matrix.check("rfc8032", &observed).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("departs from rfc8032 on vectors "));
+        assert!(matrix.check("ed448", &observed).is_err());
+        assert!(matrix.check("zip215", &observed[1..]).is_err());
+
+        // and ed25519-zebra is ZIP-215
+        #[cfg(feature = "zebra")]
+        {
+            let zebra: Vec<Verdict> = vec
+                .iter()
+                .map(|tv| {
+                    let (pk, sig) = match ZSignature::try_from(&tv.signature[..])
+                        .and_then(|sig| Ok((ZPublicKey::try_from(&tv.pub_key[..])?, sig)))
+                    {
+                        Ok(decoded) => decoded,
+                        Err(_) => return Verdict::ParseRejected,
+                    };
+                    match pk.verify(&sig, &tv.message) {
+                        Ok(()) => Verdict::Accepted,
+                        Err(_) => Verdict::VerifyRejected,
+                    }
+                })
+                .collect();
+            assert!(matrix.check("zip215", &zebra).is_ok());
+            assert!(matrix.matching(&zebra).contains(&"zip215"));
+        }
+    }
+
+    #[test]
+    fn test_verify_prehashed() {
+        // the named policies reach the same verdicts on the cases signed in Ed25519ph as on
+        // those of Ed25519
+        let vec = generate_test_vectors();
+        let ph = ed25519ph::generate_test_vectors();
+        for (name, policy) in NAMED_POLICIES.iter() {
+            for (tv, tv_ph) in vec.iter().zip(ph.iter()) {
+                let prehash = Sha512::digest(&tv_ph.message);
+                assert_eq!(
+                    policy
+                        .verify_prehashed(
+                            ed25519ph::CONTEXT,
+                            &prehash,
+                            &tv_ph.pub_key,
+                            &tv_ph.signature
+                        )
+                        .is_ok(),
+                    tv.metadata.as_ref().unwrap().expected_verdicts[*name].is_accepted()
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "digest-verifier")]
+    #[test]
+    fn test_prehash_verifier() {
+        use ::signature::DigestVerifier;
+        use ed25519_speccheck::prehash::PrehashVerifier;
+
+        for tv in ed25519ph::generate_test_vectors().iter() {
+            // S >= 2^253 does not fit in an ed25519::Signature
+            let signature = match ::ed25519::Signature::try_from(&tv.signature[..]) {
+                Ok(signature) => signature,
+                Err(_) => continue,
+            };
+            for (_, policy) in NAMED_POLICIES.iter() {
+                let verifier = PrehashVerifier::new(*policy, tv.pub_key);
+                assert_eq!(
+                    verifier
+                        .verify_digest(Sha512::new().chain(&tv.message), &signature)
+                        .is_ok(),
+                    policy
+                        .verify_prehashed(
+                            verifier.context(),
+                            &Sha512::digest(&tv.message),
+                            &tv.pub_key,
+                            &tv.signature
+                        )
+                        .is_ok()
+                );
+            }
+        }
+        assert!(PrehashVerifier::with_context(RFC_8032, [0u8; 32], &[0u8; 256]).is_err());
+    }
+
+    #[cfg(feature = "libsodium")]
+    #[test]
+    fn test_libsodium_ffi() {
+        for tv in extended_vectors().iter() {
+            assert_eq!(
+                Libsodium
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                sodium_verify_detached(tv)
+            );
+        }
+        // including on truncated signatures, which libsodium is never handed
+        for control in negative_control_vectors(&mut new_rng()).iter() {
+            let tv = &control.vector;
+            assert_eq!(
+                Libsodium
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                sodium_verify_detached(tv)
+            );
+            assert!(!sodium_open(tv));
+        }
+    }
+
+    #[test]
+    fn test_wycheproof_import() {
+        let tv = &generate_test_vectors()[6];
+        let mut forged = tv.signature.clone();
+        forged[0] ^= 1;
+        let json = format!(
+            r#"{{"algorithm": "EDDSA", "testGroups": [{{
+                "key": {{"curve": "edwards25519", "pk": "{}"}},
+                "type": "EddsaVerify",
+                "tests": [
+                    {{"tcId": 1, "comment": "", "msg": "{}", "sig": "{}", "result": "valid", "flags": []}},
+                    {{"tcId": 2, "comment": "modified r", "msg": "{}", "sig": "{}", "result": "invalid", "flags": []}},
+                    {{"tcId": 3, "comment": "truncated", "msg": "", "sig": "00", "result": "acceptable", "flags": []}}
+                ]
+            }}]}}"#,
+            hex::encode(tv.pub_key),
+            hex::encode(&tv.message),
+            hex::encode(&tv.signature),
+            hex::encode(&tv.message),
+            hex::encode(&forged),
+        );
+        let vectors = import_wycheproof(&json).unwrap();
+        assert_eq!(vectors.len(), 3);
+        assert_eq!(
+            vectors.iter().map(|sv| sv.expected).collect::<Vec<_>>(),
+            vec![Some(true), Some(false), None]
+        );
+        assert_eq!(vectors[1].id, "2");
+        assert_eq!(vectors[1].vector.signature, forged);
+
+        // the vector shared with speccheck is merged, keeping both sources
+        let mut all = speccheck_vectors();
+        all.extend(vectors);
+        let coverage = Coverage::new(&all);
+        assert!(coverage
+            .exclusive(Suite::Wycheproof)
+            .contains(&Axis::Malformed));
+        assert!(coverage
+            .exclusive(Suite::Speccheck)
+            .contains(&Axis::Cofactor));
+        let merged = merge(all);
+        assert_eq!(merged.len(), 14);
+        assert_eq!(merged[6].sources.len(), 2);
+
+        assert!(import_wycheproof(&json.replace("edwards25519", "edwards448")).is_err());
+        assert!(import_wycheproof(&json.replace("acceptable", "unknown")).is_err());
+    }
+
+    #[test]
+    fn test_wycheproof_cross_check() {
+        let vec = generate_test_vectors();
+        let strict = ValidationPolicy {
+            require_canonical_s: true,
+            reject_noncanonical_pk: true,
+            reject_noncanonical_r: true,
+            ..Default::default()
+        };
+        let json = export::wycheproof(&vec, &[strict]).unwrap();
+        let vectors = import_wycheproof(&json).unwrap();
+
+        // the verifiers of the crate depart from the strict policy where they skip its checks
+        let divergences = cross_check(&vectors, &crate_verifiers());
+        for (name, verifier) in crate_verifiers().iter() {
+            let mut ids: Vec<String> = divergences
+                .iter()
+                .filter(|d| d.verifier == *name)
+                .map(|d| d.id.clone())
+                .collect();
+            ids.sort_by_key(|id| id.parse::<usize>().unwrap());
+            let expected: Vec<String> = verdicts(*verifier, &vec)
+                .iter()
+                .zip(strict.predict(&vec).iter())
+                .enumerate()
+                .filter(|(_, (observed, predicted))| {
+                    observed.is_accepted() != predicted.is_accepted()
+                })
+                .map(|(i, _)| (i + 1).to_string())
+                .collect();
+            assert_eq!(ids, expected, "{}", name);
+        }
+        assert!(divergences.iter().any(|d| d.verifier == "cofactorless"
+            && d.id == "7"
+            && d.verdict == Verdict::Accepted));
+
+        let mut output = Vec::new();
+        cross_check_report(&vectors, &crate_verifiers(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("cofactorless: "));
+        assert!(
+            output.contains("(S >= L, breaking strong unforgeability): expected invalid, got V")
+        );
+    }
+
+    #[test]
+    fn test_torsion_clearing_vectors() {
+        for torsion in EIGHT_TORSION.iter() {
+            assert!(clear_torsion(torsion).is_identity());
+            assert_eq!(
+                clear_torsion(&(ED25519_BASEPOINT_POINT + torsion)),
+                ED25519_BASEPOINT_POINT
+            );
+        }
+
+        let vectors = torsion_clearing_vectors(&mut new_rng());
+        assert_eq!(vectors.len(), 3);
+        for pair in vectors.iter() {
+            let (before, after) = (&pair.before, &pair.after);
+            assert_eq!(
+                clear_torsion_encoded(&before.pub_key).unwrap(),
+                after.pub_key
+            );
+            assert_eq!(
+                clear_torsion_encoded(&after.pub_key).unwrap(),
+                after.pub_key
+            );
+            for policy in ValidationPolicy::all() {
+                assert!(policy
+                    .verify(&before.message, &before.pub_key, &before.signature)
+                    .is_err());
+                assert!(
+                    policy
+                        .verify(&after.message, &after.pub_key, &after.signature)
+                        .is_ok(),
+                    "{}: {}",
+                    pair.comment,
+                    policy
+                );
+            }
+
+            #[cfg(feature = "dalek")]
+            {
+                let (pk, sig) = unpack_test_vector_dalek(before);
+                assert!(pk.verify(&before.message, &sig).is_err());
+                let (pk, sig) = unpack_test_vector_dalek(after);
+                assert!(pk.verify_strict(&after.message, &sig).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalization_vectors() {
+        for torsion in EIGHT_TORSION.iter() {
+            assert!(normalize(torsion).is_identity());
+            assert_eq!(
+                normalize(&(ED25519_BASEPOINT_POINT + torsion)),
+                ED25519_BASEPOINT_POINT
+            );
+        }
+        // honest signatures are left as they are
+        let tv = SigningKey::generate(&mut new_rng()).sign_vector(b"normalized");
+        let (pub_key, signature) = normalize_signature(&tv.pub_key, &tv.signature).unwrap();
+        assert_eq!((pub_key, signature), (tv.pub_key, tv.signature.clone()));
+        assert!(normalize_signature(&tv.pub_key, &tv.signature[..63]).is_err());
+
+        // the named policies only part on the cases of a small-order A, normalized to the
+        // identity, and the equations always agree
+        let vectors = normalization_vectors().unwrap();
+        assert_eq!(vectors.len(), 12);
+        let diverging: Vec<usize> = (0..12).filter(|&i| vectors[i].diverges_after()).collect();
+        assert_eq!(diverging, vec![0, 1, 10, 11]);
+        // before, they part on every case they do not all reject
+        let diverging: Vec<usize> = (0..12).filter(|&i| vectors[i].diverges_before()).collect();
+        assert_eq!(diverging, vec![0, 1, 2, 3, 4, 5, 9, 10, 11]);
+        for v in vectors.iter() {
+            let pub_key = deserialize_point(&v.after.pub_key).unwrap();
+            let r = deserialize_point(&v.after.signature[..32]).unwrap();
+            assert!(!has_torsion_component(&pub_key));
+            assert!(!has_torsion_component(&r));
+            assert!(algorithm2::is_canonical_point_encoding(&v.after.pub_key));
+            let s = Scalar::from_bits(<[u8; 32]>::try_from(&v.after.signature[32..]).unwrap());
+            let message = &v.after.message;
+            assert_eq!(
+                verify_cofactored(message, &pub_key, &(r, s)).is_ok(),
+                verify_cofactorless(message, &pub_key, &(r, s)).is_ok()
+            );
+        }
+
+        let stored = std::fs::read("normalization.json").unwrap();
+        assert_eq!(stored, serde_json::to_vec(&vectors).unwrap());
+    }
+
+    #[test]
+    fn test_torsion_sweep_vectors() {
+        let vectors = sweep_torsion(&mut new_rng()).unwrap();
+        assert_eq!(vectors.len(), 14);
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            let (small, mixed) = if v.in_pub_key {
+                (&tv.pub_key[..], &tv.signature[..32])
+            } else {
+                (&tv.signature[..32], &tv.pub_key[..])
+            };
+            assert_eq!(
+                small,
+                &EIGHT_TORSION[v.torsion_index].compress().as_bytes()[..]
+            );
+            let mixed = deserialize_point(mixed).unwrap();
+            assert!(!mixed.is_small_order() && !has_full_order(&mixed));
+            // the verdicts are those of the reference verifiers, which accept as the policies
+            // they implement, though libsodium rejects some vectors at another stage
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+            }
+            assert!(v.expected_verdicts["cofactored"].is_accepted());
+            assert!(v.expected_verdicts["cofactorless"].is_accepted());
+        }
+        assert_eq!(
+            vectors.iter().map(|v| v.torsion_index).collect::<Vec<_>>(),
+            (1..8).flat_map(|i| vec![i, i]).collect::<Vec<_>>()
+        );
+
+        // checking the order of R rejects every point in R, and none in A
+        let family: Vec<TestVector> = vectors.iter().map(|v| v.vector.clone()).collect();
+        let checking_r = ValidationPolicy {
+            reject_small_order_r: true,
+            ..Default::default()
+        }
+        .predict(&family);
+        assert_eq!(
+            rejected_torsion(&vectors, &checking_r, false).unwrap(),
+            (1..8).collect::<Vec<_>>()
+        );
+        assert!(rejected_torsion(&vectors, &checking_r, true)
+            .unwrap()
+            .is_empty());
+        // a blocklist of the point of order 2 only is told apart
+        let blocklist: Vec<Verdict> = vectors
+            .iter()
+            .map(|v| Verdict::from_accepted(v.torsion_order != "2"))
+            .collect();
+        assert_eq!(
+            rejected_torsion(&vectors, &blocklist, true).unwrap(),
+            vec![4]
+        );
+        assert!(rejected_torsion(&vectors, &blocklist[1..], true).is_err());
+
+        let results = library_registry().results(&family);
+        for (name, verdicts) in results.rows().iter() {
+            for &in_pub_key in [true, false].iter() {
+                let rejected = rejected_torsion(&vectors, verdicts, in_pub_key).unwrap();
+                println!(
+                    "{}, small {}: rejects {:?}",
+                    name,
+                    if in_pub_key { "A" } else { "R" },
+                    rejected
+                );
+                // the libraries check the order of A and R completely, if at all
+                assert!(rejected.is_empty() || rejected.len() == 7, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_substitution_pairs() {
+        let pairs = key_substitution_pairs(&mut new_rng()).unwrap();
+        assert_eq!(pairs.len(), 13);
+        for p in pairs.iter() {
+            let (original, substitute) = (&p.original, &p.substitute);
+            assert_eq!(original.message, substitute.message);
+            assert_eq!(original.signature, substitute.signature);
+            assert_ne!(original.pub_key, substitute.pub_key);
+            let a = deserialize_point(&original.pub_key).unwrap();
+            let a_prime = deserialize_point(&substitute.pub_key).unwrap();
+            assert!(a.is_small_order() && a_prime.is_small_order());
+            assert_eq!(p.by_encoding, a == a_prime);
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                for (i, tv) in p.vectors().iter().enumerate() {
+                    assert_eq!(
+                        p.expected_verdicts[*name][i].is_accepted(),
+                        policy
+                            .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                            .is_accepted()
+                    );
+                }
+            }
+            for name in ["cofactored", "cofactorless", "zip215"].iter() {
+                assert!(p.expected_verdicts[*name].iter().all(|v| v.is_accepted()));
+            }
+            assert!(p.expected_verdicts["algorithm2"]
+                .iter()
+                .all(|v| !v.is_accepted()));
+            // the search finds the substitute from the original
+            let found = substitutes(
+                &original.message,
+                &original.pub_key,
+                &original.signature,
+                &ValidationPolicy::default(),
+            )
+            .unwrap();
+            assert!(found.contains(&substitute.pub_key), "{}", p.comment);
+        }
+        assert_eq!(pairs.iter().filter(|p| p.by_encoding).count(), 6);
+
+        // no substitute for a signature under a key of order L, with S reduced or not
+        let signer = MaliciousSigner::from_seed(&[7u8; 32]);
+        let sig = signer.sign(b"substitute me");
+        for sig in [sig.clone(), signer.tamper_lift_s(&sig, 1).unwrap()].iter() {
+            for (_, policy) in KNOWN_POLICIES.iter() {
+                assert!(
+                    substitutes(&sig.message, &sig.pub_key, &sig.signature, policy)
+                        .unwrap()
+                        .is_empty()
+                );
+            }
+        }
+
+        let family: Vec<TestVector> = pairs.iter().flat_map(|p| p.vectors().to_vec()).collect();
+        assert!(substituted(&pairs, &ALGORITHM_2.predict(&family))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            substituted(&pairs, &ZIP_215.predict(&family)).unwrap(),
+            (0..13).collect::<Vec<_>>()
+        );
+        assert!(substituted(
+            &pairs,
+            &family.iter().map(|_| Verdict::Accepted).collect::<Vec<_>>()[1..]
+        )
+        .is_err());
+
+        let results = library_registry().results(&family);
+        for (name, verdicts) in results.rows().iter() {
+            let broken = substituted(&pairs, verdicts).unwrap();
+            println!("{}: substitutes the key of pairs {:?}", name, broken);
+        }
+    }
+
+    #[test]
+    fn test_wycheproof_export() {
+        let vec = generate_test_vectors();
+        let json = export::wycheproof_file().unwrap();
+
+        // the file reads back as the vectors, under the results of the standard policies
+        let imported = import_wycheproof(&json).unwrap();
+        let mut imported: Vec<_> = imported.iter().collect();
+        imported.sort_by_key(|sv| sv.id.parse::<usize>().unwrap());
+        assert_eq!(imported.len(), vec.len());
+        for (i, (sv, tv)) in imported.iter().zip(vec.iter()).enumerate() {
+            assert_eq!(sv.id, (i + 1).to_string());
+            assert_eq!(sv.vector.signature, tv.signature);
+            assert_eq!(sv.vector.pub_key, tv.pub_key);
+        }
+        let expected: Vec<Option<bool>> = imported.iter().map(|sv| sv.expected).collect();
+        // small or mixed order is up to the verifier, S >= L and non-canonical encodings
+        // are not
+        assert_eq!(expected, [vec![None; 6], vec![Some(false); 6]].concat());
+
+        // a single policy leaves nothing acceptable
+        let cofactorless = ValidationPolicy {
+            require_canonical_s: true,
+            reject_noncanonical_pk: true,
+            reject_noncanonical_r: true,
+            ..Default::default()
+        };
+        assert_eq!(export::wycheproof_result(&[cofactorless], &vec[3]), "valid");
+        assert_eq!(
+            export::wycheproof_result(&[cofactorless], &vec[4]),
+            "invalid"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&export::wycheproof(&vec, &[cofactorless]).unwrap()).unwrap();
+        assert_eq!(json["numberOfTests"], 12);
+        assert_eq!(
+            json["testGroups"][0]["tests"][0]["flags"][0],
+            "SmallOrderAAndR"
+        );
+        assert!(json["notes"]["SmallOrderAAndR"].is_string());
+    }
+
+    #[test]
+    fn test_signing() {
+        // tests 1 and 2 of RFC 8032, Section 7.1
+        for (seed, pub_key, message, signature) in [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc6\
+                 1e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e45\
+                 8f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+        ]
+        .iter()
+        {
+            let seed = <[u8; 32]>::try_from(&hex::decode(seed).unwrap()[..]).unwrap();
+            let key = SigningKey::from_seed(&seed);
+            assert_eq!(key.seed(), seed);
+            assert_eq!(hex::encode(key.pub_key()), *pub_key);
+            let message = hex::decode(message).unwrap();
+            assert_eq!(hex::encode(key.sign(&message)), *signature);
+        }
+
+        // signatures of generated keys pass every check
+        let mut rng = new_rng();
+        for _ in 0..4 {
+            let tv = SigningKey::generate(&mut rng).sign_vector(b"control");
+            for policy in ValidationPolicy::all().iter() {
+                assert!(policy
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok());
+            }
+            assert!(algorithm2::verify(&tv.message, &tv.pub_key, &tv.signature));
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn test_strategies() {
+        use ed25519_speccheck::strategies::*;
+        use proptest::prelude::*;
+        use proptest::test_runner::{Config, TestRunner};
+
+        let mut runner = TestRunner::default();
+        runner
+            .run(&small_order_point(), |pt| {
+                prop_assert!(pt.is_small_order());
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&mixed_order_point(), |pt| {
+                prop_assert!(!pt.is_small_order() && !pt.is_torsion_free());
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&non_canonical_encoding(), |bytes| {
+                prop_assert!(!algorithm2::is_canonical_point_encoding(&bytes));
+                prop_assert!(deserialize_point_with(&bytes, DecodeMode::Lenient).is_ok());
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(
+                &(canonical_scalar(), large_scalar(), really_large_scalar()),
+                |(canonical, large, really_large)| {
+                    prop_assert!(Scalar::from_canonical_bytes(canonical.to_bytes()).is_some());
+                    prop_assert!(Scalar::from_canonical_bytes(large.to_bytes()).is_none());
+                    prop_assert_eq!(large.to_bytes()[31] & 224, 0);
+                    prop_assert_ne!(really_large.to_bytes()[31] & 224, 0);
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        // honest signatures pass every policy, those under A of mixed order the cofactored
+        // equation, and no policy rejecting non-canonical R accepts one
+        runner
+            .run(&honest_vector(), |tv| {
+                for policy in ValidationPolicy::all().iter() {
+                    prop_assert!(policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok());
+                }
+                Ok(())
+            })
+            .unwrap();
+        runner
+            .run(&mixed_order_key_vector(), |tv| {
+                prop_assert!(COFACTORED
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok());
+                Ok(())
+            })
+            .unwrap();
+        let strict_r = ValidationPolicy {
+            reject_noncanonical_r: true,
+            ..Default::default()
+        };
+        let vectors = vector_from(
+            canonical_encoding(point()),
+            non_canonical_encoding(),
+            canonical_scalar(),
+        );
+        runner
+            .run(&vectors, |tv| {
+                prop_assert!(strict_r
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_err());
+                Ok(())
+            })
+            .unwrap();
+
+        // instances of a case, each grinding every case
+        TestRunner::new(Config::with_cases(4))
+            .run(&case_vector(5), |tv| {
+                prop_assert_eq!(tv.metadata.unwrap().index, 5);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_control_vectors() {
+        let controls = control_vectors(&mut new_rng());
+        assert_eq!(controls.len(), 9);
+        // TEST 3 and TEST SHA(abc) of RFC 8032, Section 7.1
+        assert_eq!(
+            hex::encode(&controls[2].vector.signature),
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae\
+             67f760984dc6594a7c15e9716ed28dc027beceea1ec40a"
+        );
+        assert_eq!(
+            hex::encode(&controls[3].vector.signature),
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfd\
+             fbc7c66431e0303dca179c138ac17ad9bef1177331a704"
+        );
+        for control in controls.iter() {
+            assert_eq!(control.expected, controls::VALID_EVERYWHERE);
+            let tv = &control.vector;
+            for policy in ValidationPolicy::all().iter() {
+                assert!(policy
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok());
+            }
+            // as do the reference verifiers
+            assert!(classify(tv).values().all(|verdict| verdict.is_accepted()));
+        }
+    }
+
+    #[test]
+    fn test_negative_control_vectors() {
+        let controls = negative_control_vectors(&mut new_rng());
+        assert_eq!(controls.len(), 7);
+        let lengths: Vec<usize> = controls.iter().map(|c| c.vector.signature.len()).collect();
+        assert_eq!(lengths, vec![64, 64, 64, 0, 32, 63, 65]);
+        for control in controls.iter() {
+            assert_eq!(control.expected, controls::INVALID_EVERYWHERE);
+            let tv = &control.vector;
+            for policy in ValidationPolicy::all().iter() {
+                assert!(policy
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_err());
+            }
+            assert!(classify(tv).values().all(|verdict| !verdict.is_accepted()));
+        }
+    }
+
+    #[test]
+    fn test_malicious_signer() {
+        // test 1 of RFC 8032, Section 7.1
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap();
+        let signer = MaliciousSigner::from_seed(&<[u8; 32]>::try_from(&seed[..]).unwrap());
+        assert_eq!(
+            hex::encode(signer.pub_key()),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        let signed = signer.sign(b"");
+        assert_eq!(
+            hex::encode(&signed.signature),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc6\
+             1e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+
+        let signed = signer.sign(b"a real transaction");
+        let accepts = |policy: &ValidationPolicy, signed: &SignedMessage| {
+            let tv = signed.to_test_vector();
+            policy
+                .verify(&tv.message, &tv.pub_key, &tv.signature)
+                .is_ok()
+        };
+        assert!(accepts(&RFC_8032, &signed));
+        assert!(accepts(&ALGORITHM_2, &signed));
+
+        // torsion in R passes the cofactored equation only
+        for idx in 1..8 {
+            let tampered = signer.tamper_add_torsion(&signed, idx).unwrap();
+            assert_eq!(tampered.message, signed.message);
+            assert!(accepts(&ZIP_215, &tampered));
+            assert!(!accepts(&RFC_8032, &tampered));
+            // and S of the tampered signature can be lifted in turn
+            let lifted = signer.tamper_lift_s(&tampered, 1).unwrap();
+            assert!(accepts(
+                &ValidationPolicy {
+                    require_canonical_s: false,
+                    ..ZIP_215
+                },
+                &lifted
+            ));
+            assert!(!accepts(&ZIP_215, &lifted));
+        }
+        assert!(signer.tamper_add_torsion(&signed, 0).is_err());
+        assert!(signer.tamper_add_torsion(&signed, 8).is_err());
+        // adding the point of order 2 twice cancels out
+        let twice = signer.tamper_add_torsion(&signed, 4).unwrap();
+        let twice = signer.tamper_add_torsion(&twice, 4).unwrap();
+        assert_eq!(twice.signature, signed.signature);
+
+        // S + kL passes the verifiers not checking S < L, up to 32 bytes
+        let lifted = signer.tamper_lift_s(&signed, 1).unwrap();
+        assert_eq!(lifted.signature[..32], signed.signature[..32]);
+        assert!(accepts(&ValidationPolicy::default(), &lifted));
+        assert!(!accepts(&RFC_8032, &lifted));
+        assert!(signer.tamper_lift_s(&signed, 15).is_ok());
+        assert!(signer.tamper_lift_s(&signed, 16).is_err());
+
+        // the non-canonical R passes the verifiers hashing it as provided, without checks
+        let tampered = signer.tamper_noncanonical_r(&signed).unwrap();
+        assert!(!algorithm2::is_canonical_point_encoding(
+            &tampered.signature[..32]
+        ));
+        assert!(accepts(&ValidationPolicy::default(), &tampered));
+        assert!(accepts(&ZIP_215, &tampered));
+        let reserializing = ValidationPolicy {
+            reserialize_for_hash: true,
+            ..Default::default()
+        };
+        assert!(!accepts(&reserializing, &tampered));
+        assert!(!accepts(&RFC_8032, &tampered));
+        #[cfg(feature = "dalek")]
+        {
+            // dalek checks the encoding of R
+            let tv = tampered.to_test_vector();
+            let (pk, sig) = unpack_test_vector_dalek(&tv);
+            assert!(pk.verify(&tv.message, &sig).is_err());
+        }
+    }
+
+    #[test]
+    fn test_malleability_scenario() {
+        let malleability::Scenario {
+            original,
+            malleated,
+        } = malleability::scenario(&mut new_rng()).unwrap();
+        assert_eq!(original.transaction, malleated.transaction);
+        assert_eq!(original.signature[..32], malleated.signature[..32]);
+        assert_ne!(original.id(), malleated.id());
+
+        // RFC 8032, dalek and ring check S < L, hence accept the original signature only
+        let msg = original.transaction.signing_bytes();
+        let sender = &original.transaction.sender;
+        for (signed, accepted) in [(&original, true), (&malleated, false)].iter() {
+            assert_eq!(
+                RFC_8032.verify(&msg, sender, &signed.signature).is_ok(),
+                *accepted
+            );
+            #[cfg(feature = "dalek")]
+            {
+                let pk = PublicKey::from_bytes(sender).unwrap();
+                let dalek_accepts = Signature::try_from(&signed.signature[..])
+                    .map(|sig| pk.verify(&msg, &sig).is_ok())
+                    .unwrap_or(false);
+                assert_eq!(dalek_accepts, *accepted);
+            }
+            #[cfg(feature = "ring")]
+            {
+                let ring_pk = signature::UnparsedPublicKey::new(&signature::ED25519, sender);
+                assert_eq!(ring_pk.verify(&msg, &signed.signature).is_ok(), *accepted);
+            }
+        }
+
+        // mutating twice would overflow 32 bytes, hence is refused
+        assert!(malleability::malleate(&malleated.signature).is_err());
+        assert!(malleability::malleate(&malleated.signature[..63]).is_err());
+    }
+
+    #[test]
+    fn test_find_equivalent_signatures() {
+        let honest = SigningKey::generate(&mut new_rng()).sign_vector(b"payment");
+        let find = |tv: &TestVector, policy: &ValidationPolicy| {
+            malleability::find_equivalent_signatures(
+                &tv.message,
+                &tv.pub_key,
+                &tv.signature,
+                policy,
+            )
+            .unwrap()
+        };
+        // checking S < L leaves an honest signature unique
+        for policy in [RFC_8032, ZIP_215, ALGORITHM_2].iter() {
+            assert!(find(&honest, policy).is_empty());
+        }
+        // otherwise, S can be lifted by any multiple of L fitting in 32 bytes, R unchanged
+        let lifts = find(&honest, &COFACTORLESS);
+        assert!(!lifts.is_empty() && lifts.len() <= 15);
+        let s =
+            |sig: &[u8]| Scalar::from_bytes_mod_order(<[u8; 32]>::try_from(&sig[32..]).unwrap());
+        for sig in lifts.iter() {
+            assert_eq!(sig[..32], honest.signature[..32]);
+            assert_eq!(s(sig), s(&honest.signature));
+            assert!(COFACTORLESS
+                .verify(&honest.message, &honest.pub_key, sig)
+                .is_ok());
+        }
+
+        // with A and R of small order, the cofactored equation lets R move by torsion, and
+        // ZIP-215 accepts its non-canonical encodings, though not S + L
+        let small = &generate_test_vectors()[0];
+        assert!(find(small, &COFACTORED)
+            .iter()
+            .any(|sig| sig[..32] != small.signature[..32]));
+        let zip215 = find(small, &ZIP_215);
+        assert!(!zip215.is_empty());
+        assert!(zip215.iter().all(|sig| sig[32..] == small.signature[32..]));
+
+        assert!(malleability::find_equivalent_signatures(
+            &honest.message,
+            &honest.pub_key,
+            &honest.signature[..63],
+            &COFACTORLESS
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_malleability_pairs() {
+        let pairs = malleability::malleability_pairs(&mut new_rng()).unwrap();
+        let comments: Vec<&str> = pairs.iter().map(|pair| pair.comment.as_str()).collect();
+        assert_eq!(
+            comments,
+            vec![
+                "honest: S + jL",
+                "small-order-a-and-r/zero-s: S + jL",
+                "small-order-a-and-r/zero-s: R plus torsion",
+                "non-canonical-r/reduced-for-hash: S + jL",
+                "non-canonical-r/reduced-for-hash: R re-encoded",
+            ]
+        );
+        for pair in pairs.iter() {
+            let (original, malleated) = (&pair.original, &pair.malleated);
+            assert_eq!(malleated.message, original.message);
+            assert_eq!(malleated.pub_key, original.pub_key);
+            assert_ne!(malleated.signature, original.signature);
+            assert!(pair.comment.ends_with(malleability::malleation(
+                &original.signature,
+                &malleated.signature
+            )));
+            assert!(malleability::find_equivalent_signatures(
+                &original.message,
+                &original.pub_key,
+                &original.signature,
+                &COFACTORED
+            )
+            .unwrap()
+            .contains(&malleated.signature));
+            for (tv, digest) in [
+                (original, &pair.original_sha256),
+                (malleated, &pair.malleated_sha256),
+            ]
+            .iter()
+            {
+                assert_eq!(**digest, hex::encode(Sha256::digest(&tv.signature)));
+            }
+            assert_ne!(pair.original_sha256, pair.malleated_sha256);
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    pair.verdicts_malleated[*name].is_accepted(),
+                    policy
+                        .verdict(&malleated.message, &malleated.pub_key, &malleated.signature)
+                        .is_accepted()
+                );
+            }
+            // the verifiers leaving S and the encoding of R unchecked give every pair two IDs
+            let accepting_both = pair.accepting_both();
+            assert!(accepting_both.contains(&"cofactored".to_string()));
+            assert!(accepting_both.contains(&"cofactorless".to_string()));
+        }
+        assert_eq!(
+            pairs[0].accepting_both(),
+            vec!["cofactored", "cofactorless"]
+        );
+        // R plus a point of small order passes RFC 8032 and ZIP-215 too, for A of small order
+        assert!(pairs[2].accepting_both().contains(&"rfc8032".to_string()));
+        assert!(pairs[2].accepting_both().contains(&"zip215".to_string()));
+    }
+
+    #[test]
+    fn test_non_canonical_vectors() {
+        let vectors = non_canonical_vectors(&mut new_rng()).unwrap();
+        assert_eq!(vectors.len(), 8);
+        for nc in vectors.iter() {
+            let tv = &nc.vector;
+            let encoding = if nc.in_pub_key {
+                tv.pub_key
+            } else {
+                let mut r = [0u8; 32];
+                r.copy_from_slice(&tv.signature[..32]);
+                r
+            };
+            // y = 2^255 - 19, i.e. 0, for a point of order 4
+            assert_eq!(encoding[0], 237);
+            assert!(encoding[1..31].iter().all(|&b| b == 255));
+            assert_eq!(encoding[31] & 127, 127);
+            let pt = deserialize_point(&encoding).unwrap();
+            assert!(!(pt + pt).is_identity() && (pt + pt + pt + pt).is_identity());
+
+            // pre-reducing may or may not cancel the small-order components
+            for policy in ValidationPolicy::all().into_iter().filter(|p| !p.prereduce) {
+                let expected = if nc.in_pub_key {
+                    !policy.reject_noncanonical_pk
+                        && !policy.reject_small_order_pk
+                        && !policy.require_full_order_pk
+                        && (policy.cofactored || policy.reserialize_for_hash == nc.reserialized)
+                } else {
+                    !policy.reject_noncanonical_r
+                        && !policy.reject_small_order_r
+                        && !policy.require_full_order_pk
+                        && policy.reserialize_for_hash == nc.reserialized
+                };
+                assert_eq!(
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok(),
+                    expected,
+                    "{}: {}",
+                    nc.comment,
+                    policy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_canonical_encoding_vectors() {
+        let encodings = non_canonical_encodings();
+        // 6 of points of small order, and 20 of points of mixed order
+        assert_eq!(encodings.len(), 26);
+        assert_eq!(
+            encodings
+                .iter()
+                .filter(|nc| deserialize_point(&nc.bytes).unwrap().is_small_order())
+                .count(),
+            6
+        );
+        for nc in encodings.iter() {
+            assert!(!algorithm2::is_canonical_point_encoding(&nc.bytes));
+        }
+
+        let vectors = non_canonical_encoding_vectors(&mut new_rng()).unwrap();
+        assert_eq!(vectors.len(), 2 * encodings.len());
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            let encoding = if v.in_pub_key {
+                &tv.pub_key[..]
+            } else {
+                &tv.signature[..32]
+            };
+            assert_eq!(encoding, &encodings[v.encoding].bytes[..]);
+            let pt = deserialize_point(encoding).unwrap();
+
+            for policy in ValidationPolicy::all() {
+                let rejects_encoding = if v.in_pub_key {
+                    policy.reject_noncanonical_pk
+                } else {
+                    policy.reject_noncanonical_r
+                };
+                let verdict = policy.verdict(&tv.message, &tv.pub_key, &tv.signature);
+                // decoding tells the rejections of the encoding from the others
+                assert_eq!(
+                    verdict == Verdict::ParseRejected,
+                    rejects_encoding,
+                    "{}: {}",
+                    v.comment,
+                    policy
+                );
+                // pre-reducing may or may not cancel the small-order components
+                if policy.prereduce && pt.is_small_order() {
+                    continue;
+                }
+                let expected = pt.is_small_order()
+                    && !rejects_encoding
+                    && if v.in_pub_key {
+                        !policy.reject_small_order_pk && !policy.require_full_order_pk
+                    } else {
+                        !policy.reject_small_order_r
+                            && (!policy.require_full_order_pk || pt.is_identity())
+                            && !policy.reserialize_for_hash
+                    };
+                assert_eq!(verdict.is_accepted(), expected, "{}: {}", v.comment, policy);
+            }
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+            }
+        }
+
+        let family: Vec<TestVector> = vectors.iter().map(|v| v.vector.clone()).collect();
+        let results = library_registry().results(&family);
+        let rows: Vec<(&str, &[Verdict])> = results
+            .rows()
+            .iter()
+            .map(|(name, verdicts)| (name.as_str(), &verdicts[..]))
+            .collect();
+        let table = per_encoding_table(&vectors, &rows).unwrap();
+        println!("{}", table);
+        assert_eq!(table.lines().count(), 1 + encodings.len());
+        assert!(per_encoding_table(&vectors, &[("short", &[Verdict::Accepted][..])]).is_err());
+    }
+
+    #[test]
+    fn test_one_sided_non_canonical_vectors() {
+        let vectors = one_sided_non_canonical_vectors(&mut new_rng()).unwrap();
+        assert_eq!(vectors.len(), 4);
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            let (non_canonical, honest) = if v.in_pub_key {
+                (&tv.pub_key[..], &tv.signature[..32])
+            } else {
+                (&tv.signature[..32], &tv.pub_key[..])
+            };
+            assert!(!algorithm2::is_canonical_point_encoding(non_canonical));
+            assert!(algorithm2::is_canonical_point_encoding(honest));
+            assert!(has_full_order(&deserialize_point(honest).unwrap()));
+
+            // pre-reducing may or may not cancel the small-order A
+            for policy in ValidationPolicy::all().into_iter().filter(|p| !p.prereduce) {
+                let expected = if v.in_pub_key {
+                    !policy.reject_noncanonical_pk
+                        && !policy.reject_small_order_pk
+                        && !policy.require_full_order_pk
+                        && (policy.cofactored || policy.reserialize_for_hash == v.reserialized)
+                } else {
+                    !policy.reject_noncanonical_r
+                        && !policy.reject_small_order_r
+                        && policy.reserialize_for_hash == v.reserialized
+                };
+                assert_eq!(
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok(),
+                    expected,
+                    "{}: {}",
+                    v.comment,
+                    policy
+                );
+            }
+        }
+
+        // the checks of the encodings of A and R are told apart
+        let family: Vec<TestVector> = vectors.iter().map(|v| v.vector.clone()).collect();
+        let pattern = |policy: &ValidationPolicy| {
+            AcceptancePattern::new(&vectors, &policy.predict(&family)).unwrap()
+        };
+        let checking_pub_key = pattern(&ValidationPolicy {
+            reject_noncanonical_pk: true,
+            cofactored: true,
+            ..Default::default()
+        });
+        assert!(!checking_pub_key.accepts_non_canonical_pub_key());
+        assert!(checking_pub_key.accepts_non_canonical_r());
+        let checking_r = pattern(&ValidationPolicy {
+            reject_noncanonical_r: true,
+            cofactored: true,
+            ..Default::default()
+        });
+        assert!(checking_r.accepts_non_canonical_pub_key());
+        assert!(!checking_r.accepts_non_canonical_r());
+        let zip215 = pattern(&ZIP_215);
+        assert_eq!(
+            zip215.0,
+            [
+                [Verdict::Accepted, Verdict::Accepted],
+                [Verdict::VerifyRejected, Verdict::Accepted]
+            ]
+        );
+        assert!(AcceptancePattern::new(&vectors, &[Verdict::Accepted]).is_err());
+
+        let results = library_registry().results(&family);
+        for (name, verdicts) in results.rows().iter() {
+            let observed = AcceptancePattern::new(&vectors, verdicts).unwrap();
+            println!("{}\n{}", name, observed);
+            if name == "[CGN20e] Alg.2" {
+                assert_eq!(observed, pattern(&ALGORITHM_2));
+            }
+            // dalek's legacy verification and ring check the encoding of R only
+            if name == "Dalek" || name == "BoringSSL" {
+                assert!(observed.accepts_non_canonical_pub_key());
+                assert!(!observed.accepts_non_canonical_r());
+            }
+            #[cfg(feature = "zebra")]
+            {
+                if name == "Zebra" {
+                    assert_eq!(observed, zip215);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_full_order_vectors() {
+        assert!(has_full_order(&ED25519_BASEPOINT_POINT));
+        for torsion in EIGHT_TORSION.iter() {
+            assert!(!has_full_order(torsion));
+            assert!(!has_full_order(&(ED25519_BASEPOINT_POINT + torsion)) || torsion.is_identity());
+        }
+
+        // the mixed-order A of case 3 is what its comment says the full-order check rejects
+        let full_order = |policy: ValidationPolicy| ValidationPolicy {
+            require_full_order_pk: true,
+            ..policy
+        };
+        let tv = &generate_test_vectors()[3];
+        assert!(ValidationPolicy::default()
+            .verify(&tv.message, &tv.pub_key, &tv.signature)
+            .is_ok());
+        assert!(full_order(Default::default())
+            .verify(&tv.message, &tv.pub_key, &tv.signature)
+            .is_err());
+
+        let vectors = full_order_vectors(&mut new_rng());
+        assert_eq!(
+            vectors
+                .iter()
+                .map(|fv| fv.torsion_order)
+                .collect::<Vec<_>>(),
+            vec![2, 4, 8]
+        );
+        for fv in vectors.iter() {
+            let tv = &fv.vector;
+            for policy in ValidationPolicy::all() {
+                assert_eq!(
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok(),
+                    !policy.require_full_order_pk,
+                    "{}: {}",
+                    fv.comment,
+                    policy
+                );
+            }
+
+            // small-order blocklists miss A
+            #[cfg(feature = "dalek")]
+            {
+                let (pk, sig) = unpack_test_vector_dalek(tv);
+                assert!(pk.verify_strict(&tv.message, &sig).is_ok());
+            }
+            #[cfg(feature = "zebra")]
+            {
+                let (pk, sig) = unpack_test_vector_zebra(tv);
+                assert!(pk.verify(&sig, &tv.message).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_identity_pubkey_vectors() {
+        let vectors = identity_pubkey(&mut new_rng());
+        assert_eq!(
+            vectors.iter().map(|v| v.canonical).collect::<Vec<_>>(),
+            vec![true, false, false, false]
+        );
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            assert!(deserialize_point_with(&tv.pub_key, DecodeMode::Lenient)
+                .unwrap()
+                .is_identity());
+            assert_eq!(tv.signature, vectors[0].vector.signature);
+            // only the checks of A reject the vector
+            for policy in ValidationPolicy::all() {
+                let accepted = !policy.reject_small_order_pk
+                    && !policy.require_full_order_pk
+                    && (v.canonical || !policy.reject_noncanonical_pk);
+                assert_eq!(
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok(),
+                    accepted,
+                    "{}: {}",
+                    v.comment,
+                    policy
+                );
+            }
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+            }
+        }
+
+        // the table tells apart the libraries rejecting the identity
+        let family: Vec<TestVector> = vectors.iter().map(|v| v.vector.clone()).collect();
+        let results = library_registry().results(&family);
+        for (name, verdicts) in results.rows().iter() {
+            print_row(name, verdicts.iter().copied());
+            if name == "[CGN20e] Alg.2" {
+                assert!(verdicts.iter().all(|v| !v.is_accepted()));
+            }
+            #[cfg(feature = "dalek")]
+            {
+                if name == "Dalek strict" {
+                    assert!(verdicts.iter().all(|v| !v.is_accepted()));
+                }
+                if name == "Dalek" {
+                    assert!(verdicts.iter().all(|v| v.is_accepted()));
+                }
+            }
+            #[cfg(feature = "zebra")]
+            {
+                if name == "Zebra" {
+                    assert!(verdicts.iter().all(|v| v.is_accepted()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tiny_scalar_vectors() {
+        let cofactored = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+        let passes = |policy: &ValidationPolicy, tv: &TestVector| {
+            policy
+                .verify(&tv.message, &tv.pub_key, &tv.signature)
+                .is_ok()
+        };
+
+        let vectors = tiny_scalar_vectors(&mut new_rng());
+        assert_eq!(vectors.len(), 3 * TINY_SCALARS.len());
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            let mut s_bytes = [0u8; 32];
+            s_bytes[0] = v.s;
+            assert_eq!(&tv.signature[32..], &s_bytes[..]);
+            assert_eq!(passes(&cofactored, tv), v.passes_cofactored);
+            assert_eq!(
+                passes(&ValidationPolicy::default(), tv),
+                v.passes_cofactorless
+            );
+            // dalek's legacy verification is cofactorless, without checking the order of A
+            #[cfg(feature = "dalek")]
+            {
+                let (pk, sig) = unpack_test_vector_dalek(tv);
+                assert_eq!(pk.verify(&tv.message, &sig).is_ok(), v.passes_cofactorless);
+            }
+        }
+        for s in TINY_SCALARS.iter() {
+            assert!(vectors
+                .iter()
+                .any(|v| v.s == *s && v.pub_key_order == 8 && !v.passes_cofactorless));
+        }
+    }
+
+    #[test]
+    fn test_hram_prefix() {
+        // the prefix of R and A gives the hram of every message, as compute_hram does
+        for tv in generate_test_vectors().iter() {
+            let pub_key = deserialize_point(&tv.pub_key).unwrap();
+            let r = deserialize_point(&tv.signature[..32]).unwrap();
+            let prefix = HramPrefix::new(&r, &pub_key);
+            for message in [&tv.message[..], b"", &[0u8; 200][..]].iter() {
+                assert_eq!(prefix.finish(message), compute_hram(message, &pub_key, &r));
+            }
+            // as provided, the encodings are hashed as they are
+            let mut k_bytes = [0u8; 64];
+            k_bytes.copy_from_slice(
+                &Sha512::new()
+                    .chain(&tv.signature[..32])
+                    .chain(tv.pub_key)
+                    .chain(&tv.message)
+                    .finalize(),
+            );
+            assert_eq!(
+                HramPrefix::from_arrays(&tv.signature[..32], &tv.pub_key).finish(&tv.message),
+                Scalar::from_bytes_mod_order_wide(&k_bytes)
+            );
+        }
+
+        // a non-canonical encoding is not re-serialized: y = p + 1, the identity
+        let mut non_canonical = [0xffu8; 32];
+        non_canonical[0] = 0xee;
+        non_canonical[31] = 0x7f;
+        let point = deserialize_point_with(&non_canonical, DecodeMode::Lenient).unwrap();
+        let r = Scalar::from(3u8) * ED25519_BASEPOINT_POINT;
+        assert_ne!(
+            HramPrefix::from_arrays(r.compress().as_bytes(), &non_canonical).finish(b"m"),
+            HramPrefix::new(&r, &point).finish(b"m")
+        );
+    }
+
+    #[test]
+    fn test_streaming_verifier() {
+        // however the message is split, the verdict is that of the whole message
+        type VerifyFn = fn(&[u8], &EdwardsPoint, &(EdwardsPoint, Scalar)) -> Result<()>;
+        let equations: [(Equation, VerifyFn); 3] = [
+            (Equation::Cofactored, verify_cofactored),
+            (Equation::Cofactorless, verify_cofactorless),
+            (
+                Equation::PreReducedCofactored,
+                verify_pre_reduced_cofactored,
+            ),
+        ];
+        let streamed = |equation: Equation, tv: &TestVector, chunk: usize| {
+            let mut verifier =
+                StreamingVerifier::from_serialized(equation, &tv.pub_key, &tv.signature).unwrap();
+            for piece in tv.message.chunks(chunk) {
+                verifier.update(piece);
+            }
+            verifier.finalize_verify().is_ok()
+        };
+        for tv in generate_test_vectors().iter() {
+            let pub_key = deserialize_point(&tv.pub_key).unwrap();
+            let r = deserialize_point(&tv.signature[..32]).unwrap();
+            let s = Scalar::from_bits(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap());
+            for (equation, verify) in equations.iter() {
+                let expected = verify(&tv.message, &pub_key, &(r, s)).is_ok();
+                for chunk in [1, 7, 64, 128, 1000].iter() {
+                    assert_eq!(streamed(*equation, tv, *chunk), expected);
+                }
+            }
+        }
+        assert!(
+            StreamingVerifier::from_serialized(Equation::Cofactored, &[0u8; 31], &[0u8; 64])
+                .is_err()
+        );
+
+        let vectors = large_message_vectors(&mut new_rng());
+        assert_eq!(vectors.len(), MESSAGE_LENGTHS.len() + 2);
+        for (v, len) in vectors.iter().zip(MESSAGE_LENGTHS.iter()) {
+            assert_eq!(v.vector.message.len(), *len);
+        }
+        // R || A || M against the 128-byte blocks of SHA-512
+        let tails: Vec<usize> = MESSAGE_LENGTHS.iter().map(|len| (64 + len) % 128).collect();
+        assert_eq!(tails, vec![64, 0, 111, 112]);
+        let cofactored = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            assert!(tv.message.len() >= 1 << 20);
+            for chunk in [4096, 100_003].iter() {
+                assert_eq!(
+                    streamed(Equation::Cofactored, tv, *chunk),
+                    v.expected_cofactored
+                );
+                assert_eq!(
+                    streamed(Equation::Cofactorless, tv, *chunk),
+                    v.expected_cofactorless
+                );
+            }
+            assert_eq!(
+                cofactored
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                v.expected_cofactored
+            );
+            assert_eq!(
+                ValidationPolicy::default()
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                v.expected_cofactorless
+            );
+        }
+    }
+
+    #[test]
+    fn test_message_length_vectors() {
+        let vectors = message_length_vectors(&mut new_rng());
+        assert_eq!(vectors.len(), MESSAGE_SIZES.len() + 2);
+        for (v, len) in vectors.iter().zip(MESSAGE_SIZES.iter()) {
+            assert_eq!(v.vector.message.len(), *len);
+        }
+        let cofactored = ValidationPolicy {
+            cofactored: true,
+            ..Default::default()
+        };
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            assert_eq!(
+                cofactored
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                v.expected_cofactored
+            );
+            assert_eq!(
+                ValidationPolicy::default()
+                    .verify(&tv.message, &tv.pub_key, &tv.signature)
+                    .is_ok(),
+                v.expected_cofactorless
+            );
+        }
+        // the adversarial vectors are over the empty message
+        assert!(vectors[4..].iter().all(|v| v.vector.message.is_empty()));
+
+        // only the 1 MiB message goes to the blob, and each loads back as generated
+        let (stored, blob) = store_message_vectors(&vectors);
+        assert_eq!(blob.len(), 1 << 20);
+        for (s, v) in stored.iter().zip(vectors.iter()) {
+            assert_eq!(s.message.is_none(), s.message_len > INLINE_MESSAGE_LIMIT);
+            let loaded = s.load(&blob).unwrap();
+            assert_eq!(loaded.vector.message, v.vector.message);
+            assert_eq!(loaded.vector.pub_key, v.vector.pub_key);
+            assert_eq!(loaded.vector.signature, v.vector.signature);
+            assert_eq!(loaded.expected_cofactorless, v.expected_cofactorless);
+        }
+        assert!(stored[3].load(&blob[..blob.len() - 1]).is_err());
+        let mut damaged = blob.clone();
+        damaged[0] ^= 1;
+        assert!(stored[3].load(&damaged).is_err());
+
+        // the stored files are those generated
+        let json = std::fs::read("message_lengths.json").unwrap();
+        assert_eq!(json, serde_json::to_vec(&stored).unwrap());
+        assert_eq!(std::fs::read("message_lengths.bin").unwrap(), blob);
+    }
+
+    #[test]
+    fn test_s_high_bits_vectors() {
+        let vectors = s_high_bits_vectors(&mut new_rng());
+        assert_eq!(vectors.len(), 8);
+        let reduced = |tv: &TestVector| {
+            Scalar::from_bytes_mod_order(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap())
+        };
+        for (i, v) in vectors.iter().enumerate() {
+            let tv = &v.vector;
+            assert_eq!(v.top_bits as usize, i);
+            assert_eq!(tv.signature[63] >> 5, v.top_bits);
+            // every variant is the same signature modulo L
+            assert_eq!(reduced(tv), reduced(&vectors[0].vector));
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+                // S is read dropping bit 255 when not required to be canonical
+                let accepted = !policy.require_canonical_s && v.top_bits < 4;
+                assert_eq!(v.expected_verdicts[*name].is_accepted(), accepted);
+            }
+            // dalek parses S with ref10's mask, but rejects the variant it lets through
+            // while verifying, and zebra requires S < L
+            #[cfg(feature = "dalek")]
+            {
+                let dalek = Verdict::from_results(Signature::try_from(&tv.signature[..]), |_| {
+                    let (pk, sig) = unpack_test_vector_dalek(tv);
+                    pk.verify(&tv.message[..], &sig)
+                });
+                assert_eq!(
+                    dalek == Verdict::ParseRejected,
+                    !v.passes_s_checks["top-three-bits"]
+                );
+                assert!(!dalek.is_accepted());
+            }
+            #[cfg(feature = "zebra")]
+            {
+                let (pk, sig) = unpack_test_vector_zebra(tv);
+                assert!(pk.verify(&sig, &tv.message).is_err());
+            }
+        }
+        assert!(vectors.iter().all(|v| !v.passes_s_checks["canonical"]));
+        assert!(vectors.iter().all(|v| v.passes_s_checks["none"]));
+        // the variants tell every check of S apart from the others
+        for check in S_CHECKS.iter() {
+            let passing: Vec<bool> = vectors
+                .iter()
+                .map(|v| v.passes_s_checks[check.name])
+                .collect();
+            assert_eq!(
+                S_CHECKS
+                    .iter()
+                    .filter(|other| {
+                        vectors
+                            .iter()
+                            .map(|v| v.passes_s_checks[other.name])
+                            .collect::<Vec<_>>()
+                            == passing
+                    })
+                    .count(),
+                1
+            );
+        }
+    }
+
+    // The messages, public keys and signatures of a batch
+    type Unpacked<'a> = (Vec<&'a [u8]>, Vec<&'a [u8]>, Vec<&'a [u8]>);
+
+    fn unpack_batch(batch: &BatchVector) -> Unpacked<'_> {
+        (
+            batch.entries.iter().map(|tv| &tv.message[..]).collect(),
+            batch.entries.iter().map(|tv| &tv.pub_key[..]).collect(),
+            batch.entries.iter().map(|tv| &tv.signature[..]).collect(),
+        )
+    }
+
+    #[test]
+    fn test_large_s_family() {
+        let vectors = large_s_family(&mut new_rng());
+        assert_eq!(vectors.len(), PLACEMENTS.len());
+        let s_of = |tv: &TestVector| <[u8; 32]>::try_from(&tv.signature[32..]).unwrap();
+        for (v, placement) in vectors.iter().zip(PLACEMENTS.iter()) {
+            let tv = &v.vector;
+            assert_eq!(v.placement, placement.name);
+            assert_eq!(
+                Scalar::from_bytes_mod_order(s_of(tv)),
+                Scalar::from_bytes_mod_order(s_of(&vectors[0].vector))
+            );
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+            }
+        }
+        // S increases along the placements, across L, 2^253 and 2^255
+        let top_bits: Vec<u8> = vectors.iter().map(|v| s_of(&v.vector)[31] >> 5).collect();
+        assert_eq!(&top_bits[..3], &[0, 0, 1]);
+        assert!(top_bits[3] < 4 && top_bits[4] >= 4);
+        assert!(Scalar::from_canonical_bytes(s_of(&vectors[1].vector)).is_none());
+
+        // each check of S accepts a different prefix of the family, and is found back from it
+        for (prefix, check) in [1, 2, 4, 6].iter().zip(S_CHECKS.iter()) {
+            let accepted: Vec<bool> = (0..vectors.len()).map(|i| i < *prefix).collect();
+            assert_eq!(s_check_of(&vectors, &accepted).unwrap().name, check.name);
+        }
+        // the policies not requiring S < L drop bit 255
+        let accepts = |policy: &str| -> Vec<bool> {
+            vectors
+                .iter()
+                .map(|v| v.expected_verdicts[policy].is_accepted())
+                .collect()
+        };
+        let check = |policy: &str| s_check_of(&vectors, &accepts(policy)).unwrap().name;
+        assert_eq!(check("rfc8032"), "canonical");
+        assert_eq!(check("cofactored"), "top-bit");
+        assert!(s_check_of(&vectors, &[true, false, true, false, false, false]).is_none());
+        assert!(s_check_of(&vectors, &[true]).is_none());
+
+        // dalek only accepts S < L
+        #[cfg(feature = "dalek")]
+        {
+            let accepted: Vec<bool> = vectors
+                .iter()
+                .map(|v| {
+                    let tv = &v.vector;
+                    Signature::try_from(&tv.signature[..])
+                        .map(|_| {
+                            let (pk, sig) = unpack_test_vector_dalek(tv);
+                            pk.verify(&tv.message[..], &sig).is_ok()
+                        })
+                        .unwrap_or(false)
+                })
+                .collect();
+            assert_eq!(s_check_of(&vectors, &accepted).unwrap().name, "canonical");
+        }
+    }
+
+    #[test]
+    fn test_identity_r_vectors() {
+        let vectors = identity_r(&mut new_rng());
+        assert_eq!(
+            vectors
+                .iter()
+                .map(|v| v.cofactored_only)
+                .collect::<Vec<_>>(),
+            vec![false, true]
+        );
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            assert_eq!(tv.signature[..32], EIGHT_TORSION[0].compress().to_bytes());
+            let pub_key = deserialize_point(&tv.pub_key).unwrap();
+            assert_eq!(pub_key.is_torsion_free(), !v.cofactored_only);
+            // only the checks of R, and the cofactorless equation on the mixed A, reject it
+            for policy in ValidationPolicy::all() {
+                let accepted = !policy.reject_small_order_r
+                    && (!v.cofactored_only || (policy.cofactored && !policy.require_full_order_pk));
+                assert_eq!(
+                    policy
+                        .verify(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_ok(),
+                    accepted,
+                    "{}: {}",
+                    v.comment,
+                    policy
+                );
+            }
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+            }
+        }
+
+        // the table tells apart the libraries rejecting the identity as R
+        let family: Vec<TestVector> = vectors.iter().map(|v| v.vector.clone()).collect();
+        let results = library_registry().results(&family);
+        for (name, verdicts) in results.rows().iter() {
+            print_row(name, verdicts.iter().copied());
+            if name == "[CGN20e] Alg.2" {
+                assert!(verdicts.iter().all(|v| v.is_accepted()));
+            }
+            #[cfg(feature = "dalek")]
+            {
+                if name == "Dalek strict" {
+                    assert!(verdicts.iter().all(|v| !v.is_accepted()));
+                }
+                if name == "Dalek" {
+                    assert!(verdicts[0].is_accepted() && !verdicts[1].is_accepted());
+                }
+            }
+            #[cfg(feature = "zebra")]
+            {
+                if name == "Zebra" {
+                    assert!(verdicts.iter().all(|v| v.is_accepted()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduced_s_pairs() {
+        let pairs = reduced_s_pairs(&mut new_rng());
+        assert_eq!(pairs.len(), MULTIPLES_OF_L.len());
+        for (pair, k) in pairs.iter().zip(MULTIPLES_OF_L.iter()) {
+            assert_eq!(pair.multiple_of_l, *k);
+            // the same signature of the same message under the same key, but for S
+            assert_eq!(pair.original.signature, pairs[0].original.signature);
+            assert_eq!(pair.lifted.message, pair.original.message);
+            assert_eq!(pair.lifted.pub_key, pair.original.pub_key);
+            assert_eq!(pair.lifted.signature[..32], pair.original.signature[..32]);
+            let s = |tv: &TestVector| {
+                Scalar::from_bytes_mod_order(<[u8; 32]>::try_from(&tv.signature[32..]).unwrap())
+            };
+            assert_eq!(s(&pair.lifted), s(&pair.original));
+            assert!(Scalar::from_canonical_bytes(
+                <[u8; 32]>::try_from(&pair.lifted.signature[32..]).unwrap()
+            )
+            .is_none());
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                let tv = &pair.lifted;
+                assert_eq!(
+                    pair.verdicts_lifted[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+                assert!(pair.verdicts_original[*name].is_accepted());
+            }
+            // only the verifiers of this crate leaving S unchecked accept both
+            assert_eq!(pair.accepting_both(), vec!["cofactored", "cofactorless"]);
+        }
+    }
+
+    #[test]
+    fn test_s_boundary_vectors() {
+        let vectors = s_boundary_vectors(&mut new_rng());
+        let comments: Vec<&str> = vectors.iter().map(|v| v.comment.as_str()).collect();
+        assert_eq!(
+            comments,
+            vec![
+                "S = L - 1, A the identity",
+                "S = 2L - 1, A the identity",
+                "S = 0, A the identity",
+                "S = L, A the identity",
+                "S = L - 1, A of order 8",
+                "S = 2L - 1, A of order 8",
+                "S = 0, A of order 8",
+                "S = L, A of order 8",
+            ]
+        );
+        let l = curve25519_dalek::constants::BASEPOINT_ORDER.to_bytes();
+        for (i, v) in vectors.iter().enumerate() {
+            let tv = &v.vector;
+            assert_eq!(v.canonical_s, i % 2 == 0);
+            assert_eq!(v.multiple_of_l, (i % 2) as u64);
+            // both equations hold, so that only the check of S tells the pairs apart
+            assert!(v.expected_verdicts["cofactorless"].is_accepted());
+            assert!(v.expected_verdicts["cofactored"].is_accepted());
+            assert_eq!(v.expected_verdicts["rfc8032"].is_accepted(), v.canonical_s);
+            for (name, policy) in KNOWN_POLICIES.iter() {
+                assert_eq!(
+                    v.expected_verdicts[*name].is_accepted(),
+                    policy
+                        .verdict(&tv.message, &tv.pub_key, &tv.signature)
+                        .is_accepted()
+                );
+            }
+        }
+        assert_eq!(vectors[3].vector.signature[32..], l[..]);
+        let mut minus_one = l;
+        minus_one[0] -= 1;
+        assert_eq!(vectors[0].vector.signature[32..], minus_one[..]);
+        // S = L slips through the mask of the top three bits, 2L - 1 does not
+        assert!(vectors[3].passes_s_checks["top-three-bits"]);
+        assert!(!vectors[1].passes_s_checks["top-three-bits"]);
+        assert!(!vectors[3].passes_s_checks["canonical"]);
+    }
+
+    #[test]
+    fn test_algorithm3_identifies_invalid_signatures() {
+        let mut rng = new_rng();
+        let mut batches = interleaved_batches(&mut rng);
+        batches.append(&mut misattribution_batches(&mut rng));
+
+        for batch in batches.iter() {
+            let (messages, pub_keys, signatures) = unpack_batch(batch);
+            assert_eq!(
+                algorithm3::identify_invalid(&messages, &pub_keys, &signatures, &mut rng),
+                batch.invalid_algorithm2
+            );
+        }
+
+        // without random coefficients, the cancelling forgeries go unnoticed
+        let cancelling = &misattribution_batches(&mut new_rng())[0];
+        let (messages, pub_keys, signatures) = unpack_batch(cancelling);
+        let ones = vec![Scalar::one(); messages.len()];
+        assert!(algorithm3::verify_batch_with_coefficients(
+            &messages,
+            &pub_keys,
+            &signatures,
+            &ones
+        ));
+        assert_eq!(cancelling.invalid_algorithm2, vec![2, 5]);
+    }
+
+    // The verdict of OpenSSL's Ed448
+    #[cfg(feature = "ed448")]
+    fn openssl_ed448_verdict(message: &[u8], pub_key: &[u8], signature: &[u8]) -> Verdict {
+        use openssl::pkey::{Id, PKey};
+
+        let pk = match PKey::public_key_from_raw_bytes(pub_key, Id::ED448) {
+            Ok(pk) => pk,
+            Err(_e) => return Verdict::ParseRejected,
+        };
+        let mut verifier = match openssl::sign::Verifier::new_without_digest(&pk) {
+            Ok(verifier) => verifier,
+            Err(_e) => return Verdict::Error,
+        };
+        match verifier.verify_oneshot(signature, message) {
+            Ok(true) => Verdict::Accepted,
+            _ => Verdict::VerifyRejected,
+        }
+    }
+
+    #[cfg(feature = "ed448")]
+    #[test]
+    fn test_ed448_suite() {
+        // the keys of RFC 8032, section 7.4, and the signature of the 1-octet message
+        let key = |secret: &str| {
+            let secret = hex::decode(secret).unwrap();
+            Ed448Key::from_secret(&<[u8; 57]>::try_from(&secret[..]).unwrap())
+        };
+        let blank = key(concat!(
+            "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3",
+            "528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b"
+        ));
+        assert_eq!(
+            hex::encode(blank.pub_key()),
+            concat!(
+                "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778",
+                "edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180"
+            )
+        );
+        let one_octet = key(concat!(
+            "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463a",
+            "fbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e"
+        ));
+        assert_eq!(
+            hex::encode(one_octet.pub_key()),
+            concat!(
+                "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c086",
+                "6aea01eb00742802b8438ea4cb82169c235160627b4c3a9480"
+            )
+        );
+        let signature = one_octet.sign(&[3]);
+        assert_eq!(
+            hex::encode(&signature[..]),
+            concat!(
+                "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f435",
+                "2541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cb",
+                "cee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0f",
+                "f3348ab21aa4adafd1d234441cf807c03a00"
+            )
+        );
+        for (_, policy) in ED448_POLICIES.iter() {
+            assert!(policy
+                .verify(&[3], &one_octet.pub_key(), &signature)
+                .is_ok());
+            assert!(policy
+                .verify(&[4], &one_octet.pub_key(), &signature)
+                .is_err());
+        }
+
+        // B has order L, the torsion points order 1, 2, 4 and 4
+        let b = ed448::Point::basepoint();
+        assert!(b.mul(&ed448::L).is_identity());
+        assert_eq!(ed448::decompress(&ed448::BASEPOINT).unwrap(), b);
+        let torsion = ed448::Point::torsion();
+        for (i, pt) in torsion.iter().enumerate() {
+            assert!(pt.is_small_order());
+            assert_eq!(pt.mul(&[2]).is_identity(), i < 2, "{}", i);
+            assert_eq!(ed448::decompress(&pt.compress()).unwrap(), *pt);
+        }
+
+        // each vector is accepted by the policies it is built for
+        let vectors = ed448_vectors(&mut new_rng());
+        let accepted_by = |v: &Ed448Vector| -> Vec<&str> {
+            ED448_POLICIES
+                .iter()
+                .map(|(name, _)| *name)
+                .filter(|name| v.expected_verdicts[name].is_accepted())
+                .collect()
+        };
+        let all = [
+            "rfc8032",
+            "rfc8032_cofactorless",
+            "strict",
+            "lenient",
+            "lenient_reserialized",
+        ];
+        let all_but_strict = [
+            "rfc8032",
+            "rfc8032_cofactorless",
+            "lenient",
+            "lenient_reserialized",
+        ];
+        let lenient = ["lenient", "lenient_reserialized"];
+        let expected: [&[&str]; 15] = [
+            &all,
+            &all_but_strict,
+            &all_but_strict,
+            &all_but_strict,
+            &all,
+            &all[..1],
+            &lenient,
+            &lenient,
+            &lenient[1..],
+            &lenient[..1],
+            &lenient[1..],
+            &lenient[..1],
+            &lenient[1..],
+            &lenient[..1],
+            &lenient,
+        ];
+        assert_eq!(vectors.len(), expected.len());
+        for (v, expected) in vectors.iter().zip(expected.iter()) {
+            assert_eq!(accepted_by(v), **expected, "{}", v.comment);
+            for (name, policy) in ED448_POLICIES.iter() {
+                assert_eq!(
+                    policy.verdict(&v.message, &v.pub_key, &v.signature),
+                    v.expected_verdicts[name]
+                );
+            }
+        }
+
+        // OpenSSL checks the cofactored equation as RFC 8032, but rejects the point of order
+        // 2 as R, as it does the identity as A or R
+        let accepted: Vec<usize> = (0..vectors.len())
+            .filter(|i| {
+                let v = &vectors[*i];
+                openssl_ed448_verdict(&v.message, &v.pub_key, &v.signature).is_accepted()
+            })
+            .collect();
+        assert_eq!(accepted, [0, 1, 2, 4, 5]);
+    }
+
+    // The arithmetic of `ed448` is checked against OpenSSL on random keys and messages: the
+    // same public keys and (deterministic) signatures, each verifier accepting the signatures
+    // of the other, and both rejecting them once damaged
+    #[cfg(feature = "ed448")]
+    #[test]
+    fn test_ed448_openssl() {
+        use openssl::pkey::{Id, PKey};
+        use openssl::sign::Signer;
+
+        let mut rng = new_rng();
+        for round in 0..16 {
+            let mut secret = [0u8; 57];
+            rng.fill_bytes(&mut secret);
+            let mut message = vec![0u8; round * 19];
+            rng.fill_bytes(&mut message);
+            let key = Ed448Key::from_secret(&secret);
+            let openssl_key = PKey::private_key_from_raw_bytes(&secret, Id::ED448).unwrap();
+            assert_eq!(
+                openssl_key.raw_public_key().unwrap(),
+                &key.pub_key()[..],
+                "round {}",
+                round
+            );
+            let signature = key.sign(&message);
+            let openssl_signature = Signer::new_without_digest(&openssl_key)
+                .unwrap()
+                .sign_oneshot_to_vec(&message)
+                .unwrap();
+            assert_eq!(openssl_signature, &signature[..], "round {}", round);
+            assert!(openssl_ed448_verdict(&message, &key.pub_key(), &signature).is_accepted());
+            for (name, policy) in ED448_POLICIES.iter() {
+                assert!(
+                    policy
+                        .verify(&message, &key.pub_key(), &openssl_signature)
+                        .is_ok(),
+                    "{} round {}",
+                    name,
+                    round
+                );
+            }
+
+            // a flipped bit in R, S or A, or a different message
+            let mut damaged = signature;
+            let bit = (rng.next_u32() % (114 * 8)) as usize;
+            damaged[bit / 8] ^= 1 << (bit % 8);
+            let mut pub_key = key.pub_key();
+            pub_key[round % 57] ^= 1 << (round % 8);
+            let mut other = message.clone();
+            other.push(round as u8);
+            for (message, pub_key, signature) in [
+                (&message, key.pub_key(), damaged),
+                (&message, pub_key, signature),
+                (&other, key.pub_key(), signature),
+            ]
+            .iter()
+            {
+                assert!(!openssl_ed448_verdict(message, pub_key, signature).is_accepted());
+                assert!(
+                    ED448_POLICIES
+                        .iter()
+                        .all(|(_, policy)| policy.verify(message, pub_key, signature).is_err()),
+                    "round {}",
+                    round
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_x25519_suite() {
+        // the test vector of RFC 7748, section 6.1
+        let nine = {
+            let mut u = [0u8; 32];
+            u[0] = 9;
+            u
+        };
+        assert_eq!(
+            hex::encode(x25519(&SCALAR, &nine)),
+            "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+        );
+        let bob = <[u8; 32]>::try_from(
+            &hex::decode("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+                .unwrap()[..],
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(x25519(&SCALAR, &bob)),
+            "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742"
+        );
+
+        // the low-order inputs are those of the blocklist of libsodium, and their variants
+        // are all-zero as well, but for the base point
+        let vectors = x25519_vectors();
+        assert_eq!(vectors.len(), 13);
+        let control = vectors.last().unwrap();
+        assert_eq!(control.family, Family::Control);
+        for v in vectors.iter() {
+            assert_eq!(
+                v.is_all_zero(),
+                v.family != Family::Control && !v.comment.contains("base point"),
+                "{}",
+                v.comment
+            );
+            if v.comment.contains("base point") {
+                assert_eq!(v.shared_secret, control.shared_secret);
+            }
+        }
+        assert_eq!(
+            hex::encode(vectors[1].u),
+            "e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800"
+        );
+
+        let behavior = |backend: &dyn KeyAgreement| {
+            Behavior::from_outcomes(&vectors, &outcomes(backend, &vectors)).unwrap()
+        };
+        assert_eq!(
+            behavior(&Rfc7748),
+            Behavior {
+                low_order: Handling::Computes,
+                non_canonical: Handling::Computes,
+                high_bit: Handling::Computes,
+            }
+        );
+        assert_eq!(behavior(&RejectAllZero).low_order, Handling::Aborts);
+        assert_eq!(behavior(&RejectAllZero).high_bit, Handling::Computes);
+        assert_eq!(
+            behavior(&RejectAllZero).to_string(),
+            "aborts on low-order u, reduces u >= p, masks the top bit of u"
+        );
+
+        // reading the top bit of u, as 2^255 = 19 modulo p
+        let unmasked = |scalar: &[u8; 32], u: &[u8; 32]| -> Result<[u8; 32]> {
+            let mut reduced = *u;
+            if u[31] & 128 != 0 {
+                reduced[31] &= 127;
+                reduced[0] += 19;
+            }
+            Ok(x25519(scalar, &reduced))
+        };
+        assert_eq!(behavior(&unmasked).high_bit, Handling::Departs);
+        assert_eq!(outcomes(&unmasked, &vectors)[8..12], [Outcome::Departed; 4]);
+        let strict = |scalar: &[u8; 32], u: &[u8; 32]| -> Result<[u8; 32]> {
+            let non_canonical = vectors
+                .iter()
+                .any(|v| v.u == *u && v.family == Family::NonCanonical);
+            if u[31] & 128 != 0 || non_canonical {
+                return Err(anyhow!("non-canonical u"));
+            }
+            Ok(x25519(scalar, u))
+        };
+        let strict = behavior(&strict);
+        assert_eq!(
+            (strict.non_canonical, strict.high_bit),
+            (Handling::Aborts, Handling::Aborts)
+        );
+
+        // a backend panicking or wrong on the base point tells nothing
+        let panicking = |_: &[u8; 32], u: &[u8; 32]| -> Result<[u8; 32]> {
+            assert!(u[0] != 9, "broken");
+            Ok([0u8; 32])
+        };
+        let panicked = outcomes(&panicking, &vectors);
+        assert_eq!(panicked[12], Outcome::Error);
+        assert!(Behavior::from_outcomes(&vectors, &panicked).is_err());
+
+        // and the reference backends join a results table, libsodium returning a shared
+        // secret for the base point only
+        let table = x25519_results(
+            &[("RFC 7748", &Rfc7748), ("libsodium", &RejectAllZero)],
+            &vectors,
+        );
+        let accepted: Vec<usize> = (0..vectors.len())
+            .filter(|i| table.rows()[1].1[*i].is_accepted())
+            .collect();
+        assert_eq!(accepted, [7, 11, 12]);
+    }
+
+    #[test]
+    fn test_ristretto_contrast() {
+        let key = SchnorrKey::from_seed(&[7u8; 32]);
+        let vectors = contrast_vectors(&key, b"ristretto255");
+        let valid: Vec<&str> = vectors
+            .iter()
+            .filter(|v| v.valid)
+            .map(|v| v.comment.as_str())
+            .collect();
+        assert_eq!(
+            valid,
+            [
+                "honest signature",
+                "identity A, S = r",
+                "identity A and R, S = 0"
+            ]
+        );
+        for v in vectors.iter() {
+            let tv = &v.vector;
+            assert_eq!(
+                ristretto::verify_cofactored(&tv.message, &tv.pub_key, &tv.signature).is_ok(),
+                v.valid
+            );
+            if v.comment.starts_with("non-canonical") {
+                assert_eq!(
+                    RistrettoSchnorr.verdict(&tv.message, &tv.pub_key, &tv.signature),
+                    Verdict::ParseRejected
+                );
+            }
+        }
+        assert_eq!(
+            ristretto::CASE_CONTRASTS.len(),
+            generate_test_vectors().len()
+        );
+
+        // every element has order L, and the two equations agree on random signatures,
+        // unlike those over the Edwards points of the cases
+        let mut rng = new_rng();
+        for _ in 0..64 {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            let pt = RistrettoPoint::from_uniform_bytes(&bytes);
+            assert!(ristretto::has_order_dividing_l(&pt));
+            let encoding = pt.compress().to_bytes();
+            for variant in ristretto::non_canonical_variants(&encoding) {
+                assert!(ristretto::deserialize_element(&variant).is_err());
+            }
+
+            let mut tv = key.sign_vector(&bytes);
+            tv.signature[rng.next_u32() as usize % 64] ^= 1 << (rng.next_u32() % 8);
+            assert_eq!(
+                ristretto::verify(&tv.message, &tv.pub_key, &tv.signature).is_ok(),
+                ristretto::verify_cofactored(&tv.message, &tv.pub_key, &tv.signature).is_ok()
+            );
+        }
+        let vec = generate_test_vectors();
+        let mixed = deserialize_point(&vec[4].pub_key).unwrap();
+        assert!(!(-Scalar::one() * mixed + mixed).is_identity());
     }
 }